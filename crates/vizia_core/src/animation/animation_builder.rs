@@ -40,6 +40,13 @@ impl<'a> KeyframeBuilder<'a> {
         Self { time, properties: Vec::new() }
     }
 
+    /// Sets the easing function used from this keyframe to the next. Defaults to linear.
+    pub fn timing_function(mut self, val: impl Into<EasingFunction>) -> Self {
+        self.properties.push(Property::AnimationTimingFunction(val.into()));
+
+        self
+    }
+
     // DISPLAY
 
     pub fn display(mut self, val: impl Into<Display>) -> Self {
@@ -0,0 +1,31 @@
+use super::Animation;
+
+/// Events sent to a view when an animation or transition played on it ends.
+///
+/// The events propagate up the tree from the animated view, so that a parent view or model can
+/// chain animations on its children. The target of the event is the animated view.
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::prelude::*;
+/// # let cx = &mut Context::default();
+/// # let fade_in = cx.add_animation(AnimationBuilder::new());
+/// # let slide_in = cx.add_animation(AnimationBuilder::new());
+/// Element::new(cx).on_animation_end(move |cx, event| {
+///     if event == AnimationEvent::Finished(fade_in) {
+///         cx.play_animation(slide_in, Duration::from_millis(300), Duration::ZERO);
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimationEvent {
+    /// The animation with the given id played to the end.
+    Finished(Animation),
+    /// The animation with the given id was cancelled, or replaced by another animation, before it
+    /// finished.
+    Interrupted(Animation),
+    /// The transition of the given property played to the end.
+    TransitionFinished(String),
+    /// The transition of the given property was replaced by an animation before it finished.
+    TransitionInterrupted(String),
+}
@@ -1,18 +1,64 @@
 use crate::animation::Interpolator;
 use hashbrown::HashSet;
+use vizia_style::{AnimationDirection, AnimationFillMode};
 
 use crate::prelude::*;
 
-use super::TimingFunction;
+use super::{SpringMotion, TimingFunction};
 
 /// A keyframe in an animation state.
 #[derive(Debug, Clone)]
 pub(crate) struct Keyframe<T: Interpolator> {
     pub time: f32,
     pub value: T,
+    /// The timing function from this keyframe to the next, or `None` to use the timing function of the animation.
+    pub timing_function: Option<TimingFunction>,
+}
+
+/// A change to the playback of an active animation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PlaybackCommand {
+    Pause,
+    Resume,
+    /// Moves the animation to the given time after its delay.
+    Seek(Duration),
+    /// Plays the animation in the opposite direction from its current position.
+    Reverse,
+    /// Sets the speed of the animation, where 1.0 is normal speed.
+    SetRate(f32),
+}
+
+/// Describes how an animation is played for an entity.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AnimationOptions {
+    /// The duration of one iteration of the animation.
+    pub duration: Duration,
+    /// The delay before the animation starts.
+    pub delay: Duration,
+    /// The number of iterations, which is infinite for an animation which repeats forever.
+    pub iterations: f32,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+    /// The timing function used between keyframes which don't define their own.
     pub timing_function: TimingFunction,
 }
 
+impl AnimationOptions {
+    /// Creates options for playing an animation once with the given duration and delay.
+    ///
+    /// The first keyframe applies during the delay and the animation stops applying once finished.
+    pub(crate) fn new(duration: Duration, delay: Duration) -> Self {
+        Self {
+            duration,
+            delay,
+            iterations: 1.0,
+            direction: AnimationDirection::Normal,
+            fill_mode: AnimationFillMode::Backwards,
+            timing_function: TimingFunction::linear(),
+        }
+    }
+}
+
 /// Represents an animation of a property with type `T`.
 #[derive(Clone, Debug)]
 pub(crate) struct AnimationState<T: Interpolator> {
@@ -24,6 +70,14 @@ pub(crate) struct AnimationState<T: Interpolator> {
     pub duration: Duration,
     /// The delay before the animation starts.
     pub delay: Duration,
+    /// The number of times the animation is played.
+    pub iterations: f32,
+    /// The direction in which each iteration is played.
+    pub direction: AnimationDirection,
+    /// Whether the animation applies before it starts and after it finishes.
+    pub fill_mode: AnimationFillMode,
+    /// The timing function used between keyframes which don't define their own.
+    pub timing_function: TimingFunction,
     /// List of animation keyframes as (normalized time, value).
     pub keyframes: Vec<Keyframe<T>>,
     /// The output of value of the animation.
@@ -32,6 +86,15 @@ pub(crate) struct AnimationState<T: Interpolator> {
     pub persistent: bool,
     /// How far through the animation between 0.0 and 1.0.
     pub t: f32,
+    /// The speed at which the animation plays, which is negative when playing in reverse.
+    pub rate: f32,
+    /// Whether the animation is paused.
+    pub paused: bool,
+    /// The time the animation had played for at the start time, in seconds.
+    pub time_offset: f32,
+    /// The spring which moves the animation from its first to its last keyframe, in which case
+    /// the animation ignores its duration and finishes when the spring comes to rest.
+    pub spring: Option<SpringMotion>,
 
     pub dt: f32,
 
@@ -54,13 +117,21 @@ where
     pub(crate) fn new(id: Animation) -> Self {
         AnimationState {
             id,
-            start_time: Instant::now(),
+            start_time: crate::util::now(),
             duration: Duration::new(0, 0),
             delay: Duration::new(0, 0),
+            iterations: 1.0,
+            direction: AnimationDirection::Normal,
+            fill_mode: AnimationFillMode::Backwards,
+            timing_function: TimingFunction::linear(),
             keyframes: Vec::new(),
             output: None,
             persistent: false,
             t: 0.0,
+            rate: 1.0,
+            paused: false,
+            time_offset: 0.0,
+            spring: None,
             dt: 0.0,
             active: false,
             entities: HashSet::new(),
@@ -94,13 +165,237 @@ where
     pub(crate) fn play(&mut self, entity: Entity) {
         self.active = true;
         self.t = 0.0;
-        self.start_time = Instant::now();
+        self.start_time = crate::util::now();
+        self.time_offset = 0.0;
         self.entities.insert(entity);
     }
 
+    /// Restarts the animation from the beginning at the given time.
+    pub(crate) fn restart(&mut self, time: Instant) {
+        self.start_time = time;
+        self.time_offset = 0.0;
+    }
+
+    // Returns the time the animation has played for, in seconds, including the delay.
+    fn elapsed(&self, time: Instant) -> f32 {
+        if self.paused {
+            self.time_offset
+        } else {
+            self.time_offset
+                + time.saturating_duration_since(self.start_time).as_secs_f32() * self.rate
+        }
+    }
+
+    // Moves the start time of the animation to the given time, so that the playback can be
+    // changed from that point. A finished animation is kept at its end.
+    fn rebase(&mut self, time: Instant) {
+        let mut elapsed = self.elapsed(time);
+        if let Some(motion) = &self.spring {
+            let end = self.delay.as_secs_f32() + motion.settling_time;
+            elapsed = elapsed.clamp(0.0, end);
+        } else if self.iterations.is_finite() {
+            let end = (self.iterations + self.dt) * self.duration.as_secs_f32();
+            elapsed = elapsed.clamp(0.0, end.max(0.0));
+        }
+
+        self.time_offset = elapsed;
+        self.start_time = time;
+    }
+
+    /// Applies a playback command to the animation at the given time.
+    pub(crate) fn control(&mut self, command: PlaybackCommand, time: Instant) {
+        self.rebase(time);
+
+        match command {
+            PlaybackCommand::Pause => self.paused = true,
+            PlaybackCommand::Resume => self.paused = false,
+            PlaybackCommand::Reverse => self.rate = -self.rate,
+            PlaybackCommand::SetRate(rate) => self.rate = rate,
+            PlaybackCommand::Seek(offset) => {
+                self.time_offset = self.delay.as_secs_f32() + offset.as_secs_f32();
+            }
+        }
+
+        // A finished animation which is still applied can be played again, and is otherwise
+        // finished again by the next update.
+        self.t = 0.0;
+    }
+
     pub(crate) fn is_transition(&self) -> bool {
         !(self.from_rule == usize::MAX && self.to_rule == usize::MAX)
     }
+
+    /// Applies the given playback options to the animation.
+    pub(crate) fn set_options(&mut self, options: &AnimationOptions) {
+        self.duration = options.duration;
+        self.delay = options.delay;
+        self.iterations = options.iterations.max(0.0);
+        self.direction = options.direction;
+        self.fill_mode = options.fill_mode;
+        self.timing_function = options.timing_function.clone();
+        self.persistent = options.fill_mode.fills_forwards();
+    }
+}
+
+impl<T> AnimationState<T>
+where
+    T: Interpolator + Clone,
+{
+    /// Updates the output of the animation for the given time, returning true if the animation
+    /// finished.
+    pub(crate) fn update(&mut self, time: Instant) -> bool {
+        if self.keyframes.is_empty() {
+            return false;
+        }
+
+        let elapsed = self.elapsed(time);
+        if let Some(motion) = self.spring {
+            return self.update_spring(motion, elapsed);
+        }
+
+        let duration = self.duration.as_secs_f32();
+
+        // The progress is the number of iterations played so far.
+        let progress = if duration > 0.0 {
+            elapsed / duration - self.dt
+        } else if elapsed >= self.delay.as_secs_f32() {
+            self.iterations
+        } else {
+            -1.0
+        };
+
+        // An animation playing in reverse finishes at its start.
+        if self.rate < 0.0 && progress <= 0.0 {
+            self.t = 1.0;
+            self.output = Some(self.value_at(0.0, 0.0));
+            return true;
+        }
+
+        // Still waiting for the delay to pass.
+        if progress < 0.0 {
+            self.output = self.fill_mode.fills_backwards().then(|| self.value_at(0.0, 0.0));
+            return false;
+        }
+
+        let (iteration, fraction) = if progress >= self.iterations {
+            self.t = 1.0;
+            if self.iterations == 0.0 {
+                (0.0, 0.0)
+            } else if self.iterations.fract() == 0.0 {
+                // A whole number of iterations finishes at the end of the last iteration.
+                (self.iterations - 1.0, 1.0)
+            } else {
+                (self.iterations.floor(), self.iterations.fract())
+            }
+        } else {
+            // An animation which repeats forever never finishes.
+            self.t = if self.iterations.is_finite() { progress / self.iterations } else { 0.0 };
+            (progress.floor(), progress.fract())
+        };
+
+        self.output = Some(self.value_at(iteration, fraction));
+
+        self.t == 1.0
+    }
+
+    // Updates the output of an animation which follows a spring, returning true if the spring
+    // came to rest.
+    fn update_spring(&mut self, motion: SpringMotion, elapsed: f32) -> bool {
+        let time = elapsed - self.delay.as_secs_f32();
+        let first = &self.keyframes[0].value;
+        let last = &self.keyframes[self.keyframes.len() - 1].value;
+
+        if self.rate < 0.0 && time <= 0.0 {
+            self.t = 1.0;
+            self.output = Some(first.clone());
+            return true;
+        }
+
+        if time < 0.0 {
+            self.output = self.fill_mode.fills_backwards().then(|| first.clone());
+            return false;
+        }
+
+        if time >= motion.settling_time {
+            self.t = 1.0;
+            self.output = Some(last.clone());
+            return true;
+        }
+
+        // The spring may overshoot, so the progress is not clamped.
+        let (progress, _) = motion.progress(time);
+        self.t = time / motion.settling_time;
+        self.output = Some(T::interpolate(first, last, progress));
+
+        false
+    }
+
+    /// Moves a spring animation towards a new target from its current value at the given time,
+    /// keeping its current velocity.
+    pub(crate) fn retarget(&mut self, target: T, time: Instant) {
+        let Some(motion) = self.spring else {
+            return;
+        };
+
+        if self.keyframes.is_empty() {
+            return;
+        }
+
+        let elapsed = (self.elapsed(time) - self.delay.as_secs_f32()).max(0.0);
+        let (progress, velocity) = motion.progress(elapsed);
+
+        let first = &self.keyframes[0].value;
+        let last = &self.keyframes[self.keyframes.len() - 1].value;
+        let current = T::interpolate(first, last, progress);
+
+        // The velocity is relative to the distance being animated, so is scaled to the new distance.
+        let velocity = match (T::distance(first, last), T::distance(&current, &target)) {
+            (Some(old), Some(new)) if new.abs() > f32::EPSILON => velocity * old / new,
+            (Some(_), Some(_)) => 0.0,
+            _ => velocity,
+        };
+
+        self.keyframes.truncate(1);
+        self.keyframes[0].value = current;
+        self.keyframes.push(Keyframe { time: 1.0, value: target, timing_function: None });
+
+        self.spring = Some(SpringMotion::new(motion.spring, velocity));
+        self.restart(time);
+        // The spring is already moving, so the delay doesn't apply again.
+        self.time_offset = self.delay.as_secs_f32();
+        self.rate = 1.0;
+        self.paused = false;
+        self.t = 0.0;
+    }
+
+    // Returns the value of the animation at the given fraction of the given iteration.
+    fn value_at(&self, iteration: f32, fraction: f32) -> T {
+        if self.keyframes.len() == 1 {
+            return self.keyframes[0].value.clone();
+        }
+
+        let forwards = match self.direction {
+            AnimationDirection::Normal => true,
+            AnimationDirection::Reverse => false,
+            AnimationDirection::Alternate => (iteration as u32) % 2 == 0,
+            AnimationDirection::AlternateReverse => (iteration as u32) % 2 == 1,
+        };
+
+        let x = if forwards { fraction } else { 1.0 - fraction }.clamp(0.0, 1.0);
+
+        let mut i = 0;
+        while i < self.keyframes.len() - 2 && self.keyframes[i + 1].time < x {
+            i += 1;
+        }
+        let start = &self.keyframes[i];
+        let end = &self.keyframes[i + 1];
+
+        let segment = end.time - start.time;
+        let local = if segment > 0.0 { ((x - start.time) / segment).clamp(0.0, 1.0) } else { 1.0 };
+
+        let timing_function = start.timing_function.as_ref().unwrap_or(&self.timing_function);
+        T::interpolate(&start.value, &end.value, timing_function.value(local))
+    }
 }
 
 impl<Prop> Default for AnimationState<Prop>
@@ -110,13 +405,21 @@ where
     fn default() -> Self {
         AnimationState {
             id: Animation::null(),
-            start_time: Instant::now(),
+            start_time: crate::util::now(),
             duration: Duration::new(0, 0),
             delay: Duration::new(0, 0),
+            iterations: 1.0,
+            direction: AnimationDirection::Normal,
+            fill_mode: AnimationFillMode::Backwards,
+            timing_function: TimingFunction::linear(),
             keyframes: Vec::new(),
             output: None,
             persistent: true,
             t: 0.0,
+            rate: 1.0,
+            paused: false,
+            time_offset: 0.0,
+            spring: None,
             dt: 0.0,
             active: false,
             entities: HashSet::new(),
@@ -125,3 +428,108 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Spring;
+
+    fn animation(iterations: f32, direction: AnimationDirection) -> (AnimationState<f32>, Instant) {
+        let mut state = AnimationState::new(Animation::null())
+            .with_keyframe(Keyframe { time: 0.0, value: 0.0, timing_function: None })
+            .with_keyframe(Keyframe { time: 1.0, value: 10.0, timing_function: None });
+
+        state.set_options(&AnimationOptions {
+            iterations,
+            direction,
+            ..AnimationOptions::new(Duration::from_secs(1), Duration::ZERO)
+        });
+
+        let start = Instant::now();
+        state.restart(start);
+        (state, start)
+    }
+
+    fn assert_output(state: &AnimationState<f32>, expected: f32) {
+        let output = state.output.unwrap();
+        assert!((output - expected).abs() < 1e-3, "expected {} but got {}", expected, output);
+    }
+
+    #[test]
+    fn alternate_iterations() {
+        let (mut state, start) = animation(2.0, AnimationDirection::Alternate);
+
+        assert!(!state.update(start + Duration::from_millis(250)));
+        assert_output(&state, 2.5);
+
+        assert!(!state.update(start + Duration::from_millis(1250)));
+        assert_output(&state, 7.5);
+
+        assert!(state.update(start + Duration::from_millis(2500)));
+        assert_output(&state, 0.0);
+        assert_eq!(state.t, 1.0);
+    }
+
+    #[test]
+    fn pause_and_seek() {
+        let (mut state, start) = animation(1.0, AnimationDirection::Normal);
+
+        state.control(PlaybackCommand::Pause, start + Duration::from_millis(500));
+        state.update(start + Duration::from_secs(5));
+        assert_output(&state, 5.0);
+
+        state.control(PlaybackCommand::Seek(Duration::from_millis(250)), start);
+        state.update(start + Duration::from_secs(5));
+        assert_output(&state, 2.5);
+
+        let resumed = start + Duration::from_secs(5);
+        state.control(PlaybackCommand::Resume, resumed);
+        state.update(resumed + Duration::from_millis(250));
+        assert_output(&state, 5.0);
+    }
+
+    #[test]
+    fn reverse_and_rate() {
+        let (mut state, start) = animation(1.0, AnimationDirection::Normal);
+
+        state.control(PlaybackCommand::Reverse, start + Duration::from_millis(500));
+        assert!(!state.update(start + Duration::from_millis(750)));
+        assert_output(&state, 2.5);
+
+        state.control(PlaybackCommand::SetRate(2.0), start + Duration::from_millis(750));
+        assert!(!state.update(start + Duration::from_millis(1000)));
+        assert_output(&state, 7.5);
+
+        // Reversing a finished animation plays it back from the end.
+        assert!(state.update(start + Duration::from_secs(2)));
+        state.control(PlaybackCommand::Reverse, start + Duration::from_secs(2));
+        assert!(!state.update(start + Duration::from_millis(2250)));
+        assert_output(&state, 5.0);
+
+        assert!(state.update(start + Duration::from_millis(2500)));
+        assert_output(&state, 0.0);
+    }
+
+    #[test]
+    fn spring_retarget_keeps_velocity() {
+        let (mut state, start) = animation(1.0, AnimationDirection::Normal);
+        state.spring = Some(SpringMotion::new(Spring::new(1.0, 100.0, 20.0), 0.0));
+
+        let time = start + Duration::from_millis(100);
+        assert!(!state.update(time));
+        let value = state.output.unwrap();
+        let (_, velocity) = state.spring.unwrap().progress(0.1);
+
+        // Moving the target to the far side of the current value keeps the same velocity.
+        state.retarget(2.0 * value, time);
+        assert!(!state.update(time));
+        assert_output(&state, value);
+
+        let (_, retargeted) = state.spring.unwrap().progress(0.0);
+        assert!((retargeted * value - velocity * 10.0).abs() < 1e-3);
+
+        // The spring ignores the duration and comes to rest at the new target.
+        assert!(state.update(time + Duration::from_secs(10)));
+        assert_output(&state, 2.0 * value);
+    }
+}
@@ -12,6 +12,12 @@ use crate::style::ImageOrGradient;
 /// A trait which describes how a property is interpolated for animations.
 pub(crate) trait Interpolator {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self;
+
+    /// Returns the signed distance from `start` to `end` for properties with a single dimension,
+    /// which is used to keep the velocity of a spring when its target changes.
+    fn distance(_start: &Self, _end: &Self) -> Option<f32> {
+        None
+    }
 }
 
 // Implementations of `Interpolator` for various properties.
@@ -19,6 +25,10 @@ impl Interpolator for f32 {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
         start + (end - start) * t
     }
+
+    fn distance(start: &Self, end: &Self) -> Option<f32> {
+        Some(end - start)
+    }
 }
 
 impl Interpolator for i32 {
@@ -49,6 +59,15 @@ impl Interpolator for Units {
             Units::Auto => *end,
         }
     }
+
+    fn distance(start: &Self, end: &Self) -> Option<f32> {
+        match (start, end) {
+            (Units::Pixels(s), Units::Pixels(e))
+            | (Units::Percentage(s), Units::Percentage(e))
+            | (Units::Stretch(s), Units::Stretch(e)) => Some(e - s),
+            _ => None,
+        }
+    }
 }
 
 impl Interpolator for Display {
@@ -65,6 +84,10 @@ impl Interpolator for Opacity {
     fn interpolate(start: &Self, end: &Self, t: f32) -> Self {
         Opacity(start.0 + (end.0 - start.0) * t)
     }
+
+    fn distance(start: &Self, end: &Self) -> Option<f32> {
+        Some(end.0 - start.0)
+    }
 }
 
 impl Interpolator for Color {
@@ -107,6 +130,13 @@ impl Interpolator for LengthValue {
             _ => LengthValue::default(),
         }
     }
+
+    fn distance(start: &Self, end: &Self) -> Option<f32> {
+        match (start, end) {
+            (LengthValue::Px(start_val), LengthValue::Px(end_val)) => Some(end_val - start_val),
+            _ => None,
+        }
+    }
 }
 
 impl Interpolator for Length {
@@ -119,6 +149,15 @@ impl Interpolator for Length {
             _ => Length::default(),
         }
     }
+
+    fn distance(start: &Self, end: &Self) -> Option<f32> {
+        match (start, end) {
+            (Length::Value(start_val), Length::Value(end_val)) => {
+                LengthValue::distance(start_val, end_val)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Interpolator for LengthOrPercentage {
@@ -136,6 +175,19 @@ impl Interpolator for LengthOrPercentage {
             _ => LengthOrPercentage::default(),
         }
     }
+
+    fn distance(start: &Self, end: &Self) -> Option<f32> {
+        match (start, end) {
+            (LengthOrPercentage::Length(start_val), LengthOrPercentage::Length(end_val)) => {
+                Length::distance(start_val, end_val)
+            }
+            (
+                LengthOrPercentage::Percentage(start_val),
+                LengthOrPercentage::Percentage(end_val),
+            ) => Some(end_val - start_val),
+            _ => None,
+        }
+    }
 }
 
 impl Interpolator for LengthPercentageOrAuto {
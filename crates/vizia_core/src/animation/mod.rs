@@ -1,12 +1,18 @@
 mod animation_id;
 pub use animation_id::{AnimId, Animation};
 
+mod animation_event;
+pub use animation_event::AnimationEvent;
+
 mod animation_state;
-pub(crate) use animation_state::{AnimationState, Keyframe};
+pub(crate) use animation_state::{AnimationOptions, AnimationState, Keyframe, PlaybackCommand};
 
 mod interpolator;
 pub(crate) use interpolator::Interpolator;
 
+mod spring;
+pub(crate) use spring::{Spring, SpringMotion};
+
 mod timing_function;
 pub(crate) use timing_function::TimingFunction;
 
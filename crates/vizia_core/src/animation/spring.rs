@@ -0,0 +1,154 @@
+/// The distance from the target, relative to the animated distance, at which a spring is at rest.
+const REST_DISTANCE: f32 = 0.001;
+/// The longest time a spring is allowed to take to come to rest, in seconds.
+const MAX_SETTLING_TIME: f32 = 10.0;
+/// The time step used to find the settling time of a spring, in seconds.
+const SETTLING_STEP: f32 = 1.0 / 120.0;
+
+/// A damped spring which moves the progress of an animation from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Spring {
+    pub mass: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+impl Spring {
+    pub fn new(mass: f32, stiffness: f32, damping: f32) -> Self {
+        Self {
+            mass: mass.max(f32::EPSILON),
+            stiffness: stiffness.max(f32::EPSILON),
+            damping: damping.max(0.0),
+        }
+    }
+
+    // Returns the undamped angular frequency and the damping ratio of the spring.
+    fn parameters(&self) -> (f32, f32) {
+        let frequency = (self.stiffness / self.mass).sqrt();
+        let ratio = self.damping / (2.0 * (self.stiffness * self.mass).sqrt());
+        (frequency, ratio)
+    }
+
+    /// Returns the progress and velocity of the spring at the given time, in seconds, when it
+    /// starts at a progress of 0 with the given velocity.
+    pub fn progress(&self, velocity: f32, time: f32) -> (f32, f32) {
+        let (w0, zeta) = self.parameters();
+
+        // Displacement from the target
+        let x0 = -1.0;
+        let v0 = velocity;
+
+        let (x, v) = if (zeta - 1.0).abs() < 1e-4 {
+            // Critically damped
+            let b = v0 + w0 * x0;
+            let e = (-w0 * time).exp();
+            (e * (x0 + b * time), e * (b - w0 * (x0 + b * time)))
+        } else if zeta < 1.0 {
+            // Under damped
+            let wd = w0 * (1.0 - zeta * zeta).sqrt();
+            let a = x0;
+            let b = (v0 + zeta * w0 * x0) / wd;
+            let e = (-zeta * w0 * time).exp();
+            let (sin, cos) = (wd * time).sin_cos();
+            let x = e * (a * cos + b * sin);
+            let v = e * ((b * wd - zeta * w0 * a) * cos - (a * wd + zeta * w0 * b) * sin);
+            (x, v)
+        } else {
+            // Over damped
+            let s = (zeta * zeta - 1.0).sqrt();
+            let r1 = -w0 * (zeta - s);
+            let r2 = -w0 * (zeta + s);
+            let c2 = (v0 - r1 * x0) / (r2 - r1);
+            let c1 = x0 - c2;
+            let (e1, e2) = ((r1 * time).exp(), (r2 * time).exp());
+            (c1 * e1 + c2 * e2, c1 * r1 * e1 + c2 * r2 * e2)
+        };
+
+        (1.0 + x, v)
+    }
+
+    /// Returns the time, in seconds, for the spring to come to rest when it starts at a progress
+    /// of 0 with the given velocity.
+    pub fn settling_time(&self, velocity: f32) -> f32 {
+        let (w0, _) = self.parameters();
+
+        let mut time = 0.0;
+        while time < MAX_SETTLING_TIME {
+            let (progress, velocity) = self.progress(velocity, time);
+            let distance = (progress - 1.0).hypot(velocity / w0);
+            if distance < REST_DISTANCE {
+                return time;
+            }
+
+            time += SETTLING_STEP;
+        }
+
+        MAX_SETTLING_TIME
+    }
+}
+
+/// The motion of a spring animation, which starts at a progress of 0 with some velocity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SpringMotion {
+    pub spring: Spring,
+    /// The initial velocity, in units of progress per second.
+    pub velocity: f32,
+    /// The time for the spring to come to rest, in seconds.
+    pub settling_time: f32,
+}
+
+impl SpringMotion {
+    pub fn new(spring: Spring, velocity: f32) -> Self {
+        Self { spring, velocity, settling_time: spring.settling_time(velocity) }
+    }
+
+    /// Returns the progress and velocity at the given time, in seconds.
+    pub fn progress(&self, time: f32) -> (f32, f32) {
+        if time >= self.settling_time {
+            (1.0, 0.0)
+        } else {
+            self.spring.progress(self.velocity, time)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comes_to_rest() {
+        for spring in [
+            Spring::new(1.0, 100.0, 10.0),
+            Spring::new(1.0, 100.0, 20.0),
+            Spring::new(1.0, 100.0, 40.0),
+        ] {
+            let motion = SpringMotion::new(spring, 0.0);
+            assert!(motion.settling_time < MAX_SETTLING_TIME);
+            let (progress, velocity) = motion.progress(0.0);
+            assert!(progress.abs() < 1e-5 && velocity.abs() < 1e-5);
+            assert_eq!(motion.progress(motion.settling_time), (1.0, 0.0));
+
+            let (progress, _) = spring.progress(0.0, motion.settling_time - SETTLING_STEP);
+            assert!((progress - 1.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn under_damped_overshoots() {
+        let spring = Spring::new(1.0, 100.0, 5.0);
+        let overshoot =
+            (1..100).map(|i| spring.progress(0.0, i as f32 * 0.01).0).fold(f32::MIN, f32::max);
+        assert!(overshoot > 1.0);
+    }
+
+    #[test]
+    fn initial_velocity() {
+        let spring = Spring::new(1.0, 100.0, 20.0);
+        let (_, velocity) = spring.progress(-2.0, 0.0);
+        assert!((velocity + 2.0).abs() < 1e-5);
+
+        // Moving away from the target delays the spring.
+        assert!(spring.progress(-2.0, 0.1).0 < spring.progress(0.0, 0.1).0);
+    }
+}
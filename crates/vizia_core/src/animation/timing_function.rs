@@ -1,9 +1,21 @@
-#[derive(Debug, Clone, Copy)]
-pub(crate) struct TimingFunction {
-    x1: f32,
-    x2: f32,
-    y1: f32,
-    y2: f32,
+use vizia_style::{EasingFunction, StepPosition};
+
+use super::{Spring, SpringMotion};
+
+/// Maps the normalized time of an animation to the normalized progress of the animated value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TimingFunction {
+    CubicBezier {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+    Steps(u32, StepPosition),
+    /// Linear interpolation between (time, progress) points with increasing times.
+    Linear(Vec<(f32, f32)>),
+    /// The motion of a spring, scaled so that the spring comes to rest at the end.
+    Spring(SpringMotion),
 }
 
 impl Default for TimingFunction {
@@ -12,6 +24,27 @@ impl Default for TimingFunction {
     }
 }
 
+impl From<EasingFunction> for TimingFunction {
+    fn from(easing: EasingFunction) -> Self {
+        match easing {
+            EasingFunction::Linear => TimingFunction::linear(),
+            EasingFunction::Ease => TimingFunction::ease(),
+            EasingFunction::EaseIn => TimingFunction::ease_in(),
+            EasingFunction::EaseOut => TimingFunction::ease_out(),
+            EasingFunction::EaseInOut => TimingFunction::ease_in_out(),
+            EasingFunction::CubicBezier(x1, y1, x2, y2) => TimingFunction::new(x1, y1, x2, y2),
+            EasingFunction::Steps(count, position) => TimingFunction::Steps(count, position),
+            EasingFunction::LinearPoints(points) => TimingFunction::Linear(points),
+            EasingFunction::Spring { mass, stiffness, damping, velocity } => {
+                TimingFunction::Spring(SpringMotion::new(
+                    Spring::new(mass, stiffness, damping),
+                    velocity,
+                ))
+            }
+        }
+    }
+}
+
 impl TimingFunction {
     pub fn linear() -> Self {
         Self::new(0., 0., 1., 1.)
@@ -32,16 +65,74 @@ impl TimingFunction {
 
 impl TimingFunction {
     pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
-        Self { x1, y1, x2, y2 }
+        Self::CubicBezier { x1, y1, x2, y2 }
     }
 
     pub fn value(&self, x: f32) -> f32 {
-        // Linear
-        if self.x1 == self.y1 && self.x2 == self.y2 {
+        match self {
+            Self::CubicBezier { x1, y1, x2, y2 } => {
+                // Linear
+                if x1 == y1 && x2 == y2 {
+                    return x;
+                }
+
+                Self::calc_bezier(Self::find_t_for_x(x, *x1, *x2), *y1, *y2)
+            }
+
+            Self::Steps(count, position) => Self::steps(x, *count, *position),
+
+            Self::Linear(points) => Self::linear_points(x, points),
+
+            Self::Spring(motion) => {
+                if x >= 1.0 {
+                    1.0
+                } else {
+                    motion.progress(x * motion.settling_time).0
+                }
+            }
+        }
+    }
+
+    fn steps(x: f32, count: u32, position: StepPosition) -> f32 {
+        let count = count.max(1) as f32;
+        let jumps = match position {
+            StepPosition::JumpStart | StepPosition::JumpEnd => count,
+            StepPosition::JumpNone => (count - 1.0).max(1.0),
+            StepPosition::JumpBoth => count + 1.0,
+        };
+
+        let mut step = (x * count).floor();
+        if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+            step += 1.0;
+        }
+
+        step.clamp(0.0, jumps) / jumps
+    }
+
+    fn linear_points(x: f32, points: &[(f32, f32)]) -> f32 {
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
             return x;
+        };
+
+        if x <= first.0 {
+            return first.1;
+        }
+
+        if x >= last.0 {
+            return last.1;
+        }
+
+        // Use the last segment which contains x, so that points with equal times jump to the later value.
+        let Some(segment) = points.windows(2).rev().find(|segment| segment[0].0 <= x) else {
+            return first.1;
+        };
+
+        let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+        if x2 <= x1 {
+            return y2;
         }
 
-        Self::calc_bezier(self.find_t_for_x(x), self.y1, self.y2)
+        y1 + (y2 - y1) * (x - x1) / (x2 - x1)
     }
 
     fn calc_bezier(t: f32, a1: f32, a2: f32) -> f32 {
@@ -60,16 +151,16 @@ impl TimingFunction {
         3.0 * a(a1, a2) * t * t + 2.0 * b(a1, a2) * t + c(a1)
     }
 
-    fn find_t_for_x(&self, x: f32) -> f32 {
+    fn find_t_for_x(x: f32, x1: f32, x2: f32) -> f32 {
         let mut guess = x;
         let mut error = f32::MAX;
         for _ in 0..8 {
-            let pos = Self::calc_bezier(guess, self.x1, self.x2);
+            let pos = Self::calc_bezier(guess, x1, x2);
             error = pos - x;
             if error.abs() <= 0.0000001 {
                 return guess;
             }
-            let slope = Self::calc_bezier_slope(guess, self.x1, self.x2);
+            let slope = Self::calc_bezier_slope(guess, x1, x2);
             guess -= error / slope;
         }
         if error.abs() <= 0.0000001 {
@@ -83,6 +174,7 @@ impl TimingFunction {
 #[cfg(test)]
 mod tests {
     use super::TimingFunction;
+    use vizia_style::StepPosition;
 
     #[test]
     fn linear() {
@@ -95,4 +187,47 @@ mod tests {
         let timing_func = TimingFunction::ease();
         assert_eq!(timing_func.value(0.25), 0.4085106);
     }
+
+    #[test]
+    fn steps() {
+        let timing_func = TimingFunction::Steps(4, StepPosition::JumpEnd);
+        assert_eq!(timing_func.value(0.0), 0.0);
+        assert_eq!(timing_func.value(0.3), 0.25);
+        assert_eq!(timing_func.value(0.99), 0.75);
+        assert_eq!(timing_func.value(1.0), 1.0);
+
+        let timing_func = TimingFunction::Steps(4, StepPosition::JumpStart);
+        assert_eq!(timing_func.value(0.0), 0.25);
+        assert_eq!(timing_func.value(0.99), 1.0);
+
+        let timing_func = TimingFunction::Steps(3, StepPosition::JumpNone);
+        assert_eq!(timing_func.value(0.0), 0.0);
+        assert_eq!(timing_func.value(0.5), 0.5);
+        assert_eq!(timing_func.value(1.0), 1.0);
+
+        let timing_func = TimingFunction::Steps(3, StepPosition::JumpBoth);
+        assert_eq!(timing_func.value(0.0), 0.25);
+        assert_eq!(timing_func.value(0.99), 0.75);
+        assert_eq!(timing_func.value(1.0), 1.0);
+    }
+
+    #[test]
+    fn linear_points() {
+        let timing_func =
+            TimingFunction::Linear(vec![(0.0, 0.0), (0.5, 1.0), (0.5, 0.5), (1.0, 1.0)]);
+        assert_eq!(timing_func.value(0.25), 0.5);
+        assert_eq!(timing_func.value(0.5), 0.5);
+        assert_eq!(timing_func.value(0.75), 0.75);
+        assert_eq!(timing_func.value(1.0), 1.0);
+    }
+
+    #[test]
+    fn spring() {
+        let timing_func = TimingFunction::from(EasingFunction::spring(1.0, 100.0, 5.0));
+        assert!(timing_func.value(0.0).abs() < 1e-5);
+        assert_eq!(timing_func.value(1.0), 1.0);
+
+        // An under damped spring overshoots its target.
+        assert!((1..100).any(|i| timing_func.value(i as f32 / 100.0) > 1.0));
+    }
 }
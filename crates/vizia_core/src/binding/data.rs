@@ -74,10 +74,12 @@ impl_data_simple!(KeyChord);
 impl_data_simple!(FamilyOwned);
 impl_data_simple!(FontWeight);
 impl_data_simple!(TextAlign);
+impl_data_simple!(Direction);
 impl_data_simple!(LengthOrPercentage);
 impl_data_simple!(CornerShape);
 impl_data_simple!(Shadow);
 impl_data_simple!(TextDecorationLine);
+impl_data_simple!(Rope);
 
 impl Data for &'static str {
     fn same(&self, other: &Self) -> bool {
@@ -320,7 +322,7 @@ impl Data for morphorm::Units {
     }
 }
 
-impl Data for morphorm::LayoutType {
+impl Data for LayoutType {
     fn same(&self, other: &Self) -> bool {
         *self == *other
     }
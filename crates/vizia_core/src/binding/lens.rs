@@ -122,6 +122,15 @@ pub trait LensExt: Lens {
     }
 
     fn map_ref<O: 'static, F: 'static + Fn(&Self::Target) -> &O>(self, map: F) -> MapRef<Self, O> {
+        self.try_map_ref(move |target| Some(map(target)))
+    }
+
+    /// Like [`map_ref`](LensExt::map_ref), except that the lens has no value while the closure
+    /// returns `None`, such as for an item which may be removed from a list.
+    fn try_map_ref<O: 'static, F: 'static + Fn(&Self::Target) -> Option<&O>>(
+        self,
+        map: F,
+    ) -> MapRef<Self, O> {
         let id = MAP_MANAGER.with_borrow_mut(|f| f.create());
         let entity = CURRENT.with_borrow(|f| *f);
         MAPS.with_borrow_mut(|f| {
@@ -153,7 +162,7 @@ pub struct MapState<T, O> {
 }
 
 pub struct MapRefState<T, O> {
-    closure: Rc<dyn Fn(&T) -> &O>,
+    closure: Rc<dyn Fn(&T) -> Option<&O>>,
 }
 
 pub struct Map<L: Lens, O> {
@@ -227,7 +236,7 @@ impl<L: Lens, O: 'static> Lens for MapRef<L, O> {
             Some(closure.clone())
         })?;
 
-        Some(LensValue::Borrowed(closure(target)))
+        closure(target).map(LensValue::Borrowed)
     }
 }
 
@@ -116,6 +116,7 @@ impl_res_simple!(FontSlant);
 impl_res_simple!(CornerShape);
 impl_res_simple!(Angle);
 impl_res_simple!(TextAlign);
+impl_res_simple!(Direction);
 impl_res_simple!(TextOverflow);
 impl_res_simple!(LineClamp);
 impl_res_clone!(Shadow);
@@ -111,4 +111,14 @@ impl AccessNode {
     pub fn set_max_numeric_value(&mut self, value: f64) {
         self.node_builder.set_max_numeric_value(value);
     }
+
+    /// Sets whether the node, such as an item of a tree, is expanded to show its children.
+    pub fn set_expanded(&mut self, expanded: bool) {
+        self.node_builder.set_expanded(expanded);
+    }
+
+    /// Sets whether the node, such as an item of a tree, is selected.
+    pub fn set_selected(&mut self, selected: bool) {
+        self.node_builder.set_selected(selected);
+    }
 }
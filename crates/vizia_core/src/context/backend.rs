@@ -129,6 +129,13 @@ impl BackendContext {
         self.0.style.dpi_factor = scale;
     }
 
+    /// Sets the scale factor of the window with the given entity, such as when it is moved to another monitor.
+    pub fn set_window_scale_factor(&mut self, window_entity: Entity, scale: f64) {
+        if let Some(window_state) = self.0.windows.get_mut(&window_entity) {
+            window_state.scale_factor = scale as f32;
+        }
+    }
+
     /// Sets the size of the window.
     pub fn set_window_size(
         &mut self,
@@ -157,6 +164,14 @@ impl BackendContext {
         }
     }
 
+    /// Sets whether a window is hidden from the user, such as when it is minimized or covered.
+    /// Animated images in an occluded window are paused.
+    pub fn set_window_occluded(&mut self, window_entity: Entity, occluded: bool) {
+        if let Some(state) = self.0.windows.get_mut(&window_entity) {
+            state.is_occluded = occluded;
+        }
+    }
+
     /// Temporarily sets the current entity, calls the provided closure, and then resets the current entity back to previous.
     pub fn with_current(&mut self, e: Entity, f: impl FnOnce(&mut Context)) {
         let prev = self.0.current;
@@ -165,6 +180,15 @@ impl BackendContext {
         self.0.current = prev;
     }
 
+    /// Replaces the system time used by timers, scheduled events and animations with a virtual time
+    /// on the current thread. Passing `None` restores the system time.
+    ///
+    /// You should not call this method unless you are writing a windowing backend which drives the
+    /// clock itself, such as the headless backend.
+    pub fn set_virtual_time(&mut self, time: Option<Instant>) {
+        crate::util::set_virtual_time(time);
+    }
+
     /// Returns the scale factor.
     pub fn scale_factor(&self) -> f32 {
         self.0.scale_factor()
@@ -278,7 +302,7 @@ impl BackendContext {
     }
 
     pub fn emit_scheduled_events(&mut self) {
-        let now = Instant::now();
+        let now = crate::util::now();
         while let Some(timed_event) = self.0.event_schedule.peek() {
             if timed_event.time <= now {
                 self.0.event_queue.push_back(self.0.event_schedule.pop().unwrap().event);
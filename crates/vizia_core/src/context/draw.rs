@@ -18,7 +18,7 @@ use crate::cache::CachedData;
 use crate::events::ViewHandler;
 use crate::model::ModelDataStore;
 use crate::prelude::*;
-use crate::resource::{ImageOrSvg, ResourceManager};
+use crate::resource::{ImageFrame, ResourceManager};
 use crate::text::TextContext;
 use vizia_input::MouseState;
 
@@ -423,6 +423,12 @@ impl<'a> DrawContext<'a> {
     get_color_property!(caret_color);
     get_color_property!(font_color);
 
+    /// Returns the color of the custom property with the given name, e.g. `--accent`, of the
+    /// current view, or `None` if the property is not set or is not a color.
+    pub fn custom_color(&self, name: &str) -> Option<Color> {
+        self.style.custom_property_values.get(self.current)?.get(name)?.parse().ok()
+    }
+
     /// Returns whether the current view should have its text wrapped.
     pub fn text_wrap(&self) -> bool {
         self.style.text_wrap.get(self.current).copied().unwrap_or(true)
@@ -981,8 +987,14 @@ impl<'a> DrawContext<'a> {
                     ImageOrGradient::Image(image_name) => {
                         if let Some(image_id) = self.resource_manager.image_ids.get(image_name) {
                             if let Some(image) = self.resource_manager.images.get(image_id) {
-                                match &image.image {
-                                    ImageOrSvg::Image(image) => {
+                                let playback = self
+                                    .resource_manager
+                                    .image_playback
+                                    .get(&self.current)
+                                    .copied()
+                                    .unwrap_or_default();
+                                match image.image.frame(&playback) {
+                                    ImageFrame::Image(image) => {
                                         let image_width = image.width();
                                         let image_height = image.height();
                                         let (width, height) = if let Some(background_size) =
@@ -1070,7 +1082,7 @@ impl<'a> DrawContext<'a> {
                                         canvas.draw_path(path, &paint);
                                     }
 
-                                    ImageOrSvg::Svg(svg) => {
+                                    ImageFrame::Svg(svg) => {
                                         canvas.save_layer(&SaveLayerRec::default());
                                         canvas.translate((bounds.x, bounds.y));
                                         let (scale_x, scale_y) = (
@@ -1143,7 +1155,14 @@ impl<'a> DrawContext<'a> {
                 padding_top = (vertical_free_space * val / vertical_flex_sum).round()
             }
 
-            let mut padding_left = match self.child_left() {
+            // The horizontal child space is mirrored in a right-to-left direction.
+            let (child_left, child_right) = if self.style.is_rtl(self.current) {
+                (self.child_right(), self.child_left())
+            } else {
+                (self.child_left(), self.child_right())
+            };
+
+            let mut padding_left = match child_left {
                 Units::Pixels(val) => val,
                 Units::Stretch(val) => {
                     horizontal_flex_sum += val;
@@ -1152,7 +1171,7 @@ impl<'a> DrawContext<'a> {
                 _ => 0.0,
             };
 
-            let padding_right = match self.child_right() {
+            let padding_right = match child_right {
                 Units::Pixels(val) => val,
                 Units::Stretch(val) => {
                     horizontal_flex_sum += val;
@@ -1164,7 +1183,7 @@ impl<'a> DrawContext<'a> {
             let horizontal_free_space =
                 bounds.width() - paragraph.max_width() - padding_left - padding_right;
 
-            if let Units::Stretch(val) = self.child_left() {
+            if let Units::Stretch(val) = child_left {
                 padding_left = (horizontal_free_space * val / horizontal_flex_sum).round()
             }
 
@@ -2,18 +2,21 @@ use std::any::{Any, TypeId};
 use std::collections::{BinaryHeap, VecDeque};
 #[cfg(feature = "clipboard")]
 use std::error::Error;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use hashbrown::{HashMap, HashSet};
+use log::warn;
 use vizia_storage::{LayoutTreeIterator, TreeIterator};
 use vizia_window::WindowPosition;
 
-use crate::animation::{AnimId, Interpolator};
+use crate::animation::{AnimId, AnimationOptions, Interpolator, PlaybackCommand};
 use crate::cache::CachedData;
 use crate::events::{TimedEvent, TimedEventHandle, TimerState, ViewHandler};
 use crate::model::ModelDataStore;
 use crate::prelude::*;
 use crate::resource::ResourceManager;
+use crate::style::StylesheetResources;
 use crate::tree::{focus_backward, focus_forward, is_navigatable};
 use vizia_input::MouseState;
 
@@ -378,7 +381,11 @@ impl<'a> EventContext<'a> {
     /// Trigger an animation with the given id to play on the current view.
     pub fn play_animation(&mut self, anim_id: impl AnimId, duration: Duration, delay: Duration) {
         if let Some(animation_id) = anim_id.get(self) {
-            self.style.enqueue_animation(self.current, animation_id, duration, delay);
+            self.style.enqueue_animation(
+                self.current,
+                animation_id,
+                AnimationOptions::new(duration, delay),
+            );
         }
     }
 
@@ -392,7 +399,11 @@ impl<'a> EventContext<'a> {
     ) {
         if let Some(target_entity) = self.resolve_entity_identifier(target) {
             if let Some(animation_id) = anim_id.get(self) {
-                self.style.enqueue_animation(target_entity, animation_id, duration, delay)
+                self.style.enqueue_animation(
+                    target_entity,
+                    animation_id,
+                    AnimationOptions::new(duration, delay),
+                )
             }
         }
     }
@@ -406,6 +417,63 @@ impl<'a> EventContext<'a> {
         false
     }
 
+    /// Pauses an animation playing on the current view, returning false if it isn't playing.
+    pub fn pause_animation(&mut self, anim_id: impl AnimId) -> bool {
+        self.control_animation(anim_id, PlaybackCommand::Pause)
+    }
+
+    /// Resumes a paused animation on the current view, returning false if it isn't playing.
+    pub fn resume_animation(&mut self, anim_id: impl AnimId) -> bool {
+        self.control_animation(anim_id, PlaybackCommand::Resume)
+    }
+
+    /// Moves an animation playing on the current view to the given time after its delay,
+    /// returning false if it isn't playing.
+    ///
+    /// Seeking a paused animation shows the animation at that time without resuming it.
+    pub fn seek_animation(&mut self, anim_id: impl AnimId, time: Duration) -> bool {
+        self.control_animation(anim_id, PlaybackCommand::Seek(time))
+    }
+
+    /// Reverses the direction of an animation playing on the current view from its current
+    /// position, returning false if it isn't playing.
+    ///
+    /// An animation played in reverse finishes when it reaches its start.
+    pub fn reverse_animation(&mut self, anim_id: impl AnimId) -> bool {
+        self.control_animation(anim_id, PlaybackCommand::Reverse)
+    }
+
+    /// Sets the speed of an animation playing on the current view, where 1.0 is normal speed and
+    /// a negative rate plays the animation in reverse. Returns false if it isn't playing.
+    pub fn set_animation_rate(&mut self, anim_id: impl AnimId, rate: f32) -> bool {
+        self.control_animation(anim_id, PlaybackCommand::SetRate(rate))
+    }
+
+    /// Cancels an animation playing on the current view, returning false if it isn't playing.
+    ///
+    /// The view is sent an [`AnimationEvent::Interrupted`] event.
+    pub fn cancel_animation(&mut self, anim_id: impl AnimId) -> bool {
+        if let Some(animation_id) = anim_id.get(self) {
+            if self.style.stop_animation(self.current, animation_id) {
+                self.needs_relayout();
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn control_animation(&mut self, anim_id: impl AnimId, command: PlaybackCommand) -> bool {
+        if let Some(animation_id) = anim_id.get(self) {
+            if self.style.control_animation(self.current, animation_id, command) {
+                self.needs_relayout();
+                return true;
+            }
+        }
+
+        false
+    }
+
     /// Add a listener to an entity.
     ///
     /// A listener can be used to handle events which would not normally propagate to the entity.
@@ -752,19 +820,26 @@ impl<'a> EventContext<'a> {
 
         self.style.clear_style_rules();
 
-        let mut overall_theme = String::new();
+        let mut resources = StylesheetResources::default();
 
         // Reload built-in themes
         for theme in self.resource_manager.themes.iter() {
-            overall_theme += theme;
+            self.style.parse_theme(theme, None, &mut resources);
         }
 
-        for style_string in self.resource_manager.styles.iter().flat_map(|style| style.get_style())
-        {
-            overall_theme += &style_string;
+        // Stylesheets loaded from files are re-read so that changes to them, and to the files they
+        // import, are picked up.
+        for style in self.resource_manager.styles.iter() {
+            match style.get_style() {
+                Ok(style_string) => {
+                    self.style.parse_theme(&style_string, style.path(), &mut resources)
+                }
+
+                Err(err) => warn!("Failed to load stylesheet: {}", err),
+            }
         }
 
-        self.style.parse_theme(&overall_theme);
+        self.load_font_faces(resources.fonts);
 
         for entity in self.tree.into_iter() {
             self.style.needs_restyle(entity);
@@ -776,6 +851,36 @@ impl<'a> EventContext<'a> {
         Ok(())
     }
 
+    // Registers the fonts declared by `@font-face` rules which have not already been registered.
+    fn load_font_faces(&mut self, fonts: Vec<(String, PathBuf)>) {
+        let mut loaded = false;
+        for (family, path) in fonts {
+            if self.resource_manager.font_faces.contains(&(family.clone(), path.clone())) {
+                continue;
+            }
+
+            let typeface = std::fs::read(&path)
+                .ok()
+                .and_then(|data| self.text_context.default_font_manager.new_from_data(&data, None));
+
+            match typeface {
+                Some(typeface) => {
+                    self.text_context
+                        .asset_provider
+                        .register_typeface(typeface, Some(family.as_str()));
+                    self.resource_manager.font_faces.insert((family, path));
+                    loaded = true;
+                }
+
+                None => warn!("Failed to load font {} from {}", family, path.display()),
+            }
+        }
+
+        if loaded {
+            self.text_context.font_collection.clear_caches();
+        }
+    }
+
     /// Spawns a thread and provides a [ContextProxy] for sending events back to the main UI thread.
     pub fn spawn<F>(&self, target: F)
     where
@@ -1184,10 +1289,10 @@ impl<'a> EventContext<'a> {
         self.timers.push(TimerState {
             entity: Entity::root(),
             id,
-            time: Instant::now(),
+            time: crate::util::now(),
             interval,
             duration,
-            start_time: Instant::now(),
+            start_time: crate::util::now(),
             callback: Rc::new(callback),
             ticking: false,
             stopping: false,
@@ -1208,7 +1313,7 @@ impl<'a> EventContext<'a> {
         }
 
         self.modify_timer(timer, |timer_state| {
-            let now = Instant::now();
+            let now = crate::util::now();
             timer_state.start_time = now;
             timer_state.time = now;
             timer_state.entity = current;
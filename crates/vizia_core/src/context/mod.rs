@@ -8,7 +8,7 @@ mod event;
 mod proxy;
 mod resource;
 
-use log::debug;
+use log::{debug, warn};
 use skia_safe::{
     svg,
     textlayout::{FontCollection, TypefaceFontProvider},
@@ -17,6 +17,7 @@ use skia_safe::{
 use std::any::{Any, TypeId};
 use std::cell::RefCell;
 use std::collections::{BinaryHeap, VecDeque};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Mutex;
 use vizia_id::IdManager;
@@ -40,14 +41,19 @@ use crate::{
     binding::{BindingHandler, MapId},
     resource::StoredImage,
 };
-use crate::{cache::CachedData, resource::ImageOrSvg};
+use crate::{
+    cache::CachedData,
+    resource::{decode_animated_image, AnimatedImage, ImageOrSvg},
+};
 
 use crate::model::ModelDataStore;
 use crate::prelude::*;
 use crate::resource::ResourceManager;
+use crate::systems::{matches_selectors, reload_changed_images};
 use crate::text::TextContext;
 use vizia_input::MouseState;
 use vizia_storage::{ChildIterator, LayoutTreeIterator};
+use vizia_style::parse_selector_list;
 
 static DEFAULT_LAYOUT: &str = include_str!("../../resources/themes/default_layout.css");
 static DARK_THEME: &str = include_str!("../../resources/themes/dark_theme.css");
@@ -78,6 +84,8 @@ pub struct WindowState {
     pub is_modal: bool,
     pub should_close: bool,
     pub position: WindowPosition,
+    /// Whether the window is hidden from the user, such as when it is minimized or covered.
+    pub is_occluded: bool,
 }
 
 /// The main storage and control object for a Vizia application.
@@ -189,6 +197,7 @@ impl Context {
                     asset_provider,
                     text_bounds: Default::default(),
                     text_paragraphs: Default::default(),
+                    caret_stops: Default::default(),
                 }
             },
 
@@ -205,7 +214,7 @@ impl Context {
                 #[cfg(not(feature = "x11"))]
                 Box::new(NopClipboardContext::new().unwrap())
             },
-            click_time: Instant::now(),
+            click_time: crate::util::now(),
             clicks: 0,
             click_pos: (0.0, 0.0),
             click_button: MouseButton::Left,
@@ -416,6 +425,7 @@ impl Context {
                 // no need to drop them here. garbage collection happens after draw (policy based)
                 image.observers.remove(entity);
             }
+            self.resource_manager.image_playback.remove(entity);
 
             if let Some(identifier) = self.style.ids.get(*entity) {
                 self.entity_identifiers.remove(identifier);
@@ -496,6 +506,7 @@ impl Context {
             self.views.remove(entity);
             self.text_context.text_bounds.remove(*entity);
             self.text_context.text_paragraphs.remove(*entity);
+            self.text_context.caret_stops.remove(*entity);
             self.entity_manager.destroy(*entity);
         }
     }
@@ -568,6 +579,11 @@ impl Context {
         EventContext::new(self).reload_styles().expect("Failed to reload styles");
     }
 
+    /// Adds a stylesheet, either a CSS string or the path to a CSS file, to the application.
+    ///
+    /// Stylesheets loaded from a file may include other stylesheets with `@import` rules and
+    /// declare fonts with `@font-face` rules, with urls resolved relative to the file. Imported
+    /// files are re-read when the styles are reloaded.
     pub fn add_stylesheet(&mut self, style: impl IntoCssStr) -> Result<(), std::io::Error> {
         self.resource_manager.styles.push(Box::new(style));
 
@@ -595,10 +611,44 @@ impl Context {
         self.style.add_animation(animation)
     }
 
+    /// Sets the function which loads images which are used by name, such as by
+    /// `background-image: url("...")` or an [`Image`](crate::views::Image) view, but have not
+    /// been loaded. This replaces the default loader, [`ResourceContext::load_file`].
     pub fn set_image_loader<F: 'static + Fn(&mut ResourceContext, &str)>(&mut self, loader: F) {
         self.resource_manager.image_loader = Some(Box::new(loader));
     }
 
+    /// Sets the directory which the default image loader loads images with a relative path from.
+    /// By default relative paths are loaded from the working directory.
+    pub fn set_asset_root(&mut self, path: impl Into<PathBuf>) {
+        self.resource_manager.asset_root = path.into();
+    }
+
+    /// Polls the files which images were loaded from at the given interval, and reloads the images
+    /// whose files have changed, redrawing the views which show them. Calling this again changes
+    /// the interval.
+    ///
+    /// The files are those of images loaded by the default image loader, and those set with
+    /// [`Context::set_image_file`]. This is useful while designing icons and other images, so
+    /// that changes to them are shown without restarting the application.
+    pub fn watch_image_files(&mut self, interval: Duration) {
+        if let Some(timer) = self.resource_manager.image_watcher.take() {
+            self.stop_timer(timer);
+        }
+
+        let timer = self.add_timer(interval, None, reload_changed_images);
+        self.resource_manager.image_watcher = Some(timer);
+        self.with_current(Entity::root(), |cx| cx.start_timer(timer));
+    }
+
+    /// Sets the file which an image loaded from memory comes from, such as with
+    /// [`Context::load_image`], so that [`Context::watch_image_files`] reloads the image from the
+    /// file when it changes. A relative path is resolved against the asset root.
+    pub fn set_image_file(&mut self, name: &str, path: impl Into<PathBuf>) {
+        let path = self.resource_manager.asset_root.join(path.into());
+        self.resource_manager.watch_image_file(name, path);
+    }
+
     pub fn add_translation(&mut self, lang: LanguageIdentifier, ftl: impl ToString) {
         self.resource_manager.add_translation(lang, ftl.to_string());
     }
@@ -643,10 +693,10 @@ impl Context {
         self.timers.push(TimerState {
             entity: Entity::root(),
             id,
-            time: Instant::now(),
+            time: crate::util::now(),
             interval,
             duration,
-            start_time: Instant::now(),
+            start_time: crate::util::now(),
             callback: Rc::new(callback),
             ticking: false,
             stopping: false,
@@ -667,7 +717,7 @@ impl Context {
         }
 
         self.modify_timer(timer, |timer_state| {
-            let now = Instant::now();
+            let now = crate::util::now();
             timer_state.start_time = now;
             timer_state.time = now;
             timer_state.entity = current;
@@ -729,7 +779,7 @@ impl Context {
 
     // Tick all timers.
     pub(crate) fn tick_timers(&mut self) {
-        let now = Instant::now();
+        let now = crate::util::now();
         while let Some(next_timer_state) = self.running_timers.peek() {
             if next_timer_state.time <= now {
                 let mut timer_state = self.running_timers.pop().unwrap();
@@ -770,18 +820,22 @@ impl Context {
             id
         };
 
-        if let Some(image) =
+        // Every frame of an animated image is decoded up front.
+        let image = decode_animated_image(data).map(ImageOrSvg::Animated).or_else(|| {
             skia_safe::Image::from_encoded(unsafe { skia_safe::Data::new_bytes(data) })
-        {
+                .map(ImageOrSvg::Image)
+        });
+
+        if let Some(image) = image {
             match self.resource_manager.images.entry(id) {
                 Entry::Occupied(mut occ) => {
-                    occ.get_mut().image = ImageOrSvg::Image(image);
+                    occ.get_mut().image = image;
                     occ.get_mut().dirty = true;
                     occ.get_mut().retention_policy = policy;
                 }
                 Entry::Vacant(vac) => {
                     vac.insert(StoredImage {
-                        image: ImageOrSvg::Image(image),
+                        image,
                         retention_policy: policy,
                         used: true,
                         dirty: false,
@@ -849,6 +903,45 @@ impl Context {
         self.entity_identifiers.get(identity).cloned()
     }
 
+    /// Returns the entities of all views which match the given CSS selector, in tree order.
+    ///
+    /// Returns an empty list if the selector is invalid.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// Element::new(cx).class("swatch");
+    /// Element::new(cx).class("swatch");
+    ///
+    /// assert_eq!(cx.query_selector_all(".swatch").len(), 2);
+    /// ```
+    pub fn query_selector_all(&self, selector: &str) -> Vec<Entity> {
+        let Ok(selector_list) = parse_selector_list(selector) else {
+            warn!("Invalid selector: {}", selector);
+            return Vec::new();
+        };
+
+        self.tree
+            .into_iter()
+            .filter(|entity| matches_selectors(self, *entity, &selector_list))
+            .collect()
+    }
+
+    /// Returns the entity of the first view, in tree order, which matches the given CSS selector.
+    pub fn query_selector(&self, selector: &str) -> Option<Entity> {
+        self.query_selector_all(selector).first().copied()
+    }
+
+    /// Returns true if the view with the given entity matches the given CSS selector.
+    ///
+    /// This can be used to check the pseudo-classes of a view, e.g. `cx.matches_selector(entity, ":checked")`.
+    pub fn matches_selector(&self, entity: Entity, selector: &str) -> bool {
+        parse_selector_list(selector)
+            .map(|selector_list| matches_selectors(self, entity, &selector_list))
+            .unwrap_or_default()
+    }
+
     /// Toggles the addition/removal of a class name for the current view.
     ///
     /// # Example
@@ -878,7 +971,21 @@ impl Context {
 
 pub(crate) enum InternalEvent {
     Redraw,
-    LoadImage { path: String, image: Mutex<Option<skia_safe::Image>>, policy: ImageRetentionPolicy },
+    LoadImage {
+        path: String,
+        image: Mutex<Option<skia_safe::Image>>,
+        policy: ImageRetentionPolicy,
+    },
+    LoadAnimatedImage {
+        path: String,
+        image: Mutex<Option<AnimatedImage>>,
+        policy: ImageRetentionPolicy,
+    },
+    LoadSvg {
+        path: String,
+        svg: Mutex<Option<skia_safe::svg::Dom>>,
+        policy: ImageRetentionPolicy,
+    },
 }
 
 pub struct LocalizationContext<'a> {
@@ -5,6 +5,7 @@ use std::sync::Mutex;
 use super::InternalEvent;
 
 use crate::prelude::*;
+use crate::resource::{broken_image, decode_animated_image, decode_image};
 
 /// A bundle of data representing a snapshot of the context when a thread was spawned.
 ///
@@ -74,17 +75,56 @@ impl ContextProxy {
         self.emit(InternalEvent::Redraw)
     }
 
+    /// Decodes an encoded image, such as a PNG, JPEG or WebP file, on the current thread and sends
+    /// it to the application to be stored with the given name. Every frame of an animated image,
+    /// such as a GIF, is decoded, up to a limit on the memory used by the frames. If the image
+    /// can't be decoded, the broken image is stored with the name instead.
     pub fn load_image(
         &mut self,
         path: String,
         data: &[u8],
         policy: ImageRetentionPolicy,
     ) -> Result<(), ProxyEmitError> {
-        if let Some(image) = skia_safe::Image::from_encoded(skia_safe::Data::new_copy(data)) {
-            self.emit(InternalEvent::LoadImage { path, image: Mutex::new(Some(image)), policy })?
+        if let Some(image) = decode_animated_image(data) {
+            let image = Mutex::new(Some(image));
+            self.emit(InternalEvent::LoadAnimatedImage { path, image, policy })
+        } else if let Some(image) = decode_image(data) {
+            self.emit(InternalEvent::LoadImage { path, image: Mutex::new(Some(image)), policy })
+        } else {
+            log::warn!("Failed to decode image {}", path);
+            self.load_broken_image(path, policy)
+        }
+    }
+
+    /// Parses an SVG document on the current thread and sends it to the application to be stored
+    /// with the given name. If the document can't be parsed, the broken image is stored with the
+    /// name instead.
+    pub fn load_svg(
+        &mut self,
+        path: String,
+        data: &[u8],
+        policy: ImageRetentionPolicy,
+    ) -> Result<(), ProxyEmitError> {
+        match skia_safe::svg::Dom::from_bytes(data, skia_safe::FontMgr::default()) {
+            Ok(svg) => {
+                self.emit(InternalEvent::LoadSvg { path, svg: Mutex::new(Some(svg)), policy })
+            }
+            Err(_) => {
+                log::warn!("Failed to parse SVG image {}", path);
+                self.load_broken_image(path, policy)
+            }
         }
+    }
 
-        Ok(())
+    // Sends the broken image to the application to be stored with the given name, in place of an
+    // image which failed to load.
+    pub(crate) fn load_broken_image(
+        &mut self,
+        path: String,
+        policy: ImageRetentionPolicy,
+    ) -> Result<(), ProxyEmitError> {
+        let image = Mutex::new(Some(broken_image()));
+        self.emit(InternalEvent::LoadImage { path, image, policy })
     }
 
     pub fn spawn<F>(&self, target: F)
@@ -1,10 +1,14 @@
+use std::path::Path;
+
 use hashbrown::{hash_map::Entry, HashSet};
 
 use vizia_storage::Tree;
 
 use crate::{
     entity::Entity,
-    resource::{ImageOrSvg, ImageRetentionPolicy, ResourceManager, StoredImage},
+    resource::{
+        AnimatedImage, ImageId, ImageOrSvg, ImageRetentionPolicy, ResourceManager, StoredImage,
+    },
     style::Style,
 };
 
@@ -42,27 +46,107 @@ impl<'a> ResourceContext<'a> {
         std::thread::spawn(move || target(&mut cxp));
     }
 
+    /// Loads an image from a file. This is the default image loader.
+    ///
+    /// The name of the image is a path, which may start with `file://`, and a relative path is
+    /// resolved against the directory set with [`Context::set_asset_root`]. PNG, JPEG, WebP and
+    /// GIF images, including every frame of an animated image, and SVG files are read and decoded
+    /// on a background thread. Until then, views show a transparent placeholder the size of the
+    /// broken image, and if the file can't be loaded they show the broken image. Names with any
+    /// other scheme, such as `https://`, are left to a custom image loader set with
+    /// [`Context::set_image_loader`].
+    ///
+    /// The image is reloaded when the file changes if [`Context::watch_image_files`] is enabled.
+    pub fn load_file(&mut self, name: &str) {
+        let policy = ImageRetentionPolicy::DropWhenNoObservers;
+
+        // The placeholder stops the loader from being called again while the image is pending.
+        self.load_placeholder(name);
+
+        // There is no file system, or threads, to load the image with on the web.
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+
+        let path = match name.strip_prefix("file://") {
+            Some(path) => path,
+            None if name.contains("://") => return,
+            None => name,
+        };
+
+        let path = self.resource_manager.asset_root.join(path);
+        self.resource_manager.watch_image_file(name, path.clone());
+
+        let name = name.to_owned();
+        self.spawn(move |cx| read_image_file(cx, name, &path, policy));
+    }
+
+    /// Stores an image with the given name, replacing any image with the same name, and returns
+    /// its id.
     pub fn load_image(
         &mut self,
         path: String,
         image: skia_safe::Image,
         policy: ImageRetentionPolicy,
-    ) {
+    ) -> ImageId {
+        self.store_image(path, ImageOrSvg::Image(image), policy)
+    }
+
+    // Stores the frames of an animated image with the given name, replacing any image with the
+    // same name, and returns its id.
+    pub(crate) fn load_animated_image(
+        &mut self,
+        path: String,
+        image: AnimatedImage,
+        policy: ImageRetentionPolicy,
+    ) -> ImageId {
+        self.store_image(path, ImageOrSvg::Animated(image), policy)
+    }
+
+    /// Stores an SVG document with the given name, replacing any image with the same name, and
+    /// returns its id.
+    pub fn load_svg(
+        &mut self,
+        path: String,
+        svg: skia_safe::svg::Dom,
+        policy: ImageRetentionPolicy,
+    ) -> ImageId {
+        self.store_image(path, ImageOrSvg::Svg(svg), policy)
+    }
+
+    // Stores the pending placeholder under the given name, until the image is loaded.
+    fn load_placeholder(&mut self, name: &str) {
+        if let Some(StoredImage { image: ImageOrSvg::Image(image), .. }) =
+            self.resource_manager.images.get(&ImageId::pending())
+        {
+            let image = image.clone();
+            self.load_image(name.to_owned(), image, ImageRetentionPolicy::DropWhenNoObservers);
+        }
+    }
+
+    fn store_image(
+        &mut self,
+        path: String,
+        image: ImageOrSvg,
+        policy: ImageRetentionPolicy,
+    ) -> ImageId {
         let id = if let Some(image_id) = self.resource_manager.image_ids.get(&path) {
             *image_id
         } else {
-            self.resource_manager.image_id_manager.create()
+            let id = self.resource_manager.image_id_manager.create();
+            self.resource_manager.image_ids.insert(path, id);
+            id
         };
 
         match self.resource_manager.images.entry(id) {
             Entry::Occupied(mut occ) => {
-                occ.get_mut().image = ImageOrSvg::Image(image);
+                occ.get_mut().image = image;
                 occ.get_mut().dirty = true;
                 occ.get_mut().retention_policy = policy;
             }
             Entry::Vacant(vac) => {
                 vac.insert(StoredImage {
-                    image: ImageOrSvg::Image(image),
+                    image,
                     retention_policy: policy,
                     used: true,
                     dirty: false,
@@ -71,5 +155,31 @@ impl<'a> ResourceContext<'a> {
             }
         }
         self.style.needs_relayout();
+
+        id
+    }
+}
+
+// Reads an image file and sends the image to the application to be stored with the given name. An
+// encoded image is decoded, and an SVG document parsed, on the current thread. The broken image is
+// sent instead if the file can't be read.
+pub(crate) fn read_image_file(
+    cx: &mut ContextProxy,
+    name: String,
+    path: &Path,
+    policy: ImageRetentionPolicy,
+) {
+    let is_svg = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    let result = match std::fs::read(path) {
+        Ok(data) if is_svg => cx.load_svg(name, &data, policy),
+        Ok(data) => cx.load_image(name, &data, policy),
+        Err(err) => {
+            log::warn!("Failed to read image {}: {}", path.display(), err);
+            cx.load_broken_image(name, policy)
+        }
+    };
+
+    if let Err(err) = result {
+        log::warn!("Failed to load image {}: {}", path.display(), err);
     }
 }
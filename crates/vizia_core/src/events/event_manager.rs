@@ -53,7 +53,26 @@ impl EventManager {
                 InternalEvent::Redraw => cx.needs_redraw(Entity::root()),
                 InternalEvent::LoadImage { path, image, policy } => {
                     if let Some(image) = image.lock().unwrap().take() {
-                        ResourceContext::new(cx).load_image(path, image, policy);
+                        let id = ResourceContext::new(cx).load_image(path, image, policy);
+                        for entity in cx.resource_manager.image_observers(id) {
+                            cx.needs_redraw(entity);
+                        }
+                    }
+                }
+                InternalEvent::LoadAnimatedImage { path, image, policy } => {
+                    if let Some(image) = image.lock().unwrap().take() {
+                        let id = ResourceContext::new(cx).load_animated_image(path, image, policy);
+                        for entity in cx.resource_manager.image_observers(id) {
+                            cx.needs_redraw(entity);
+                        }
+                    }
+                }
+                InternalEvent::LoadSvg { path, svg, policy } => {
+                    if let Some(svg) = svg.lock().unwrap().take() {
+                        let id = ResourceContext::new(cx).load_svg(path, svg, policy);
+                        for entity in cx.resource_manager.image_observers(id) {
+                            cx.needs_redraw(entity);
+                        }
                     }
                 }
             });
@@ -282,7 +301,7 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
             }
 
             // track double/triple -click
-            let new_click_time = Instant::now();
+            let new_click_time = crate::util::now();
             let click_duration = new_click_time - cx.click_time;
             let new_click_pos = (cx.mouse.cursor_x, cx.mouse.cursor_y);
             if click_duration <= DOUBLE_CLICK_INTERVAL
@@ -596,7 +615,7 @@ fn internal_state_updates(cx: &mut Context, window_event: &WindowEvent, meta: &m
                 cx.triggered = Entity::null();
             }
         }
-        WindowEvent::CharInput(_) => {
+        WindowEvent::CharInput(_) | WindowEvent::ImePreedit(..) | WindowEvent::ImeCommit(_) => {
             meta.target = cx.focused;
         }
         WindowEvent::FocusOut => {
@@ -0,0 +1,568 @@
+use hashbrown::HashSet;
+use morphorm::Node;
+use vizia_storage::{LayoutTreeIterator, MorphormChildIter};
+
+use crate::layout::node::SubLayout;
+use crate::prelude::*;
+
+/// The computed cell of a grid item, relative to the top-left of the grid, in physical pixels.
+///
+/// The width and height are `None` while the tracks of the grid are measured, in which case the
+/// item is laid out with its content size unless it has a fixed size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GridPlacement {
+    pub left: f32,
+    pub top: f32,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+}
+
+impl GridPlacement {
+    const MEASURE: Self = Self { left: 0.0, top: 0.0, width: None, height: None };
+}
+
+/// The grids of the tree, with the sizes of their `auto` tracks measured from their items.
+///
+/// Morphorm has no grid layout, so the children of a grid are self-directed and positioned within
+/// their cell. A layout is performed in three steps:
+/// 1. [`GridLayout::measure`] sizes the `auto` tracks of each grid from the content size of the
+///    items which occupy only that track, and stores the total size of the tracks as the content
+///    size of the grid, which morphorm uses if the grid has an `auto` size.
+/// 2. The tree is laid out, which determines the size of each grid.
+/// 3. [`GridLayout::place`] places the items into the cells of each grid and lays out the items
+///    which moved again. The rest of the tree isn't affected, as the size of a grid doesn't depend
+///    on the placement of its items.
+///
+/// Nothing depends on the previous layout, so the result is the same in every frame.
+pub(crate) struct GridLayout {
+    grids: Vec<MeasuredGrid>,
+}
+
+struct MeasuredGrid {
+    grid: Entity,
+    items: Vec<Entity>,
+    columns: Axis,
+    rows: Axis,
+}
+
+// The tracks along one axis of a grid, and the tracks occupied by each item.
+struct Axis {
+    tracks: Vec<Units>,
+    // The measured size of each `auto` track, or zero for other tracks.
+    auto_sizes: Vec<f32>,
+    // The first track and number of tracks occupied by each item.
+    areas: Vec<(usize, usize)>,
+}
+
+impl GridLayout {
+    /// Measures the grids of the tree, from the innermost, so that a nested grid has a content size
+    /// when the item containing it is measured.
+    pub(crate) fn measure(cx: &mut Context) -> Self {
+        let grids = LayoutTreeIterator::full(&cx.tree)
+            .filter(|entity| cx.style.layout_type.get(*entity).copied() == Some(LayoutType::Grid))
+            .collect::<Vec<_>>();
+
+        // Remove the placement of views which are no longer the children of a grid.
+        if grids.is_empty() {
+            cx.style.grid_placement.clear();
+            cx.style.grid_content_size.clear();
+            return Self { grids: Vec::new() };
+        }
+
+        let mut measured = Vec::with_capacity(grids.len());
+        let mut placed = HashSet::new();
+
+        for grid in grids.into_iter().rev() {
+            let items = MorphormChildIter::new(&cx.tree, grid)
+                .copied()
+                .filter(|child| {
+                    cx.style.display.get(*child).copied().unwrap_or_default() != Display::None
+                        && cx.style.position_type.get(*child).copied()
+                            != Some(PositionType::SelfDirected)
+                })
+                .collect::<Vec<_>>();
+
+            let style = &cx.style;
+            let columns = track_list(style.grid_columns.get(grid));
+            let rows = track_list(style.grid_rows.get(grid));
+
+            let column_areas = items
+                .iter()
+                .map(|item| {
+                    area(style.column_start.get(*item), style.column_span.get(*item), columns.len())
+                })
+                .collect::<Vec<_>>();
+
+            let row_areas = items
+                .iter()
+                .map(|item| area(style.row_start.get(*item), style.row_span.get(*item), rows.len()))
+                .collect::<Vec<_>>();
+
+            let mut item_sizes = Vec::with_capacity(items.len());
+            for (index, item) in items.iter().enumerate() {
+                cx.style.grid_placement.insert(*item, GridPlacement::MEASURE);
+                placed.insert(*item);
+
+                let (column_start, column_span) = column_areas[index];
+                let (row_start, row_span) = row_areas[index];
+                let measure = (column_span == 1 && columns[column_start] == Units::Auto)
+                    || (row_span == 1 && rows[row_start] == Units::Auto);
+
+                // Laying out the item on its own gives its content size.
+                item_sizes.push(if measure {
+                    item.layout(
+                        &mut cx.cache,
+                        &cx.tree,
+                        &cx.style,
+                        &mut SubLayout {
+                            text_context: &mut cx.text_context,
+                            resource_manager: &cx.resource_manager,
+                        },
+                    );
+
+                    let bounds = cx.cache.relative_bounds.get(*item).copied().unwrap_or_default();
+                    (bounds.w, bounds.h)
+                } else {
+                    (0.0, 0.0)
+                });
+            }
+
+            let columns = Axis {
+                auto_sizes: auto_sizes(
+                    &columns,
+                    column_areas.iter().zip(item_sizes.iter().map(|size| size.0)),
+                ),
+                tracks: columns,
+                areas: column_areas,
+            };
+
+            let rows = Axis {
+                auto_sizes: auto_sizes(
+                    &rows,
+                    row_areas.iter().zip(item_sizes.iter().map(|size| size.1)),
+                ),
+                tracks: rows,
+                areas: row_areas,
+            };
+
+            let style = &cx.style;
+            let border = style
+                .border_width
+                .get(grid)
+                .and_then(|width| match width {
+                    LengthOrPercentage::Length(length) => length.to_px(),
+                    LengthOrPercentage::Percentage(_) => None,
+                })
+                .map(|width| 2.0 * style.logical_to_physical(width))
+                .unwrap_or_default();
+
+            // The content size of the grid doesn't include tracks which depend on its size.
+            let content_width = border
+                + resolve(style, style.child_left.get(grid), 0.0)
+                + resolve(style, style.child_right.get(grid), 0.0)
+                + tracks(
+                    style,
+                    &columns.tracks,
+                    &columns.auto_sizes,
+                    0.0,
+                    col_between(style, grid, 0.0),
+                )
+                .total();
+            let content_height = border
+                + resolve(style, style.child_top.get(grid), 0.0)
+                + resolve(style, style.child_bottom.get(grid), 0.0)
+                + tracks(style, &rows.tracks, &rows.auto_sizes, 0.0, row_between(style, grid, 0.0))
+                    .total();
+
+            cx.style.grid_content_size.insert(grid, (content_width, content_height));
+
+            measured.push(MeasuredGrid { grid, items, columns, rows });
+        }
+
+        measured.reverse();
+
+        // Remove the placement of views which are no longer the children of a grid.
+        for entity in LayoutTreeIterator::full(&cx.tree) {
+            if !placed.contains(&entity) {
+                cx.style.grid_placement.remove(entity);
+            }
+
+            if cx.style.layout_type.get(entity).copied() != Some(LayoutType::Grid) {
+                cx.style.grid_content_size.remove(entity);
+            }
+        }
+
+        Self { grids: measured }
+    }
+
+    /// Returns true if there are no grids in the tree.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.grids.is_empty()
+    }
+
+    /// Places the items of each grid into their cells, using the size of the grid from the last
+    /// layout, and lays out the items which moved. Returns true if any placement changed, in which
+    /// case the grids nested within the moved items need to be placed again.
+    pub(crate) fn place(&self, cx: &mut Context) -> bool {
+        let mut changed = false;
+
+        for MeasuredGrid { grid, items, columns, rows } in self.grids.iter() {
+            let grid = *grid;
+            let Some(bounds) = cx.cache.relative_bounds.get(grid).copied() else {
+                continue;
+            };
+
+            let style = &cx.style;
+            let border = style
+                .border_width
+                .get(grid)
+                .map(|width| match width {
+                    LengthOrPercentage::Length(length) => {
+                        style.logical_to_physical(length.to_px().unwrap_or_default())
+                    }
+                    LengthOrPercentage::Percentage(val) => val * bounds.w / 100.0,
+                })
+                .unwrap_or_default();
+
+            let child_left = resolve(style, style.child_left.get(grid), bounds.w);
+            let child_right = resolve(style, style.child_right.get(grid), bounds.w);
+            let child_top = resolve(style, style.child_top.get(grid), bounds.h);
+            let child_bottom = resolve(style, style.child_bottom.get(grid), bounds.h);
+
+            let content_width = bounds.w - 2.0 * border - child_left - child_right;
+            let content_height = bounds.h - 2.0 * border - child_top - child_bottom;
+
+            let column_tracks = tracks(
+                style,
+                &columns.tracks,
+                &columns.auto_sizes,
+                content_width,
+                col_between(style, grid, content_width),
+            );
+
+            let row_tracks = tracks(
+                style,
+                &rows.tracks,
+                &rows.auto_sizes,
+                content_height,
+                row_between(style, grid, content_height),
+            );
+
+            let mut placements = Vec::with_capacity(items.len());
+            for (index, item) in items.iter().enumerate() {
+                let (column_start, column_span) = columns.areas[index];
+                let (row_start, row_span) = rows.areas[index];
+
+                let (x, cell_width) = column_tracks.area(column_start, column_span);
+                let (y, cell_height) = row_tracks.area(row_start, row_span);
+
+                let (left, width) = place(
+                    style,
+                    (style.left.get(*item), style.width.get(*item), style.right.get(*item)),
+                    (style.min_width.get(*item), style.max_width.get(*item)),
+                    cell_width,
+                );
+
+                let (top, height) = place(
+                    style,
+                    (style.top.get(*item), style.height.get(*item), style.bottom.get(*item)),
+                    (style.min_height.get(*item), style.max_height.get(*item)),
+                    cell_height,
+                );
+
+                placements.push(GridPlacement {
+                    left: border + child_left + x + left,
+                    top: border + child_top + y + top,
+                    width: Some(width),
+                    height: Some(height),
+                });
+            }
+
+            for (item, placement) in items.iter().zip(placements) {
+                if cx.style.grid_placement.get(*item) != Some(&placement) {
+                    cx.style.grid_placement.insert(*item, placement);
+                    layout_item(cx, *item, placement);
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+// Lays out a grid item, which has the size of its cell, and moves it into the cell.
+fn layout_item(cx: &mut Context, item: Entity, placement: GridPlacement) {
+    item.layout(
+        &mut cx.cache,
+        &cx.tree,
+        &cx.style,
+        &mut SubLayout {
+            text_context: &mut cx.text_context,
+            resource_manager: &cx.resource_manager,
+        },
+    );
+
+    if let Some(bounds) = cx.cache.relative_bounds.get_mut(item) {
+        bounds.x = placement.left.round();
+        bounds.y = placement.top.round();
+    }
+}
+
+// The computed start position and size of each track along one axis of a grid.
+#[derive(Debug, PartialEq)]
+struct Tracks {
+    starts: Vec<f32>,
+    sizes: Vec<f32>,
+}
+
+impl Tracks {
+    // Returns the position and size of the area covered by `span` tracks from `start`.
+    fn area(&self, start: usize, span: usize) -> (f32, f32) {
+        let end = start + span - 1;
+        let pos = self.starts[start];
+        (pos, self.starts[end] + self.sizes[end] - pos)
+    }
+
+    // Returns the total size of the tracks, including the space between them.
+    fn total(&self) -> f32 {
+        match (self.starts.last(), self.sizes.last()) {
+            (Some(start), Some(size)) => start + size,
+            _ => 0.0,
+        }
+    }
+}
+
+// Resolves fixed units to physical pixels. Stretch and auto units resolve to zero.
+fn resolve(style: &Style, units: Option<&Units>, parent: f32) -> f32 {
+    match units {
+        Some(Units::Pixels(val)) => style.logical_to_physical(*val),
+        Some(Units::Percentage(val)) => val * parent / 100.0,
+        _ => 0.0,
+    }
+}
+
+fn col_between(style: &Style, grid: Entity, available: f32) -> f32 {
+    resolve(style, style.col_between.get(grid), available)
+}
+
+fn row_between(style: &Style, grid: Entity, available: f32) -> f32 {
+    resolve(style, style.row_between.get(grid), available)
+}
+
+// Returns the tracks along one axis of a grid. A grid without tracks has a single stretch track.
+fn track_list(tracks: Option<&Vec<Units>>) -> Vec<Units> {
+    match tracks {
+        Some(tracks) if !tracks.is_empty() => tracks.clone(),
+        _ => vec![Units::Stretch(1.0)],
+    }
+}
+
+// Returns the first track and number of tracks occupied by an item, clamped to the grid.
+fn area(start: Option<&usize>, span: Option<&usize>, num_tracks: usize) -> (usize, usize) {
+    let num_tracks = num_tracks.max(1);
+    let start = start.copied().unwrap_or_default().min(num_tracks - 1);
+    let span = span.copied().unwrap_or(1).clamp(1, num_tracks - start);
+    (start, span)
+}
+
+// Sizes each `auto` track to fit the items which occupy only that track.
+fn auto_sizes<'a>(
+    tracks: &[Units],
+    items: impl Iterator<Item = (&'a (usize, usize), f32)>,
+) -> Vec<f32> {
+    let mut sizes = vec![0.0; tracks.len()];
+
+    for ((start, span), size) in items {
+        if *span == 1 && tracks[*start] == Units::Auto {
+            sizes[*start] = f32::max(sizes[*start], size);
+        }
+    }
+
+    sizes
+}
+
+// Computes the tracks along one axis of a grid, given the measured sizes of the `auto` tracks.
+// Stretch tracks share the space which remains after the other tracks.
+fn tracks(style: &Style, tracks: &[Units], auto_sizes: &[f32], available: f32, gap: f32) -> Tracks {
+    let mut sizes = auto_sizes.to_vec();
+
+    let mut total_stretch = 0.0;
+    for (index, track) in tracks.iter().enumerate() {
+        match track {
+            Units::Pixels(_) | Units::Percentage(_) => {
+                sizes[index] = resolve(style, Some(track), available);
+            }
+            Units::Stretch(factor) => total_stretch += factor,
+            Units::Auto => {}
+        }
+    }
+
+    let gaps = gap * (tracks.len().max(1) - 1) as f32;
+    let free_space = (available - gaps - sizes.iter().sum::<f32>()).max(0.0);
+    if total_stretch > 0.0 {
+        for (index, track) in tracks.iter().enumerate() {
+            if let Units::Stretch(factor) = track {
+                sizes[index] = free_space * factor / total_stretch;
+            }
+        }
+    }
+
+    let mut starts = Vec::with_capacity(tracks.len());
+    let mut position = 0.0;
+    for size in sizes.iter() {
+        starts.push(position);
+        position += size + gap;
+    }
+
+    Tracks { starts, sizes }
+}
+
+// Positions an item within its cell along one axis, given its space before, size and space after,
+// and its minimum and maximum size. Returns the offset of the item within the cell and its size.
+fn place(
+    style: &Style,
+    (before, size, after): (Option<&Units>, Option<&Units>, Option<&Units>),
+    (min, max): (Option<&Units>, Option<&Units>),
+    cell: f32,
+) -> (f32, f32) {
+    let fixed_before = resolve(style, before, cell);
+    let fixed_after = resolve(style, after, cell);
+
+    // Items fill their cell unless they have a fixed size.
+    let size = match size {
+        Some(Units::Pixels(_)) | Some(Units::Percentage(_)) => resolve(style, size, cell),
+        _ => (cell - fixed_before - fixed_after).max(0.0),
+    };
+
+    let max = match max {
+        Some(Units::Pixels(_)) | Some(Units::Percentage(_)) => resolve(style, max, cell),
+        _ => f32::MAX,
+    };
+    let size = size.min(max).max(resolve(style, min, cell));
+
+    let stretch = |units: Option<&Units>| match units {
+        Some(Units::Stretch(factor)) => *factor,
+        _ => 0.0,
+    };
+
+    let stretch_before = stretch(before);
+    let total_stretch = stretch_before + stretch(after);
+    let free_space = (cell - size - fixed_before - fixed_after).max(0.0);
+
+    let offset = if total_stretch > 0.0 {
+        fixed_before + free_space * stretch_before / total_stretch
+    } else {
+        fixed_before
+    };
+
+    (offset, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style() -> Style {
+        Style { dpi_factor: 1.0, ..Default::default() }
+    }
+
+    #[test]
+    fn auto_tracks_fit_items_which_occupy_only_that_track() {
+        let tracks = [Units::Auto, Units::Auto, Units::Pixels(10.0)];
+        let items = [((0, 1), 30.0), ((0, 1), 50.0), ((1, 2), 100.0), ((2, 1), 40.0)];
+        let sizes = auto_sizes(&tracks, items.iter().map(|(area, size)| (area, *size)));
+        assert_eq!(sizes, [50.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn stretch_tracks_share_the_remaining_space() {
+        let tracks = tracks(
+            &style(),
+            &[Units::Auto, Units::Stretch(1.0), Units::Pixels(20.0), Units::Stretch(3.0)],
+            &[30.0, 0.0, 0.0, 0.0],
+            200.0,
+            10.0,
+        );
+
+        assert_eq!(tracks.sizes, [30.0, 30.0, 20.0, 90.0]);
+        assert_eq!(tracks.starts, [0.0, 40.0, 80.0, 110.0]);
+        assert_eq!(tracks.area(1, 2), (40.0, 60.0));
+        assert_eq!(tracks.total(), 200.0);
+    }
+
+    #[test]
+    fn content_size_excludes_stretch_tracks() {
+        let tracks = tracks(
+            &style(),
+            &[Units::Auto, Units::Stretch(1.0), Units::Pixels(20.0)],
+            &[30.0, 0.0, 0.0],
+            0.0,
+            10.0,
+        );
+
+        assert_eq!(tracks.total(), 70.0);
+    }
+
+    #[test]
+    fn items_fill_their_cell_unless_they_have_a_fixed_size() {
+        let style = style();
+        assert_eq!(
+            place(&style, (None, Some(&Units::Stretch(1.0)), None), (None, None), 50.0),
+            (0.0, 50.0)
+        );
+        assert_eq!(
+            place(&style, (None, Some(&Units::Auto), None), (None, None), 50.0),
+            (0.0, 50.0)
+        );
+        assert_eq!(
+            place(
+                &style,
+                (Some(&Units::Pixels(5.0)), None, Some(&Units::Pixels(5.0))),
+                (None, None),
+                50.0
+            ),
+            (5.0, 40.0)
+        );
+        assert_eq!(
+            place(
+                &style,
+                (
+                    Some(&Units::Stretch(1.0)),
+                    Some(&Units::Pixels(20.0)),
+                    Some(&Units::Stretch(1.0))
+                ),
+                (None, None),
+                50.0
+            ),
+            (15.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn items_are_sized_within_their_min_and_max_size() {
+        let style = style();
+        assert_eq!(
+            place(&style, (None, None, None), (None, Some(&Units::Pixels(30.0))), 50.0),
+            (0.0, 30.0)
+        );
+        assert_eq!(
+            place(
+                &style,
+                (Some(&Units::Stretch(1.0)), None, Some(&Units::Stretch(1.0))),
+                (None, Some(&Units::Percentage(50.0))),
+                50.0
+            ),
+            (12.5, 25.0)
+        );
+        assert_eq!(
+            place(
+                &style,
+                (None, Some(&Units::Pixels(10.0)), None),
+                (Some(&Units::Pixels(20.0)), None),
+                50.0
+            ),
+            (0.0, 20.0)
+        );
+    }
+}
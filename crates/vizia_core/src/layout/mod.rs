@@ -27,10 +27,21 @@
 //! - `Auto` - The spacing is determined by the corresponding `child_space` of the parent. So `left` would be determined by the parent `child_left` etc.
 //!
 //! # Child Space
+//!
+//! # Grids
+//! A view with a `Grid` layout type places its children into the cells of a grid. The tracks of the grid are specified with
+//! the [`grid_columns()`](crate::modifiers::LayoutModifiers::grid_columns) and [`grid_rows()`](crate::modifiers::LayoutModifiers::grid_rows)
+//! modifiers (or `grid-columns` and `grid-rows` css properties), where each track is sized in [`Units`]. `Auto` tracks fit the children
+//! which occupy only that track. The `col_between` and `row_between` of the grid add space between the tracks.
+//!
+//! A child is placed with the `column_start`, `column_span`, `row_start`, and `row_span` modifiers (or css properties), which default
+//! to the first cell with a span of one. A child fills its cell unless it has a fixed size, and its space is applied within the cell.
 pub(crate) mod cache;
+pub(crate) mod grid;
 pub(crate) mod node;
 
-pub use morphorm::{LayoutType, PositionType, Units};
+pub use morphorm::{PositionType, Units};
+pub use vizia_style::LayoutType;
 
 pub mod bounds;
 
@@ -3,7 +3,7 @@ use skia_safe::wrapper::PointerWrapper;
 use vizia_storage::MorphormChildIter;
 
 use crate::prelude::*;
-use crate::resource::{ImageOrSvg, ResourceManager};
+use crate::resource::{ImageFrame, ImagePlayback, ResourceManager};
 use crate::text::TextContext;
 
 pub struct SubLayout<'a> {
@@ -31,14 +31,23 @@ impl Node for Entity {
     }
 
     fn layout_type(&self, store: &Self::Store) -> Option<morphorm::LayoutType> {
-        store.layout_type.get(*self).cloned()
+        store.layout_type.get(*self).copied().map(Into::into)
     }
 
     fn position_type(&self, store: &Self::Store) -> Option<morphorm::PositionType> {
+        // Grid items are positioned within their cell, see `GridLayout`.
+        if store.grid_placement.contains(*self) {
+            return Some(PositionType::SelfDirected);
+        }
+
         store.position_type.get(*self).cloned()
     }
 
     fn left(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        if let Some(placement) = store.grid_placement.get(*self) {
+            return Some(Units::Pixels(placement.left));
+        }
+
         store.left.get(*self).cloned().map(|l| match l {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -60,6 +69,10 @@ impl Node for Entity {
     }
 
     fn right(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        if store.grid_placement.contains(*self) {
+            return Some(Units::Pixels(0.0));
+        }
+
         store.right.get(*self).cloned().map(|r| match r {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -81,6 +94,10 @@ impl Node for Entity {
     }
 
     fn top(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        if let Some(placement) = store.grid_placement.get(*self) {
+            return Some(Units::Pixels(placement.top));
+        }
+
         store.top.get(*self).cloned().map(|t| match t {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -102,6 +119,10 @@ impl Node for Entity {
     }
 
     fn bottom(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        if store.grid_placement.contains(*self) {
+            return Some(Units::Pixels(0.0));
+        }
+
         store.bottom.get(*self).cloned().map(|b| match b {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -123,6 +144,17 @@ impl Node for Entity {
     }
 
     fn width(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        if let Some(placement) = store.grid_placement.get(*self) {
+            match placement.width {
+                Some(width) => return Some(Units::Pixels(width)),
+                // Grid items are measured with their content size, see `GridLayout`.
+                None if !matches!(store.width.get(*self), Some(Units::Pixels(_))) => {
+                    return Some(Units::Auto)
+                }
+                None => {}
+            }
+        }
+
         store.width.get(*self).cloned().map(|w| match w {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -150,7 +182,14 @@ impl Node for Entity {
         width: Option<f32>,
         height: Option<f32>,
     ) -> Option<(f32, f32)> {
+        // The content size of a grid is the size of its tracks, see `GridLayout`.
+        if let Some((content_width, content_height)) = store.grid_content_size.get(*self) {
+            return Some((width.unwrap_or(*content_width), height.unwrap_or(*content_height)));
+        }
+
         if let Some(paragraph) = sublayout.text_context.text_paragraphs.get_mut(*self) {
+            sublayout.text_context.caret_stops.remove(*self);
+
             // // If the width is known use that, else use 0 for wrapping text or 999999 for non-wrapping text.
             // let max_width = if let Some(width) = width {
             //     let child_left =
@@ -276,16 +315,16 @@ impl Node for Entity {
                                 .resource_manager
                                 .images
                                 .get(image_id)
-                                .map(|stored_img| &stored_img.image)
+                                .map(|stored_img| stored_img.image.frame(&ImagePlayback::default()))
                             {
-                                Some(ImageOrSvg::Image(image)) => {
+                                Some(ImageFrame::Image(image)) => {
                                     max_width =
                                         max_width.max(image.width() as f32 * store.scale_factor());
                                     max_height = max_height
                                         .max(image.height() as f32 * store.scale_factor());
                                 }
 
-                                Some(ImageOrSvg::Svg(svg)) => {
+                                Some(ImageFrame::Svg(svg)) => {
                                     max_width = max_width.max(
                                         svg.inner().fContainerSize.fWidth * store.scale_factor(),
                                     );
@@ -311,6 +350,16 @@ impl Node for Entity {
     }
 
     fn height(&self, store: &Self::Store) -> Option<morphorm::Units> {
+        if let Some(placement) = store.grid_placement.get(*self) {
+            match placement.height {
+                Some(height) => return Some(Units::Pixels(height)),
+                None if !matches!(store.height.get(*self), Some(Units::Pixels(_))) => {
+                    return Some(Units::Auto)
+                }
+                None => {}
+            }
+        }
+
         store.height.get(*self).cloned().map(|h| match h {
             Units::Pixels(val) => Units::Pixels(store.logical_to_physical(val)),
             t => t,
@@ -62,7 +62,7 @@ pub mod prelude {
 
     pub use crate::model::Model;
 
-    pub use super::animation::{Animation, AnimationBuilder, KeyframeBuilder};
+    pub use super::animation::{Animation, AnimationBuilder, AnimationEvent, KeyframeBuilder};
     pub use super::context::{
         AccessContext, AccessNode, Context, ContextProxy, DataContext, DrawContext, EmitContext,
         EventContext, ProxyEmitError, WindowState,
@@ -79,11 +79,16 @@ pub mod prelude {
         LinearGradientBuilder, ShadowBuilder, StyleModifiers, TextModifiers,
     };
     pub use super::resource::{ImageId, ImageRetentionPolicy};
+    pub use super::text::{
+        CheckKind, ConfigHighlighter, DictionaryChecker, HighlightSpan, Highlighter, Highlights,
+        RichText, RunStyle, SearchQuery, StyledRun, TextCheck, TextChecker, TextSearch,
+    };
     pub use super::util::{IntoCssStr, CSS};
     pub use super::view::{Handle, View};
     pub use super::views::*;
     pub use super::window::{DropData, WindowEvent};
     pub use accesskit::{Action, DefaultActionVerb, Live, Role};
+    pub use ropey::Rope;
     pub use skia_safe::Canvas;
     pub use vizia_derive::{Data, Lens};
     pub use vizia_id::GenerationalId;
@@ -94,7 +99,8 @@ pub mod prelude {
     pub use super::style::*;
 
     pub use morphorm::Units::*;
-    pub use morphorm::{LayoutType, PositionType, Units};
+    pub use morphorm::{PositionType, Units};
     pub use unic_langid::{langid, LanguageIdentifier};
+    pub use vizia_style::LayoutType;
     pub use web_time::{Duration, Instant};
 }
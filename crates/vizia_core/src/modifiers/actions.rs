@@ -59,6 +59,8 @@ pub(crate) struct ActionsModel {
     pub(crate) on_geo_changed: Option<Box<dyn Fn(&mut EventContext, GeoChanged) + Send + Sync>>,
     pub(crate) on_drag_start: Option<Box<dyn Fn(&mut EventContext) + Send + Sync>>,
     pub(crate) on_drop: Option<Box<dyn Fn(&mut EventContext, DropData) + Send + Sync>>,
+    pub(crate) on_animation_end:
+        Option<Box<dyn Fn(&mut EventContext, AnimationEvent) + Send + Sync>>,
 }
 
 impl ActionsModel {
@@ -79,6 +81,7 @@ impl ActionsModel {
             on_geo_changed: None,
             on_drag_start: None,
             on_drop: None,
+            on_animation_end: None,
         }
     }
 }
@@ -147,6 +150,18 @@ impl Model for ActionsModel {
             ActionsEvent::OnDrop(on_drop) => {
                 self.on_drop = Some(on_drop);
             }
+
+            ActionsEvent::OnAnimationEnd(on_animation_end) => {
+                self.on_animation_end = Some(on_animation_end);
+            }
+        });
+
+        event.map(|animation_event: &AnimationEvent, meta| {
+            if meta.target == cx.current() {
+                if let Some(action) = &self.on_animation_end {
+                    (action)(cx, animation_event.clone());
+                }
+            }
         });
 
         event.map(|window_event, meta| match window_event {
@@ -293,6 +308,7 @@ pub(crate) enum ActionsEvent {
     OnGeoChanged(Box<dyn Fn(&mut EventContext, GeoChanged) + Send + Sync>),
     OnDragStart(Box<dyn Fn(&mut EventContext) + Send + Sync>),
     OnDrop(Box<dyn Fn(&mut EventContext, DropData) + Send + Sync>),
+    OnAnimationEnd(Box<dyn Fn(&mut EventContext, AnimationEvent) + Send + Sync>),
 }
 
 /// Modifiers which add an action callback to a view.
@@ -474,6 +490,19 @@ pub trait ActionModifiers<V> {
     fn on_drop<F>(self, action: F) -> Self
     where
         F: 'static + Fn(&mut EventContext, DropData) + Send + Sync;
+
+    /// Adds a callback which is performed when an animation or transition played on the view
+    /// finishes or is interrupted.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// # let mut cx = &mut Context::default();
+    /// Element::new(cx).on_animation_end(|_, event| debug!("Animation ended: {:?}", event));
+    /// ```
+    fn on_animation_end<F>(self, action: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, AnimationEvent) + Send + Sync;
 }
 
 // If the entity doesn't have an `ActionsModel` then add one to the entity
@@ -785,4 +814,19 @@ impl<'a, V: View> ActionModifiers<V> for Handle<'a, V> {
 
         self
     }
+
+    fn on_animation_end<F>(self, action: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, AnimationEvent) + Send + Sync,
+    {
+        build_action_model(self.cx, self.entity);
+
+        self.cx.emit_custom(
+            Event::new(ActionsEvent::OnAnimationEnd(Box::new(action)))
+                .target(self.entity)
+                .origin(self.entity),
+        );
+
+        self
+    }
 }
@@ -12,7 +12,7 @@ pub trait LayoutModifiers: internal::Modifiable {
         /// There are three variants:
         /// - `LayoutType::Row` - Parent will stack its children horizontally.
         /// - `LayoutType::Column` - (default) Parent will stack its children vertically.
-        /// - `LayoutType::Grid` - The position of children is determine by the grid properties.
+        /// - `LayoutType::Grid` - The position of children is determined by the grid properties.
         ///
         /// # Example
         /// ```
@@ -35,6 +35,39 @@ pub trait LayoutModifiers: internal::Modifiable {
         SystemFlags::RELAYOUT
     );
 
+    /// Sets the direction of the view, which is inherited by its descendants.
+    ///
+    /// In the `Direction::Rtl` direction the children of a row are placed from right to left, the left
+    /// and right space of children is mirrored, and text is laid out from the right. The direction
+    /// can also be matched with the `:dir()` pseudo-class.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// # let cx = &mut Context::default();
+    /// HStack::new(cx, |cx| {
+    ///     Label::new(cx, "مرحبا");
+    ///     Label::new(cx, "Hello");
+    /// })
+    /// .direction(Direction::Rtl);
+    /// ```
+    fn direction<U: Into<Direction>>(mut self, value: impl Res<U>) -> Self {
+        let entity = self.entity();
+        let current = self.current();
+        self.context().with_current(current, move |cx| {
+            value.set_or_bind(cx, entity, move |cx, v| {
+                cx.style.direction.insert(entity, v.get(cx).into());
+                cx.style.needs_restyle(entity);
+                cx.style.needs_access_update(entity);
+                cx.style.needs_text_update(entity);
+                cx.needs_relayout();
+                cx.needs_redraw(entity);
+            });
+        });
+
+        self
+    }
+
     modifier!(
         /// Sets the position type of the view.
         ///
@@ -261,6 +294,60 @@ pub trait LayoutModifiers: internal::Modifiable {
         SystemFlags::RELAYOUT
     );
 
+    modifier!(
+        /// Sets the column tracks of the view when its layout type is `LayoutType::Grid`.
+        ///
+        /// Each track is sized by its units, where `Units::Auto` sizes the track to fit the children
+        /// which occupy only that track.
+        ///
+        /// # Example
+        /// ```
+        /// # use vizia_core::prelude::*;
+        /// # let cx = &mut Context::default();
+        /// Element::new(cx)
+        ///     .layout_type(LayoutType::Grid)
+        ///     .grid_columns(vec![Pixels(100.0), Stretch(1.0), Auto]);
+        /// ```
+        grid_columns,
+        Vec<Units>,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Sets the row tracks of the view when its layout type is `LayoutType::Grid`.
+        grid_rows,
+        Vec<Units>,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Sets the index of the first column occupied by the view within a grid.
+        column_start,
+        usize,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Sets the number of columns occupied by the view within a grid.
+        column_span,
+        usize,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Sets the index of the first row occupied by the view within a grid.
+        row_start,
+        usize,
+        SystemFlags::RELAYOUT
+    );
+
+    modifier!(
+        /// Sets the number of rows occupied by the view within a grid.
+        row_span,
+        usize,
+        SystemFlags::RELAYOUT
+    );
+
     modifier!(
         /// Sets the minimum width of the view.
         min_width,
@@ -0,0 +1,385 @@
+use skia_safe::codec::{self, codec_animation::DisposalMethod, Codec};
+use skia_safe::{images, AlphaType, ColorType, Data, ImageInfo};
+use web_time::Duration;
+
+/// The frames of an animated image, such as a GIF, animated WebP or APNG file.
+pub(crate) struct AnimatedImage {
+    pub frames: Vec<skia_safe::Image>,
+    // How long each frame is shown for.
+    pub durations: Vec<Duration>,
+    // How many times the animation plays, or `None` if it repeats forever.
+    pub plays: Option<usize>,
+}
+
+impl AnimatedImage {
+    // Returns the frame shown by a view with the given playback state.
+    pub fn frame(&self, playback: &ImagePlayback) -> &skia_safe::Image {
+        &self.frames[frame_index(&self.durations, playback.plays(self.plays), playback.elapsed)]
+    }
+
+    // Returns whether a view with the given playback state has played the animation to its end.
+    pub fn is_finished(&self, playback: &ImagePlayback) -> bool {
+        match playback.plays(self.plays) {
+            Some(plays) => {
+                playback.elapsed >= self.durations.iter().sum::<Duration>() * plays as u32
+            }
+            None => false,
+        }
+    }
+}
+
+/// The playback state of the animated images shown by a view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ImagePlayback {
+    pub playing: bool,
+    // Whether the animation repeats forever, or plays once, instead of as often as the file says.
+    pub looping: Option<bool>,
+    // How long the animation has been playing for.
+    pub elapsed: Duration,
+}
+
+impl Default for ImagePlayback {
+    fn default() -> Self {
+        Self { playing: true, looping: None, elapsed: Duration::ZERO }
+    }
+}
+
+impl ImagePlayback {
+    fn plays(&self, plays: Option<usize>) -> Option<usize> {
+        match self.looping {
+            Some(true) => None,
+            Some(false) => Some(1),
+            None => plays,
+        }
+    }
+}
+
+// Returns the index of the frame shown after playing for the given time. An animation which plays
+// a limited number of times stops on its last frame.
+pub(crate) fn frame_index(
+    durations: &[Duration],
+    plays: Option<usize>,
+    elapsed: Duration,
+) -> usize {
+    let total = durations.iter().sum::<Duration>().as_nanos();
+    if total == 0 {
+        return 0;
+    }
+
+    let elapsed = elapsed.as_nanos();
+    if plays.is_some_and(|plays| elapsed / total >= plays.max(1) as u128) {
+        return durations.len() - 1;
+    }
+
+    let mut time = elapsed % total;
+    for (index, duration) in durations.iter().enumerate() {
+        if time < duration.as_nanos() {
+            return index;
+        }
+
+        time -= duration.as_nanos();
+    }
+
+    durations.len() - 1
+}
+
+// The most memory which the decoded frames of an animated image can use. Every frame is decoded
+// when the image is loaded, so a long animation stops at the last frame which fits.
+const MAX_DECODED_BYTES: usize = 256 * 1024 * 1024;
+
+// The signature which every PNG file starts with.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+// Decodes the frames of an animated image. Returns `None` if the image has only one frame.
+pub(crate) fn decode_animated_image(data: &[u8]) -> Option<AnimatedImage> {
+    // The PNG codec of Skia decodes only the default image of an APNG file.
+    if data.starts_with(PNG_SIGNATURE) {
+        decode_apng_frames(data, MAX_DECODED_BYTES)
+    } else {
+        decode_frames(data, MAX_DECODED_BYTES)
+    }
+}
+
+// Returns how many frames of the given size fit within the memory limit, and logs a warning if
+// the image has more frames than that.
+fn frames_within_limit(frame_count: usize, info: &ImageInfo, max_bytes: usize) -> usize {
+    let max_frames = max_bytes / info.compute_min_byte_size().max(1);
+    if frame_count > max_frames {
+        log::warn!(
+            "Decoding only the first {} of the {} frames of an animated image",
+            max_frames,
+            frame_count
+        );
+    }
+
+    frame_count.min(max_frames)
+}
+
+// Returns how long a frame with the given delay is shown for. Like browsers, very short frames are
+// shown for 100ms, as many files rely on it.
+fn frame_duration(millis: u64) -> Duration {
+    Duration::from_millis(if millis <= 10 { 100 } else { millis })
+}
+
+fn decode_frames(data: &[u8], max_bytes: usize) -> Option<AnimatedImage> {
+    let mut codec = Codec::from_data(Data::new_copy(data))?;
+
+    // Frames are blended onto earlier frames, which needs premultiplied pixels.
+    let info = codec.info();
+    let info = match info.alpha_type() {
+        AlphaType::Unpremul => info.with_alpha_type(AlphaType::Premul),
+        _ => info,
+    };
+
+    let frame_count = frames_within_limit(codec.get_frame_count(), &info, max_bytes);
+    if frame_count < 2 {
+        return None;
+    }
+
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0; info.compute_byte_size(row_bytes)];
+
+    let mut frames = Vec::with_capacity(frame_count);
+    let mut durations = Vec::with_capacity(frame_count);
+    let mut previous_frame: Option<codec::FrameInfo> = None;
+    for index in 0..frame_count {
+        let frame_info = codec.get_frame_info(index)?;
+
+        // A frame which is drawn on top of earlier frames is decoded onto the pixels of the
+        // previous frame, so that the codec doesn't decode the earlier frames again. A frame which
+        // restores what was there before it can't be drawn on top of.
+        let prior_frame = previous_frame
+            .filter(|previous| {
+                frame_info.required_frame != codec::NO_FRAME
+                    && previous.disposal_method != DisposalMethod::RestorePrevious
+            })
+            .map(|_| index - 1);
+
+        let options = codec::Options { frame_index: index, prior_frame, ..Default::default() };
+        let result = codec.get_pixels_with_options(&info, &mut pixels, row_bytes, Some(&options));
+        if result != codec::Result::Success {
+            return None;
+        }
+
+        frames.push(images::raster_from_data(&info, Data::new_copy(&pixels), row_bytes)?);
+        durations.push(frame_duration(frame_info.duration.max(0) as u64));
+        previous_frame = Some(frame_info);
+    }
+
+    // The repetition count doesn't include the first time the animation plays.
+    let plays = codec.get_repetition_count().map(|repetitions| repetitions + 1);
+
+    Some(AnimatedImage { frames, durations, plays })
+}
+
+// Decodes the frames of an APNG file. Returns `None` if the file isn't animated, or has only one
+// frame.
+fn decode_apng_frames(data: &[u8], max_bytes: usize) -> Option<AnimatedImage> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let animation = *reader.info().animation_control()?;
+
+    let (width, height) = reader.info().size();
+    let info = ImageInfo::new(
+        (width as i32, height as i32),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    );
+
+    let frame_count = frames_within_limit(animation.num_frames as usize, &info, max_bytes);
+    if frame_count < 2 {
+        return None;
+    }
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+
+    // The default image is shown only by decoders which don't support APNG, unless it's also the
+    // first frame of the animation.
+    if reader.info().frame_control().is_none() {
+        reader.next_frame(&mut buffer).ok()?;
+    }
+
+    // Each frame is drawn onto the pixels left by the previous frame, which start transparent.
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0; info.compute_byte_size(row_bytes)];
+
+    let mut frames = Vec::with_capacity(frame_count);
+    let mut durations = Vec::with_capacity(frame_count);
+    for _ in 0..frame_count {
+        let output = reader.next_frame(&mut buffer).ok()?;
+        let control = *reader.info().frame_control()?;
+
+        let previous = (control.dispose_op == png::DisposeOp::Previous).then(|| pixels.clone());
+        draw_apng_frame(&mut pixels, row_bytes, &buffer, &output, &control);
+        frames.push(images::raster_from_data(&info, Data::new_copy(&pixels), row_bytes)?);
+
+        // A delay with a denominator of 0 is in hundredths of a second.
+        let denominator = if control.delay_den == 0 { 100 } else { control.delay_den as u64 };
+        durations.push(frame_duration(control.delay_num as u64 * 1000 / denominator));
+
+        match control.dispose_op {
+            png::DisposeOp::None => {}
+            png::DisposeOp::Background => {
+                for y in control.y_offset..control.y_offset + control.height {
+                    let start = y as usize * row_bytes + control.x_offset as usize * 4;
+                    pixels[start..start + control.width as usize * 4].fill(0);
+                }
+            }
+            png::DisposeOp::Previous => pixels = previous.unwrap(),
+        }
+    }
+
+    // A play count of 0 repeats the animation forever.
+    let plays = (animation.num_plays != 0).then_some(animation.num_plays as usize);
+
+    Some(AnimatedImage { frames, durations, plays })
+}
+
+// Draws a decoded APNG frame onto RGBA pixels, at the offset of the frame.
+fn draw_apng_frame(
+    pixels: &mut [u8],
+    row_bytes: usize,
+    frame: &[u8],
+    output: &png::OutputInfo,
+    control: &png::FrameControl,
+) {
+    let samples = output.color_type.samples();
+    for (y, row) in frame.chunks(output.line_size).take(output.height as usize).enumerate() {
+        let start = (control.y_offset as usize + y) * row_bytes + control.x_offset as usize * 4;
+        let targets = pixels[start..start + output.width as usize * 4].chunks_exact_mut(4);
+        for (source, target) in row.chunks_exact(samples).zip(targets) {
+            let source = match output.color_type {
+                png::ColorType::Grayscale => [source[0], source[0], source[0], 255],
+                png::ColorType::GrayscaleAlpha => [source[0], source[0], source[0], source[1]],
+                png::ColorType::Rgb => [source[0], source[1], source[2], 255],
+                _ => [source[0], source[1], source[2], source[3]],
+            };
+
+            match control.blend_op {
+                png::BlendOp::Source => target.copy_from_slice(&source),
+                png::BlendOp::Over => blend_over(source, target),
+            }
+        }
+    }
+}
+
+// Blends an unpremultiplied RGBA pixel over another.
+fn blend_over(source: [u8; 4], target: &mut [u8]) {
+    let source_alpha = source[3] as u32;
+    let target_alpha = target[3] as u32 * (255 - source_alpha) / 255;
+    let alpha = source_alpha + target_alpha;
+    if alpha == 0 {
+        target.fill(0);
+        return;
+    }
+
+    for channel in 0..3 {
+        let color = source[channel] as u32 * source_alpha + target[channel] as u32 * target_alpha;
+        target[channel] = ((color + alpha / 2) / alpha) as u8;
+    }
+    target[3] = alpha as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use skia_safe::Color;
+
+    const GIF: &[u8] = include_bytes!("../../resources/images/test/animated.gif");
+    const WEBP: &[u8] = include_bytes!("../../resources/images/test/animated.webp");
+    const APNG: &[u8] = include_bytes!("../../resources/images/test/animated.png");
+
+    fn color(image: &skia_safe::Image, x: i32, y: i32) -> Color {
+        image.peek_pixels().unwrap().get_color((x, y))
+    }
+
+    // Each image has a red frame shown for 100ms, then a blue frame shown for 200ms, and repeats
+    // forever.
+    #[test]
+    fn decode_animations() {
+        for data in [GIF, WEBP, APNG] {
+            let image = decode_animated_image(data).unwrap();
+            assert_eq!(image.frames.len(), 2);
+            assert_eq!(image.frames[1].dimensions(), (2, 2).into());
+            assert_eq!(color(&image.frames[0], 1, 1), Color::RED);
+            assert_eq!(color(&image.frames[1], 1, 1), Color::BLUE);
+            assert_eq!(image.durations, [Duration::from_millis(100), Duration::from_millis(200)]);
+            assert_eq!(image.plays, None);
+        }
+    }
+
+    #[test]
+    fn decode_frames_within_limit() {
+        // Each frame of 2x2 pixels takes 16 bytes.
+        assert!(decode_frames(GIF, 16).is_none());
+        assert_eq!(decode_frames(GIF, 32).unwrap().frames.len(), 2);
+        assert!(decode_apng_frames(APNG, 16).is_none());
+        assert_eq!(decode_apng_frames(APNG, 32).unwrap().frames.len(), 2);
+    }
+
+    // The position and size of a frame of an APNG file, the color it's filled with, and how it's
+    // blended and disposed of.
+    type Frame = ((u32, u32, u32, u32), [u8; 4], png::BlendOp, png::DisposeOp);
+
+    // Encodes an APNG file of 4x4 pixels with the given frames.
+    fn apng(frames: &[Frame]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, 4, 4);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_animated(frames.len() as u32, 0).unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        for &((x, y, width, height), color, blend, dispose) in frames {
+            writer.set_frame_dimension(width, height).unwrap();
+            writer.set_frame_position(x, y).unwrap();
+            writer.set_blend_op(blend).unwrap();
+            writer.set_dispose_op(dispose).unwrap();
+            writer.write_image_data(&color.repeat((width * height) as usize)).unwrap();
+        }
+        writer.finish().unwrap();
+        data
+    }
+
+    #[test]
+    fn blend_and_dispose_apng_frames() {
+        use png::{BlendOp::*, DisposeOp};
+
+        let image = decode_animated_image(&apng(&[
+            ((0, 0, 4, 4), [255, 0, 0, 255], Source, DisposeOp::None),
+            ((2, 2, 2, 2), [0, 0, 255, 128], Over, DisposeOp::Previous),
+            ((0, 0, 2, 2), [0, 255, 0, 255], Source, DisposeOp::Background),
+            ((3, 3, 1, 1), [0, 0, 0, 0], Over, DisposeOp::None),
+        ]))
+        .unwrap();
+        let frames = &image.frames;
+
+        // A half transparent frame is blended over the one before it.
+        assert_eq!(color(&frames[1], 0, 0), Color::RED);
+        assert_eq!(color(&frames[1], 3, 3), Color::from_argb(255, 127, 0, 128));
+
+        // The frame is then removed again.
+        assert_eq!(color(&frames[2], 0, 0), Color::GREEN);
+        assert_eq!(color(&frames[2], 3, 3), Color::RED);
+
+        // The area of the previous frame is cleared.
+        assert_eq!(color(&frames[3], 0, 0), Color::TRANSPARENT);
+        assert_eq!(color(&frames[3], 2, 0), Color::RED);
+        assert_eq!(color(&frames[3], 3, 3), Color::RED);
+    }
+
+    #[test]
+    fn frames_at_times() {
+        let durations = [Duration::from_millis(100), Duration::from_millis(50)];
+        let at = |plays, millis| frame_index(&durations, plays, Duration::from_millis(millis));
+
+        assert_eq!(at(None, 0), 0);
+        assert_eq!(at(None, 99), 0);
+        assert_eq!(at(None, 100), 1);
+        assert_eq!(at(None, 160), 0);
+        assert_eq!(at(Some(2), 260), 1);
+        assert_eq!(at(Some(2), 310), 1);
+        assert_eq!(at(Some(1), 0), 0);
+        assert_eq!(frame_index(&[], None, Duration::from_millis(10)), 0);
+    }
+}
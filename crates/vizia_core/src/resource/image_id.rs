@@ -8,3 +8,11 @@ use vizia_id::{
 pub struct ImageId(u64);
 
 impl_generational_id!(ImageId);
+
+impl ImageId {
+    // The placeholder shown while an image is loading. The root id is the broken image, which is
+    // shown if an image fails to load.
+    pub(crate) fn pending() -> Self {
+        Self::new(1, 0)
+    }
+}
@@ -1,21 +1,51 @@
 //! Resource management for fonts, themes, images, and translations.
 
+mod animated_image;
 mod image_id;
 
+pub(crate) use animated_image::{decode_animated_image, AnimatedImage, ImagePlayback};
 pub use image_id::ImageId;
 use vizia_id::{GenerationalId, IdManager};
 
 use crate::context::ResourceContext;
 use crate::entity::Entity;
+use crate::events::Timer;
 use crate::prelude::IntoCssStr;
 // use crate::view::Canvas;
 use fluent_bundle::{FluentBundle, FluentResource};
 use hashbrown::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use unic_langid::LanguageIdentifier;
+use web_time::Instant;
 
 pub(crate) enum ImageOrSvg {
     Svg(skia_safe::svg::Dom),
     Image(skia_safe::Image),
+    Animated(AnimatedImage),
+}
+
+// An image to draw, which for an animated image is one of its frames.
+pub(crate) enum ImageFrame<'a> {
+    Svg(&'a skia_safe::svg::Dom),
+    Image(&'a skia_safe::Image),
+}
+
+impl ImageOrSvg {
+    // Returns the frame shown by a view with the given playback state.
+    pub fn frame(&self, playback: &ImagePlayback) -> ImageFrame<'_> {
+        match self {
+            ImageOrSvg::Svg(svg) => ImageFrame::Svg(svg),
+            ImageOrSvg::Image(image) => ImageFrame::Image(image),
+            ImageOrSvg::Animated(animated) => ImageFrame::Image(animated.frame(playback)),
+        }
+    }
+}
+
+// A file which an image was loaded from, and when the file was last modified.
+pub(crate) struct ImageFile {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
 }
 
 pub(crate) struct StoredImage {
@@ -38,11 +68,28 @@ pub enum ImageRetentionPolicy {
 pub struct ResourceManager {
     pub themes: Vec<String>, // Themes are the string content stylesheets
     pub styles: Vec<Box<dyn IntoCssStr>>,
+    // Font files registered by `@font-face` rules, keyed by family name and path.
+    pub(crate) font_faces: HashSet<(String, PathBuf)>,
 
     pub(crate) image_id_manager: IdManager<ImageId>,
     pub(crate) images: HashMap<ImageId, StoredImage>,
     pub(crate) image_ids: HashMap<String, ImageId>,
 
+    // The directory which relative image paths are loaded from by the default image loader.
+    pub(crate) asset_root: PathBuf,
+
+    // The playback state of the animated images shown by each view.
+    pub(crate) image_playback: HashMap<Entity, ImagePlayback>,
+    // The views which are showing an animated image which is playing, and the image.
+    pub(crate) animated_views: Vec<(Entity, ImageId)>,
+    // The timer which advances animated images, and when it last did so.
+    pub(crate) image_timer: Option<Timer>,
+    pub(crate) image_clock: Option<Instant>,
+    // The files which images were loaded from, by the name of the image.
+    pub(crate) image_files: HashMap<String, ImageFile>,
+    // The timer which polls the files of images for changes.
+    pub(crate) image_watcher: Option<Timer>,
+
     pub translations: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
 
     pub language: LanguageIdentifier,
@@ -55,56 +102,35 @@ impl ResourceManager {
         // Get the system locale
         let locale = sys_locale::get_locale().and_then(|l| l.parse().ok()).unwrap_or_default();
 
-        let default_image_loader: Option<Box<dyn Fn(&mut ResourceContext, &str)>> = None;
-
-        // Disable this for now because reqwest pulls in too many dependencies.
-        // let default_image_loader: Option<Box<dyn Fn(&mut ResourceContext, &str)>> =
-        //     Some(Box::new(|cx: &mut ResourceContext, path: &str| {
-        //         if path.starts_with("https://") {
-        //             let path = path.to_string();
-        //             cx.spawn(move |cx| {
-        //                 let data = reqwest::blocking::get(&path).unwrap().bytes().unwrap();
-        //                 cx.load_image(
-        //                     path,
-        //                     image::load_from_memory_with_format(
-        //                         &data,
-        //                         image::guess_format(&data).unwrap(),
-        //                     )
-        //                     .unwrap(),
-        //                     ImageRetentionPolicy::DropWhenUnusedForOneFrame,
-        //                 )
-        //                 .unwrap();
-        //             });
-        //         } else {
-        //             // TODO: Try to load path from file
-        //         }
-        //     }));
+        // Images are loaded from files by default. A custom image loader can load other URLs and
+        // still call `load_file` for paths.
+        let default_image_loader: Option<Box<dyn Fn(&mut ResourceContext, &str)>> =
+            Some(Box::new(|cx: &mut ResourceContext, name: &str| cx.load_file(name)));
 
         let mut image_id_manager = IdManager::new();
 
         // Create root id for broken image
         image_id_manager.create();
+        // Create id for the placeholder shown while an image is loading
+        image_id_manager.create();
+
+        let broken_image = broken_image();
+        let pending_image = pending_image(&broken_image);
 
         let mut images = HashMap::new();
 
-        images.insert(
-            ImageId::root(),
-            StoredImage {
-                image: ImageOrSvg::Image(
-                    skia_safe::Image::from_encoded(unsafe {
-                        skia_safe::Data::new_bytes(include_bytes!(
-                            "../../resources/images/broken_image.png"
-                        ))
-                    })
-                    .unwrap(),
-                ),
-
-                retention_policy: ImageRetentionPolicy::Forever,
-                used: true,
-                dirty: false,
-                observers: HashSet::new(),
-            },
-        );
+        for (id, image) in [(ImageId::root(), broken_image), (ImageId::pending(), pending_image)] {
+            images.insert(
+                id,
+                StoredImage {
+                    image: ImageOrSvg::Image(image),
+                    retention_policy: ImageRetentionPolicy::Forever,
+                    used: true,
+                    dirty: false,
+                    observers: HashSet::new(),
+                },
+            );
+        }
 
         ResourceManager {
             themes: Vec::new(),
@@ -113,6 +139,14 @@ impl ResourceManager {
             images,
             image_ids: HashMap::new(),
             styles: Vec::new(),
+            font_faces: HashSet::new(),
+            asset_root: PathBuf::new(),
+            image_playback: HashMap::new(),
+            animated_views: Vec::new(),
+            image_timer: None,
+            image_clock: None,
+            image_files: HashMap::new(),
+            image_watcher: None,
 
             translations: HashMap::from([(
                 LanguageIdentifier::default(),
@@ -164,6 +198,43 @@ impl ResourceManager {
         }
     }
 
+    // Returns the views which show the image with the given id.
+    pub(crate) fn image_observers(&self, id: ImageId) -> Vec<Entity> {
+        self.images
+            .get(&id)
+            .map(|image| image.observers.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    // Records the file which an image is loaded from, so that the image can be reloaded when the
+    // file changes.
+    pub(crate) fn watch_image_file(&mut self, name: &str, path: PathBuf) {
+        let modified = file_modified(&path);
+        self.image_files.insert(name.to_owned(), ImageFile { path, modified });
+    }
+
+    // Returns the name, file and retention policy of each loaded image whose file has changed
+    // since the image was loaded or this was last called.
+    pub(crate) fn changed_image_files(&mut self) -> Vec<(String, PathBuf, ImageRetentionPolicy)> {
+        let mut changed = Vec::new();
+        for (name, file) in self.image_files.iter_mut() {
+            let Some(image) = self.image_ids.get(name).and_then(|id| self.images.get(id)) else {
+                continue;
+            };
+
+            let modified = file_modified(&file.path);
+            if modified != file.modified {
+                file.modified = modified;
+                // A file which has been removed keeps its last image.
+                if modified.is_some() {
+                    changed.push((name.clone(), file.path.clone(), image.retention_policy));
+                }
+            }
+        }
+
+        changed
+    }
+
     pub fn mark_images_unused(&mut self) {
         for (_, img) in self.images.iter_mut() {
             img.used = false;
@@ -192,3 +263,65 @@ impl ResourceManager {
         }
     }
 }
+
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+// Returns the image shown in place of an image which failed to load.
+pub(crate) fn broken_image() -> skia_safe::Image {
+    skia_safe::Image::from_encoded(unsafe {
+        skia_safe::Data::new_bytes(include_bytes!("../../resources/images/broken_image.png"))
+    })
+    .unwrap()
+}
+
+// Returns a transparent image the size of the broken image, so that a view doesn't change size when
+// an image which is loading fails to load.
+fn pending_image(broken_image: &skia_safe::Image) -> skia_safe::Image {
+    let mut surface = skia_safe::surfaces::raster_n32_premul(broken_image.dimensions()).unwrap();
+    surface.canvas().clear(skia_safe::Color::TRANSPARENT);
+    surface.image_snapshot()
+}
+
+// Decodes an encoded image, such as a PNG, JPEG or WebP file, into pixels. Images created from
+// encoded data are otherwise only decoded when they are first drawn, on the main thread.
+pub(crate) fn decode_image(data: &[u8]) -> Option<skia_safe::Image> {
+    let image = skia_safe::Image::from_encoded(skia_safe::Data::new_copy(data))?;
+    let mut surface = skia_safe::surfaces::raster_n32_premul(image.dimensions())?;
+    surface.canvas().draw_image(&image, (0, 0), None);
+    Some(surface.image_snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn changed_image_files() {
+        // The process id keeps test runs which happen at the same time from sharing the file.
+        let name = format!("vizia_changed_image_files_{}.png", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        let file = File::create(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+
+        let mut resource_manager = ResourceManager::new();
+        resource_manager.watch_image_file("icon.png", path.clone());
+        assert!(resource_manager.changed_image_files().is_empty());
+
+        // Only the files of images which are loaded are checked.
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(120)).unwrap();
+        assert!(resource_manager.changed_image_files().is_empty());
+
+        resource_manager.image_ids.insert(String::from("icon.png"), ImageId::root());
+        let changed = resource_manager.changed_image_files();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0, "icon.png");
+        assert_eq!(changed[0].1, path);
+        assert!(resource_manager.changed_image_files().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
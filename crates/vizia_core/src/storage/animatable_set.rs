@@ -1,4 +1,4 @@
-use crate::animation::{AnimationState, Interpolator};
+use crate::animation::{AnimationOptions, AnimationState, Interpolator, PlaybackCommand};
 use crate::prelude::*;
 use vizia_storage::{SparseSet, SparseSetGeneric, SparseSetIndex};
 
@@ -129,7 +129,9 @@ impl SparseSetIndex for SharedIndex {
 /// rule which defines a trnasition.
 ///
 /// Animations are moved from animations to active_animations when played. This allows the active
-/// animations to be quickly iterated to update the value.
+/// animations to be quickly iterated to update the value. An entity can play several animations of
+/// the same property at once, in which case the one played last determines the value, and each
+/// entity is linked to the last of the active animations which contain it.
 #[derive(Default, Debug)]
 pub(crate) struct AnimatableSet<T: Interpolator> {
     /// Shared data determined by style rules
@@ -140,6 +142,25 @@ pub(crate) struct AnimatableSet<T: Interpolator> {
     animations: SparseSet<AnimationState<T>>,
     /// Animations which are currently playing
     active_animations: Vec<AnimationState<T>>,
+    /// Animations which have ended since the last call to `take_ended`, as (entity, animation,
+    /// finished), where finished is false if the animation was interrupted.
+    ended: Vec<(Entity, Animation, bool)>,
+}
+
+/// Controls the playback of the active animations of an [`AnimatableSet`] independent of the
+/// type of its values.
+pub(crate) trait AnimationPlayback {
+    /// Applies a playback command to the given animation of an entity, returning true if it is active.
+    fn control(
+        &mut self,
+        entity: Entity,
+        animation: Animation,
+        command: PlaybackCommand,
+        time: Instant,
+    ) -> bool;
+
+    /// Returns the animations which have ended since the last call, as (entity, animation, finished).
+    fn take_ended(&mut self) -> Vec<(Entity, Animation, bool)>;
 }
 
 impl<T> AnimatableSet<T>
@@ -156,11 +177,13 @@ where
         let entity_index = entity.index();
 
         if entity_index < self.inline_data.sparse.len() {
-            let active_anim_index = self.inline_data.sparse[entity_index].anim_index as usize;
-
-            if active_anim_index < self.active_animations.len() {
-                let anim_state = &mut self.active_animations[active_anim_index];
-                anim_state.t = 1.0;
+            if self.inline_data.sparse[entity_index].anim_index != u32::MAX {
+                for anim_state in self.active_animations.iter_mut() {
+                    if anim_state.entities.remove(&entity) && anim_state.entities.is_empty() {
+                        anim_state.t = 1.0;
+                        anim_state.persistent = false;
+                    }
+                }
 
                 self.remove_innactive_animations();
             }
@@ -282,8 +305,9 @@ where
     pub(crate) fn insert_animation(
         &mut self,
         animation: Animation,
-        animation_description: AnimationState<T>,
+        mut animation_description: AnimationState<T>,
     ) {
+        animation_description.id = animation;
         self.animations.insert(animation, animation_description);
     }
 
@@ -312,8 +336,7 @@ where
         entity: Entity,
         animation: Animation,
         start_time: Instant,
-        duration: Duration,
-        delay: Duration,
+        options: &AnimationOptions,
     ) {
         let entity_index = entity.index();
 
@@ -326,108 +349,90 @@ where
             self.inline_data.sparse.resize(entity_index + 1, InlineIndex::null());
         }
 
-        if entity_index < self.inline_data.sparse.len() {
-            let active_anim_index = self.inline_data.sparse[entity_index].anim_index as usize;
-            if active_anim_index < self.active_animations.len() {
-                let anim_state = &mut self.active_animations[active_anim_index];
-                if anim_state.id == animation {
-                    anim_state.active = true;
-                    anim_state.t = 0.0;
-                    anim_state.start_time = start_time;
-                    anim_state.output = Some(
-                        self.animations
-                            .get(animation)
-                            .cloned()
-                            .unwrap()
-                            .keyframes
-                            .first()
-                            .unwrap()
-                            .value
-                            .clone(),
-                    );
-                } else {
-                    anim_state.output = Some(
-                        self.animations
-                            .get(animation)
-                            .cloned()
-                            .unwrap()
-                            .keyframes
-                            .first()
-                            .unwrap()
-                            .value
-                            .clone(),
-                    );
-                    anim_state.entities.remove(&entity);
-                }
-            }
+        // Safe to unwrap because already checked that the animation exists
+        let mut anim_state = self.animations.get(animation).cloned().unwrap();
 
-            // Safe to unwrap because already checked that the animation exists
-            let mut anim_state = self.animations.get(animation).cloned().unwrap();
-            anim_state.duration = duration;
-            anim_state.delay = delay;
-            anim_state.dt = delay.as_secs_f32() / duration.as_secs_f32();
-            anim_state.output = Some(
-                self.animations
-                    .get(animation)
-                    .cloned()
-                    .unwrap()
-                    .keyframes
-                    .first()
-                    .unwrap()
-                    .value
-                    .clone(),
-            );
-            anim_state.play(entity);
-            self.inline_data.sparse[entity_index].anim_index = self.active_animations.len() as u32;
-            self.active_animations.push(anim_state);
+        // Unlink the entity from the animation if it is already playing it, and from any transition
+        // if this is a transition. Other animations keep playing underneath this one.
+        let is_transition = anim_state.is_transition();
+        self.detach(entity, |state| {
+            state.id == animation || (is_transition && state.is_transition())
+        });
+        self.remove_innactive_animations();
+
+        anim_state.set_options(options);
+        anim_state.dt = options.delay.as_secs_f32() / options.duration.as_secs_f32();
+        anim_state.play(entity);
+        anim_state.start_time = start_time;
+        if anim_state.update(start_time) {
+            self.ended.push((entity, animation, true));
         }
+        self.inline_data.sparse[entity_index].anim_index = self.active_animations.len() as u32;
+        self.active_animations.push(anim_state);
     }
 
-    pub fn tick(&mut self, time: Instant) -> Vec<Entity> {
+    /// Stops the given animation for an entity, returning true if it was playing.
+    pub(crate) fn stop_animation(&mut self, entity: Entity, animation: Animation) -> bool {
+        if !self.has_active_animation(entity, animation) {
+            return false;
+        }
+
+        self.detach(entity, |state| state.id == animation);
         self.remove_innactive_animations();
 
-        if self.has_animations() {
-            for state in self.active_animations.iter_mut() {
-                // If the animation is already finished then skip
-                if state.t == 1.0 {
-                    continue;
-                }
+        true
+    }
 
-                if state.keyframes.len() == 1 {
-                    state.output = Some(state.keyframes[0].value.clone());
-                    continue;
-                }
+    // Unlinks an entity from the matching active animations, each of which finishes if no other
+    // entities are linked to it. The entity is linked to its remaining animations by
+    // `remove_innactive_animations`, which should be called afterwards.
+    fn detach(&mut self, entity: Entity, matches: impl Fn(&AnimationState<T>) -> bool) {
+        for anim_state in self.active_animations.iter_mut() {
+            if !matches(anim_state) || !anim_state.entities.contains(&entity) {
+                continue;
+            }
 
-                let elapsed_time = time.duration_since(state.start_time);
-                let mut normalised_time =
-                    (elapsed_time.as_secs_f32() / state.duration.as_secs_f32()) - state.dt;
+            if anim_state.t < 1.0 {
+                self.ended.push((entity, anim_state.id, false));
+            }
 
-                normalised_time = normalised_time.clamp(0.0, 1.0);
+            anim_state.entities.remove(&entity);
+            if anim_state.entities.is_empty() {
+                anim_state.t = 1.0;
+                anim_state.persistent = false;
+            }
+        }
+    }
 
-                let mut i = 0;
-                while i < state.keyframes.len() - 1 && state.keyframes[i + 1].time < normalised_time
-                {
-                    i += 1;
-                }
-                let start = &state.keyframes[i];
-                let end = &state.keyframes[i + 1];
+    pub fn tick(&mut self, time: Instant) -> Vec<Entity> {
+        // Entities whose animations finished on the last tick and no longer apply
+        let mut entities = self
+            .active_animations
+            .iter()
+            .filter(|state| state.t == 1.0 && !state.persistent)
+            .flat_map(|state| state.entities.iter().copied())
+            .collect::<Vec<Entity>>();
 
-                let normalised_elapsed_time =
-                    (normalised_time - start.time) / (end.time - start.time);
+        self.remove_innactive_animations();
 
-                state.t = normalised_time;
+        if !self.has_animations() {
+            return entities;
+        }
 
-                let timing_t = start.timing_function.value(normalised_elapsed_time);
-                state.output = Some(T::interpolate(&start.value, &end.value, timing_t));
+        for state in self.active_animations.iter_mut() {
+            // If the animation is already finished or is paused then skip
+            if state.t == 1.0 || state.paused {
+                continue;
             }
 
-            self.active_animations
-                .iter()
-                .flat_map(|state| state.entities.clone())
-                .collect::<Vec<Entity>>()
-        } else {
-            Vec::new()
+            if state.update(time) {
+                self.ended.extend(state.entities.iter().map(|entity| (*entity, state.id, true)));
+            }
+
+            entities.extend(state.entities.iter().copied());
         }
+
+        entities
     }
 
     /// Returns true if the given entity is linked to an active animation
@@ -462,6 +467,7 @@ where
             }
         }
 
+        // Later animations take precedence, so each entity is linked to the last which contains it.
         for (index, state) in self.active_animations.iter().enumerate() {
             for entity in state.entities.iter() {
                 self.inline_data.sparse[entity.index()].anim_index = index as u32;
@@ -471,7 +477,7 @@ where
 
     pub fn has_animations(&self) -> bool {
         for state in self.active_animations.iter() {
-            if state.t < 1.0 {
+            if state.t < 1.0 && !state.paused {
                 return true;
             }
         }
@@ -480,17 +486,20 @@ where
     }
 
     pub fn has_active_animation(&self, entity: Entity, animation: Animation) -> bool {
-        let entity_index = entity.index();
-        if entity_index < self.inline_data.sparse.len() {
-            let anim_index = self.inline_data.sparse[entity_index].anim_index as usize;
-            if anim_index < self.active_animations.len()
-                && self.active_animations[anim_index].id == animation
-            {
-                return true;
-            }
+        self.active_animation_index(entity, animation).is_some()
+    }
+
+    // Returns the index of the given active animation of an entity.
+    fn active_animation_index(&self, entity: Entity, animation: Animation) -> Option<usize> {
+        if entity.index() >= self.inline_data.sparse.len()
+            || self.inline_data.sparse[entity.index()].anim_index == u32::MAX
+        {
+            return None;
         }
 
-        false
+        self.active_animations
+            .iter()
+            .position(|state| state.id == animation && state.entities.contains(&entity))
     }
 
     /// Returns a reference to any inline data on the entity if it exists.
@@ -557,8 +566,16 @@ where
             // Animations override inline and shared styling
             let animation_index = self.inline_data.sparse[entity_index].anim_index as usize;
 
+            // An animation without an output, e.g. during its delay, doesn't override anything
             if animation_index < self.active_animations.len() {
-                return self.active_animations[animation_index].get_output();
+                if let Some(output) = self.active_animations[..=animation_index]
+                    .iter()
+                    .rev()
+                    .filter(|state| state.entities.contains(&entity))
+                    .find_map(|state| state.get_output())
+                {
+                    return Some(output);
+                }
             }
 
             let data_index = self.inline_data.sparse[entity_index].data_index;
@@ -609,7 +626,16 @@ where
                     if current_anim_state.is_transition() {
                         // Skip if the transition hasn't changed
                         if current_anim_state.to_rule != rule_data_index {
-                            if rule_data_index == current_anim_state.from_rule {
+                            if current_anim_state.spring.is_some() {
+                                // A spring moves towards the new rule from where it is, keeping
+                                // its velocity
+                                current_anim_state.from_rule = current_anim_state.to_rule;
+                                current_anim_state.to_rule = rule_data_index;
+                                current_anim_state.retarget(
+                                    self.shared_data.dense[rule_data_index].value.clone(),
+                                    crate::util::now(),
+                                );
+                            } else if rule_data_index == current_anim_state.from_rule {
                                 // Transitioning back to previous rule
                                 current_anim_state.from_rule = current_anim_state.to_rule;
                                 current_anim_state.to_rule = rule_data_index;
@@ -624,7 +650,7 @@ where
                                         .clone();
 
                                 current_anim_state.dt = current_anim_state.t - 1.0;
-                                current_anim_state.start_time = Instant::now();
+                                current_anim_state.restart(crate::util::now());
                             } else {
                                 // Transitioning to new rule
                                 current_anim_state.to_rule = rule_data_index;
@@ -635,7 +661,7 @@ where
                                         .value
                                         .clone();
                                 current_anim_state.t = 0.0;
-                                current_anim_state.start_time = Instant::now();
+                                current_anim_state.restart(crate::util::now());
                             }
                         }
                     }
@@ -660,19 +686,13 @@ where
                         self.inline_data.sparse[entity_index].data_index.index();
                     transition_state.to_rule = shared_data_index.index();
 
-                    let duration = transition_state.duration;
-                    let delay = transition_state.delay;
+                    let options =
+                        AnimationOptions::new(transition_state.duration, transition_state.delay);
 
                     if transition_state.from_rule != DataIndex::null().index()
                         && transition_state.from_rule != transition_state.to_rule
                     {
-                        self.play_animation(
-                            entity,
-                            rule_animation,
-                            Instant::now(),
-                            duration,
-                            delay,
-                        );
+                        self.play_animation(entity, rule_animation, crate::util::now(), &options);
                     }
                     //}
                 }
@@ -724,9 +744,40 @@ where
     }
 }
 
+impl<T> AnimationPlayback for AnimatableSet<T>
+where
+    T: 'static + Default + Clone + Interpolator + PartialEq + std::fmt::Debug,
+{
+    fn control(
+        &mut self,
+        entity: Entity,
+        animation: Animation,
+        command: PlaybackCommand,
+        time: Instant,
+    ) -> bool {
+        let Some(index) = self.active_animation_index(entity, animation) else {
+            return false;
+        };
+
+        let anim_state = &mut self.active_animations[index];
+        let was_finished = anim_state.t == 1.0;
+        anim_state.control(command, time);
+        if anim_state.update(time) && !was_finished {
+            self.ended.extend(anim_state.entities.iter().map(|entity| (*entity, animation, true)));
+        }
+
+        true
+    }
+
+    fn take_ended(&mut self) -> Vec<(Entity, Animation, bool)> {
+        std::mem::take(&mut self.ended)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::animation::Keyframe;
 
     // DataIndex tests
 
@@ -795,4 +846,54 @@ mod tests {
         animatable_storage.insert(Entity::root(), 5.0);
         //assert_eq!(animatable_storage.entity_indices.first().unwrap().data_index, DataIndex::inline(0));
     }
+
+    fn animation(set: &mut AnimatableSet<f32>, index: u64, from: f32, to: f32) -> Animation {
+        let animation = Animation::new(index, 0);
+        set.insert_animation(
+            animation,
+            AnimationState::new(animation)
+                .with_keyframe(Keyframe { time: 0.0, value: from, timing_function: None })
+                .with_keyframe(Keyframe { time: 1.0, value: to, timing_function: None }),
+        );
+        animation
+    }
+
+    fn assert_value(set: &AnimatableSet<f32>, entity: Entity, expected: f32) {
+        let value = *set.get(entity).unwrap();
+        assert!((value - expected).abs() < 1e-3, "expected {} but got {}", expected, value);
+    }
+
+    /// Test that the animation played last determines the value of the property, while earlier
+    /// animations of the property keep playing underneath it.
+    #[test]
+    fn later_animation_takes_precedence() {
+        let mut set = AnimatableSet::default();
+        let entity = Entity::root();
+        set.insert(entity, 0.0);
+
+        let first = animation(&mut set, 0, 0.0, 10.0);
+        let second = animation(&mut set, 1, 100.0, 200.0);
+
+        let start = Instant::now();
+        let options = |secs| AnimationOptions::new(Duration::from_secs(secs), Duration::ZERO);
+        set.play_animation(entity, first, start, &options(2));
+        set.play_animation(entity, second, start, &options(1));
+        assert!(set.has_active_animation(entity, first));
+        assert!(set.has_active_animation(entity, second));
+
+        set.tick(start + Duration::from_millis(500));
+        assert_value(&set, entity, 150.0);
+
+        // The first animation applies again once the second has finished.
+        set.tick(start + Duration::from_millis(1500));
+        set.tick(start + Duration::from_millis(1500));
+        assert_value(&set, entity, 7.5);
+        assert!(set.has_active_animation(entity, first));
+        assert!(!set.has_active_animation(entity, second));
+        assert_eq!(set.take_ended(), [(entity, second, true)]);
+
+        // Stopping the first animation leaves the inline value.
+        assert!(set.stop_animation(entity, first));
+        assert_value(&set, entity, 0.0);
+    }
 }
@@ -65,21 +65,25 @@ use indexmap::IndexMap;
 use log::warn;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut, Range};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 use crate::prelude::*;
 
 pub use vizia_style::{
     Angle, BackgroundImage, BackgroundSize, BorderStyleKeyword, ClipPath, Color, CornerShape,
-    CssRule, CursorIcon, Display, Filter, FontFamily, FontSize, FontSlant, FontVariation,
-    FontWeight, FontWeightKeyword, FontWidth, GenericFontFamily, Gradient, HorizontalPosition,
-    HorizontalPositionKeyword, Length, LengthOrPercentage, LengthValue, LineClamp, LineDirection,
-    LinearGradient, Matrix, Opacity, Overflow, PointerEvents, Position, Scale, Shadow, TextAlign,
-    TextDecorationLine, TextDecorationStyle, TextOverflow, Transform, Transition, Translate,
-    VerticalPosition, VerticalPositionKeyword, Visibility, RGBA,
+    CssRule, CursorIcon, Direction, Display, EasingFunction, Filter, FontFamily, FontSize,
+    FontSlant, FontVariation, FontWeight, FontWeightKeyword, FontWidth, GenericFontFamily,
+    Gradient, HorizontalPosition, HorizontalPositionKeyword, Length, LengthOrPercentage,
+    LengthValue, LineClamp, LineDirection, LinearGradient, Matrix, Opacity, Overflow,
+    PointerEvents, Position, Scale, Shadow, StepPosition, TextAlign, TextDecorationLine,
+    TextDecorationStyle, TextOverflow, Transform, Transition, Translate, VerticalPosition,
+    VerticalPositionKeyword, Visibility, RGBA,
 };
 
 use vizia_style::{
-    BlendMode, EasingFunction, KeyframeSelector, ParserOptions, Property, SelectorList, Selectors,
+    AnimationDirection, AnimationFillMode, AnimationIterationCount, AnimationName, BlendMode,
+    FontFaceSource, KeyframeSelector, MediaList, ParserOptions, Property, SelectorList, Selectors,
     StyleSheet,
 };
 
@@ -92,8 +96,11 @@ pub(crate) use pseudoclass::*;
 mod transform;
 pub(crate) use transform::*;
 
-use crate::animation::{AnimationState, Interpolator, Keyframe, TimingFunction};
-use crate::storage::animatable_set::AnimatableSet;
+use crate::animation::{
+    AnimationOptions, AnimationState, Interpolator, Keyframe, PlaybackCommand, TimingFunction,
+};
+use crate::layout::grid::GridPlacement;
+use crate::storage::animatable_set::{AnimatableSet, AnimationPlayback};
 use crate::storage::style_set::StyleSet;
 use bitflags::bitflags;
 use vizia_id::IdManager;
@@ -165,6 +172,32 @@ impl AsRef<str> for FamilyOwned {
     }
 }
 
+/// The external resources referenced by the stylesheets of the application.
+#[derive(Default)]
+pub(crate) struct StylesheetResources {
+    /// The paths of the stylesheets included with `@import` rules.
+    pub imports: Vec<PathBuf>,
+    /// The font families declared with `@font-face` rules, along with the path of the font file.
+    pub fonts: Vec<(String, PathBuf)>,
+}
+
+// Resolves a url within a stylesheet relative to the directory of the stylesheet.
+fn resolve_url(dir: Option<&Path>, url: &str) -> PathBuf {
+    match dir {
+        Some(dir) => dir.join(url),
+        None => PathBuf::from(url),
+    }
+}
+
+/// A rule created by substituting the `var()` references in the declarations of a rule.
+pub(crate) struct ResolvedRule {
+    pub rule: Rule,
+    /// The names of the properties declared by the rule, joined by semicolons.
+    pub names: String,
+    /// The number of entities which use the rule.
+    pub users: usize,
+}
+
 pub(crate) struct Bloom(pub(crate) qfilter::Filter);
 
 impl Default for Bloom {
@@ -196,11 +229,46 @@ pub struct Style {
     pub(crate) animation_manager: IdManager<Animation>,
     pub(crate) animations: HashMap<String, Animation>,
     // List of animations to be started on the next frame
-    pub(crate) pending_animations: Vec<(Entity, Animation, Duration, Duration)>,
+    pub(crate) pending_animations: Vec<(Entity, Animation, AnimationOptions)>,
+    // The property animated by each transition.
+    pub(crate) transition_properties: HashMap<Animation, String>,
+
+    // Animation properties, which play the `@keyframes` animations named by `animation-name`.
+    pub(crate) animation_name: StyleSet<Vec<AnimationName>>,
+    pub(crate) animation_duration: StyleSet<Vec<Duration>>,
+    pub(crate) animation_timing_function: StyleSet<Vec<EasingFunction>>,
+    pub(crate) animation_delay: StyleSet<Vec<Duration>>,
+    pub(crate) animation_iteration_count: StyleSet<Vec<AnimationIterationCount>>,
+    pub(crate) animation_direction: StyleSet<Vec<AnimationDirection>>,
+    pub(crate) animation_fill_mode: StyleSet<Vec<AnimationFillMode>>,
+    // The animations played by the animation properties of each entity.
+    pub(crate) css_animations: SparseSet<Vec<(Animation, AnimationOptions)>>,
 
     // List of rules
     pub(crate) rules: IndexMap<Rule, SelectorList<Selectors>>,
 
+    // Media query lists of `@media` rules.
+    pub(crate) media_queries: Vec<MediaList>,
+    // Indices of the media query lists which must match for a rule to apply.
+    pub(crate) rule_media: HashMap<Rule, Vec<usize>>,
+    // The result of evaluating each media query list for each window.
+    pub(crate) media_query_results: HashMap<Entity, Vec<bool>>,
+
+    // Custom property declarations, e.g. `--accent: red`, for each rule.
+    pub(crate) custom_properties: HashMap<Rule, Vec<(String, String)>>,
+    // Declarations containing `var()` references for each rule, which are resolved per entity by the style system.
+    pub(crate) variable_declarations: HashMap<Rule, Vec<(String, String)>>,
+    // Rules created from the variable declarations of a rule for each distinct set of substituted values.
+    pub(crate) resolved_variable_rules: HashMap<(Rule, String), ResolvedRule>,
+    // Resolved rules which are no longer used by any entity, for each rule and the names of the properties
+    // they declare. These are reused rather than creating new rules, so that the number of resolved rules is
+    // bounded by how many are in use at once instead of by how many values the custom properties have taken.
+    pub(crate) unused_variable_rules: HashMap<(Rule, String), Vec<Rule>>,
+    // The keys of the resolved rules used by each entity.
+    pub(crate) entity_variable_rules: SparseSet<Vec<(Rule, String)>>,
+    // The computed custom properties of each entity, including inherited custom properties.
+    pub(crate) custom_property_values: SparseSet<Rc<HashMap<String, String>>>,
+
     pub(crate) default_font: Vec<FamilyOwned>,
 
     // CSS Selector Properties
@@ -319,6 +387,9 @@ pub struct Style {
     // Display
     pub(crate) display: AnimatableSet<Display>,
 
+    // Direction
+    pub(crate) direction: StyleSet<Direction>,
+
     // Layout Type
     pub(crate) layout_type: StyleSet<LayoutType>,
 
@@ -339,6 +410,16 @@ pub struct Style {
     pub(crate) row_between: AnimatableSet<Units>,
     pub(crate) col_between: AnimatableSet<Units>,
 
+    // Grid
+    pub(crate) grid_columns: StyleSet<Vec<Units>>,
+    pub(crate) grid_rows: StyleSet<Vec<Units>>,
+    pub(crate) column_start: StyleSet<usize>,
+    pub(crate) column_span: StyleSet<usize>,
+    pub(crate) row_start: StyleSet<usize>,
+    pub(crate) row_span: StyleSet<usize>,
+    pub(crate) grid_placement: SparseSet<GridPlacement>,
+    pub(crate) grid_content_size: SparseSet<(f32, f32)>,
+
     // Size
     pub(crate) width: AnimatableSet<Units>,
     pub(crate) height: AnimatableSet<Units>,
@@ -391,6 +472,9 @@ impl Style {
     pub(crate) fn remove_rules(&mut self) {
         self.rule_manager.reset();
         self.rules.clear();
+        self.rule_media.clear();
+        self.media_queries.clear();
+        self.media_query_results.clear();
     }
 
     pub(crate) fn get_animation(&self, name: &str) -> Option<&Animation> {
@@ -407,55 +491,90 @@ impl Style {
             storage: &mut AnimatableSet<T>,
             animation_id: Animation,
             time: f32,
+            timing_function: &Option<TimingFunction>,
             value: T,
         ) {
-            let keyframe = Keyframe { time, value, timing_function: TimingFunction::linear() };
+            let keyframe = Keyframe { time, value, timing_function: timing_function.clone() };
 
             if let Some(anim_state) = storage.get_animation_mut(animation_id) {
-                anim_state.keyframes.push(keyframe)
+                // Keep the keyframes in order, as they may be declared in any order.
+                let index = anim_state.keyframes.partition_point(|k| k.time <= time);
+                anim_state.keyframes.insert(index, keyframe)
             } else {
                 let anim_state = AnimationState::new(animation_id).with_keyframe(keyframe);
                 storage.insert_animation(animation_id, anim_state);
             }
         }
 
+        // The timing function of a keyframe applies from that keyframe to the next. Keyframes
+        // without one use the timing function the animation is played with.
+        let timing_function = properties.iter().rev().find_map(|property| match property {
+            Property::AnimationTimingFunction(easing) => Some(easing.clone().into()),
+            _ => None,
+        });
+        let timing_function = &timing_function;
+
         for property in properties.iter() {
             match property {
                 // DISPLAY
                 Property::Display(value) => {
-                    insert_keyframe(&mut self.display, animation_id, time, *value);
+                    insert_keyframe(&mut self.display, animation_id, time, timing_function, *value);
                 }
 
                 Property::Opacity(value) => {
-                    insert_keyframe(&mut self.opacity, animation_id, time, *value);
+                    insert_keyframe(&mut self.opacity, animation_id, time, timing_function, *value);
                 }
 
                 Property::ClipPath(value) => {
-                    insert_keyframe(&mut self.clip_path, animation_id, time, value.clone());
+                    insert_keyframe(
+                        &mut self.clip_path,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value.clone(),
+                    );
                 }
 
                 // TRANSFORM
                 Property::Transform(value) => {
-                    insert_keyframe(&mut self.transform, animation_id, time, value.clone());
+                    insert_keyframe(
+                        &mut self.transform,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value.clone(),
+                    );
                 }
 
                 Property::TransformOrigin(transform_origin) => {
                     let x = transform_origin.x.to_length_or_percentage();
                     let y = transform_origin.y.to_length_or_percentage();
                     let value = Translate { x, y };
-                    insert_keyframe(&mut self.transform_origin, animation_id, time, value);
+                    insert_keyframe(
+                        &mut self.transform_origin,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value,
+                    );
                 }
 
                 Property::Translate(value) => {
-                    insert_keyframe(&mut self.translate, animation_id, time, value.clone());
+                    insert_keyframe(
+                        &mut self.translate,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value.clone(),
+                    );
                 }
 
                 Property::Rotate(value) => {
-                    insert_keyframe(&mut self.rotate, animation_id, time, *value);
+                    insert_keyframe(&mut self.rotate, animation_id, time, timing_function, *value);
                 }
 
                 Property::Scale(value) => {
-                    insert_keyframe(&mut self.scale, animation_id, time, *value);
+                    insert_keyframe(&mut self.scale, animation_id, time, timing_function, *value);
                 }
 
                 // BORDER
@@ -464,12 +583,19 @@ impl Style {
                         &mut self.border_width,
                         animation_id,
                         time,
+                        timing_function,
                         value.left.0.clone(),
                     );
                 }
 
                 Property::BorderColor(value) => {
-                    insert_keyframe(&mut self.border_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.border_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::CornerTopLeftRadius(value) => {
@@ -477,6 +603,7 @@ impl Style {
                         &mut self.corner_top_left_radius,
                         animation_id,
                         time,
+                        timing_function,
                         value.clone(),
                     );
                 }
@@ -486,6 +613,7 @@ impl Style {
                         &mut self.corner_top_right_radius,
                         animation_id,
                         time,
+                        timing_function,
                         value.clone(),
                     );
                 }
@@ -495,6 +623,7 @@ impl Style {
                         &mut self.corner_bottom_left_radius,
                         animation_id,
                         time,
+                        timing_function,
                         value.clone(),
                     );
                 }
@@ -504,6 +633,7 @@ impl Style {
                         &mut self.corner_bottom_right_radius,
                         animation_id,
                         time,
+                        timing_function,
                         value.clone(),
                     );
                 }
@@ -514,21 +644,40 @@ impl Style {
                         &mut self.outline_width,
                         animation_id,
                         time,
+                        timing_function,
                         value.left.0.clone(),
                     );
                 }
 
                 Property::OutlineColor(value) => {
-                    insert_keyframe(&mut self.outline_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.outline_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::OutlineOffset(value) => {
-                    insert_keyframe(&mut self.outline_offset, animation_id, time, value.clone());
+                    insert_keyframe(
+                        &mut self.outline_offset,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value.clone(),
+                    );
                 }
 
                 // BACKGROUND
                 Property::BackgroundColor(value) => {
-                    insert_keyframe(&mut self.background_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.background_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::BackgroundImage(images) => {
@@ -544,142 +693,286 @@ impl Style {
                             }
                         })
                         .collect::<Vec<_>>();
-                    insert_keyframe(&mut self.background_image, animation_id, time, images);
+                    insert_keyframe(
+                        &mut self.background_image,
+                        animation_id,
+                        time,
+                        timing_function,
+                        images,
+                    );
                 }
 
                 Property::BackgroundSize(value) => {
-                    insert_keyframe(&mut self.background_size, animation_id, time, value.clone());
+                    insert_keyframe(
+                        &mut self.background_size,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value.clone(),
+                    );
                 }
 
                 // BOX SHADOW
                 Property::Shadow(value) => {
-                    insert_keyframe(&mut self.shadow, animation_id, time, value.clone());
+                    insert_keyframe(
+                        &mut self.shadow,
+                        animation_id,
+                        time,
+                        timing_function,
+                        value.clone(),
+                    );
                 }
 
                 // TEXT
                 Property::FontColor(value) => {
-                    insert_keyframe(&mut self.font_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.font_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::FontSize(value) => {
-                    insert_keyframe(&mut self.font_size, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.font_size,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::CaretColor(value) => {
-                    insert_keyframe(&mut self.caret_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.caret_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::SelectionColor(value) => {
-                    insert_keyframe(&mut self.selection_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.selection_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 // SPACE
                 Property::Left(value) => {
-                    insert_keyframe(&mut self.left, animation_id, time, *value);
+                    insert_keyframe(&mut self.left, animation_id, time, timing_function, *value);
                 }
 
                 Property::Right(value) => {
-                    insert_keyframe(&mut self.right, animation_id, time, *value);
+                    insert_keyframe(&mut self.right, animation_id, time, timing_function, *value);
                 }
 
                 Property::Top(value) => {
-                    insert_keyframe(&mut self.top, animation_id, time, *value);
+                    insert_keyframe(&mut self.top, animation_id, time, timing_function, *value);
                 }
 
                 Property::Bottom(value) => {
-                    insert_keyframe(&mut self.bottom, animation_id, time, *value);
+                    insert_keyframe(&mut self.bottom, animation_id, time, timing_function, *value);
                 }
 
                 // CHILD SPACE
                 Property::ChildLeft(value) => {
-                    insert_keyframe(&mut self.child_left, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.child_left,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::ChildRight(value) => {
-                    insert_keyframe(&mut self.child_right, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.child_right,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::ChildTop(value) => {
-                    insert_keyframe(&mut self.child_top, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.child_top,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::ChildBottom(value) => {
-                    insert_keyframe(&mut self.child_bottom, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.child_bottom,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::ColBetween(value) => {
-                    insert_keyframe(&mut self.col_between, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.col_between,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::RowBetween(value) => {
-                    insert_keyframe(&mut self.row_between, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.row_between,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 // SIZE
                 Property::Width(value) => {
-                    insert_keyframe(&mut self.width, animation_id, time, *value);
+                    insert_keyframe(&mut self.width, animation_id, time, timing_function, *value);
                 }
 
                 Property::Height(value) => {
-                    insert_keyframe(&mut self.height, animation_id, time, *value);
+                    insert_keyframe(&mut self.height, animation_id, time, timing_function, *value);
                 }
 
                 // SIZE CONSTRAINTS
                 Property::MinWidth(value) => {
-                    insert_keyframe(&mut self.min_width, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.min_width,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MaxWidth(value) => {
-                    insert_keyframe(&mut self.max_width, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.max_width,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MinHeight(value) => {
-                    insert_keyframe(&mut self.min_height, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.min_height,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MaxHeight(value) => {
-                    insert_keyframe(&mut self.max_height, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.max_height,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 // SPACE CONSTRAINTS
                 Property::MinLeft(value) => {
-                    insert_keyframe(&mut self.min_left, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.min_left,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MaxLeft(value) => {
-                    insert_keyframe(&mut self.max_left, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.max_left,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MinRight(value) => {
-                    insert_keyframe(&mut self.min_right, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.min_right,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MaxRight(value) => {
-                    insert_keyframe(&mut self.max_right, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.max_right,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MinTop(value) => {
-                    insert_keyframe(&mut self.min_top, animation_id, time, *value);
+                    insert_keyframe(&mut self.min_top, animation_id, time, timing_function, *value);
                 }
 
                 Property::MaxTop(value) => {
-                    insert_keyframe(&mut self.max_top, animation_id, time, *value);
+                    insert_keyframe(&mut self.max_top, animation_id, time, timing_function, *value);
                 }
 
                 Property::MinBottom(value) => {
-                    insert_keyframe(&mut self.min_bottom, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.min_bottom,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::MaxBottom(value) => {
-                    insert_keyframe(&mut self.max_bottom, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.max_bottom,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::UnderlineColor(value) => {
-                    insert_keyframe(&mut self.underline_color, animation_id, time, *value);
+                    insert_keyframe(
+                        &mut self.underline_color,
+                        animation_id,
+                        time,
+                        timing_function,
+                        *value,
+                    );
                 }
 
                 Property::Fill(value) => {
-                    insert_keyframe(&mut self.fill, animation_id, time, *value);
+                    insert_keyframe(&mut self.fill, animation_id, time, timing_function, *value);
                 }
 
                 _ => {}
@@ -700,19 +993,18 @@ impl Style {
         &mut self,
         entity: Entity,
         animation: Animation,
-        duration: Duration,
-        delay: Duration,
+        options: AnimationOptions,
     ) {
-        self.pending_animations.push((entity, animation, duration, delay));
+        self.pending_animations.push((entity, animation, options));
     }
 
     pub(crate) fn play_pending_animations(&mut self) {
-        let start_time = Instant::now();
+        let start_time = crate::util::now();
 
         let pending_animations = self.pending_animations.drain(..).collect::<Vec<_>>();
 
-        for (entity, animation, duration, delay) in pending_animations {
-            self.play_animation(entity, animation, start_time + delay, duration, delay)
+        for (entity, animation, options) in pending_animations {
+            self.play_animation(entity, animation, start_time, &options)
         }
     }
 
@@ -721,76 +1013,170 @@ impl Style {
         entity: Entity,
         animation: Animation,
         start_time: Instant,
-        duration: Duration,
-        delay: Duration,
+        options: &AnimationOptions,
     ) {
-        self.display.play_animation(entity, animation, start_time, duration, delay);
-        self.opacity.play_animation(entity, animation, start_time, duration, delay);
-        self.clip_path.play_animation(entity, animation, start_time, duration, delay);
-
-        self.transform.play_animation(entity, animation, start_time, duration, delay);
-        self.transform_origin.play_animation(entity, animation, start_time, duration, delay);
-        self.translate.play_animation(entity, animation, start_time, duration, delay);
-        self.rotate.play_animation(entity, animation, start_time, duration, delay);
-        self.scale.play_animation(entity, animation, start_time, duration, delay);
-
-        self.border_width.play_animation(entity, animation, start_time, duration, delay);
-        self.border_color.play_animation(entity, animation, start_time, duration, delay);
-
-        self.corner_top_left_radius.play_animation(entity, animation, start_time, duration, delay);
-        self.corner_top_right_radius.play_animation(entity, animation, start_time, duration, delay);
-        self.corner_bottom_left_radius
-            .play_animation(entity, animation, start_time, duration, delay);
-        self.corner_bottom_right_radius
-            .play_animation(entity, animation, start_time, duration, delay);
-
-        self.outline_width.play_animation(entity, animation, start_time, duration, delay);
-        self.outline_color.play_animation(entity, animation, start_time, duration, delay);
-        self.outline_offset.play_animation(entity, animation, start_time, duration, delay);
-
-        self.background_color.play_animation(entity, animation, start_time, duration, delay);
-        self.background_image.play_animation(entity, animation, start_time, duration, delay);
-        self.background_size.play_animation(entity, animation, start_time, duration, delay);
-
-        self.shadow.play_animation(entity, animation, start_time, duration, delay);
-
-        self.font_color.play_animation(entity, animation, start_time, duration, delay);
-        self.font_size.play_animation(entity, animation, start_time, duration, delay);
-        self.caret_color.play_animation(entity, animation, start_time, duration, delay);
-        self.selection_color.play_animation(entity, animation, start_time, duration, delay);
-
-        self.left.play_animation(entity, animation, start_time, duration, delay);
-        self.right.play_animation(entity, animation, start_time, duration, delay);
-        self.top.play_animation(entity, animation, start_time, duration, delay);
-        self.bottom.play_animation(entity, animation, start_time, duration, delay);
-
-        self.child_left.play_animation(entity, animation, start_time, duration, delay);
-        self.child_right.play_animation(entity, animation, start_time, duration, delay);
-        self.child_top.play_animation(entity, animation, start_time, duration, delay);
-        self.child_bottom.play_animation(entity, animation, start_time, duration, delay);
-        self.col_between.play_animation(entity, animation, start_time, duration, delay);
-        self.row_between.play_animation(entity, animation, start_time, duration, delay);
-
-        self.width.play_animation(entity, animation, start_time, duration, delay);
-        self.height.play_animation(entity, animation, start_time, duration, delay);
-
-        self.min_width.play_animation(entity, animation, start_time, duration, delay);
-        self.max_width.play_animation(entity, animation, start_time, duration, delay);
-        self.min_height.play_animation(entity, animation, start_time, duration, delay);
-        self.max_height.play_animation(entity, animation, start_time, duration, delay);
-
-        self.min_left.play_animation(entity, animation, start_time, duration, delay);
-        self.max_left.play_animation(entity, animation, start_time, duration, delay);
-        self.min_right.play_animation(entity, animation, start_time, duration, delay);
-        self.max_right.play_animation(entity, animation, start_time, duration, delay);
-        self.min_top.play_animation(entity, animation, start_time, duration, delay);
-        self.max_top.play_animation(entity, animation, start_time, duration, delay);
-        self.min_bottom.play_animation(entity, animation, start_time, duration, delay);
-        self.max_bottom.play_animation(entity, animation, start_time, duration, delay);
-
-        self.underline_color.play_animation(entity, animation, start_time, duration, delay);
-
-        self.fill.play_animation(entity, animation, start_time, duration, delay);
+        self.display.play_animation(entity, animation, start_time, options);
+        self.opacity.play_animation(entity, animation, start_time, options);
+        self.clip_path.play_animation(entity, animation, start_time, options);
+
+        self.transform.play_animation(entity, animation, start_time, options);
+        self.transform_origin.play_animation(entity, animation, start_time, options);
+        self.translate.play_animation(entity, animation, start_time, options);
+        self.rotate.play_animation(entity, animation, start_time, options);
+        self.scale.play_animation(entity, animation, start_time, options);
+
+        self.border_width.play_animation(entity, animation, start_time, options);
+        self.border_color.play_animation(entity, animation, start_time, options);
+
+        self.corner_top_left_radius.play_animation(entity, animation, start_time, options);
+        self.corner_top_right_radius.play_animation(entity, animation, start_time, options);
+        self.corner_bottom_left_radius.play_animation(entity, animation, start_time, options);
+        self.corner_bottom_right_radius.play_animation(entity, animation, start_time, options);
+
+        self.outline_width.play_animation(entity, animation, start_time, options);
+        self.outline_color.play_animation(entity, animation, start_time, options);
+        self.outline_offset.play_animation(entity, animation, start_time, options);
+
+        self.background_color.play_animation(entity, animation, start_time, options);
+        self.background_image.play_animation(entity, animation, start_time, options);
+        self.background_size.play_animation(entity, animation, start_time, options);
+
+        self.shadow.play_animation(entity, animation, start_time, options);
+
+        self.font_color.play_animation(entity, animation, start_time, options);
+        self.font_size.play_animation(entity, animation, start_time, options);
+        self.caret_color.play_animation(entity, animation, start_time, options);
+        self.selection_color.play_animation(entity, animation, start_time, options);
+
+        self.left.play_animation(entity, animation, start_time, options);
+        self.right.play_animation(entity, animation, start_time, options);
+        self.top.play_animation(entity, animation, start_time, options);
+        self.bottom.play_animation(entity, animation, start_time, options);
+
+        self.child_left.play_animation(entity, animation, start_time, options);
+        self.child_right.play_animation(entity, animation, start_time, options);
+        self.child_top.play_animation(entity, animation, start_time, options);
+        self.child_bottom.play_animation(entity, animation, start_time, options);
+        self.col_between.play_animation(entity, animation, start_time, options);
+        self.row_between.play_animation(entity, animation, start_time, options);
+
+        self.width.play_animation(entity, animation, start_time, options);
+        self.height.play_animation(entity, animation, start_time, options);
+
+        self.min_width.play_animation(entity, animation, start_time, options);
+        self.max_width.play_animation(entity, animation, start_time, options);
+        self.min_height.play_animation(entity, animation, start_time, options);
+        self.max_height.play_animation(entity, animation, start_time, options);
+
+        self.min_left.play_animation(entity, animation, start_time, options);
+        self.max_left.play_animation(entity, animation, start_time, options);
+        self.min_right.play_animation(entity, animation, start_time, options);
+        self.max_right.play_animation(entity, animation, start_time, options);
+        self.min_top.play_animation(entity, animation, start_time, options);
+        self.max_top.play_animation(entity, animation, start_time, options);
+        self.min_bottom.play_animation(entity, animation, start_time, options);
+        self.max_bottom.play_animation(entity, animation, start_time, options);
+
+        self.underline_color.play_animation(entity, animation, start_time, options);
+
+        self.fill.play_animation(entity, animation, start_time, options);
+    }
+
+    // Calls the given function with each set of animatable properties.
+    fn for_each_animatable_set(&mut self, mut f: impl FnMut(&mut dyn AnimationPlayback)) {
+        f(&mut self.display);
+        f(&mut self.opacity);
+        f(&mut self.clip_path);
+        f(&mut self.transform);
+        f(&mut self.transform_origin);
+        f(&mut self.translate);
+        f(&mut self.rotate);
+        f(&mut self.scale);
+        f(&mut self.border_width);
+        f(&mut self.border_color);
+        f(&mut self.corner_top_left_radius);
+        f(&mut self.corner_top_right_radius);
+        f(&mut self.corner_bottom_left_radius);
+        f(&mut self.corner_bottom_right_radius);
+        f(&mut self.outline_width);
+        f(&mut self.outline_color);
+        f(&mut self.outline_offset);
+        f(&mut self.background_color);
+        f(&mut self.background_image);
+        f(&mut self.background_size);
+        f(&mut self.shadow);
+        f(&mut self.font_color);
+        f(&mut self.font_size);
+        f(&mut self.caret_color);
+        f(&mut self.selection_color);
+        f(&mut self.left);
+        f(&mut self.right);
+        f(&mut self.top);
+        f(&mut self.bottom);
+        f(&mut self.child_left);
+        f(&mut self.child_right);
+        f(&mut self.child_top);
+        f(&mut self.child_bottom);
+        f(&mut self.col_between);
+        f(&mut self.row_between);
+        f(&mut self.width);
+        f(&mut self.height);
+        f(&mut self.min_width);
+        f(&mut self.max_width);
+        f(&mut self.min_height);
+        f(&mut self.max_height);
+        f(&mut self.min_left);
+        f(&mut self.max_left);
+        f(&mut self.min_right);
+        f(&mut self.max_right);
+        f(&mut self.min_top);
+        f(&mut self.max_top);
+        f(&mut self.min_bottom);
+        f(&mut self.max_bottom);
+        f(&mut self.underline_color);
+        f(&mut self.fill);
+    }
+
+    /// Applies a playback command to an animation of an entity, returning true if it is playing.
+    pub(crate) fn control_animation(
+        &mut self,
+        entity: Entity,
+        animation: Animation,
+        command: PlaybackCommand,
+    ) -> bool {
+        // Start any animations which were played this frame so that they can be controlled.
+        self.play_pending_animations();
+
+        let time = crate::util::now();
+        let mut playing = false;
+        self.for_each_animatable_set(|set| {
+            playing |= set.control(entity, animation, command, time);
+        });
+
+        playing
+    }
+
+    /// Returns the events for the animations and transitions which have ended since the last call.
+    pub(crate) fn take_animation_events(&mut self) -> Vec<(Entity, AnimationEvent)> {
+        let mut ended = Vec::new();
+        self.for_each_animatable_set(|set| ended.extend(set.take_ended()));
+
+        let mut events = Vec::new();
+        for (entity, animation, finished) in ended {
+            let event = match (self.transition_properties.get(&animation), finished) {
+                (Some(property), true) => AnimationEvent::TransitionFinished(property.clone()),
+                (Some(property), false) => AnimationEvent::TransitionInterrupted(property.clone()),
+                (None, true) => AnimationEvent::Finished(animation),
+                (None, false) => AnimationEvent::Interrupted(animation),
+            };
+
+            // An animation of several properties ends for each of them.
+            if !events.contains(&(entity, event.clone())) {
+                events.push((entity, event));
+            }
+        }
+
+        events
     }
 
     pub(crate) fn is_animating(&self, entity: Entity, animation: Animation) -> bool {
@@ -847,69 +1233,298 @@ impl Style {
             | self.fill.has_active_animation(entity, animation)
     }
 
-    pub(crate) fn parse_theme(&mut self, stylesheet: &str) {
-        if let Ok(stylesheet) = StyleSheet::parse("test.css", stylesheet, ParserOptions::default())
-        {
-            let rules = stylesheet.rules.0;
+    /// Stops an animation for an entity, including one which is yet to start, returning true if
+    /// it was playing.
+    pub(crate) fn stop_animation(&mut self, entity: Entity, animation: Animation) -> bool {
+        let pending = self.pending_animations.len();
+        self.pending_animations.retain(|(e, a, _)| *e != entity || *a != animation);
+
+        (pending != self.pending_animations.len())
+            | self.display.stop_animation(entity, animation)
+            | self.opacity.stop_animation(entity, animation)
+            | self.clip_path.stop_animation(entity, animation)
+            | self.transform.stop_animation(entity, animation)
+            | self.transform_origin.stop_animation(entity, animation)
+            | self.translate.stop_animation(entity, animation)
+            | self.rotate.stop_animation(entity, animation)
+            | self.scale.stop_animation(entity, animation)
+            | self.border_width.stop_animation(entity, animation)
+            | self.border_color.stop_animation(entity, animation)
+            | self.corner_top_left_radius.stop_animation(entity, animation)
+            | self.corner_top_right_radius.stop_animation(entity, animation)
+            | self.corner_bottom_left_radius.stop_animation(entity, animation)
+            | self.corner_bottom_right_radius.stop_animation(entity, animation)
+            | self.outline_width.stop_animation(entity, animation)
+            | self.outline_color.stop_animation(entity, animation)
+            | self.outline_offset.stop_animation(entity, animation)
+            | self.background_color.stop_animation(entity, animation)
+            | self.background_image.stop_animation(entity, animation)
+            | self.background_size.stop_animation(entity, animation)
+            | self.shadow.stop_animation(entity, animation)
+            | self.font_color.stop_animation(entity, animation)
+            | self.font_size.stop_animation(entity, animation)
+            | self.caret_color.stop_animation(entity, animation)
+            | self.selection_color.stop_animation(entity, animation)
+            | self.left.stop_animation(entity, animation)
+            | self.right.stop_animation(entity, animation)
+            | self.top.stop_animation(entity, animation)
+            | self.bottom.stop_animation(entity, animation)
+            | self.child_left.stop_animation(entity, animation)
+            | self.child_right.stop_animation(entity, animation)
+            | self.child_top.stop_animation(entity, animation)
+            | self.child_bottom.stop_animation(entity, animation)
+            | self.col_between.stop_animation(entity, animation)
+            | self.row_between.stop_animation(entity, animation)
+            | self.width.stop_animation(entity, animation)
+            | self.height.stop_animation(entity, animation)
+            | self.min_width.stop_animation(entity, animation)
+            | self.max_width.stop_animation(entity, animation)
+            | self.min_height.stop_animation(entity, animation)
+            | self.max_height.stop_animation(entity, animation)
+            | self.min_left.stop_animation(entity, animation)
+            | self.max_left.stop_animation(entity, animation)
+            | self.min_right.stop_animation(entity, animation)
+            | self.max_right.stop_animation(entity, animation)
+            | self.min_top.stop_animation(entity, animation)
+            | self.max_top.stop_animation(entity, animation)
+            | self.min_bottom.stop_animation(entity, animation)
+            | self.max_bottom.stop_animation(entity, animation)
+            | self.underline_color.stop_animation(entity, animation)
+            | self.fill.stop_animation(entity, animation)
+    }
 
-            for rule in rules {
-                match rule {
-                    CssRule::Style(style_rule) => {
-                        let rule_id = self.rule_manager.create();
+    /// Links the animation properties of an entity to the matched rules, and plays or stops the
+    /// `@keyframes` animations they name. Returns true if an animation was stopped.
+    pub(crate) fn link_animations(&mut self, entity: Entity, matched_rules: &[Rule]) -> bool {
+        let mut changed = self.animation_name.link(entity, matched_rules);
+        changed |= self.animation_duration.link(entity, matched_rules);
+        changed |= self.animation_timing_function.link(entity, matched_rules);
+        changed |= self.animation_delay.link(entity, matched_rules);
+        changed |= self.animation_iteration_count.link(entity, matched_rules);
+        changed |= self.animation_direction.link(entity, matched_rules);
+        changed |= self.animation_fill_mode.link(entity, matched_rules);
+
+        if !changed {
+            return false;
+        }
 
-                        let selectors = style_rule.selectors;
+        // The other animation properties are repeated to match the number of animation names.
+        fn nth<T: Clone>(values: Option<&Vec<T>>, index: usize) -> Option<T> {
+            values
+                .filter(|values| !values.is_empty())
+                .map(|values| values[index % values.len()].clone())
+        }
+
+        let mut animations = Vec::new();
+        let names = self.animation_name.get(entity).cloned().unwrap_or_default();
+        for (index, name) in names.iter().enumerate() {
+            let AnimationName::Name(name) = name else {
+                continue;
+            };
+
+            let Some(animation) = self.animations.get(name).copied() else {
+                continue;
+            };
+
+            let iterations = match nth(self.animation_iteration_count.get(entity), index) {
+                Some(AnimationIterationCount::Count(count)) => count,
+                Some(AnimationIterationCount::Infinite) => f32::INFINITY,
+                None => 1.0,
+            };
+
+            let options = AnimationOptions {
+                duration: nth(self.animation_duration.get(entity), index).unwrap_or_default(),
+                delay: nth(self.animation_delay.get(entity), index).unwrap_or_default(),
+                iterations,
+                direction: nth(self.animation_direction.get(entity), index).unwrap_or_default(),
+                fill_mode: nth(self.animation_fill_mode.get(entity), index).unwrap_or_default(),
+                timing_function: nth(self.animation_timing_function.get(entity), index)
+                    .unwrap_or(EasingFunction::Ease)
+                    .into(),
+            };
+
+            animations.push((animation, options));
+        }
 
-                        self.rules.insert(rule_id, selectors);
+        let previous = self.css_animations.remove(entity).unwrap_or_default();
 
-                        for property in style_rule.declarations.declarations {
-                            match property {
-                                Property::Transition(transitions) => {
-                                    for transition in transitions.iter() {
-                                        self.insert_transition(rule_id, transition);
-                                    }
-                                }
+        // Animations which are no longer named, or whose properties changed, are stopped.
+        let mut stopped = false;
+        for playing in previous.iter() {
+            if !animations.contains(playing) {
+                stopped |= self.stop_animation(entity, playing.0);
+            }
+        }
+
+        for animation in animations.iter() {
+            if !previous.contains(animation) {
+                self.enqueue_animation(entity, animation.0, animation.1.clone());
+            }
+        }
+
+        if !animations.is_empty() {
+            self.css_animations.insert(entity, animations);
+        }
+
+        stopped
+    }
+
+    /// Parses a stylesheet and inserts its rules.
+    ///
+    /// The `path` of the stylesheet, if it was loaded from a file, is used to resolve the urls of
+    /// `@import` and `@font-face` rules. Imported files and declared fonts are added to `resources`.
+    pub(crate) fn parse_theme(
+        &mut self,
+        stylesheet: &str,
+        path: Option<&Path>,
+        resources: &mut StylesheetResources,
+    ) {
+        let filename = path.map(|path| path.to_string_lossy()).unwrap_or_default();
+        match StyleSheet::parse(&filename, stylesheet, ParserOptions::default()) {
+            Ok(stylesheet) => {
+                let dir = path.and_then(|path| path.parent());
+                self.insert_rules(stylesheet.rules.0, &[], dir, resources);
+            }
+
+            Err(err) => {
+                if path.is_some() {
+                    warn!("Failed to parse stylesheet {}: {:?}", filename, err);
+                }
+            }
+        }
+    }
+
+    // Inserts the rules of a stylesheet, which only apply when the media query lists with the given
+    // indices match if they are nested within `@media` rules. Urls are resolved relative to `dir`.
+    fn insert_rules(
+        &mut self,
+        rules: Vec<CssRule>,
+        media: &[usize],
+        dir: Option<&Path>,
+        resources: &mut StylesheetResources,
+    ) {
+        for rule in rules {
+            match rule {
+                CssRule::Import(import_rule) => {
+                    let path = resolve_url(dir, &import_rule.url);
+
+                    // Skip stylesheets which have already been imported to avoid import cycles.
+                    if resources.imports.contains(&path) {
+                        continue;
+                    }
 
-                                _ => {
-                                    self.insert_property(rule_id, property);
+                    resources.imports.push(path.clone());
+
+                    match std::fs::read_to_string(&path) {
+                        Ok(stylesheet) => {
+                            let filename = path.to_string_lossy();
+                            match StyleSheet::parse(
+                                &filename,
+                                &stylesheet,
+                                ParserOptions::default(),
+                            ) {
+                                Ok(stylesheet) => self.insert_rules(
+                                    stylesheet.rules.0,
+                                    media,
+                                    path.parent(),
+                                    resources,
+                                ),
+
+                                Err(err) => {
+                                    warn!("Failed to parse stylesheet {}: {:?}", filename, err)
                                 }
                             }
                         }
+
+                        Err(err) => {
+                            warn!("Failed to import stylesheet {}: {}", path.display(), err)
+                        }
                     }
+                }
+
+                CssRule::FontFace(font_face_rule) => {
+                    let Some(family) = font_face_rule.family else {
+                        warn!("@font-face rule is missing a font-family descriptor");
+                        continue;
+                    };
 
-                    CssRule::Keyframes(keyframes_rule) => {
-                        let name = keyframes_rule.name.as_string();
-
-                        let animation_id = self.animation_manager.create();
-
-                        for keyframes in keyframes_rule.keyframes {
-                            for selector in keyframes.selectors.iter() {
-                                let time = match selector {
-                                    KeyframeSelector::From => 0.0,
-                                    KeyframeSelector::To => 1.0,
-                                    KeyframeSelector::Percentage(percentage) => {
-                                        percentage.0 / 100.0
-                                    }
-                                };
-
-                                self.add_keyframe(
-                                    animation_id,
-                                    time,
-                                    &keyframes.declarations.declarations,
-                                );
+                    // Fonts installed on the system are already available by name.
+                    let source = font_face_rule.sources.iter().find_map(|source| match source {
+                        FontFaceSource::Url(url) => Some(resolve_url(dir, &url.url)),
+                        FontFaceSource::Local(_) => None,
+                    });
+
+                    if let Some(path) = source {
+                        resources.fonts.push((family.to_string(), path));
+                    }
+                }
+
+                CssRule::Style(style_rule) => {
+                    let rule_id = self.rule_manager.create();
+
+                    let selectors = style_rule.selectors;
+
+                    self.rules.insert(rule_id, selectors);
+
+                    if !media.is_empty() {
+                        self.rule_media.insert(rule_id, media.to_vec());
+                    }
+
+                    for property in style_rule.declarations.declarations {
+                        match property {
+                            Property::Transition(transitions) => {
+                                for transition in transitions.iter() {
+                                    self.insert_transition(rule_id, transition);
+                                }
+                            }
+
+                            _ => {
+                                self.insert_property(rule_id, property);
                             }
                         }
+                    }
+                }
+
+                CssRule::Keyframes(keyframes_rule) => {
+                    let name = keyframes_rule.name.as_string();
+
+                    let animation_id = self.animation_manager.create();
+
+                    for keyframes in keyframes_rule.keyframes {
+                        for selector in keyframes.selectors.iter() {
+                            let time = match selector {
+                                KeyframeSelector::From => 0.0,
+                                KeyframeSelector::To => 1.0,
+                                KeyframeSelector::Percentage(percentage) => percentage.0 / 100.0,
+                            };
 
-                        self.animations.insert(name, animation_id);
+                            self.add_keyframe(
+                                animation_id,
+                                time,
+                                &keyframes.declarations.declarations,
+                            );
+                        }
                     }
 
-                    _ => {}
+                    self.animations.insert(name, animation_id);
                 }
+
+                CssRule::Media(media_rule) => {
+                    let mut media = media.to_vec();
+                    media.push(self.media_queries.len());
+                    self.media_queries.push(media_rule.query);
+
+                    self.insert_rules(media_rule.rules.0, &media, dir, resources);
+                }
+
+                _ => {}
             }
         }
     }
 
     fn insert_transition(&mut self, rule_id: Rule, transition: &Transition) {
         let animation = self.animation_manager.create();
+        self.transition_properties.insert(animation, transition.property.clone());
         match transition.property.as_ref() {
             "display" => {
                 self.display.insert_animation(animation, self.add_transition(transition));
@@ -1235,6 +1850,11 @@ impl Style {
                 self.blend_mode.insert_rule(rule_id, blend_mode);
             }
 
+            // Direction
+            Property::Direction(direction) => {
+                self.direction.insert_rule(rule_id, direction);
+            }
+
             // Layout Type
             Property::LayoutType(layout_type) => {
                 self.layout_type.insert_rule(rule_id, layout_type);
@@ -1315,6 +1935,79 @@ impl Style {
                 self.col_between.insert_rule(rule_id, col_between);
             }
 
+            // Grid
+            Property::GridColumns(grid_columns) => {
+                self.grid_columns.insert_rule(rule_id, grid_columns);
+            }
+
+            Property::GridRows(grid_rows) => {
+                self.grid_rows.insert_rule(rule_id, grid_rows);
+            }
+
+            Property::ColumnStart(column_start) => {
+                self.column_start.insert_rule(rule_id, column_start);
+            }
+
+            Property::ColumnSpan(column_span) => {
+                self.column_span.insert_rule(rule_id, column_span);
+            }
+
+            Property::RowStart(row_start) => {
+                self.row_start.insert_rule(rule_id, row_start);
+            }
+
+            Property::RowSpan(row_span) => {
+                self.row_span.insert_rule(rule_id, row_span);
+            }
+
+            // Animations
+            Property::Animation(animations) => {
+                self.animation_name
+                    .insert_rule(rule_id, animations.iter().map(|a| a.name.clone()).collect());
+                self.animation_duration
+                    .insert_rule(rule_id, animations.iter().map(|a| a.duration).collect());
+                self.animation_timing_function.insert_rule(
+                    rule_id,
+                    animations.iter().map(|a| a.timing_function.clone()).collect(),
+                );
+                self.animation_delay
+                    .insert_rule(rule_id, animations.iter().map(|a| a.delay).collect());
+                self.animation_iteration_count
+                    .insert_rule(rule_id, animations.iter().map(|a| a.iteration_count).collect());
+                self.animation_direction
+                    .insert_rule(rule_id, animations.iter().map(|a| a.direction).collect());
+                self.animation_fill_mode
+                    .insert_rule(rule_id, animations.iter().map(|a| a.fill_mode).collect());
+            }
+
+            Property::AnimationName(animation_name) => {
+                self.animation_name.insert_rule(rule_id, animation_name);
+            }
+
+            Property::AnimationDuration(animation_duration) => {
+                self.animation_duration.insert_rule(rule_id, animation_duration);
+            }
+
+            Property::AnimationTimingFunction(timing_function) => {
+                self.animation_timing_function.insert_rule(rule_id, vec![timing_function]);
+            }
+
+            Property::AnimationDelay(animation_delay) => {
+                self.animation_delay.insert_rule(rule_id, animation_delay);
+            }
+
+            Property::AnimationIterationCount(iteration_count) => {
+                self.animation_iteration_count.insert_rule(rule_id, iteration_count);
+            }
+
+            Property::AnimationDirection(animation_direction) => {
+                self.animation_direction.insert_rule(rule_id, animation_direction);
+            }
+
+            Property::AnimationFillMode(animation_fill_mode) => {
+                self.animation_fill_mode.insert_rule(rule_id, animation_fill_mode);
+            }
+
             // Space Constraints
             Property::MinSpace(min_space) => {
                 self.min_left.insert_rule(rule_id, min_space);
@@ -1634,14 +2327,28 @@ impl Style {
                 self.pointer_events.insert_rule(rule_id, pointer_events);
             }
 
-            // Unparsed. TODO: Log the error.
+            // Unparsed. Values containing `var()` references are resolved for each entity by the style system.
             Property::Unparsed(unparsed) => {
-                warn!("Unparsed: {}", unparsed.name);
+                if unparsed.value.has_variables() {
+                    self.variable_declarations
+                        .entry(rule_id)
+                        .or_default()
+                        .push((unparsed.name.to_string(), unparsed.css.to_string()));
+                } else {
+                    warn!("Unparsed: {}", unparsed.name);
+                }
             }
 
-            // TODO: Custom property support
+            // Custom Property
             Property::Custom(custom) => {
-                warn!("Custom Property: {}", custom.name);
+                if custom.name.starts_with("--") {
+                    self.custom_properties
+                        .entry(rule_id)
+                        .or_default()
+                        .push((custom.name.to_string(), custom.css.to_string()));
+                } else {
+                    warn!("Unknown Property: {}", custom.name);
+                }
             }
             Property::TextOverflow(text_overflow) => {
                 self.text_overflow.insert_rule(rule_id, text_overflow);
@@ -1659,28 +2366,49 @@ impl Style {
         }
     }
 
+    // Inserts a declaration whose `var()` references have been substituted into the given rule.
+    pub(crate) fn insert_declaration(&mut self, rule_id: Rule, property: Property) {
+        match property {
+            Property::Transition(transitions) => {
+                for transition in transitions.iter() {
+                    self.insert_transition(rule_id, transition);
+                }
+            }
+
+            property => {
+                self.insert_property(rule_id, property);
+            }
+        }
+    }
+
     // Helper function for generating AnimationState from a transition definition.
     fn add_transition<T: Default + Interpolator>(
         &self,
         transition: &Transition,
     ) -> AnimationState<T> {
-        let timing_function = transition
-            .timing_function
-            .map(|easing| match easing {
-                EasingFunction::Linear => TimingFunction::linear(),
-                EasingFunction::Ease => TimingFunction::ease(),
-                EasingFunction::EaseIn => TimingFunction::ease_in(),
-                EasingFunction::EaseOut => TimingFunction::ease_out(),
-                EasingFunction::EaseInOut => TimingFunction::ease_in_out(),
-                EasingFunction::CubicBezier(x1, y1, x2, y2) => TimingFunction::new(x1, y1, x2, y2),
-            })
-            .unwrap_or_default();
+        let timing_function: TimingFunction =
+            transition.timing_function.clone().map(Into::into).unwrap_or_default();
 
-        AnimationState::new(Animation::null())
+        let mut state = AnimationState::new(Animation::null())
             .with_duration(transition.duration)
             .with_delay(transition.delay.unwrap_or_default())
-            .with_keyframe(Keyframe { time: 0.0, value: Default::default(), timing_function })
-            .with_keyframe(Keyframe { time: 1.0, value: Default::default(), timing_function })
+            .with_keyframe(Keyframe {
+                time: 0.0,
+                value: Default::default(),
+                timing_function: Some(timing_function.clone()),
+            })
+            .with_keyframe(Keyframe {
+                time: 1.0,
+                value: Default::default(),
+                timing_function: Some(timing_function.clone()),
+            });
+
+        // Spring transitions follow the physics of the spring rather than the duration.
+        if let TimingFunction::Spring(motion) = timing_function {
+            state.spring = Some(motion);
+        }
+
+        state
     }
 
     // Add style data for the given entity.
@@ -1795,6 +2523,9 @@ impl Style {
 
         self.pointer_events.remove(entity);
 
+        // Direction
+        self.direction.remove(entity);
+
         // Layout Type
         self.layout_type.remove(entity);
 
@@ -1815,6 +2546,26 @@ impl Style {
         self.row_between.remove(entity);
         self.col_between.remove(entity);
 
+        // Grid
+        self.grid_columns.remove(entity);
+        self.grid_rows.remove(entity);
+        self.column_start.remove(entity);
+        self.column_span.remove(entity);
+        self.row_start.remove(entity);
+        self.row_span.remove(entity);
+        self.grid_placement.remove(entity);
+        self.grid_content_size.remove(entity);
+
+        // Animations
+        self.animation_name.remove(entity);
+        self.animation_duration.remove(entity);
+        self.animation_timing_function.remove(entity);
+        self.animation_delay.remove(entity);
+        self.animation_iteration_count.remove(entity);
+        self.animation_direction.remove(entity);
+        self.animation_fill_mode.remove(entity);
+        self.css_animations.remove(entity);
+
         // Size
         self.width.remove(entity);
         self.height.remove(entity);
@@ -1838,6 +2589,45 @@ impl Style {
         self.text_span.remove(entity);
 
         self.fill.remove(entity);
+
+        self.custom_property_values.remove(entity);
+        self.set_variable_rules(entity, Vec::new());
+    }
+
+    // Sets the keys of the resolved rules used by an entity, keeping rules which are no longer used by any
+    // entity for reuse.
+    pub(crate) fn set_variable_rules(&mut self, entity: Entity, keys: Vec<(Rule, String)>) {
+        for key in keys.iter() {
+            if let Some(resolved) = self.resolved_variable_rules.get_mut(key) {
+                resolved.users += 1;
+            }
+        }
+
+        let previous = self.entity_variable_rules.remove(entity).unwrap_or_default();
+        if !keys.is_empty() {
+            self.entity_variable_rules.insert(entity, keys);
+        }
+
+        for key in previous {
+            let unused = self.resolved_variable_rules.get_mut(&key).is_some_and(|resolved| {
+                resolved.users = resolved.users.saturating_sub(1);
+                resolved.users == 0
+            });
+
+            if let Some(resolved) =
+                unused.then(|| self.resolved_variable_rules.remove(&key)).flatten()
+            {
+                self.unused_variable_rules
+                    .entry((key.0, resolved.names))
+                    .or_default()
+                    .push(resolved.rule);
+            }
+        }
+    }
+
+    /// Returns true if the given entity lays out its text and children from right to left.
+    pub fn is_rtl(&self, entity: Entity) -> bool {
+        self.direction.get(entity).copied().unwrap_or_default() == Direction::Rtl
     }
 
     pub fn needs_restyle(&mut self, entity: Entity) {
@@ -1870,6 +2660,7 @@ impl Style {
     // Remove all shared style data.
     pub fn clear_style_rules(&mut self) {
         self.disabled.clear_rules();
+        self.transition_properties.clear();
         // Display
         self.display.clear_rules();
         // Visibility
@@ -1933,6 +2724,7 @@ impl Style {
 
         self.shadow.clear_rules();
 
+        self.direction.clear_rules();
         self.layout_type.clear_rules();
         self.position_type.clear_rules();
 
@@ -1970,6 +2762,23 @@ impl Style {
         self.col_between.clear_rules();
         self.row_between.clear_rules();
 
+        // Grid
+        self.grid_columns.clear_rules();
+        self.grid_rows.clear_rules();
+        self.column_start.clear_rules();
+        self.column_span.clear_rules();
+        self.row_start.clear_rules();
+        self.row_span.clear_rules();
+
+        // Animations
+        self.animation_name.clear_rules();
+        self.animation_duration.clear_rules();
+        self.animation_timing_function.clear_rules();
+        self.animation_delay.clear_rules();
+        self.animation_iteration_count.clear_rules();
+        self.animation_direction.clear_rules();
+        self.animation_fill_mode.clear_rules();
+
         // Text and Font
         self.text_wrap.clear_rules();
         self.text_overflow.clear_rules();
@@ -1992,5 +2801,13 @@ impl Style {
         self.name.clear_rules();
 
         self.fill.clear_rules();
+
+        // Custom Properties
+        self.custom_properties.clear();
+        self.variable_declarations.clear();
+        self.resolved_variable_rules.clear();
+        self.unused_variable_rules.clear();
+        self.entity_variable_rules.clear();
+        self.custom_property_values.clear();
     }
 }
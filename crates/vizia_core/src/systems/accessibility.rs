@@ -1,5 +1,5 @@
 use crate::{accessibility::IntoNode, events::ViewHandler, prelude::*};
-use accesskit::{NodeBuilder, NodeId, Rect, Toggled, TreeUpdate};
+use accesskit::{NodeBuilder, NodeId, Rect, TextDirection, Toggled, TreeUpdate};
 use hashbrown::HashMap;
 use vizia_storage::LayoutTreeIterator;
 
@@ -114,6 +114,12 @@ pub(crate) fn get_access_node(
         node_builder.set_name(name.clone().into_boxed_str());
     }
 
+    if cx.style.is_rtl(entity) {
+        node_builder.set_text_direction(TextDirection::RightToLeft);
+    } else {
+        node_builder.clear_text_direction();
+    }
+
     if let Some(numeric_value) = cx.style.numeric_value.get(entity) {
         node_builder.set_numeric_value(*numeric_value);
     }
@@ -5,7 +5,7 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
 
     // Tick all animations
 
-    let time = Instant::now();
+    let time = crate::util::now();
 
     let mut redraw_entities = Vec::new();
     let mut reflow_entities = Vec::new();
@@ -94,5 +94,15 @@ pub(crate) fn animation_system(cx: &mut Context) -> bool {
         cx.style.text_construction.insert(*entity).unwrap();
     }
 
-    !redraw_entities.is_empty() | !relayout_entities.is_empty() | !reflow_entities.is_empty()
+    // Notify views of any animations which have ended
+    let animation_events = cx.style.take_animation_events();
+    let has_events = !animation_events.is_empty();
+    for (entity, event) in animation_events {
+        cx.event_queue.push_back(Event::new(event).target(entity).origin(entity));
+    }
+
+    !redraw_entities.is_empty()
+        | !relayout_entities.is_empty()
+        | !reflow_entities.is_empty()
+        | has_events
 }
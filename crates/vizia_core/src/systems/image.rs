@@ -1,23 +1,27 @@
-use crate::context::{Context, ResourceContext};
+use crate::context::{read_image_file, Context, ResourceContext};
 use crate::prelude::*;
+use crate::resource::ImageOrSvg;
 // use crate::resource::{ImageId, ImageRetentionPolicy, StoredImage};
 use crate::style::ImageOrGradient;
-// use hashbrown::HashSet;
+use hashbrown::HashMap;
+
+// Advance animated images at about the frame rate of a display.
+const ANIMATED_IMAGE_INTERVAL: Duration = Duration::from_millis(16);
 
 // Iterate the tree and load any images used by entities which aren't already loaded. Remove any images no longer being used.
 pub(crate) fn image_system(cx: &mut Context) {
-    let cx = &mut ResourceContext::new(cx);
+    let rcx = &mut ResourceContext::new(cx);
 
-    cx.resource_manager.mark_images_unused();
+    rcx.resource_manager.mark_images_unused();
 
     // Iterate the tree and load any defined images that aren't already loaded
-    for entity in cx.tree.into_iter() {
+    for entity in rcx.tree.into_iter() {
         // Load a background-image if the entity has one
-        if let Some(background_images) = cx.style.background_image.get(entity).cloned() {
+        if let Some(background_images) = rcx.style.background_image.get(entity).cloned() {
             for image in background_images.iter() {
                 match image {
                     ImageOrGradient::Image(name) => {
-                        load_image(cx, entity, name);
+                        load_image(rcx, entity, name);
                     }
                     _ => {}
                 }
@@ -25,7 +29,134 @@ pub(crate) fn image_system(cx: &mut Context) {
         }
     }
 
-    cx.resource_manager.evict_unused_images();
+    rcx.resource_manager.evict_unused_images();
+
+    animated_image_system(cx);
+}
+
+// Finds the animated images which are playing and visible, and runs the timer which advances them
+// only while there are any.
+fn animated_image_system(cx: &mut Context) {
+    let mut animated_views = Vec::new();
+    for entity in cx.tree.into_iter() {
+        let Some(background_images) = cx.style.background_image.get(entity) else {
+            continue;
+        };
+
+        let playback = cx.resource_manager.image_playback.get(&entity).copied().unwrap_or_default();
+        if !playback.playing {
+            continue;
+        }
+
+        for image in background_images.iter() {
+            if let ImageOrGradient::Image(name) = image {
+                let Some(id) = cx.resource_manager.image_ids.get(name) else {
+                    continue;
+                };
+
+                if let Some(ImageOrSvg::Animated(animated)) =
+                    cx.resource_manager.images.get(id).map(|image| &image.image)
+                {
+                    if !animated.is_finished(&playback) && is_shown(cx, entity) {
+                        animated_views.push((entity, *id));
+                    }
+                }
+            }
+        }
+    }
+
+    let animating = !animated_views.is_empty();
+    cx.resource_manager.animated_views = animated_views;
+
+    let timer = match cx.resource_manager.image_timer {
+        Some(timer) => timer,
+        None if animating => {
+            let timer = cx.add_timer(ANIMATED_IMAGE_INTERVAL, None, advance_animated_images);
+            cx.resource_manager.image_timer = Some(timer);
+            timer
+        }
+        None => return,
+    };
+
+    if animating != cx.timer_is_running(timer) {
+        if animating {
+            cx.with_current(Entity::root(), |cx| cx.start_timer(timer));
+        } else {
+            cx.stop_timer(timer);
+        }
+    }
+}
+
+// Advances the animated images which are playing and visible, redrawing the views which show a
+// new frame.
+fn advance_animated_images(cx: &mut EventContext, action: TimerAction) {
+    let now = crate::util::now();
+    let delta = match action {
+        TimerAction::Start | TimerAction::Stop => Duration::ZERO,
+        TimerAction::Tick(_) => {
+            cx.resource_manager.image_clock.map(|clock| now - clock).unwrap_or_default()
+        }
+    };
+    cx.resource_manager.image_clock = Some(now);
+
+    if delta.is_zero() {
+        return;
+    }
+
+    // A view with several animated images advances them together.
+    let animated_views = cx.resource_manager.animated_views.clone();
+    let mut previous = HashMap::new();
+    for (entity, _) in animated_views.iter() {
+        previous.entry(*entity).or_insert_with(|| {
+            let playback = cx.resource_manager.image_playback.entry(*entity).or_default();
+            let previous = *playback;
+            playback.elapsed += delta;
+            previous
+        });
+    }
+
+    for (entity, id) in animated_views {
+        let Some(ImageOrSvg::Animated(animated)) =
+            cx.resource_manager.images.get(&id).map(|image| &image.image)
+        else {
+            continue;
+        };
+
+        let playback = cx.resource_manager.image_playback.get(&entity).copied().unwrap_or_default();
+        if !std::ptr::eq(animated.frame(&previous[&entity]), animated.frame(&playback)) {
+            cx.with_current(entity, |cx| cx.needs_redraw());
+        }
+    }
+}
+
+// Reloads the images whose files have changed, reading and decoding each on a background thread.
+pub(crate) fn reload_changed_images(cx: &mut EventContext, action: TimerAction) {
+    if let TimerAction::Tick(_) = action {
+        for (name, path, policy) in cx.resource_manager.changed_image_files() {
+            cx.spawn(move |cx| read_image_file(cx, name, &path, policy));
+        }
+    }
+}
+
+// Returns whether a view is displayed, and is within its window and the bounds it is clipped to.
+fn is_shown(cx: &Context, entity: Entity) -> bool {
+    let window = cx.tree.get_parent_window(entity).unwrap_or(Entity::root());
+    if cx.windows.get(&window).is_some_and(|window| window.is_occluded) {
+        return false;
+    }
+
+    let hidden = |entity: Entity| {
+        cx.style.display.get(entity).copied().unwrap_or_default() == Display::None
+            || cx.style.visibility.get(entity).copied() == Some(Visibility::Hidden)
+    };
+
+    if entity.parent_iter(&cx.tree).any(hidden) {
+        return false;
+    }
+
+    let bounds = cx.cache.get_bounds(entity);
+    let clip_bounds = cx.cache.clip_path.get(entity).copied().unwrap_or(bounds);
+    bounds.intersects(&cx.cache.get_bounds(window)) && bounds.intersects(&clip_bounds)
 }
 
 fn load_image(cx: &mut ResourceContext, entity: Entity, image_name: &str) {
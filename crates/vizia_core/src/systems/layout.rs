@@ -1,11 +1,15 @@
 use morphorm::Node;
 use vizia_storage::LayoutTreeIterator;
 
+use crate::layout::grid::GridLayout;
 use crate::layout::node::SubLayout;
 use crate::prelude::*;
 
 use super::{text_layout_system, text_system};
 
+// The maximum number of times the items of grids are placed after the tree is laid out.
+const MAX_GRID_PASSES: usize = 4;
+
 /// Determines the size and position of views.
 /// TODO: Currently relayout is done on an entire tree rather than incrementally.
 /// Incremental relayout can be done by keeping a list of nodes that need relayout,
@@ -16,15 +20,19 @@ pub(crate) fn layout_system(cx: &mut Context) {
 
     if cx.style.system_flags.contains(SystemFlags::RELAYOUT) {
         // Perform layout on the whole tree.
-        Entity::root().layout(
-            &mut cx.cache,
-            &cx.tree,
-            &cx.style,
-            &mut SubLayout {
-                text_context: &mut cx.text_context,
-                resource_manager: &cx.resource_manager,
-            },
-        );
+        let grids = GridLayout::measure(cx);
+        layout_tree(cx);
+
+        // Grid items are placed using the sizes of the grids computed by the layout, and only the
+        // items which moved are laid out again. Placing the items of a grid can resize the grids
+        // nested within them, so this is repeated until nothing moves, up to a limit.
+        if !grids.is_empty() {
+            for _ in 0..MAX_GRID_PASSES {
+                if !grids.place(cx) {
+                    break;
+                }
+            }
+        }
 
         let cx = &mut EventContext::new(cx);
 
@@ -38,9 +46,16 @@ pub(crate) fn layout_system(cx: &mut Context) {
             // Morphorm produces relative positions so convert to absolute.
             if let Some(parent) = cx.tree.get_layout_parent(entity) {
                 let parent_bounds = cx.cache.get_bounds(parent);
+                let mirrored = cx.style.is_rtl(parent);
                 if let Some(bounds) = cx.cache.bounds.get_mut(entity) {
                     if let Some(relative_bounds) = cx.cache.relative_bounds.get(entity) {
-                        let x = relative_bounds.x + parent_bounds.x;
+                        // Children of a right-to-left view are laid out left-to-right and then
+                        // mirrored, which reverses rows and swaps the left and right space.
+                        let x = if mirrored {
+                            parent_bounds.right() - relative_bounds.right()
+                        } else {
+                            relative_bounds.x + parent_bounds.x
+                        };
                         let y = relative_bounds.y + parent_bounds.y;
                         let w = relative_bounds.w;
                         let h = relative_bounds.h;
@@ -119,6 +134,18 @@ pub(crate) fn layout_system(cx: &mut Context) {
     text_layout_system(cx);
 }
 
+fn layout_tree(cx: &mut Context) {
+    Entity::root().layout(
+        &mut cx.cache,
+        &cx.tree,
+        &cx.style,
+        &mut SubLayout {
+            text_context: &mut cx.text_context,
+            resource_manager: &cx.resource_manager,
+        },
+    );
+}
+
 fn visit_entity(cx: &mut EventContext, entity: Entity, event: &mut Event) {
     // Send event to models attached to the entity
     if let Some(ids) = cx
@@ -1,6 +1,8 @@
-use crate::{events::ViewHandler, prelude::*};
+use crate::{events::ViewHandler, prelude::*, style::ResolvedRule};
 use hashbrown::HashMap;
-use vizia_storage::{LayoutParentIterator, TreeBreadthIterator};
+use log::warn;
+use std::rc::Rc;
+use vizia_storage::{ChildIterator, LayoutParentIterator, TreeBreadthIterator};
 use vizia_style::{
     matches_selector_list,
     selectors::{
@@ -9,7 +11,8 @@ use vizia_style::{
         SelectorImpl,
     },
     selectors::{matching::ElementSelectorFlags, OpaqueElement},
-    Element, MatchingContext, MatchingMode, PseudoClass, QuirksMode, SelectorIdent, Selectors,
+    substitute_variables, ColorScheme, Element, MatchingContext, MatchingMode, MediaEnvironment,
+    Property, PseudoClass, QuirksMode, SelectorIdent, SelectorList, Selectors,
 };
 
 /// A node used for style matching.
@@ -221,7 +224,9 @@ impl<'s, 't, 'v> Element for Node<'s, 't, 'v> {
                     psudeo_class_flag.contains(PseudoClassFlags::USER_INVALID)
                 }
                 PseudoClass::Lang(_) => todo!(),
-                PseudoClass::Dir(_) => todo!(),
+                PseudoClass::Dir(direction) => {
+                    self.store.direction.get(self.entity).copied().unwrap_or_default() == *direction
+                }
                 PseudoClass::Custom(name) => {
                     println!("custom: {}", name);
                     todo!()
@@ -255,6 +260,12 @@ pub(crate) fn inline_inheritance_system(cx: &mut Context, redraw_entities: &mut
             {
                 cx.style.needs_text_update(entity);
             }
+
+            if cx.style.direction.inherit_inline(entity, parent) {
+                cx.style.needs_text_update(entity);
+                cx.style.needs_relayout();
+                cx.style.needs_restyle(entity);
+            }
         }
     }
 }
@@ -275,6 +286,11 @@ pub(crate) fn shared_inheritance_system(cx: &mut Context, redraw_entities: &mut
                 cx.style.needs_text_update(entity);
             }
 
+            if cx.style.direction.inherit_shared(entity, parent) {
+                cx.style.needs_text_update(entity);
+                cx.style.needs_relayout();
+            }
+
             if cx.style.caret_color.inherit_shared(entity, parent)
                 | cx.style.selection_color.inherit_shared(entity, parent)
             {
@@ -487,6 +503,12 @@ fn link_style_data(
         should_redraw = true;
     }
 
+    if style.direction.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+        should_reflow = true;
+    }
+
     if style.layout_type.link(entity, matched_rules) {
         should_relayout = true;
         should_redraw = true;
@@ -651,6 +673,36 @@ fn link_style_data(
         should_redraw = true;
     }
 
+    if style.grid_columns.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if style.grid_rows.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if style.column_start.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if style.column_span.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if style.row_start.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
+    if style.row_span.link(entity, matched_rules) {
+        should_relayout = true;
+        should_redraw = true;
+    }
+
     if style.cursor.link(entity, matched_rules) {
         should_redraw = true;
     }
@@ -704,13 +756,200 @@ fn link_style_data(
     }
 }
 
+/// Computes the custom properties of an entity and resolves the `var()` references of its matched rules.
+///
+/// The declarations of a rule which contain `var()` references are substituted and parsed into a new
+/// rule, which is shared by all entities resolving to the same values. The new rule is inserted into
+/// the matched rules directly before the rule it was resolved from, so that it takes precedence over
+/// the rule but not over rules with a higher specificity.
+fn resolve_custom_properties(
+    style: &mut Style,
+    tree: &Tree<Entity>,
+    entity: Entity,
+    matched_rules: &mut Vec<Rule>,
+) {
+    if style.custom_properties.is_empty() && style.variable_declarations.is_empty() {
+        return;
+    }
+
+    let inherited = tree
+        .get_layout_parent(entity)
+        .and_then(|parent| style.custom_property_values.get(parent))
+        .cloned();
+
+    // Later declarations override earlier ones, so iterate from the lowest specificity.
+    let mut declared = HashMap::new();
+    for rule in matched_rules.iter().rev() {
+        if let Some(custom_properties) = style.custom_properties.get(rule) {
+            for (name, value) in custom_properties.iter() {
+                declared.insert(name.as_str(), value.as_str());
+            }
+        }
+    }
+
+    let values = if declared.is_empty() {
+        inherited
+    } else {
+        let mut resolved = HashMap::new();
+        let mut values = inherited.as_deref().cloned().unwrap_or_default();
+        for name in declared.keys() {
+            match resolve_custom_property(
+                name,
+                &declared,
+                inherited.as_deref(),
+                &mut resolved,
+                &mut Vec::new(),
+            ) {
+                Some(value) => values.insert(name.to_string(), value),
+                None => values.remove(*name),
+            };
+        }
+
+        Some(Rc::new(values))
+    };
+
+    let previous = style.custom_property_values.get(entity);
+    if previous != values.as_ref() {
+        // Descendants inherit the custom properties so need to resolve their var() references again.
+        for child in ChildIterator::new(tree, entity) {
+            style.needs_restyle(child);
+        }
+
+        match &values {
+            Some(values) => style.custom_property_values.insert(entity, values.clone()),
+            None => {
+                style.custom_property_values.remove(entity);
+            }
+        }
+    }
+
+    let mut keys = Vec::new();
+    let mut index = 0;
+    while index < matched_rules.len() {
+        let rule = matched_rules[index];
+        if let Some(declarations) = style.variable_declarations.get(&rule).cloned() {
+            let substituted = declarations
+                .into_iter()
+                .filter_map(|(name, value)| {
+                    let value = substitute_variables(&value, &mut |name| {
+                        values.as_ref().and_then(|values| values.get(name)).cloned()
+                    })?;
+                    Some((name, value))
+                })
+                .collect::<Vec<_>>();
+
+            let key = substituted
+                .iter()
+                .map(|(name, value)| format!("{}: {};", name, value))
+                .collect::<String>();
+
+            let resolved_rule = match style.resolved_variable_rules.get(&(rule, key.clone())) {
+                Some(resolved) => resolved.rule,
+                None => {
+                    // An unused rule can only be reused if it declares the same properties, since the
+                    // values of properties it no longer declares can't be removed from it.
+                    let properties = substituted
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            match Property::parse_declaration(name, value) {
+                                Ok(property) => Some((name.as_str(), property)),
+                                Err(_) => {
+                                    warn!(
+                                        "Invalid value for {} after var() substitution: {}",
+                                        name, value
+                                    );
+                                    None
+                                }
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    let names =
+                        properties.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+
+                    let resolved_rule = style
+                        .unused_variable_rules
+                        .get_mut(&(rule, names.clone()))
+                        .and_then(Vec::pop)
+                        .unwrap_or_else(|| style.rule_manager.create());
+
+                    for (_, property) in properties {
+                        style.insert_declaration(resolved_rule, property);
+                    }
+
+                    style.resolved_variable_rules.insert(
+                        (rule, key.clone()),
+                        ResolvedRule { rule: resolved_rule, names, users: 0 },
+                    );
+                    resolved_rule
+                }
+            };
+
+            keys.push((rule, key));
+            matched_rules.insert(index, resolved_rule);
+            index += 1;
+        }
+
+        index += 1;
+    }
+
+    style.set_variable_rules(entity, keys);
+}
+
+// Resolves the value of a custom property, substituting any `var()` references to other custom
+// properties. Returns `None` if the value references an undefined property or forms a cycle.
+fn resolve_custom_property(
+    name: &str,
+    declared: &HashMap<&str, &str>,
+    inherited: Option<&HashMap<String, String>>,
+    resolved: &mut HashMap<String, Option<String>>,
+    stack: &mut Vec<String>,
+) -> Option<String> {
+    if let Some(value) = resolved.get(name) {
+        return value.clone();
+    }
+
+    let Some(value) = declared.get(name) else {
+        return inherited.and_then(|inherited| inherited.get(name)).cloned();
+    };
+
+    if stack.iter().any(|visited| visited == name) {
+        return None;
+    }
+
+    stack.push(name.to_owned());
+    let value = substitute_variables(value, &mut |name| {
+        resolve_custom_property(name, declared, inherited, resolved, stack)
+    });
+    stack.pop();
+
+    resolved.insert(name.to_owned(), value.clone());
+    value
+}
+
 /// Compute a list of matching style rules for a given entity.
 pub(crate) fn compute_matched_rules(
     cx: &Context,
     entity: Entity,
     matched_rules: &mut Vec<(Rule, u32)>,
 ) {
+    let window =
+        if cx.tree.is_window(entity) { Some(entity) } else { cx.tree.get_parent_window(entity) };
+
+    let media_query_results = window.and_then(|window| cx.style.media_query_results.get(&window));
+
     for (rule, selector_list) in cx.style.rules.iter() {
+        // Skip rules within `@media` rules which don't match the window of the entity.
+        if let Some(media) = cx.style.rule_media.get(rule) {
+            let Some(results) = media_query_results else {
+                continue;
+            };
+
+            if !media.iter().all(|index| results[*index]) {
+                continue;
+            }
+        }
+
         let mut context =
             MatchingContext::new(MatchingMode::Normal, None, None, QuirksMode::NoQuirks);
 
@@ -729,6 +968,23 @@ pub(crate) fn compute_matched_rules(
     matched_rules.reverse();
 }
 
+/// Returns true if the given entity matches any of the selectors in a selector list.
+pub(crate) fn matches_selectors(
+    cx: &Context,
+    entity: Entity,
+    selector_list: &SelectorList<Selectors>,
+) -> bool {
+    let mut context = MatchingContext::new(MatchingMode::Normal, None, None, QuirksMode::NoQuirks);
+
+    let (matches, _) = matches_selector_list(
+        selector_list,
+        &Node { entity, store: &cx.style, tree: &cx.tree, views: &cx.views },
+        &mut context,
+    );
+
+    matches
+}
+
 fn has_same_selector(cx: &Context, entity1: Entity, entity2: Entity) -> bool {
     let element1 = cx.views.get(&entity1).and_then(|view| view.element()).unwrap_or_default();
 
@@ -769,10 +1025,73 @@ pub(crate) struct MatchedRulesCache {
     pub rules: Vec<(Rule, u32)>,
 }
 
+/// Evaluates the media query lists of `@media` rules for each window and restyles the views of any
+/// window for which a result has changed.
+fn media_query_system(cx: &mut Context) {
+    if cx.style.media_queries.is_empty() {
+        return;
+    }
+
+    let color_scheme = match cx.data::<Environment>().map(|env| env.theme.get_current_theme()) {
+        Some(ThemeMode::DarkMode) => ColorScheme::Dark,
+        _ => ColorScheme::Light,
+    };
+
+    let mut changed_windows = Vec::new();
+    for (window, window_state) in cx.windows.iter() {
+        // Windows can be on monitors with different scale factors. Backends which don't report the scale
+        // factor of each window fall back to the scale factor of the application.
+        let resolution = match window_state.scale_factor {
+            scale_factor if scale_factor > 0.0 => scale_factor,
+            _ => cx.style.scale_factor(),
+        };
+
+        let bounds = cx.cache.get_bounds(*window);
+        let environment = MediaEnvironment {
+            color_scheme,
+            width: bounds.width() / resolution,
+            height: bounds.height() / resolution,
+            resolution,
+        };
+
+        let results = cx
+            .style
+            .media_queries
+            .iter()
+            .map(|media_list| media_list.matches(&environment))
+            .collect::<Vec<_>>();
+
+        if cx.style.media_query_results.get(window) != Some(&results) {
+            cx.style.media_query_results.insert(*window, results);
+            changed_windows.push(*window);
+        }
+    }
+
+    cx.style.media_query_results.retain(|window, _| cx.windows.contains_key(window));
+
+    if changed_windows.is_empty() {
+        return;
+    }
+
+    for entity in cx.tree.into_iter() {
+        let window = if cx.tree.is_window(entity) {
+            Some(entity)
+        } else {
+            cx.tree.get_parent_window(entity)
+        };
+
+        if window.is_some_and(|window| changed_windows.contains(&window)) {
+            cx.style.needs_restyle(entity);
+        }
+    }
+}
+
 // Iterates the tree and determines the matching style rules for each entity, then links the entity to the corresponding style rule data.
 pub(crate) fn style_system(cx: &mut Context) {
     let mut redraw_entities = Vec::new();
 
+    media_query_system(cx);
+
     inline_inheritance_system(cx, &mut redraw_entities);
 
     if !cx.style.restyle.is_empty() {
@@ -843,15 +1162,25 @@ pub(crate) fn style_system(cx: &mut Context) {
                 cache.push(MatchedRulesCache { entity, rules: matched_rules.clone() });
             }
 
+            let mut matched_rules = matched_rules.iter().map(|(rule, _)| *rule).collect::<Vec<_>>();
+
+            resolve_custom_properties(&mut cx.style, &cx.tree, entity, &mut matched_rules);
+
             if !matched_rules.is_empty() {
                 link_style_data(
                     &mut cx.style,
                     &cx.tree,
                     entity,
                     &mut redraw_entities,
-                    &matched_rules.iter().map(|(rule, _)| *rule).collect::<Vec<_>>(),
+                    &matched_rules,
                 );
             }
+
+            // Linked even without matching rules so that animations stop when their rules no longer apply.
+            if cx.style.link_animations(entity, &matched_rules) {
+                cx.style.system_flags.set(SystemFlags::RELAYOUT, true);
+                redraw_entities.push(entity);
+            }
         }
         cx.style.restyle.clear();
 
@@ -24,6 +24,7 @@ pub(crate) fn text_system(cx: &mut Context) {
                 build_paragraph(entity, &mut cx.style, &cx.tree, cx.text_context.font_collection())
             {
                 cx.text_context.text_paragraphs.insert(entity, paragraph);
+                cx.text_context.caret_stops.remove(entity);
                 cx.style.needs_relayout();
                 cx.style.needs_text_layout(entity);
             }
@@ -41,9 +42,10 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
             continue;
         }
 
+        cx.text_context.caret_stops.remove(entity);
         if let Some(paragraph) = cx.text_context.text_paragraphs.get_mut(entity) {
             let bounds = cx.cache.get_bounds(entity);
-            let padding_left = cx
+            let mut padding_left = cx
                 .style
                 .child_left
                 .get(entity)
@@ -51,7 +53,7 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
                 .unwrap_or_default()
                 .to_px(bounds.width(), 0.0)
                 * cx.style.scale_factor();
-            let padding_right = cx
+            let mut padding_right = cx
                 .style
                 .child_right
                 .get(entity)
@@ -59,6 +61,9 @@ pub(crate) fn text_layout_system(cx: &mut Context) {
                 .unwrap_or_default()
                 .to_px(bounds.width(), 0.0)
                 * cx.style.scale_factor();
+            if cx.style.is_rtl(entity) {
+                std::mem::swap(&mut padding_left, &mut padding_right);
+            }
             let text_bounds = cx
                 .text_context
                 .text_bounds
@@ -177,7 +182,13 @@ pub fn build_paragraph(
         }
     }
 
+    // Text Direction
+    let direction = style.direction.get(entity).copied().unwrap_or_default();
+    paragraph_style.set_text_direction(direction.into());
+
     // Text Align
+    // Without an explicit alignment the text is aligned to the side with fixed child space, which is
+    // mirrored in a right-to-left direction.
     paragraph_style.set_text_align(
         if let Some(text_align) = style.text_align.get(entity) {
             *text_align
@@ -185,10 +196,10 @@ pub fn build_paragraph(
             if let Some(Units::Stretch(_)) = style.child_right.get(entity) {
                 TextAlign::Center
             } else {
-                TextAlign::Right
+                TextAlign::End
             }
         } else {
-            TextAlign::Left
+            TextAlign::Start
         }
         .into(),
     );
@@ -293,3 +304,22 @@ fn add_block(
         }
     }
 }
+
+// Returns the text of the paragraph of an entity, which includes the text of its text spans.
+pub(crate) fn paragraph_text(style: &Style, tree: &Tree<Entity>, entity: Entity) -> String {
+    let mut text = String::new();
+    add_paragraph_text(style, tree, entity, &mut text);
+    text
+}
+
+fn add_paragraph_text(style: &Style, tree: &Tree<Entity>, entity: Entity, text: &mut String) {
+    if let Some(span_text) = style.text.get(entity) {
+        text.push_str(span_text);
+    }
+
+    for child in LayoutChildIterator::new(tree, entity) {
+        if style.text_span.get(child).copied().unwrap_or_default() {
+            add_paragraph_text(style, tree, child, text);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The largest number of edits between a misspelled word and a suggested replacement.
+const MAX_DISTANCE: usize = 2;
+/// The largest number of suggestions returned for a misspelled word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The kind of problem flagged by a [`TextChecker`], which determines how it is underlined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckKind {
+    /// A misspelled word.
+    Spelling,
+    /// A grammatical or stylistic problem.
+    Grammar,
+    /// Text which is not valid input, such as a malformed value.
+    Validation,
+}
+
+/// A range of text flagged by a [`TextChecker`], along with suggested replacements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextCheck {
+    /// The byte range of the flagged text.
+    pub range: Range<usize>,
+    /// The kind of problem with the text.
+    pub kind: CheckKind,
+    /// Replacements for the flagged text, in order of preference.
+    pub suggestions: Vec<String>,
+}
+
+impl TextCheck {
+    /// Creates a new check for the given byte range without any suggestions.
+    pub fn new(range: Range<usize>, kind: CheckKind) -> Self {
+        Self { range, kind, suggestions: Vec::new() }
+    }
+
+    /// Sets the suggested replacements for the flagged text.
+    pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+}
+
+/// A source of checks on some text, such as a spell checker or a validator, used by the
+/// [`Textbox`](crate::views::Textbox) to underline problems with its text.
+///
+/// Checks are run on the whole text each time it changes, while suggestions are only looked up
+/// when they are shown. A closure which takes the text and returns a list of checks can also be
+/// used as a checker.
+pub trait TextChecker {
+    /// Returns the ranges of the given text which have problems, in order of their position.
+    fn check(&self, text: &str) -> Vec<TextCheck>;
+
+    /// Returns the replacements for a check of the given text, in order of preference.
+    ///
+    /// By default these are the suggestions stored in the check.
+    fn suggest(&self, text: &str, check: &TextCheck) -> Vec<String> {
+        let _ = text;
+        check.suggestions.clone()
+    }
+}
+
+impl<F> TextChecker for F
+where
+    F: Fn(&str) -> Vec<TextCheck>,
+{
+    fn check(&self, text: &str) -> Vec<TextCheck> {
+        (self)(text)
+    }
+}
+
+/// A [`TextChecker`] which flags words that are not in a list of known words as misspelled.
+///
+/// Words are compared case-insensitively and words containing digits are ignored. The suggestions
+/// for a misspelled word are the known words which are the fewest edits away from it, which are
+/// only looked up when they are shown.
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryChecker {
+    words: HashSet<String>,
+}
+
+impl DictionaryChecker {
+    /// Creates a new checker which knows the given words.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// let checker = DictionaryChecker::new(["hello", "world"]);
+    /// assert!(checker.contains("Hello"));
+    /// ```
+    pub fn new(words: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        let mut checker = Self::default();
+        for word in words {
+            checker.add_word(word.as_ref());
+        }
+
+        checker
+    }
+
+    /// Adds a word to the list of known words. Blank words are ignored.
+    pub fn add_word(&mut self, word: &str) {
+        let word = word.trim();
+        if !word.is_empty() {
+            self.words.insert(word.to_lowercase());
+        }
+    }
+
+    /// Returns true if the given word is known.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// Returns the known words closest to the given word, matching its capitalization.
+    pub fn suggestions(&self, word: &str) -> Vec<String> {
+        let lowercase = word.to_lowercase();
+        let chars = lowercase.chars().collect::<Vec<_>>();
+
+        let mut suggestions = self
+            .words
+            .iter()
+            .filter(|known| known.chars().count().abs_diff(chars.len()) <= MAX_DISTANCE)
+            .filter_map(|known| {
+                let distance = edit_distance(&chars, &known.chars().collect::<Vec<_>>());
+                (distance <= MAX_DISTANCE).then_some((distance, known))
+            })
+            .collect::<Vec<_>>();
+
+        suggestions.sort();
+
+        suggestions
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, known)| match_case(word, known))
+            .collect()
+    }
+}
+
+impl TextChecker for DictionaryChecker {
+    fn check(&self, text: &str) -> Vec<TextCheck> {
+        text.unicode_word_indices()
+            .filter(|(_, word)| !word.chars().any(char::is_numeric) && !self.contains(word))
+            .map(|(offset, word)| TextCheck::new(offset..offset + word.len(), CheckKind::Spelling))
+            .collect()
+    }
+
+    fn suggest(&self, text: &str, check: &TextCheck) -> Vec<String> {
+        text.get(check.range.clone()).map(|word| self.suggestions(word)).unwrap_or_default()
+    }
+}
+
+// Returns the number of single character insertions, deletions or substitutions which turn one
+// word into another.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+// Returns the lowercase suggestion with the capitalization of the word it replaces.
+fn match_case(word: &str, suggestion: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) if first.is_uppercase() => {
+            if word.chars().count() > 1 && chars.all(|c| !c.is_lowercase()) {
+                suggestion.to_uppercase()
+            } else {
+                let mut suggestion_chars = suggestion.chars();
+                suggestion_chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(suggestion_chars).collect())
+                    .unwrap_or_default()
+            }
+        }
+
+        _ => suggestion.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> DictionaryChecker {
+        DictionaryChecker::new(["hello", "world", "word", "help", "don't"])
+    }
+
+    #[test]
+    fn flags_unknown_words() {
+        let checks = checker().check("Hello wrold, don't panic 42");
+        let ranges = checks.iter().map(|check| check.range.clone()).collect::<Vec<_>>();
+        assert_eq!(ranges, vec![6..11, 19..24]);
+        assert!(checks.iter().all(|check| check.kind == CheckKind::Spelling));
+        assert!(checks.iter().all(|check| check.suggestions.is_empty()));
+        assert_eq!(checker().suggest("Hello wrold", &checks[0]), vec!["word", "world"]);
+    }
+
+    #[test]
+    fn suggestions() {
+        let checker = checker();
+        assert_eq!(checker.suggestions("wrold"), vec!["word", "world"]);
+        assert_eq!(checker.suggestions("Helo"), vec!["Hello", "Help"]);
+        assert_eq!(checker.suggestions("WROD"), vec!["WORD", "WORLD"]);
+        assert!(checker.suggestions("xyzzy").is_empty());
+    }
+
+    #[test]
+    fn distance() {
+        let distance = |a: &str, b: &str| {
+            edit_distance(&a.chars().collect::<Vec<_>>(), &b.chars().collect::<Vec<_>>())
+        };
+
+        assert_eq!(distance("word", "word"), 0);
+        assert_eq!(distance("word", "world"), 1);
+        assert_eq!(distance("wrold", "world"), 2);
+        assert_eq!(distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closure_checker() {
+        let checker = |text: &str| {
+            text.find('!')
+                .map(|offset| vec![TextCheck::new(offset..offset + 1, CheckKind::Validation)])
+                .unwrap_or_default()
+        };
+
+        let checks = checker.check("hi!");
+        assert_eq!(checks[0].range, 2..3);
+        assert!(checker.suggest("hi!", &checks[0]).is_empty());
+        assert!(checker.check("hi").is_empty());
+    }
+}
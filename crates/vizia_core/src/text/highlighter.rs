@@ -0,0 +1,723 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use crate::prelude::*;
+
+/// A range of text given a style class by a [`Highlighter`].
+#[derive(Debug, Clone, PartialEq, Data)]
+pub struct HighlightSpan {
+    /// The byte range of the highlighted text.
+    pub range: Range<usize>,
+    /// The style class of the text, such as `keyword` or `string`.
+    pub class: &'static str,
+}
+
+impl HighlightSpan {
+    /// Creates a new span for the given byte range.
+    pub fn new(range: Range<usize>, class: &'static str) -> Self {
+        Self { range, class }
+    }
+}
+
+/// A source of syntax highlighting, which splits text into spans with style classes such as
+/// `keyword` or `string`, used by the [`TextEditor`](crate::views::TextEditor) and by
+/// [`Label::highlighted`](crate::views::Label::highlighted).
+///
+/// Each span is displayed with the `token` class and its own class, so that it is styled by the
+/// stylesheet, e.g. with a `.token.keyword` selector.
+///
+/// Text is highlighted a line at a time, so that only the lines which change need to be
+/// highlighted again after an edit. The state carried from the end of one line to the start of
+/// the next, such as whether a line ends inside of a multi-line string, is given as a number which
+/// is zero at the start of the text. A closure which takes a line and the state can also be used
+/// as a highlighter.
+pub trait Highlighter {
+    /// Returns the spans of a line without its line break, in order of their position, given the
+    /// state at the end of the previous line. The state is updated to the state at the end of the line.
+    fn highlight_line(&self, line: &str, state: &mut u32) -> Vec<HighlightSpan>;
+
+    /// Returns the spans of some text, with byte ranges into the whole text.
+    fn highlight(&self, text: &str) -> Vec<HighlightSpan> {
+        let mut state = 0;
+        let mut offset = 0;
+        let mut spans = Vec::new();
+
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            spans.extend(self.highlight_line(content, &mut state).into_iter().map(|span| {
+                HighlightSpan::new(span.range.start + offset..span.range.end + offset, span.class)
+            }));
+            offset += line.len();
+        }
+
+        spans
+    }
+}
+
+impl<F> Highlighter for F
+where
+    F: Fn(&str, &mut u32) -> Vec<HighlightSpan>,
+{
+    fn highlight_line(&self, line: &str, state: &mut u32) -> Vec<HighlightSpan> {
+        (self)(line, state)
+    }
+}
+
+/// The highlighting of each line of some text, which is updated incrementally as the text changes.
+#[derive(Debug, Clone, Default)]
+pub struct Highlights {
+    lines: Vec<HighlightedLine>,
+}
+
+#[derive(Debug, Clone)]
+struct HighlightedLine {
+    // A hash of the text of the line, to find the lines which changed.
+    hash: u64,
+    // The state of the highlighter at the start and end of the line.
+    start: u32,
+    end: u32,
+    spans: Vec<HighlightSpan>,
+}
+
+impl Highlights {
+    /// Creates a new empty highlighting.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the highlighting for the given lines of the text, without their line breaks.
+    ///
+    /// Only the lines which changed, and the lines after them which now start in a different
+    /// state, are highlighted again. Returns the number of lines which were highlighted.
+    pub fn update<S: AsRef<str>>(
+        &mut self,
+        highlighter: &dyn Highlighter,
+        lines: impl IntoIterator<Item = S>,
+    ) -> usize {
+        let lines = lines.into_iter().collect::<Vec<_>>();
+        let hashes = lines.iter().map(|line| hash_line(line.as_ref())).collect::<Vec<_>>();
+
+        // The unchanged lines at the start and end of the text.
+        let mut old = std::mem::take(&mut self.lines);
+        let prefix = old.iter().zip(&hashes).take_while(|(line, hash)| line.hash == **hash).count();
+        let suffix = old
+            .iter()
+            .rev()
+            .zip(hashes.iter().rev())
+            .take(old.len().min(hashes.len()) - prefix)
+            .take_while(|(line, hash)| line.hash == **hash)
+            .count();
+
+        let old_suffix = old.split_off(old.len() - suffix);
+        old.truncate(prefix);
+        self.lines = old;
+
+        let mut state = self.lines.last().map_or(0, |line| line.end);
+        let mut highlighted = 0;
+        let mut highlight = |index: usize, state: &mut u32| {
+            highlighted += 1;
+            let start = *state;
+            let spans = highlighter.highlight_line(lines[index].as_ref(), state);
+            HighlightedLine { hash: hashes[index], start, end: *state, spans }
+        };
+
+        for index in prefix..lines.len() - suffix {
+            let line = highlight(index, &mut state);
+            self.lines.push(line);
+        }
+
+        // An unchanged line is kept if it still starts in the same state.
+        for (index, line) in (lines.len() - suffix..).zip(old_suffix) {
+            let line = if line.start == state { line } else { highlight(index, &mut state) };
+            state = line.end;
+            self.lines.push(line);
+        }
+
+        highlighted
+    }
+
+    /// Updates the highlighting after an edit which replaced the lines in the range `old` with
+    /// `new` lines, given a function returning the text of a line of the edited text without its
+    /// line break.
+    ///
+    /// Only the new lines, and the lines after them which now start in a different state, are
+    /// highlighted. Returns the number of lines which were highlighted.
+    pub fn splice<S: AsRef<str>>(
+        &mut self,
+        highlighter: &dyn Highlighter,
+        old: Range<usize>,
+        new: usize,
+        line: impl Fn(usize) -> S,
+    ) -> usize {
+        let old = old.start.min(self.lines.len())..old.end.min(self.lines.len());
+        let mut state = old.start.checked_sub(1).map_or(0, |index| self.lines[index].end);
+        let highlight = |index: usize, state: &mut u32| {
+            let text = line(index);
+            let start = *state;
+            let spans = highlighter.highlight_line(text.as_ref(), state);
+            HighlightedLine { hash: hash_line(text.as_ref()), start, end: *state, spans }
+        };
+
+        let lines = (old.start..old.start + new).map(|index| highlight(index, &mut state));
+        self.lines.splice(old.clone(), lines.collect::<Vec<_>>());
+
+        // The lines after the edit are highlighted again until one starts in the same state.
+        let mut index = old.start + new;
+        while index < self.lines.len() && self.lines[index].start != state {
+            self.lines[index] = highlight(index, &mut state);
+            index += 1;
+        }
+
+        index - old.start
+    }
+
+    /// Returns the spans of a line, with byte ranges into the line.
+    pub fn line(&self, line: usize) -> &[HighlightSpan] {
+        self.lines.get(line).map_or(&[], |line| &line.spans)
+    }
+}
+
+fn hash_line(line: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Builds a text span for each part of some highlighted text, with the `token` class and the class
+// of the highlight span which covers it.
+pub(crate) fn highlighted_spans(cx: &mut Context, text: &str, spans: &[HighlightSpan]) {
+    for (range, class) in highlight_segments(text.len(), spans) {
+        let span = TextSpan::new(cx, &text[range], |_| {});
+        if let Some(class) = class {
+            span.class("token").class(class);
+        }
+    }
+}
+
+// Splits some text into the parts which are covered by spans, with their class, and the parts which are not.
+fn highlight_segments(
+    len: usize,
+    spans: &[HighlightSpan],
+) -> Vec<(Range<usize>, Option<&'static str>)> {
+    let mut segments = Vec::new();
+    let mut offset = 0;
+
+    for span in spans {
+        let range = span.range.start.max(offset)..span.range.end.min(len);
+        if range.is_empty() {
+            continue;
+        }
+
+        if range.start > offset {
+            segments.push((offset..range.start, None));
+        }
+
+        offset = range.end;
+        segments.push((range, Some(span.class)));
+    }
+
+    if offset < len {
+        segments.push((offset..len, None));
+    }
+
+    segments
+}
+
+// The states of the TOML highlighter at the end of a line inside of a multi-line string.
+const IN_BASIC_STRING: u32 = 1;
+const IN_LITERAL_STRING: u32 = 2;
+// The rest of the state of the TOML highlighter is the number of arrays which are open at the end
+// of a line, in multiples of this.
+const ARRAY_DEPTH: u32 = 4;
+
+/// A [`Highlighter`] for configuration files in JSON or TOML.
+///
+/// Text is given the classes `key`, `string`, `number`, `keyword` for booleans and null,
+/// `comment`, `table` for TOML table headers, and `punctuation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigHighlighter {
+    /// Highlights JSON.
+    Json,
+    /// Highlights TOML.
+    Toml,
+}
+
+impl ConfigHighlighter {
+    /// Returns the highlighter for a language name, such as the info string of a fenced code
+    /// block, or `None` if the language is not supported.
+    pub fn for_language(language: &str) -> Option<Self> {
+        match language.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+impl Highlighter for ConfigHighlighter {
+    fn highlight_line(&self, line: &str, state: &mut u32) -> Vec<HighlightSpan> {
+        match self {
+            Self::Json => highlight_json(line),
+            Self::Toml => highlight_toml(line, state),
+        }
+    }
+}
+
+fn highlight_json(line: &str) -> Vec<HighlightSpan> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+
+    while let Some(c) = line[offset..].chars().next() {
+        let start = offset;
+        let class = match c {
+            '"' => {
+                offset = string_end(line, start + 1, "\"", true).unwrap_or(line.len());
+                if next_char(line, offset) == Some(':') {
+                    "key"
+                } else {
+                    "string"
+                }
+            }
+
+            '-' | '0'..='9' => {
+                offset = token_end(line, start, |c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+                "number"
+            }
+
+            '{' | '}' | '[' | ']' | ':' | ',' => {
+                offset += 1;
+                "punctuation"
+            }
+
+            c if c.is_alphabetic() => {
+                offset = token_end(line, start, char::is_alphanumeric);
+                match &line[start..offset] {
+                    "true" | "false" | "null" => "keyword",
+                    _ => continue,
+                }
+            }
+
+            c => {
+                offset += c.len_utf8();
+                continue;
+            }
+        };
+
+        spans.push(HighlightSpan::new(start..offset, class));
+    }
+
+    spans
+}
+
+fn highlight_toml(line: &str, state: &mut u32) -> Vec<HighlightSpan> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    let mut string = *state % ARRAY_DEPTH;
+    let mut depth = *state / ARRAY_DEPTH;
+
+    // The rest of a multi-line string from the previous line.
+    if string != 0 {
+        let (delimiter, escapes) =
+            if string == IN_BASIC_STRING { ("\"\"\"", true) } else { ("'''", false) };
+
+        offset = match string_end(line, 0, delimiter, escapes) {
+            Some(end) => {
+                string = 0;
+                end
+            }
+
+            None => line.len(),
+        };
+
+        if offset > 0 {
+            spans.push(HighlightSpan::new(0..offset, "string"));
+        }
+    }
+
+    // A table header starts a line, while a line within an array which is spread over several
+    // lines can also start with a bracket.
+    if offset == 0 && depth == 0 {
+        if let Some(header) = table_header(line) {
+            offset = header.end;
+            spans.push(HighlightSpan::new(header, "table"));
+        }
+    }
+
+    while let Some(c) = line[offset..].chars().next() {
+        let start = offset;
+        let class = match c {
+            '#' => {
+                offset = line.len();
+                "comment"
+            }
+
+            '"' | '\'' => {
+                let (delimiter, escapes, multi_line_state) = match (c, &line[start..]) {
+                    ('"', rest) if rest.starts_with("\"\"\"") => ("\"\"\"", true, IN_BASIC_STRING),
+                    ('\'', rest) if rest.starts_with("'''") => ("'''", false, IN_LITERAL_STRING),
+                    ('"', _) => ("\"", true, 0),
+                    _ => ("'", false, 0),
+                };
+
+                offset = match string_end(line, start + delimiter.len(), delimiter, escapes) {
+                    Some(end) => end,
+                    None => {
+                        string = multi_line_state;
+                        line.len()
+                    }
+                };
+
+                if is_toml_key(line, offset) {
+                    "key"
+                } else {
+                    "string"
+                }
+            }
+
+            '=' | ',' | '{' | '}' | '[' | ']' | '.' => {
+                match c {
+                    '[' => depth += 1,
+                    ']' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+
+                offset += 1;
+                "punctuation"
+            }
+
+            '+' | '-' | '0'..='9' => {
+                offset =
+                    token_end(line, start, |c| c.is_ascii_alphanumeric() || "+-_.:".contains(c));
+
+                if is_toml_key(line, offset) {
+                    "key"
+                } else {
+                    "number"
+                }
+            }
+
+            c if c.is_alphabetic() || c == '_' => {
+                offset = token_end(line, start, |c| c.is_alphanumeric() || c == '_' || c == '-');
+
+                if is_toml_key(line, offset) {
+                    "key"
+                } else {
+                    match &line[start..offset] {
+                        "true" | "false" => "keyword",
+                        "inf" | "nan" => "number",
+                        _ => continue,
+                    }
+                }
+            }
+
+            c => {
+                offset += c.len_utf8();
+                continue;
+            }
+        };
+
+        spans.push(HighlightSpan::new(start..offset, class));
+    }
+
+    *state = string + depth * ARRAY_DEPTH;
+    spans
+}
+
+// Returns the range of a table header, such as `[table]` or `[[array.of.tables]]`, which starts
+// the given line. The header ends after its closing brackets, which are the first outside of a
+// quoted key, or before a comment if it is not closed.
+fn table_header(line: &str) -> Option<Range<usize>> {
+    let start = line.len() - line.trim_start().len();
+    if !line[start..].starts_with('[') {
+        return None;
+    }
+
+    let close = if line[start..].starts_with("[[") { "]]" } else { "]" };
+    let mut offset = start + 1;
+    while let Some(c) = line[offset..].chars().next() {
+        match c {
+            '"' | '\'' => {
+                let delimiter = if c == '"' { "\"" } else { "'" };
+                offset = string_end(line, offset + 1, delimiter, c == '"').unwrap_or(line.len());
+            }
+
+            ']' => {
+                offset += if line[offset..].starts_with(close) { close.len() } else { 1 };
+                break;
+            }
+
+            '#' => break,
+
+            c => offset += c.len_utf8(),
+        }
+    }
+
+    Some(start..line[..offset].trim_end().len())
+}
+
+// Returns the byte offset after the closing delimiter of a string which starts at the given
+// offset, or `None` if the string is not closed on the line.
+fn string_end(line: &str, start: usize, delimiter: &str, escapes: bool) -> Option<usize> {
+    let mut chars = line[start..].char_indices();
+    while let Some((offset, c)) = chars.next() {
+        if escapes && c == '\\' {
+            chars.next();
+        } else if line[start + offset..].starts_with(delimiter) {
+            return Some(start + offset + delimiter.len());
+        }
+    }
+
+    None
+}
+
+// Returns the byte offset after the characters from the given offset which match the predicate.
+fn token_end(line: &str, start: usize, predicate: impl Fn(char) -> bool) -> usize {
+    line[start..].find(|c| !predicate(c)).map_or(line.len(), |end| start + end)
+}
+
+// Returns the first character after the given offset which is not whitespace.
+fn next_char(line: &str, offset: usize) -> Option<char> {
+    line[offset..].trim_start().chars().next()
+}
+
+// Returns true if the token before the given offset is a key or part of a dotted key.
+fn is_toml_key(line: &str, offset: usize) -> bool {
+    matches!(next_char(line, offset), Some('=' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classes<'a>(line: &'a str, spans: &[HighlightSpan]) -> Vec<(&'a str, &'static str)> {
+        spans.iter().map(|span| (&line[span.range.clone()], span.class)).collect()
+    }
+
+    #[test]
+    fn json() {
+        let line = r#"{"name": "vi\"zia", "stars": -4.5e3, "ok": true, "none": null}"#;
+        let spans = ConfigHighlighter::Json.highlight_line(line, &mut 0);
+        assert_eq!(
+            classes(line, &spans),
+            vec![
+                ("{", "punctuation"),
+                ("\"name\"", "key"),
+                (":", "punctuation"),
+                ("\"vi\\\"zia\"", "string"),
+                (",", "punctuation"),
+                ("\"stars\"", "key"),
+                (":", "punctuation"),
+                ("-4.5e3", "number"),
+                (",", "punctuation"),
+                ("\"ok\"", "key"),
+                (":", "punctuation"),
+                ("true", "keyword"),
+                (",", "punctuation"),
+                ("\"none\"", "key"),
+                (":", "punctuation"),
+                ("null", "keyword"),
+                ("}", "punctuation"),
+            ]
+        );
+    }
+
+    #[test]
+    fn toml() {
+        let highlighter = ConfigHighlighter::Toml;
+        let mut state = 0;
+
+        let line = "[package.metadata] # table";
+        let spans = highlighter.highlight_line(line, &mut state);
+        assert_eq!(
+            classes(line, &spans),
+            vec![("[package.metadata]", "table"), ("# table", "comment")]
+        );
+
+        let line = "name.first = 'vizia' # name";
+        let spans = highlighter.highlight_line(line, &mut state);
+        assert_eq!(
+            classes(line, &spans),
+            vec![
+                ("name", "key"),
+                (".", "punctuation"),
+                ("first", "key"),
+                ("=", "punctuation"),
+                ("'vizia'", "string"),
+                ("# name", "comment"),
+            ]
+        );
+
+        let line = "flags = [true, 1_000, 1979-05-27]";
+        let spans = highlighter.highlight_line(line, &mut state);
+        assert_eq!(
+            classes(line, &spans),
+            vec![
+                ("flags", "key"),
+                ("=", "punctuation"),
+                ("[", "punctuation"),
+                ("true", "keyword"),
+                (",", "punctuation"),
+                ("1_000", "number"),
+                (",", "punctuation"),
+                ("1979-05-27", "number"),
+                ("]", "punctuation"),
+            ]
+        );
+        assert_eq!(state, 0);
+    }
+
+    #[test]
+    fn toml_table_headers() {
+        let highlighter = ConfigHighlighter::Toml;
+
+        let line = "[[bin]] # see [[bin]] ]";
+        let spans = highlighter.highlight_line(line, &mut 0);
+        assert_eq!(
+            classes(line, &spans),
+            vec![("[[bin]]", "table"), ("# see [[bin]] ]", "comment")]
+        );
+
+        let line = "[a.\"b]#\"] #c";
+        let spans = highlighter.highlight_line(line, &mut 0);
+        assert_eq!(classes(line, &spans), vec![("[a.\"b]#\"]", "table"), ("#c", "comment")]);
+
+        let line = "[unclosed # c";
+        let spans = highlighter.highlight_line(line, &mut 0);
+        assert_eq!(classes(line, &spans), vec![("[unclosed", "table"), ("# c", "comment")]);
+    }
+
+    #[test]
+    fn toml_multi_line_array() {
+        let highlighter = ConfigHighlighter::Toml;
+        let mut state = 0;
+
+        let spans = highlighter.highlight_line("points = [", &mut state);
+        assert_eq!(spans.last(), Some(&HighlightSpan::new(9..10, "punctuation")));
+
+        // The lines of the array are not table headers, even if they start with a bracket.
+        for line in ["  [1, 2],", "  [3],", "  [\"a\"]"] {
+            let spans = highlighter.highlight_line(line, &mut state);
+            assert!(spans.iter().all(|span| span.class != "table"), "{}", line);
+        }
+
+        highlighter.highlight_line("]", &mut state);
+        assert_eq!(state, 0);
+
+        let line = "[table]";
+        let spans = highlighter.highlight_line(line, &mut state);
+        assert_eq!(classes(line, &spans), vec![("[table]", "table")]);
+    }
+
+    #[test]
+    fn toml_multi_line_string() {
+        let highlighter = ConfigHighlighter::Toml;
+        let mut state = 0;
+
+        let spans = highlighter.highlight_line("text = \"\"\"first", &mut state);
+        assert_eq!(spans.last(), Some(&HighlightSpan::new(7..15, "string")));
+        assert_eq!(state, IN_BASIC_STRING);
+
+        let spans = highlighter.highlight_line("[not a table]", &mut state);
+        assert_eq!(spans, vec![HighlightSpan::new(0..13, "string")]);
+
+        let spans = highlighter.highlight_line("last\"\"\" # done", &mut state);
+        assert_eq!(
+            spans,
+            vec![HighlightSpan::new(0..7, "string"), HighlightSpan::new(8..14, "comment")]
+        );
+        assert_eq!(state, 0);
+    }
+
+    #[test]
+    fn whole_text() {
+        let text = "a = 1\r\nb = true\n";
+        let spans = ConfigHighlighter::Toml.highlight(text);
+        assert_eq!(
+            classes(text, &spans),
+            vec![
+                ("a", "key"),
+                ("=", "punctuation"),
+                ("1", "number"),
+                ("b", "key"),
+                ("=", "punctuation"),
+                ("true", "keyword"),
+            ]
+        );
+    }
+
+    #[test]
+    fn incremental_updates() {
+        let highlighter = ConfigHighlighter::Toml;
+        let mut lines = vec!["a = 1", "b = 2", "c = 3", "d = 4"];
+        let mut highlights = Highlights::new();
+        assert_eq!(highlights.update(&highlighter, &lines), 4);
+        assert_eq!(highlights.update(&highlighter, &lines), 0);
+
+        lines[1] = "b = 'two'";
+        assert_eq!(highlights.update(&highlighter, &lines), 1);
+        assert_eq!(highlights.line(1)[2], HighlightSpan::new(4..9, "string"));
+
+        lines.insert(2, "# comment");
+        assert_eq!(highlights.update(&highlighter, &lines), 1);
+        assert_eq!(highlights.line(3)[0], HighlightSpan::new(0..1, "key"));
+
+        // Opening a multi-line string changes the highlighting of the lines after it.
+        lines[0] = "a = '''";
+        assert_eq!(highlights.update(&highlighter, &lines), 5);
+        assert_eq!(highlights.line(4), &[HighlightSpan::new(0..5, "string")]);
+
+        lines.remove(0);
+        assert_eq!(highlights.update(&highlighter, &lines), 4);
+        assert_eq!(highlights.line(0)[0], HighlightSpan::new(0..1, "key"));
+        assert!(highlights.line(10).is_empty());
+    }
+
+    #[test]
+    fn splice() {
+        let highlighter = ConfigHighlighter::Toml;
+        let mut lines = vec!["a = 1", "b = 2", "c = 3", "d = 4"];
+        let mut highlights = Highlights::new();
+        highlights.update(&highlighter, &lines);
+
+        // Splitting the second line replaces it with two lines.
+        lines.splice(1..2, ["b = 2 # two", "e = 5"]);
+        assert_eq!(highlights.splice(&highlighter, 1..2, 2, |index| lines[index]), 2);
+        assert_eq!(highlights.line(1)[3], HighlightSpan::new(6..11, "comment"));
+        assert_eq!(highlights.line(4)[0], HighlightSpan::new(0..1, "key"));
+
+        // Opening a multi-line string changes the highlighting of the lines after it.
+        lines[0] = "a = '''";
+        assert_eq!(highlights.splice(&highlighter, 0..1, 1, |index| lines[index]), 5);
+        assert_eq!(highlights.line(4), &[HighlightSpan::new(0..5, "string")]);
+
+        lines.remove(0);
+        assert_eq!(highlights.splice(&highlighter, 0..1, 0, |index| lines[index]), 4);
+        assert_eq!(highlights.line(0)[0], HighlightSpan::new(0..1, "key"));
+        assert_eq!(highlights.update(&highlighter, &lines), 0);
+    }
+
+    #[test]
+    fn closure_highlighter() {
+        let highlighter = |line: &str, _: &mut u32| {
+            line.find('!')
+                .map(|offset| vec![HighlightSpan::new(offset..offset + 1, "bang")])
+                .unwrap_or_default()
+        };
+
+        assert_eq!(
+            highlighter.highlight("hi!\nyo!"),
+            vec![HighlightSpan::new(2..3, "bang"), HighlightSpan::new(6..7, "bang")]
+        );
+    }
+
+    #[test]
+    fn segments() {
+        let spans = [HighlightSpan::new(2..4, "a"), HighlightSpan::new(4..5, "b")];
+        assert_eq!(
+            highlight_segments(7, &spans),
+            vec![(0..2, None), (2..4, Some("a")), (4..5, Some("b")), (5..7, None)]
+        );
+        assert_eq!(highlight_segments(0, &[]), vec![]);
+    }
+}
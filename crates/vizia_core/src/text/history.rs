@@ -0,0 +1,476 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use ropey::Rope;
+
+use super::Selection;
+
+/// The kind of an edit, which determines whether it can be merged with the previous edit into a
+/// single undo step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKind {
+    /// Typing a single character. Consecutive typing is merged up to the start of the next word.
+    Insert,
+    /// Deleting a single character or word with backspace or delete. Consecutive deletions are merged.
+    Delete,
+    /// Any other edit, such as pasting or replacing a selection, which is never merged.
+    Other,
+}
+
+/// Text which can be edited through an [`EditHistory`].
+///
+/// The history records the fragments of text removed and inserted by each edit, which have the same
+/// type as the text itself so that, for example, styled text keeps its styles when an edit is undone.
+pub trait HistoryText: Clone + PartialEq {
+    /// Returns a copy of the given byte range of the text, or `None` if the range is not valid.
+    fn fragment(&self, range: Range<usize>) -> Option<Self>;
+
+    /// Replaces the given byte range of the text with a fragment.
+    fn splice(&mut self, range: Range<usize>, fragment: &Self);
+
+    /// Returns the length of the text in bytes.
+    fn byte_len(&self) -> usize;
+
+    /// Returns the first character of the text.
+    fn first_char(&self) -> Option<char>;
+
+    /// Returns the last character of the text.
+    fn last_char(&self) -> Option<char>;
+}
+
+impl HistoryText for String {
+    fn fragment(&self, range: Range<usize>) -> Option<Self> {
+        self.get(range).map(str::to_owned)
+    }
+
+    fn splice(&mut self, range: Range<usize>, fragment: &Self) {
+        self.replace_range(range, fragment);
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn first_char(&self) -> Option<char> {
+        self.chars().next()
+    }
+
+    fn last_char(&self) -> Option<char> {
+        self.chars().next_back()
+    }
+}
+
+impl HistoryText for Rope {
+    fn fragment(&self, range: Range<usize>) -> Option<Self> {
+        self.get_byte_slice(range).map(Rope::from)
+    }
+
+    fn splice(&mut self, range: Range<usize>, fragment: &Self) {
+        let mut offset = self.byte_to_char(range.start);
+        self.remove(offset..self.byte_to_char(range.end));
+        for chunk in fragment.chunks() {
+            self.insert(offset, chunk);
+            offset += chunk.chars().count();
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len_bytes()
+    }
+
+    fn first_char(&self) -> Option<char> {
+        self.get_char(0)
+    }
+
+    fn last_char(&self) -> Option<char> {
+        self.len_chars().checked_sub(1).and_then(|index| self.get_char(index))
+    }
+}
+
+/// A single undoable change to some text.
+#[derive(Debug, Clone)]
+struct Edit<T> {
+    // The byte offset at which the text was replaced.
+    offset: usize,
+    // The text which was removed.
+    deleted: T,
+    // The text which was inserted in its place.
+    inserted: T,
+    // The selection before and after the edit.
+    selection_before: Selection,
+    selection_after: Selection,
+    kind: EditKind,
+}
+
+/// An undo/redo history of the edits made to some text.
+///
+/// Edits are recorded with [`EditHistory::edit`], which also applies them, and consecutive typing
+/// or deleting is merged into a single step. The oldest steps are discarded once the history
+/// reaches its limit. The history edits a `String` by default, or any other [`HistoryText`].
+#[derive(Debug, Clone)]
+pub struct EditHistory<T = String> {
+    undo_stack: VecDeque<Edit<T>>,
+    redo_stack: Vec<Edit<T>>,
+    limit: usize,
+    // Whether the next edit may be merged with the previous one.
+    merge: bool,
+}
+
+impl<T> Default for EditHistory<T> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_LIMIT)
+    }
+}
+
+impl<T> EditHistory<T> {
+    /// The default maximum number of undo steps.
+    pub const DEFAULT_LIMIT: usize = 100;
+
+    /// Creates a new history which keeps at most `limit` undo steps.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            limit: limit.max(1),
+            merge: true,
+        }
+    }
+
+    /// Prevents the next edit from being merged with the previous one, e.g. after the cursor moves.
+    pub fn break_merge(&mut self) {
+        self.merge = false;
+    }
+
+    /// Removes all undo and redo steps.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.merge = true;
+    }
+}
+
+impl<T: HistoryText> EditHistory<T> {
+    /// Replaces the given range of the text with `inserted` and records the change, along with the
+    /// selection before and after it.
+    pub fn edit(
+        &mut self,
+        text: &mut T,
+        range: Range<usize>,
+        inserted: impl Into<T>,
+        kind: EditKind,
+        selection_before: Selection,
+        selection_after: Selection,
+    ) {
+        let inserted = inserted.into();
+        let Some(deleted) = text.fragment(range.clone()) else {
+            return;
+        };
+
+        if deleted.byte_len() == 0 && inserted.byte_len() == 0 {
+            return;
+        }
+
+        text.splice(range.clone(), &inserted);
+        self.redo_stack.clear();
+
+        let edit = Edit {
+            offset: range.start,
+            deleted,
+            inserted,
+            selection_before,
+            selection_after,
+            kind,
+        };
+
+        if self.merge {
+            if let Some(previous) = self.undo_stack.back_mut() {
+                if previous.merge(&edit) {
+                    return;
+                }
+            }
+        }
+
+        self.merge = true;
+        self.undo_stack.push_back(edit);
+        if self.undo_stack.len() > self.limit {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Returns the byte range of the text which the next undo replaces, and the length in bytes of
+    /// the text it is replaced with.
+    pub fn next_undo(&self) -> Option<(Range<usize>, usize)> {
+        self.undo_stack.back().map(|edit| {
+            (edit.offset..edit.offset + edit.inserted.byte_len(), edit.deleted.byte_len())
+        })
+    }
+
+    /// Returns the byte range of the text which the next redo replaces, and the length in bytes of
+    /// the text it is replaced with.
+    pub fn next_redo(&self) -> Option<(Range<usize>, usize)> {
+        self.redo_stack.last().map(|edit| {
+            (edit.offset..edit.offset + edit.deleted.byte_len(), edit.inserted.byte_len())
+        })
+    }
+
+    /// Reverts the last undo step, returning the selection to restore, or `None` if there is nothing to undo.
+    pub fn undo(&mut self, text: &mut T) -> Option<Selection> {
+        let edit = self.undo_stack.pop_back()?;
+        let range = edit.offset..edit.offset + edit.inserted.byte_len();
+
+        // The text was changed without going through the history, so its edits no longer apply.
+        if text.fragment(range.clone()).as_ref() != Some(&edit.inserted) {
+            self.clear();
+            return None;
+        }
+
+        text.splice(range, &edit.deleted);
+        let selection = edit.selection_before;
+        self.redo_stack.push(edit);
+        self.merge = false;
+        Some(selection)
+    }
+
+    /// Reapplies the last undone step, returning the selection to restore, or `None` if there is nothing to redo.
+    pub fn redo(&mut self, text: &mut T) -> Option<Selection> {
+        let edit = self.redo_stack.pop()?;
+        let range = edit.offset..edit.offset + edit.deleted.byte_len();
+
+        if text.fragment(range.clone()).as_ref() != Some(&edit.deleted) {
+            self.clear();
+            return None;
+        }
+
+        text.splice(range, &edit.inserted);
+        let selection = edit.selection_after;
+        self.undo_stack.push_back(edit);
+        self.merge = false;
+        Some(selection)
+    }
+}
+
+impl<T: HistoryText> Edit<T> {
+    // Merges the next edit into this one if it continues the same typing or deletion, returning
+    // true if it was merged.
+    fn merge(&mut self, next: &Edit<T>) -> bool {
+        if self.kind != next.kind {
+            return false;
+        }
+
+        match self.kind {
+            EditKind::Insert => {
+                // Typing after whitespace starts a new word, and so a new step.
+                let starts_word = self.inserted.last_char().is_some_and(char::is_whitespace)
+                    && !next.inserted.first_char().is_some_and(char::is_whitespace);
+
+                if next.deleted.byte_len() != 0
+                    || next.offset != self.offset + self.inserted.byte_len()
+                    || starts_word
+                {
+                    return false;
+                }
+
+                let end = self.inserted.byte_len();
+                self.inserted.splice(end..end, &next.inserted);
+            }
+
+            EditKind::Delete => {
+                if self.inserted.byte_len() != 0 || next.inserted.byte_len() != 0 {
+                    return false;
+                }
+
+                if next.offset + next.deleted.byte_len() == self.offset {
+                    // Backspace
+                    self.deleted.splice(0..0, &next.deleted);
+                    self.offset = next.offset;
+                } else if next.offset == self.offset {
+                    // Forward delete
+                    let end = self.deleted.byte_len();
+                    self.deleted.splice(end..end, &next.deleted);
+                } else {
+                    return false;
+                }
+            }
+
+            EditKind::Other => return false,
+        }
+
+        self.selection_after = next.selection_after;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(history: &mut EditHistory, text: &mut String, typed: &str) {
+        for c in typed.chars() {
+            let offset = text.len();
+            let mut buf = [0; 4];
+            history.edit(
+                text,
+                offset..offset,
+                c.encode_utf8(&mut buf),
+                EditKind::Insert,
+                Selection::caret(offset),
+                Selection::caret(offset + c.len_utf8()),
+            );
+        }
+    }
+
+    #[test]
+    fn undo_redo_typing() {
+        let mut history = EditHistory::default();
+        let mut text = String::new();
+
+        type_text(&mut history, &mut text, "hello world");
+        assert_eq!(text, "hello world");
+
+        let selection = history.undo(&mut text).unwrap();
+        assert_eq!(text, "hello ");
+        assert_eq!(selection.active, 6);
+
+        history.undo(&mut text);
+        assert_eq!(text, "");
+        assert!(history.undo_stack.is_empty());
+
+        let selection = history.redo(&mut text).unwrap();
+        assert_eq!(text, "hello ");
+        assert_eq!(selection.active, 6);
+
+        history.redo(&mut text);
+        assert_eq!(text, "hello world");
+        assert!(history.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn merge_backspace() {
+        let mut history = EditHistory::default();
+        let mut text = String::from("abc");
+
+        for offset in (0..3).rev() {
+            history.edit(
+                &mut text,
+                offset..offset + 1,
+                "",
+                EditKind::Delete,
+                Selection::caret(offset + 1),
+                Selection::caret(offset),
+            );
+        }
+
+        assert_eq!(text, "");
+        let selection = history.undo(&mut text).unwrap();
+        assert_eq!(text, "abc");
+        assert_eq!(selection.active, 3);
+        assert!(history.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn restore_selection() {
+        let mut history = EditHistory::default();
+        let mut text = String::from("hello world");
+
+        history.edit(
+            &mut text,
+            0..5,
+            "goodbye",
+            EditKind::Other,
+            Selection::new(0, 5),
+            Selection::caret(7),
+        );
+        assert_eq!(text, "goodbye world");
+
+        let selection = history.undo(&mut text).unwrap();
+        assert_eq!(text, "hello world");
+        assert_eq!(selection.range(), 0..5);
+    }
+
+    #[test]
+    fn break_merge() {
+        let mut history = EditHistory::default();
+        let mut text = String::new();
+
+        type_text(&mut history, &mut text, "ab");
+        history.break_merge();
+        type_text(&mut history, &mut text, "cd");
+
+        history.undo(&mut text);
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn new_edit_clears_redo() {
+        let mut history = EditHistory::default();
+        let mut text = String::new();
+
+        type_text(&mut history, &mut text, "ab");
+        history.undo(&mut text);
+        assert!(!history.redo_stack.is_empty());
+
+        type_text(&mut history, &mut text, "c");
+        assert!(history.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn limit() {
+        let mut history = EditHistory::new(2);
+        let mut text = String::new();
+
+        for word in ["a ", "b ", "c "] {
+            type_text(&mut history, &mut text, word);
+            history.break_merge();
+        }
+
+        assert!(history.undo(&mut text).is_some());
+        assert!(history.undo(&mut text).is_some());
+        assert!(history.undo(&mut text).is_none());
+        assert_eq!(text, "a ");
+    }
+
+    #[test]
+    fn external_change_clears_history() {
+        let mut history = EditHistory::default();
+        let mut text = String::new();
+
+        type_text(&mut history, &mut text, "abc");
+        text = String::from("xyz");
+
+        assert!(history.undo(&mut text).is_none());
+        assert_eq!(text, "xyz");
+        assert!(history.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn rope() {
+        let mut history = EditHistory::default();
+        let mut text = Rope::from_str("h\u{e9}llo world");
+
+        history.edit(
+            &mut text,
+            0..6,
+            "goodbye",
+            EditKind::Other,
+            Selection::new(0, 6),
+            Selection::caret(7),
+        );
+        assert_eq!(text, "goodbye world");
+        assert_eq!(history.next_undo(), Some((0..7, 6)));
+
+        history.undo(&mut text);
+        assert_eq!(text, "h\u{e9}llo world");
+        assert_eq!(history.next_redo(), Some((0..6, 7)));
+
+        // A range which does not lie on char boundaries is not edited.
+        history.edit(
+            &mut text,
+            0..2,
+            "",
+            EditKind::Other,
+            Selection::caret(0),
+            Selection::caret(0),
+        );
+        assert_eq!(text, "h\u{e9}llo world");
+    }
+}
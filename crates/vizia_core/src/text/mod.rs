@@ -15,3 +15,18 @@ pub use selection::*;
 
 pub mod backspace;
 pub use backspace::*;
+
+pub mod history;
+pub use history::*;
+
+pub mod checker;
+pub use checker::*;
+
+pub mod rich_text;
+pub use rich_text::*;
+
+pub mod highlighter;
+pub use highlighter::*;
+
+pub mod search;
+pub use search::*;
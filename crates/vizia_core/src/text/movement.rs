@@ -1,5 +1,5 @@
 use log::warn;
-use skia_safe::textlayout::Paragraph;
+use skia_safe::textlayout::{Paragraph, RectHeightStyle, RectWidthStyle, TextBox, TextDirection};
 
 use super::{EditableText, Selection};
 
@@ -54,13 +54,162 @@ pub enum VerticalMovement {
     DocumentEnd,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WritingDirection {
     LeftToRight,
     RightToLeft,
+    /// The direction of the first character of the text with a strong direction.
     Natural,
 }
 
+impl WritingDirection {
+    /// Resolves a natural direction from the text, which is left-to-right if the text has no
+    /// characters with a strong direction.
+    pub fn resolve<T: EditableText>(self, text: &T) -> WritingDirection {
+        if self != WritingDirection::Natural {
+            return self;
+        }
+
+        let rtl = text
+            .slice(0..text.len())
+            .and_then(|text| text.chars().find_map(strong_direction))
+            .unwrap_or_default();
+
+        if rtl {
+            WritingDirection::RightToLeft
+        } else {
+            WritingDirection::LeftToRight
+        }
+    }
+}
+
+// Returns true if the character is strongly right-to-left, false if it is strongly left-to-right,
+// or `None` if it has no strong direction.
+fn strong_direction(c: char) -> Option<bool> {
+    match c as u32 {
+        // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and their presentation forms.
+        0x0590..=0x08FF
+        | 0xFB1D..=0xFDFF
+        | 0xFE70..=0xFEFF
+        | 0x10800..=0x10FFF
+        | 0x1E800..=0x1EFFF => Some(true),
+        _ if c.is_alphabetic() => Some(false),
+        _ => None,
+    }
+}
+
+/// Returns the horizontal position of the caret before the grapheme with the given bounds, which is
+/// on the right of the grapheme if it is laid out right-to-left.
+pub(crate) fn caret_position(text_box: &TextBox) -> f32 {
+    if text_box.direct == TextDirection::RTL {
+        text_box.rect.right
+    } else {
+        text_box.rect.left
+    }
+}
+
+/// The position of the caret before a grapheme of a paragraph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CaretStop {
+    /// The byte offset of the grapheme in the text.
+    offset: usize,
+    x: f32,
+    /// The vertical center of the line containing the grapheme.
+    y: f32,
+}
+
+// Returns the caret stops of every grapheme of the text, including the end of the text.
+fn caret_stops<T: EditableText>(text: &T, paragraph: &Paragraph) -> Vec<CaretStop> {
+    let mut stops = Vec::new();
+    let mut offset = 0;
+    let mut index = 0;
+
+    // Graphemes are indexed by count, as when drawing the caret. The end of the text is the zero
+    // width space which is added to the end of every paragraph.
+    loop {
+        let rects = paragraph.get_rects_for_range(
+            index..index + 1,
+            RectHeightStyle::Max,
+            RectWidthStyle::Tight,
+        );
+        if let Some(text_box) = rects.first() {
+            stops.push(CaretStop {
+                offset,
+                x: caret_position(text_box),
+                y: text_box.rect.center_y(),
+            });
+        }
+
+        match text.next_grapheme_offset(offset) {
+            Some(next) => offset = next,
+            None => break,
+        }
+
+        index += 1;
+    }
+
+    stops
+}
+
+/// The caret stops of a paragraph, which are computed the first time the caret moves visually
+/// within it and kept until the paragraph is built or laid out again.
+#[derive(Debug, Default, Clone)]
+pub struct CaretStops {
+    stops: Option<Vec<CaretStop>>,
+}
+
+impl CaretStops {
+    fn get<T: EditableText>(&mut self, text: &T, paragraph: &Paragraph) -> &[CaretStop] {
+        self.stops.get_or_insert_with(|| caret_stops(text, paragraph))
+    }
+}
+
+// Returns the offset of the caret stop visually next to the stop at the given offset on the same
+// line, or `None` if the stop is at the edge of its line.
+//
+// At the boundary between a left-to-right and a right-to-left run two stops can have the same
+// position, and are ordered by offset so that every stop can be reached.
+fn visual_neighbor(stops: &[CaretStop], offset: usize, right: bool) -> Option<usize> {
+    let current = stops.iter().find(|stop| stop.offset == offset)?;
+    let mut line =
+        stops.iter().filter(|stop| (stop.y - current.y).abs() < 0.5).copied().collect::<Vec<_>>();
+    line.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.offset.cmp(&b.offset)));
+
+    let index = line.iter().position(|stop| stop.offset == offset)?;
+    let neighbor = if right { line.get(index + 1) } else { index.checked_sub(1).map(|i| &line[i]) };
+    neighbor.map(|stop| stop.offset)
+}
+
+// Moves the caret one grapheme to the left or right in the order the text is displayed, which
+// differs from the order of the text within runs of the opposite direction.
+fn visual_grapheme_offset<T: EditableText>(
+    text: &T,
+    paragraph: &Paragraph,
+    caret_stops: &mut CaretStops,
+    offset: usize,
+    right: bool,
+    writing_direction: WritingDirection,
+) -> usize {
+    let stops = caret_stops.get(text, paragraph);
+    if let Some(neighbor) = visual_neighbor(stops, offset, right) {
+        return neighbor;
+    }
+
+    // At the edge of a line the caret moves onto the next or previous line, if there is one.
+    let upstream = right == matches!(writing_direction, WritingDirection::RightToLeft);
+    let next = if upstream {
+        text.prev_grapheme_offset(offset)
+    } else {
+        text.next_grapheme_offset(offset)
+    };
+
+    let line = |offset| stops.iter().find(|stop| stop.offset == offset).map(|stop| stop.y);
+    match next {
+        Some(next) if line(next) != line(offset) => next,
+        _ => offset,
+    }
+}
+
 /// Compute the result of a [`Movement`] on a [`Selection`].
 ///
 /// returns a new selection representing the state after the movement.
@@ -68,22 +217,34 @@ pub enum WritingDirection {
 /// If `modify` is true, only the 'active' edge (the `end`) of the selection
 /// should be changed; this is the case when the user moves with the shift
 /// key pressed.
+///
+/// Moving left or right by a grapheme follows the order in which the text is displayed, while other
+/// movements to the left or right follow the order of the text in the given writing direction.
+/// The caret stops of the paragraph are computed once and reused by later movements.
 pub fn apply_movement<T: EditableText>(
     m: Movement,
     s: Selection,
     text: &T,
     paragraph: &Paragraph,
+    caret_stops: &mut CaretStops,
+    writing_direction: WritingDirection,
     modify: bool,
 ) -> Selection {
-    // let writing_direction = if crate::piet::util::first_strong_rtl(text.as_str()) {
-    //     WritingDirection::RightToLeft
-    // } else {
-    //     WritingDirection::LeftToRight
-    // };
-
-    let writing_direction = WritingDirection::LeftToRight;
+    let writing_direction = writing_direction.resolve(text);
 
     let (offset, h_pos) = match m {
+        Movement::Grapheme(d @ (Direction::Left | Direction::Right)) if s.is_caret() || modify => {
+            let right = d == Direction::Right;
+            let offset = visual_grapheme_offset(
+                text,
+                paragraph,
+                caret_stops,
+                s.active,
+                right,
+                writing_direction,
+            );
+            (offset, None)
+        }
         Movement::Grapheme(d) if d.is_upstream_for_direction(writing_direction) => {
             if s.is_caret() || modify {
                 text.prev_grapheme_offset(s.active).map(|off| (off, None)).unwrap_or((0, s.h_pos))
@@ -203,3 +364,52 @@ pub fn apply_movement<T: EditableText>(
     let start = if modify { s.anchor } else { offset };
     Selection::new(start, offset).with_h_pos(h_pos)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stops(stops: &[(usize, f32)]) -> Vec<CaretStop> {
+        stops.iter().map(|&(offset, x)| CaretStop { offset, x, y: 0.0 }).collect()
+    }
+
+    #[test]
+    fn resolve_natural_direction() {
+        let resolve = |text: &str| WritingDirection::Natural.resolve(&text.to_owned());
+        assert_eq!(resolve("hello"), WritingDirection::LeftToRight);
+        assert_eq!(resolve("123 שלום abc"), WritingDirection::RightToLeft);
+        assert_eq!(resolve("(مرحبا)"), WritingDirection::RightToLeft);
+        assert_eq!(resolve("123"), WritingDirection::LeftToRight);
+        assert_eq!(
+            WritingDirection::LeftToRight.resolve(&String::from("שלום")),
+            WritingDirection::LeftToRight
+        );
+    }
+
+    #[test]
+    fn visual_movement_in_mixed_runs() {
+        // "ab אב" displayed as "ab בא", where the caret before א is displayed on its right.
+        let stops = stops(&[(0, 0.0), (1, 10.0), (2, 20.0), (3, 50.0), (5, 40.0), (7, 50.0)]);
+
+        let mut offset = 0;
+        let mut visited = vec![offset];
+        while let Some(next) = visual_neighbor(&stops, offset, true) {
+            offset = next;
+            visited.push(offset);
+        }
+        assert_eq!(visited, vec![0, 1, 2, 5, 3, 7]);
+
+        assert_eq!(visual_neighbor(&stops, 5, false), Some(2));
+        assert_eq!(visual_neighbor(&stops, 0, false), None);
+    }
+
+    #[test]
+    fn visual_movement_stays_on_line() {
+        let mut stops = stops(&[(0, 0.0), (1, 10.0), (2, 0.0), (3, 10.0)]);
+        stops[2].y = 20.0;
+        stops[3].y = 20.0;
+
+        assert_eq!(visual_neighbor(&stops, 1, true), None);
+        assert_eq!(visual_neighbor(&stops, 2, true), Some(3));
+    }
+}
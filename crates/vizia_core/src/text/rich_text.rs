@@ -0,0 +1,627 @@
+use std::ops::Range;
+
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::{parse_document, Arena, Options};
+
+use super::HistoryText;
+use crate::prelude::*;
+
+/// The font sizes of markdown headings from level 1 to 6, which match those of the `Markdown` view.
+const HEADING_SIZES: [f32; 6] = [32.0, 24.0, 20.0, 16.0, 14.0, 13.5];
+/// The text which starts each list item, indented by two spaces for each level of nesting.
+const BULLET: &str = "\u{2022} ";
+
+/// The style of a run of text within a [`RichText`] document.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct RunStyle {
+    /// Whether the text is bold.
+    pub bold: bool,
+    /// Whether the text is italic.
+    pub italic: bool,
+    /// Whether the text is underlined.
+    pub underline: bool,
+    /// The color of the text, or `None` to use the color of the view displaying it.
+    pub color: Option<Color>,
+    /// The font size of the text, or `None` to use the font size of the view displaying it.
+    pub font_size: Option<f32>,
+    /// The URL the text links to.
+    pub link: Option<String>,
+}
+
+/// A run of text with a single style.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct StyledRun {
+    /// The text of the run.
+    pub text: String,
+    /// The style of the text.
+    pub style: RunStyle,
+}
+
+/// A document of styled text, made up of runs of text which each have a single [`RunStyle`].
+///
+/// Ranges within the document are byte offsets into its plain [`text`](RichText::text), with
+/// lines separated by `\n`. Runs are never empty and adjacent runs always have different styles.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// let mut document = RichText::from("Hello world");
+/// document.update_style(6..11, |style| style.bold = true);
+/// assert_eq!(document.runs().len(), 2);
+/// assert_eq!(document.to_markdown(), "Hello **world**");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct RichText {
+    runs: Vec<StyledRun>,
+}
+
+impl RichText {
+    /// Creates a new empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the runs of styled text which make up the document.
+    pub fn runs(&self) -> &[StyledRun] {
+        &self.runs
+    }
+
+    /// Returns the plain text of the document.
+    pub fn text(&self) -> String {
+        self.runs.iter().map(|run| run.text.as_str()).collect()
+    }
+
+    /// Returns the length of the text of the document in bytes.
+    pub fn len(&self) -> usize {
+        self.runs.iter().map(|run| run.text.len()).sum()
+    }
+
+    /// Returns true if the document has no text.
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Appends text with the given style to the end of the document.
+    pub fn push(&mut self, text: &str, style: RunStyle) {
+        if text.is_empty() {
+            return;
+        }
+
+        match self.runs.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(text),
+            _ => self.runs.push(StyledRun { text: text.to_owned(), style }),
+        }
+    }
+
+    /// Appends the runs of another document to the end of the document.
+    pub fn append(&mut self, other: &RichText) {
+        for run in other.runs.iter() {
+            self.push(&run.text, run.style.clone());
+        }
+    }
+
+    /// Returns the part of the document within the given byte range.
+    ///
+    /// # Panics
+    /// Panics if the range does not lie on `char` boundaries of the text.
+    pub fn slice(&self, range: Range<usize>) -> RichText {
+        let mut slice = RichText::new();
+        let mut start = 0;
+        for run in self.runs.iter() {
+            let end = start + run.text.len();
+            let (from, to) = (range.start.max(start), range.end.min(end));
+            if from < to {
+                slice.push(&run.text[from - start..to - start], run.style.clone());
+            }
+
+            start = end;
+        }
+
+        slice
+    }
+
+    /// Replaces the given byte range of the document with another document.
+    ///
+    /// # Panics
+    /// Panics if the range does not lie on `char` boundaries of the text.
+    pub fn replace(&mut self, range: Range<usize>, with: &RichText) {
+        let mut document = self.slice(0..range.start);
+        document.append(with);
+        document.append(&self.slice(range.end..self.len()));
+        *self = document;
+    }
+
+    /// Returns the style of the text before the given byte offset, which is continued by text
+    /// inserted there, or the style of the first run if the offset is at the start of the document.
+    pub fn style_at(&self, offset: usize) -> RunStyle {
+        let mut start = 0;
+        for run in self.runs.iter() {
+            let end = start + run.text.len();
+            if start < offset && offset <= end {
+                return run.style.clone();
+            }
+
+            start = end;
+        }
+
+        self.runs.first().map(|run| run.style.clone()).unwrap_or_default()
+    }
+
+    /// Returns true if the given byte range is not empty and all of its text has a style which
+    /// matches the predicate.
+    pub fn is_styled(&self, range: Range<usize>, predicate: impl Fn(&RunStyle) -> bool) -> bool {
+        let slice = self.slice(range);
+        !slice.is_empty() && slice.runs.iter().all(|run| predicate(&run.style))
+    }
+
+    /// Modifies the style of the text within the given byte range.
+    ///
+    /// # Panics
+    /// Panics if the range does not lie on `char` boundaries of the text.
+    pub fn update_style(&mut self, range: Range<usize>, update: impl Fn(&mut RunStyle)) {
+        let mut document = self.slice(0..range.start);
+        for run in self.slice(range.clone()).runs {
+            let mut style = run.style;
+            (update)(&mut style);
+            document.push(&run.text, style);
+        }
+
+        document.append(&self.slice(range.end..self.len()));
+        *self = document;
+    }
+
+    /// Parses a document from markdown.
+    ///
+    /// Bold, italic and links are kept, as is underlined text between `<u>` and `</u>` tags.
+    /// Headings become bold text with the font size of the heading, each block starts a new line,
+    /// and list items start with a bullet. Other markdown, such as code, is kept as plain text.
+    ///
+    /// # Example
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// let document = RichText::from_markdown("Some *emphasis* and a [link](https://vizia.dev)");
+    /// assert_eq!(document.text(), "Some emphasis and a link");
+    /// assert!(document.runs()[1].style.italic);
+    /// ```
+    pub fn from_markdown(markdown: &str) -> Self {
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &Options::default());
+
+        let mut document = RichText::new();
+        import_blocks(root, &mut document, 0);
+        document
+    }
+
+    /// Converts the document to markdown.
+    ///
+    /// Each line becomes a paragraph, or a heading if it is bold with the font size of a heading,
+    /// or a list item if it starts with a bullet. Text colors and other font sizes are not kept.
+    pub fn to_markdown(&self) -> String {
+        let text = self.text();
+
+        let mut markdown = String::new();
+        let mut previous_item = false;
+        let mut start = 0;
+        for line in text.split('\n') {
+            let range = start..start + line.len();
+            start = range.end + 1;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (block, item) = export_line(&self.slice(range));
+
+            // Consecutive list items are kept together in a single list.
+            if !markdown.is_empty() {
+                markdown.push_str(if item && previous_item { "\n" } else { "\n\n" });
+            }
+
+            markdown.push_str(&block);
+            previous_item = item;
+        }
+
+        markdown
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        let mut document = RichText::new();
+        document.push(text, RunStyle::default());
+        document
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        RichText::from(text.as_str())
+    }
+}
+
+impl HistoryText for RichText {
+    fn fragment(&self, range: Range<usize>) -> Option<Self> {
+        self.text().get(range.clone()).is_some().then(|| self.slice(range))
+    }
+
+    fn splice(&mut self, range: Range<usize>, fragment: &Self) {
+        self.replace(range, fragment);
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn first_char(&self) -> Option<char> {
+        self.runs.first().and_then(|run| run.text.chars().next())
+    }
+
+    fn last_char(&self) -> Option<char> {
+        self.runs.last().and_then(|run| run.text.chars().next_back())
+    }
+}
+
+// Appends the blocks within a markdown node to the document, each on a new line.
+fn import_blocks<'a>(node: &'a AstNode<'a>, document: &mut RichText, depth: usize) {
+    for (index, child) in node.children().enumerate() {
+        if index > 0 {
+            document.push("\n", RunStyle::default());
+        }
+
+        match &child.data.borrow().value {
+            NodeValue::Paragraph => import_inlines(child, &RunStyle::default(), document),
+
+            NodeValue::Heading(heading) => {
+                let level = (heading.level as usize).clamp(1, HEADING_SIZES.len());
+                let style = RunStyle {
+                    bold: true,
+                    font_size: Some(HEADING_SIZES[level - 1]),
+                    ..Default::default()
+                };
+
+                import_inlines(child, &style, document);
+            }
+
+            NodeValue::List(_) => import_blocks(child, document, depth + 1),
+
+            NodeValue::Item(_) => {
+                let indent = "  ".repeat(depth.saturating_sub(1));
+                document.push(&format!("{indent}{BULLET}"), RunStyle::default());
+                import_blocks(child, document, depth);
+            }
+
+            NodeValue::CodeBlock(code_block) => {
+                document.push(code_block.literal.trim_end_matches('\n'), RunStyle::default());
+            }
+
+            _ => import_blocks(child, document, depth),
+        }
+    }
+}
+
+// Appends the inline text within a markdown node to the document.
+fn import_inlines<'a>(node: &'a AstNode<'a>, style: &RunStyle, document: &mut RichText) {
+    // Underlining is toggled by the inline html tags between siblings.
+    let mut style = style.clone();
+    for child in node.children() {
+        match &child.data.borrow().value {
+            NodeValue::Text(text) => document.push(text, style.clone()),
+
+            NodeValue::Code(code) => document.push(&code.literal, style.clone()),
+
+            NodeValue::SoftBreak => document.push(" ", style.clone()),
+
+            NodeValue::LineBreak => document.push("\n", style.clone()),
+
+            NodeValue::Emph => {
+                import_inlines(child, &RunStyle { italic: true, ..style.clone() }, document)
+            }
+
+            NodeValue::Strong => {
+                import_inlines(child, &RunStyle { bold: true, ..style.clone() }, document)
+            }
+
+            NodeValue::Link(link) => import_inlines(
+                child,
+                &RunStyle { link: Some(link.url.clone()), ..style.clone() },
+                document,
+            ),
+
+            NodeValue::HtmlInline(html) => match html.trim().to_ascii_lowercase().as_str() {
+                "<u>" => style.underline = true,
+                "</u>" => style.underline = false,
+                _ => {}
+            },
+
+            _ => import_inlines(child, &style, document),
+        }
+    }
+}
+
+// Converts a line of the document to a markdown block, and returns whether it is a list item.
+fn export_line(line: &RichText) -> (String, bool) {
+    let text = line.text();
+
+    let content = text.trim_start_matches(' ');
+    let indent = text.len() - content.len();
+    if content.starts_with(BULLET) {
+        let item = line.slice(indent + BULLET.len()..text.len());
+        return (format!("{}- {}", " ".repeat(indent), export_inlines(&item)), true);
+    }
+
+    if let Some(level) = heading_level(line) {
+        let mut heading = line.clone();
+        heading.update_style(0..text.len(), |style| {
+            style.bold = false;
+            style.font_size = None;
+        });
+
+        return (format!("{} {}", "#".repeat(level), export_inlines(&heading)), false);
+    }
+
+    (export_inlines(line), false)
+}
+
+// Returns the level of the heading with the font size of a line, if all of its text is bold with that size.
+fn heading_level(line: &RichText) -> Option<usize> {
+    let font_size = line.runs.first()?.style.font_size?;
+    let level = HEADING_SIZES.iter().position(|size| *size == font_size)? + 1;
+
+    line.runs
+        .iter()
+        .all(|run| run.style.bold && run.style.font_size == Some(font_size))
+        .then_some(level)
+}
+
+// A markdown delimiter around styled text. Marks are nested in the order of the variants.
+#[derive(Debug, Clone, PartialEq)]
+enum Mark {
+    Link(String),
+    Bold,
+    Italic,
+    Underline,
+}
+
+impl Mark {
+    fn marks(style: &RunStyle) -> Vec<Mark> {
+        let mut marks = Vec::new();
+        if let Some(link) = &style.link {
+            marks.push(Mark::Link(link.clone()));
+        }
+
+        if style.bold {
+            marks.push(Mark::Bold);
+        }
+
+        if style.italic {
+            marks.push(Mark::Italic);
+        }
+
+        if style.underline {
+            marks.push(Mark::Underline);
+        }
+
+        marks
+    }
+
+    // Returns the opening and closing delimiters of the mark. Italic text uses underscores rather
+    // than asterisks after an asterisk, which would otherwise be read as part of the same delimiter.
+    fn delimiters(&self, after_asterisk: bool) -> (&'static str, String) {
+        match self {
+            Mark::Link(url) => {
+                if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+                    ("[", format!("](<{url}>)"))
+                } else {
+                    ("[", format!("]({url})"))
+                }
+            }
+
+            Mark::Bold => ("**", String::from("**")),
+            Mark::Italic if after_asterisk => ("_", String::from("_")),
+            Mark::Italic => ("*", String::from("*")),
+            Mark::Underline => ("<u>", String::from("</u>")),
+        }
+    }
+}
+
+// Converts the runs of a line of the document to inline markdown.
+fn export_inlines(line: &RichText) -> String {
+    let mut markdown = String::new();
+    // The open marks and their closing delimiters.
+    let mut open: Vec<(Mark, String)> = Vec::new();
+
+    for run in line.runs.iter() {
+        let text = escape(&run.text, markdown.is_empty());
+
+        // Whitespace continues the marks around it, as delimiters cannot be next to whitespace.
+        if text.trim().is_empty() {
+            markdown.push_str(&text);
+            continue;
+        }
+
+        let marks = Mark::marks(&run.style);
+        let kept =
+            open.iter().zip(marks.iter()).take_while(|((open, _), mark)| open == *mark).count();
+        close_marks(&mut markdown, &mut open, kept);
+
+        let content = text.trim_start();
+        markdown.push_str(&text[..text.len() - content.len()]);
+
+        for mark in marks.into_iter().skip(kept) {
+            let (opening, closing) = mark.delimiters(markdown.ends_with('*'));
+            markdown.push_str(opening);
+            open.push((mark, closing));
+        }
+
+        markdown.push_str(content);
+    }
+
+    close_marks(&mut markdown, &mut open, 0);
+
+    markdown
+}
+
+// Closes the open marks after the first `kept`, moving trailing whitespace after the delimiters.
+fn close_marks(markdown: &mut String, open: &mut Vec<(Mark, String)>, kept: usize) {
+    if open.len() <= kept {
+        return;
+    }
+
+    let whitespace = markdown.split_off(markdown.trim_end().len());
+    for (_, closing) in open.drain(kept..).rev() {
+        markdown.push_str(&closing);
+    }
+
+    markdown.push_str(&whitespace);
+}
+
+// Escapes the characters of some text which markdown would otherwise interpret.
+fn escape(text: &str, line_start: bool) -> String {
+    // Digits at the start of a line followed by a period or bracket would start an ordered list.
+    let digits = if line_start { text.chars().take_while(char::is_ascii_digit).count() } else { 0 };
+
+    let mut escaped = String::with_capacity(text.len());
+    for (index, c) in text.chars().enumerate() {
+        let special = matches!(c, '\\' | '*' | '_' | '[' | ']' | '<' | '>' | '`' | '&')
+            || (line_start && index == 0 && matches!(c, '#' | '-' | '+'))
+            || (digits > 0 && index == digits && matches!(c, '.' | ')'));
+
+        if special {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{EditHistory, EditKind, Selection};
+
+    fn styled(text: &str, update: impl Fn(&mut RunStyle)) -> RichText {
+        let mut style = RunStyle::default();
+        (update)(&mut style);
+        let mut document = RichText::new();
+        document.push(text, style);
+        document
+    }
+
+    #[test]
+    fn runs_are_merged() {
+        let mut document = RichText::from("Hello");
+        document.push(" world", RunStyle::default());
+        document.push("", RunStyle { bold: true, ..Default::default() });
+        assert_eq!(document.runs().len(), 1);
+
+        document.update_style(0..5, |style| style.bold = true);
+        document.update_style(0..11, |style| style.bold = true);
+        assert_eq!(document.runs().len(), 1);
+        assert!(document.is_styled(0..11, |style| style.bold));
+        assert!(!document.is_styled(3..3, |style| style.bold));
+    }
+
+    #[test]
+    fn replace_and_slice() {
+        let mut document = RichText::from("Hello world");
+        document.replace(6..11, &styled("there", |style| style.italic = true));
+        assert_eq!(document.text(), "Hello there");
+        assert_eq!(document.runs().len(), 2);
+
+        assert_eq!(document.slice(4..8).text(), "o th");
+        assert!(document.slice(6..8).runs()[0].style.italic);
+        assert!(!document.style_at(6).italic);
+        assert!(document.style_at(7).italic);
+        assert!(!document.style_at(0).italic);
+    }
+
+    #[test]
+    fn import_markdown() {
+        let document = RichText::from_markdown(
+            "# Title\n\nSome **bold *and* italic**, <u>underlined</u> and [linked](https://vizia.dev) text.\n\n- One\n- Two",
+        );
+
+        assert_eq!(
+            document.text(),
+            "Title\nSome bold and italic, underlined and linked text.\n\u{2022} One\n\u{2022} Two"
+        );
+
+        assert_eq!(document.slice(0..5).runs()[0].style.font_size, Some(32.0));
+        assert!(document.is_styled(11..15, |style| style.bold && !style.italic));
+        assert!(document.is_styled(16..19, |style| style.bold && style.italic));
+        assert!(document.is_styled(28..38, |style| style.underline));
+        assert!(
+            document.is_styled(43..49, |style| style.link.as_deref() == Some("https://vizia.dev"))
+        );
+    }
+
+    #[test]
+    fn export_markdown() {
+        let mut document = RichText::from("Title\nSome bold and italic text.\n\u{2022} One");
+        document.update_style(0..5, |style| {
+            style.bold = true;
+            style.font_size = Some(24.0);
+        });
+        document.update_style(11..26, |style| style.bold = true);
+        document.update_style(16..26, |style| style.italic = true);
+        document.update_style(27..31, |style| style.underline = true);
+
+        assert_eq!(
+            document.to_markdown(),
+            "## Title\n\nSome **bold *and italic*** <u>text</u>.\n\n- One"
+        );
+        assert_eq!(RichText::from_markdown(&document.to_markdown()), document);
+    }
+
+    #[test]
+    fn markdown_round_trip() {
+        let mut document = RichText::from("Bold then italic, a link and 1. *stars*");
+        document.update_style(0..4, |style| style.bold = true);
+        document.update_style(10..16, |style| style.italic = true);
+        document.update_style(20..24, |style| style.link = Some(String::from("a b.html")));
+
+        let markdown = document.to_markdown();
+        assert_eq!(markdown, "**Bold** then *italic*, a [link](<a b.html>) and 1. \\*stars\\*");
+        assert_eq!(RichText::from_markdown(&markdown), document);
+
+        // Adjacent delimiters are kept apart.
+        let mut document = RichText::from("ab");
+        document.update_style(0..1, |style| style.bold = true);
+        document.update_style(1..2, |style| style.italic = true);
+        assert_eq!(RichText::from_markdown(&document.to_markdown()), document);
+
+        let document = RichText::from("1. Not a list");
+        assert_eq!(document.to_markdown(), "1\\. Not a list");
+        assert_eq!(RichText::from_markdown(&document.to_markdown()), document);
+    }
+
+    #[test]
+    fn undo_restores_styles() {
+        let mut history = EditHistory::default();
+        let mut document = RichText::from("Hello world");
+
+        let mut bold = document.slice(6..11);
+        bold.update_style(0..5, |style| style.bold = true);
+        let selection = Selection::new(6, 11);
+        history.edit(&mut document, 6..11, bold, EditKind::Other, selection, selection);
+        history.edit(
+            &mut document,
+            11..11,
+            "!",
+            EditKind::Insert,
+            Selection::caret(11),
+            Selection::caret(12),
+        );
+        assert_eq!(document.text(), "Hello world!");
+        assert!(document.is_styled(6..11, |style| style.bold));
+
+        history.undo(&mut document);
+        history.undo(&mut document);
+        assert_eq!(document, RichText::from("Hello world"));
+
+        history.redo(&mut document);
+        assert!(document.is_styled(6..11, |style| style.bold));
+    }
+}
@@ -0,0 +1,187 @@
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::prelude::*;
+
+/// A query for finding text, as plain text or as a regular expression.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Data)]
+pub struct SearchQuery {
+    /// The text to find, or the pattern if `regex` is true.
+    pub text: String,
+    /// Whether the text is a regular expression.
+    pub regex: bool,
+    /// Whether letters only match letters of the same case.
+    pub case_sensitive: bool,
+}
+
+impl SearchQuery {
+    /// Creates a new case-insensitive query for some plain text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), regex: false, case_sensitive: false }
+    }
+
+    /// Sets whether the text of the query is a regular expression.
+    pub fn regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+
+    /// Sets whether letters only match letters of the same case.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+}
+
+/// A compiled [`SearchQuery`], which finds and replaces the matches of the query in some text.
+#[derive(Debug, Clone)]
+pub struct TextSearch {
+    regex: Regex,
+    // Whether replacements expand references to capture groups, such as `$1`.
+    expand: bool,
+}
+
+impl TextSearch {
+    /// Compiles a query, returning `None` if the query is empty or is not a valid regular expression.
+    pub fn new(query: &SearchQuery) -> Option<Self> {
+        if query.text.is_empty() {
+            return None;
+        }
+
+        let pattern = if query.regex { query.text.clone() } else { regex::escape(&query.text) };
+        let regex =
+            RegexBuilder::new(&pattern).case_insensitive(!query.case_sensitive).build().ok()?;
+
+        Some(Self { regex, expand: query.regex })
+    }
+
+    /// Returns the byte ranges of the matches in some text, in order. Empty matches are skipped.
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        self.regex.find_iter(text).map(|m| m.range()).filter(|range| !range.is_empty()).collect()
+    }
+
+    /// Returns the text which replaces the match at the given byte range. For a regular expression,
+    /// references to capture groups in the replacement, such as `$1` or `${name}`, are expanded.
+    pub fn replacement(&self, text: &str, range: Range<usize>, replacement: &str) -> String {
+        if !self.expand {
+            return replacement.to_owned();
+        }
+
+        match self.regex.captures_at(text, range.start) {
+            Some(captures) if captures.get(0).map(|m| m.range()) == Some(range) => {
+                let mut expanded = String::new();
+                captures.expand(replacement, &mut expanded);
+                expanded
+            }
+
+            _ => replacement.to_owned(),
+        }
+    }
+
+    /// Replaces every match in some text. Returns the byte range from the start of the first match
+    /// to the end of the last, and the text which replaces it, so that replacing every match can
+    /// be recorded as a single edit. Returns `None` if there are no matches.
+    pub fn replace_all(&self, text: &str, replacement: &str) -> Option<(Range<usize>, String)> {
+        let mut start = None;
+        let mut end = 0;
+        let mut replaced = String::new();
+
+        for captures in self.regex.captures_iter(text) {
+            let Some(range) = captures.get(0).map(|m| m.range()).filter(|r| !r.is_empty()) else {
+                continue;
+            };
+
+            if start.is_some() {
+                replaced.push_str(&text[end..range.start]);
+            } else {
+                start = Some(range.start);
+            }
+
+            if self.expand {
+                captures.expand(replacement, &mut replaced);
+            } else {
+                replaced.push_str(replacement);
+            }
+
+            end = range.end;
+        }
+
+        start.map(|start| (start..end, replaced))
+    }
+}
+
+/// Returns the index of the first match which starts at or after the given offset, or with
+/// `backwards` the index of the last match which starts before it. The search wraps around the
+/// ends of the text, and returns `None` only if there are no matches.
+pub fn find_match(matches: &[Range<usize>], offset: usize, backwards: bool) -> Option<usize> {
+    if matches.is_empty() {
+        return None;
+    }
+
+    let index = matches.partition_point(|range| range.start < offset);
+    Some(if backwards {
+        index.checked_sub(1).unwrap_or(matches.len() - 1)
+    } else if index < matches.len() {
+        index
+    } else {
+        0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text() {
+        let search = TextSearch::new(&SearchQuery::new("a.b")).unwrap();
+        assert_eq!(search.find_all("A.B axb a.b"), vec![0..3, 8..11]);
+
+        let search = TextSearch::new(&SearchQuery::new("a.b").case_sensitive(true)).unwrap();
+        assert_eq!(search.find_all("A.B axb a.b"), vec![8..11]);
+
+        assert!(TextSearch::new(&SearchQuery::new("")).is_none());
+    }
+
+    #[test]
+    fn regex() {
+        let search = TextSearch::new(&SearchQuery::new(r"(\w+)@(\w+)").regex(true)).unwrap();
+        assert_eq!(search.find_all("mail me@host or you@there"), vec![5..12, 16..25]);
+        assert_eq!(search.replacement("me@host", 0..7, "$2:$1"), "host:me");
+
+        // Matches of an empty string are skipped.
+        let search = TextSearch::new(&SearchQuery::new("x*").regex(true)).unwrap();
+        assert_eq!(search.find_all("axxb"), vec![1..3]);
+
+        assert!(TextSearch::new(&SearchQuery::new("(").regex(true)).is_none());
+    }
+
+    #[test]
+    fn replace_all() {
+        let search = TextSearch::new(&SearchQuery::new("cat")).unwrap();
+        assert_eq!(
+            search.replace_all("a cat, a CAT, a dog", "$1dog"),
+            Some((2..12, String::from("$1dog, a $1dog")))
+        );
+        assert_eq!(search.replace_all("a dog", "cat"), None);
+
+        let search = TextSearch::new(&SearchQuery::new(r"(\d+)px").regex(true)).unwrap();
+        assert_eq!(
+            search.replace_all("width: 10px; height: 20px;", "${1}em"),
+            Some((7..25, String::from("10em; height: 20em")))
+        );
+    }
+
+    #[test]
+    fn stepping_through_matches() {
+        let matches = [2..4, 6..8, 10..12];
+        assert_eq!(find_match(&matches, 0, false), Some(0));
+        assert_eq!(find_match(&matches, 4, false), Some(1));
+        assert_eq!(find_match(&matches, 6, false), Some(1));
+        assert_eq!(find_match(&matches, 12, false), Some(0));
+        assert_eq!(find_match(&matches, 6, true), Some(0));
+        assert_eq!(find_match(&matches, 2, true), Some(2));
+        assert_eq!(find_match(&[], 2, true), None);
+    }
+}
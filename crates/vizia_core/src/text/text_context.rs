@@ -2,6 +2,7 @@ use skia_safe::textlayout::{Paragraph, TypefaceFontProvider};
 use skia_safe::{textlayout::FontCollection, FontMgr};
 use vizia_storage::SparseSet;
 
+use super::CaretStops;
 use crate::{entity::Entity, layout::BoundingBox};
 
 pub struct TextContext {
@@ -10,6 +11,7 @@ pub struct TextContext {
     pub asset_provider: TypefaceFontProvider,
     pub text_bounds: SparseSet<BoundingBox>,
     pub text_paragraphs: SparseSet<Paragraph>,
+    pub(crate) caret_stops: SparseSet<CaretStops>,
 }
 
 impl TextContext {
@@ -21,4 +23,18 @@ impl TextContext {
     pub(crate) fn set_text_bounds(&mut self, entity: Entity, bounds: BoundingBox) {
         self.text_bounds.insert(entity, bounds);
     }
+
+    /// Returns the paragraph of an entity along with its caret stops, which are reset whenever the
+    /// paragraph is built or laid out again.
+    pub(crate) fn paragraph_with_caret_stops(
+        &mut self,
+        entity: Entity,
+    ) -> Option<(&Paragraph, &mut CaretStops)> {
+        let paragraph = self.text_paragraphs.get(entity)?;
+        if !self.caret_stops.contains(entity) {
+            self.caret_stops.insert(entity, CaretStops::default());
+        }
+
+        Some((paragraph, self.caret_stops.get_mut(entity)?))
+    }
 }
@@ -0,0 +1,19 @@
+use std::cell::Cell;
+use web_time::Instant;
+
+thread_local! {
+    static VIRTUAL_TIME: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Returns the current time as seen by vizia timers, scheduled events and animations.
+///
+/// This is the system time unless a windowing backend has installed a virtual clock, such as the
+/// headless backend used for testing.
+pub fn now() -> Instant {
+    VIRTUAL_TIME.with(|time| time.get()).unwrap_or_else(Instant::now)
+}
+
+/// Overrides the time returned by [`now`] on the current thread, or restores the system time if `None`.
+pub(crate) fn set_virtual_time(time: Option<Instant>) {
+    VIRTUAL_TIME.with(|virtual_time| virtual_time.set(time));
+}
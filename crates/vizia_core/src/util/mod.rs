@@ -1,8 +1,17 @@
 use std::path::{Path, PathBuf};
 
+mod clock;
+pub use clock::now;
+pub(crate) use clock::set_virtual_time;
+
 // Helper trait for getting CSS from a string or path.
 pub trait IntoCssStr: 'static {
     fn get_style(&self) -> Result<String, std::io::Error>;
+
+    // The file the CSS was loaded from, used to resolve relative urls within the stylesheet.
+    fn path(&self) -> Option<&Path> {
+        None
+    }
 }
 
 impl IntoCssStr for CSS {
@@ -13,6 +22,14 @@ impl IntoCssStr for CSS {
             CSS::String(style_string) => Ok(style_string.to_owned()),
         }
     }
+
+    fn path(&self) -> Option<&Path> {
+        match self {
+            CSS::Path(path) => Some(path),
+
+            CSS::String(_) => None,
+        }
+    }
 }
 
 impl IntoCssStr for &'static str {
@@ -25,12 +42,20 @@ impl IntoCssStr for PathBuf {
     fn get_style(&self) -> Result<String, std::io::Error> {
         std::fs::read_to_string(self)
     }
+
+    fn path(&self) -> Option<&Path> {
+        Some(self)
+    }
 }
 
 impl IntoCssStr for Path {
     fn get_style(&self) -> Result<String, std::io::Error> {
         std::fs::read_to_string(self)
     }
+
+    fn path(&self) -> Option<&Path> {
+        Some(self)
+    }
 }
 
 #[doc(hidden)]
@@ -0,0 +1,320 @@
+use crate::icons::{ICON_CHEVRON_DOWN, ICON_CHEVRON_UP, ICON_X};
+use crate::prelude::*;
+
+/// Events sent to a text view to search its text, usually by a [`FindBar`].
+///
+/// The [`Textbox`], [`TextEditor`] and [`Markdown`] views highlight the matches of the search
+/// and select the current match, scrolling it into view. After each search they report the
+/// matches back up the tree with a [`FindBarEvent::SetMatches`].
+pub enum SearchEvent {
+    /// Finds the matches of a query, and selects the first match at or after the selection.
+    Search(SearchQuery),
+    /// Selects the next match, wrapping around to the first.
+    Next,
+    /// Selects the previous match, wrapping around to the last.
+    Previous,
+    /// Replaces the selected match with the given text and selects the next match. The text can
+    /// refer to the capture groups of a regular expression, such as `$1`.
+    Replace(String),
+    /// Replaces every match with the given text as a single undoable edit.
+    ReplaceAll(String),
+    /// Removes the matches of the search.
+    Clear,
+}
+
+/// Events for controlling a [`FindBar`].
+pub enum FindBarEvent {
+    /// Opens the find bar to search the text view which sent the event, which can also replace
+    /// text if `replace` is true. Sent by a text view within the find bar when Ctrl+F is pressed.
+    Open { replace: bool },
+    /// Closes the find bar and returns the focus to the text view it searched.
+    Close,
+    /// Sets the text to find.
+    SetQuery(String),
+    /// Sets the text which replaces the matches.
+    SetReplacement(String),
+    /// Toggles whether the text to find is a regular expression.
+    ToggleRegex,
+    /// Toggles whether the search is case sensitive.
+    ToggleCaseSensitive,
+    /// Selects the next match.
+    Next,
+    /// Selects the previous match.
+    Previous,
+    /// Replaces the selected match.
+    Replace,
+    /// Replaces every match.
+    ReplaceAll,
+    /// Sets the index of the selected match, if any, and the number of matches. Sent by the text
+    /// view being searched.
+    SetMatches(Option<usize>, usize),
+}
+
+/// A container which shows a bar for finding, and replacing, text in the text views within it.
+///
+/// Pressing Ctrl+F (Cmd+F on macOS) in a [`Textbox`], [`TextEditor`] or [`Markdown`] view within
+/// the find bar opens the bar above its content to search that view. Enter and Shift+Enter step
+/// through the matches, and Escape closes the bar. For a text view which can be edited the bar
+/// also offers to replace the selected match or every match.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     text: String,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { text: String::from("Hello World") }.build(cx);
+/// #
+/// FindBar::new(cx, |cx| {
+///     Textbox::new_multiline(cx, AppData::text, true);
+/// });
+/// ```
+#[derive(Lens)]
+pub struct FindBar {
+    open: bool,
+    query: String,
+    replacement: String,
+    regex: bool,
+    case_sensitive: bool,
+    can_replace: bool,
+    // Whether the query can be searched for, which is updated when the query or its options change.
+    valid: bool,
+    // The index of the selected match, if any, and the number of matches.
+    matches: (Option<usize>, usize),
+    // The text view being searched.
+    #[lens(ignore)]
+    target: Entity,
+    #[lens(ignore)]
+    controls: Entity,
+    #[lens(ignore)]
+    query_box: Entity,
+}
+
+impl FindBar {
+    /// Creates a new find bar for the text views built by the given content.
+    pub fn new(cx: &mut Context, content: impl FnOnce(&mut Context)) -> Handle<Self> {
+        let mut controls = Entity::null();
+        let mut query_box = Entity::null();
+
+        Self {
+            open: false,
+            query: String::new(),
+            replacement: String::new(),
+            regex: false,
+            case_sensitive: false,
+            can_replace: false,
+            valid: true,
+            matches: (None, 0),
+            target: Entity::null(),
+            controls: Entity::null(),
+            query_box: Entity::null(),
+        }
+        .build(cx, |cx| {
+            controls = VStack::new(cx, |cx| {
+                HStack::new(cx, |cx| {
+                    query_box = Textbox::new(cx, Self::query)
+                        .placeholder("Find")
+                        .on_edit(|cx, text| cx.emit(FindBarEvent::SetQuery(text)))
+                        .on_submit(|cx, _, enter| {
+                            if enter {
+                                cx.emit(if cx.modifiers.shift() {
+                                    FindBarEvent::Previous
+                                } else {
+                                    FindBarEvent::Next
+                                });
+                            }
+                        })
+                        .on_cancel(|cx| cx.emit(FindBarEvent::Close))
+                        .toggle_class("invalid", Self::valid.map(|valid| !valid))
+                        .class("find-query")
+                        .entity();
+
+                    ToggleButton::new(cx, Self::case_sensitive, |cx| Label::new(cx, "Aa"))
+                        .on_toggle(|cx| cx.emit(FindBarEvent::ToggleCaseSensitive))
+                        .name("Match case");
+
+                    ToggleButton::new(cx, Self::regex, |cx| Label::new(cx, ".*"))
+                        .on_toggle(|cx| cx.emit(FindBarEvent::ToggleRegex))
+                        .name("Regular expression");
+
+                    Label::new(cx, Self::root.map(|bar| match_count(&bar.query, bar.matches)))
+                        .class("match-count");
+
+                    Button::new(cx, |cx| Svg::new(cx, ICON_CHEVRON_UP))
+                        .on_press(|cx| cx.emit(FindBarEvent::Previous))
+                        .name("Previous match");
+
+                    Button::new(cx, |cx| Svg::new(cx, ICON_CHEVRON_DOWN))
+                        .on_press(|cx| cx.emit(FindBarEvent::Next))
+                        .name("Next match");
+
+                    Button::new(cx, |cx| Svg::new(cx, ICON_X))
+                        .on_press(|cx| cx.emit(FindBarEvent::Close))
+                        .name("Close");
+                })
+                .class("find-row");
+
+                HStack::new(cx, |cx| {
+                    Textbox::new(cx, Self::replacement)
+                        .placeholder("Replace")
+                        .on_edit(|cx, text| cx.emit(FindBarEvent::SetReplacement(text)))
+                        .on_submit(|cx, _, enter| {
+                            if enter {
+                                cx.emit(FindBarEvent::Replace);
+                            }
+                        })
+                        .on_cancel(|cx| cx.emit(FindBarEvent::Close))
+                        .class("find-replacement");
+
+                    Button::new(cx, |cx| Label::new(cx, "Replace"))
+                        .on_press(|cx| cx.emit(FindBarEvent::Replace));
+
+                    Button::new(cx, |cx| Label::new(cx, "Replace all"))
+                        .on_press(|cx| cx.emit(FindBarEvent::ReplaceAll));
+                })
+                .class("replace-row")
+                .display(Self::can_replace);
+            })
+            .class("find-bar-controls")
+            .display(Self::open)
+            .entity();
+
+            content(cx);
+        })
+        .modify(|bar| {
+            bar.controls = controls;
+            bar.query_box = query_box;
+        })
+    }
+
+    fn search_query(&self) -> SearchQuery {
+        SearchQuery::new(self.query.clone()).regex(self.regex).case_sensitive(self.case_sensitive)
+    }
+
+    // Checks whether the query is valid and searches the target for it. An empty query is valid so
+    // that it is not shown as an error while nothing has been typed.
+    fn search(&mut self, cx: &mut EventContext) {
+        let query = self.search_query();
+        self.valid = self.query.is_empty() || TextSearch::new(&query).is_some();
+
+        if self.target != Entity::null() {
+            cx.emit_to(self.target, SearchEvent::Search(query));
+        }
+    }
+}
+
+impl View for FindBar {
+    fn element(&self) -> Option<&'static str> {
+        Some("find-bar")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|find_bar_event, meta| {
+            match find_bar_event {
+                FindBarEvent::Open { replace } => {
+                    // The text boxes of the find bar itself are not searched.
+                    if !meta.origin.is_descendant_of(cx.tree, self.controls) {
+                        self.target = meta.origin;
+                        self.can_replace = *replace;
+                        self.open = true;
+                        self.search(cx);
+                    }
+
+                    cx.emit_to(self.query_box, TextEvent::StartEdit);
+                }
+
+                FindBarEvent::Close => {
+                    if self.open {
+                        self.open = false;
+                        self.matches = (None, 0);
+                        if self.target != Entity::null() {
+                            // The target is focused before the search is cleared so that it keeps
+                            // the selected match.
+                            cx.with_current(self.target, |cx| cx.focus());
+                            cx.emit_to(self.target, SearchEvent::Clear);
+                        }
+                    }
+                }
+
+                FindBarEvent::SetQuery(query) => {
+                    self.query.clone_from(query);
+                    self.search(cx);
+                }
+
+                FindBarEvent::SetReplacement(replacement) => {
+                    self.replacement.clone_from(replacement);
+                }
+
+                FindBarEvent::ToggleRegex => {
+                    self.regex ^= true;
+                    self.search(cx);
+                }
+
+                FindBarEvent::ToggleCaseSensitive => {
+                    self.case_sensitive ^= true;
+                    self.search(cx);
+                }
+
+                FindBarEvent::Next | FindBarEvent::Previous => {
+                    if self.target != Entity::null() {
+                        let event = if matches!(find_bar_event, FindBarEvent::Next) {
+                            SearchEvent::Next
+                        } else {
+                            SearchEvent::Previous
+                        };
+                        cx.emit_to(self.target, event);
+                    }
+                }
+
+                FindBarEvent::Replace | FindBarEvent::ReplaceAll => {
+                    if self.can_replace && self.target != Entity::null() {
+                        let replacement = self.replacement.clone();
+                        let event = if matches!(find_bar_event, FindBarEvent::Replace) {
+                            SearchEvent::Replace(replacement)
+                        } else {
+                            SearchEvent::ReplaceAll(replacement)
+                        };
+                        cx.emit_to(self.target, event);
+                    }
+                }
+
+                FindBarEvent::SetMatches(current, total) => {
+                    self.matches = (*current, *total);
+                }
+            }
+
+            // Find bars within find bars only search their own content.
+            meta.consume();
+        });
+    }
+}
+
+// Returns the text describing the matches of a query, such as "2 of 5".
+fn match_count(query: &str, (current, total): (Option<usize>, usize)) -> String {
+    match (current, total) {
+        _ if query.is_empty() => String::new(),
+        (_, 0) => String::from("No results"),
+        (Some(current), total) => format!("{} of {}", current + 1, total),
+        (None, total) => format!("{} results", total),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_counts() {
+        assert_eq!(match_count("", (None, 3)), "");
+        assert_eq!(match_count("a", (None, 0)), "No results");
+        assert_eq!(match_count("a", (Some(1), 3)), "2 of 3");
+        assert_eq!(match_count("a", (None, 3)), "3 results");
+    }
+}
@@ -2,7 +2,20 @@ use sha2::{Digest, Sha256};
 use vizia_style::Url;
 
 use crate::prelude::*;
+use crate::resource::ImagePlayback;
 
+/// A view which displays an image, loaded by name through the image loader of the application.
+///
+/// Animated images, such as GIF, animated WebP and APNG files, play while the image is visible.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// Image::new(cx, "spinner.gif").looping(true);
+/// ```
 pub struct Image {}
 
 impl Image {
@@ -13,6 +26,34 @@ impl Image {
     }
 }
 
+impl Handle<'_, Image> {
+    /// Sets whether an animated image is playing. A paused animation stays on its current frame,
+    /// and resumes from it. Animated images play by default.
+    pub fn playing(self, playing: impl Res<bool>) -> Self {
+        self.bind(playing, |mut handle, playing| {
+            let playing = playing.get(&handle);
+            set_playback(&mut handle, |playback| playback.playing = playing);
+        })
+    }
+
+    /// Sets whether an animated image repeats forever, or plays once and stops on its last frame.
+    /// By default the animation repeats as many times as the image file says.
+    pub fn looping(self, looping: impl Res<bool>) -> Self {
+        self.bind(looping, |mut handle, looping| {
+            let looping = looping.get(&handle);
+            set_playback(&mut handle, |playback| playback.looping = Some(looping));
+        })
+    }
+}
+
+// Changes the playback state of the animated images shown by a view.
+fn set_playback(handle: &mut Handle<Image>, f: impl FnOnce(&mut ImagePlayback)) {
+    let entity = handle.entity();
+    let cx = handle.context();
+    f(cx.resource_manager.image_playback.entry(entity).or_default());
+    cx.needs_redraw(entity);
+}
+
 impl View for Image {
     fn element(&self) -> Option<&'static str> {
         Some("image")
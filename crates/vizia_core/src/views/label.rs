@@ -1,5 +1,7 @@
 use crate::prelude::*;
 
+use crate::text::highlighted_spans;
+
 /// A label used to display text.
 ///
 /// # Examples
@@ -122,6 +124,52 @@ impl Label {
             .role(Role::Label)
             .name(text)
     }
+
+    /// Creates a new label which displays some text with syntax highlighting. Each highlighted
+    /// part of the text is displayed with the `token` class and the class given by the
+    /// [`Highlighter`], e.g. `.token.string`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// # #[derive(Lens)]
+    /// # struct AppData {
+    /// #     config: String,
+    /// # }
+    /// #
+    /// # impl Model for AppData {}
+    /// #
+    /// # AppData { config: String::from("{\"size\": 12}") }.build(cx);
+    /// #
+    /// Label::highlighted(cx, AppData::config, ConfigHighlighter::Json);
+    /// ```
+    pub fn highlighted<L>(
+        cx: &mut Context,
+        lens: L,
+        highlighter: impl Highlighter + 'static,
+    ) -> Handle<Self>
+    where
+        L: Lens<Target = String>,
+    {
+        Self { describing: None }
+            .build(cx, move |cx| {
+                // The text is displayed by a text span for each highlighted part.
+                let label = cx.current();
+                Binding::new(cx, lens, move |cx, lens| {
+                    let text = lens.get(cx);
+                    let spans = highlighter.highlight(&text);
+                    highlighted_spans(cx, &text, &spans);
+                    cx.style.needs_text_update(label);
+                });
+            })
+            .text("")
+            .role(Role::Label)
+            .name(lens)
+    }
 }
 
 impl Handle<'_, Label> {
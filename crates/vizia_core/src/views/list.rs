@@ -12,6 +12,87 @@ pub enum Selectable {
 
 impl_res_simple!(Selectable);
 
+// The selected items of a view which shows a list of items, such as a table or tree view, by a key
+// which identifies each item.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ItemSelection<K> {
+    // The selected items, in the order they were selected.
+    pub selected: Vec<K>,
+    // The item which a selection is extended from.
+    pub anchor: Option<K>,
+}
+
+impl<K> Default for ItemSelection<K> {
+    fn default() -> Self {
+        Self { selected: Vec::new(), anchor: None }
+    }
+}
+
+impl<K: Clone + PartialEq> ItemSelection<K> {
+    pub fn contains(&self, item: &K) -> bool {
+        self.selected.contains(item)
+    }
+
+    // Selects an item, either toggling whether it is selected or extending the selection to it
+    // from the anchor over the items in the order they are shown, if more than one item can be
+    // selected. Returns false if items can't be selected.
+    pub fn select(
+        &mut self,
+        selectable: Selectable,
+        items: &[K],
+        item: &K,
+        toggle: bool,
+        extend: bool,
+    ) -> bool {
+        match selectable {
+            Selectable::None => return false,
+
+            Selectable::Single => {
+                self.selected = vec![item.clone()];
+                self.anchor = Some(item.clone());
+            }
+
+            Selectable::Multi => {
+                let position = |item: &K| items.iter().position(|other| other == item);
+
+                match self.anchor.as_ref().and_then(position).zip(position(item)) {
+                    Some((anchor, position)) if extend => {
+                        self.selected = items[anchor.min(position)..=anchor.max(position)].to_vec();
+                    }
+
+                    _ => {
+                        if !toggle {
+                            self.selected.clear();
+                        }
+
+                        if let Some(index) = self.selected.iter().position(|other| other == item) {
+                            self.selected.remove(index);
+                        } else {
+                            self.selected.push(item.clone());
+                        }
+
+                        self.anchor = Some(item.clone());
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    // Replaces the selected items, which clears the anchor.
+    pub fn set(&mut self, selected: Vec<K>) {
+        self.selected = selected;
+        self.anchor = None;
+    }
+
+    // Keeps only the selected items, and the anchor, for which the predicate returns true.
+    pub fn retain(&mut self, f: impl Fn(&K) -> bool) {
+        self.selected.retain(&f);
+        self.anchor = self.anchor.take().filter(&f);
+    }
+}
+
 pub enum ListEvent {
     Select(usize),
     SelectFocused,
@@ -111,3 +192,36 @@ impl View for List {
         Some("list")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_items() {
+        let items = [3, 1, 4, 5];
+        let mut selection = ItemSelection::default();
+
+        assert!(!selection.select(Selectable::None, &items, &1, false, false));
+        assert!(selection.selected.is_empty());
+
+        // Extending the selection selects the items from the anchor to the item, as they are shown.
+        selection.select(Selectable::Multi, &items, &1, false, false);
+        selection.select(Selectable::Multi, &items, &5, false, true);
+        assert_eq!(selection.selected, [1, 4, 5]);
+        assert_eq!(selection.anchor, Some(1));
+
+        selection.select(Selectable::Multi, &items, &4, true, false);
+        assert_eq!(selection.selected, [1, 5]);
+        selection.select(Selectable::Multi, &items, &3, true, false);
+        assert_eq!(selection.selected, [1, 5, 3]);
+        assert_eq!(selection.anchor, Some(3));
+
+        selection.select(Selectable::Single, &items, &4, true, true);
+        assert_eq!(selection.selected, [4]);
+
+        selection.retain(|item| *item != 4);
+        assert!(selection.selected.is_empty());
+        assert_eq!(selection.anchor, None);
+    }
+}
@@ -1,30 +1,282 @@
-use std::cell::RefCell;
+use std::ops::Range;
 
-use comrak::nodes::{Ast, NodeValue};
+use comrak::nodes::{AstNode, NodeFootnoteDefinition, NodeFootnoteReference, NodeValue};
 use comrak::{parse_document, Arena, Options};
+use hashbrown::HashMap;
+use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle};
+use skia_safe::{Paint, PaintStyle, Rect};
 
+use super::textbox::{child_space, match_color, text_bounds, text_offset};
 use crate::prelude::*;
+use crate::systems::paragraph_text;
+use crate::text::{highlighted_spans, EditableText};
 
-pub struct Markdown {}
+/// Events for controlling a [`Markdown`] view.
+pub enum MarkdownEvent {
+    /// Scrolls the heading or footnote with the given anchor to the top of the nearest scroll
+    /// view. Sent by links to an anchor of the document, such as `[Usage](#usage)`.
+    GoToAnchor(String),
+    /// Toggles the task list item on the given line of the document, counting from zero, which
+    /// is `checked` in the document. Sent by the checkbox of the item.
+    ToggleTask { line: usize, checked: bool },
+}
+
+/// A view which displays a Markdown document.
+///
+/// Besides CommonMark, the document can contain tables, task lists, strikethrough and footnotes.
+/// Images are loaded through the image loader of the application, which loads them from files by
+/// default, see [`Context::set_image_loader`]. Each heading has an anchor, like on GitHub, which
+/// links such as `[Usage](#usage)` scroll to, and each footnote has the anchor `fn-` followed by
+/// its name.
+///
+/// Pressing Ctrl+F (Cmd+F on macOS) in the document, within a [`FindBar`], searches the text of
+/// its paragraphs, headings, list items and code blocks.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     notes: String,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { notes: String::from("- [ ] Write the notes") }.build(cx);
+/// #
+/// Markdown::with_lens(cx, AppData::notes)
+///     .on_toggle_task(|_, line, checked| println!("Task on line {} is {}", line, checked));
+/// ```
+#[derive(Lens)]
+pub struct Markdown {
+    document: String,
+    // The top-level blocks of the document, each of which is built again when it changes.
+    #[lens(ignore)]
+    blocks: Vec<MarkdownNode>,
+    // The line each top-level block starts on, counting from zero.
+    #[lens(ignore)]
+    block_lines: Vec<usize>,
+    // The task list items which have been toggled since the document last changed, by line.
+    #[lens(ignore)]
+    tasks: HashMap<usize, bool>,
+    #[lens(ignore)]
+    on_toggle_task: Option<Box<dyn Fn(&mut EventContext, usize, bool) + Send + Sync>>,
+    #[lens(ignore)]
+    search: Option<TextSearch>,
+    // The paragraphs which match the search, and the range of graphemes of each match.
+    #[lens(ignore)]
+    matches: Vec<(Entity, Range<usize>)>,
+    #[lens(ignore)]
+    current_match: Option<usize>,
+}
 
 impl Markdown {
+    /// Creates a new markdown view which displays the given document.
     pub fn new<'a>(cx: &'a mut Context, document: &str) -> Handle<'a, Self> {
-        Self {}
-            .build(cx, |cx| {
-                // The returned nodes are created in the supplied Arena, and are bound by its lifetime.
-                let arena = Arena::new();
+        let document = document.to_owned();
+        Self::build_blocks(cx).modify(|markdown| markdown.set_document(document))
+    }
+
+    /// Creates a new markdown view which displays the document of the given lens. When the
+    /// document changes, only the blocks of the document whose source changed are built again.
+    pub fn with_lens<L>(cx: &mut Context, lens: L) -> Handle<Self>
+    where
+        L: Lens<Target = String>,
+    {
+        Self::build_blocks(cx).bind(lens, |handle, lens| {
+            let document = lens.get(&handle);
+            handle.modify(|markdown| markdown.set_document(document));
+        })
+    }
+
+    fn build_blocks(cx: &mut Context) -> Handle<Self> {
+        Self {
+            document: String::new(),
+            blocks: Vec::new(),
+            block_lines: Vec::new(),
+            tasks: HashMap::new(),
+            on_toggle_task: None,
+            search: None,
+            matches: Vec::new(),
+            current_match: None,
+        }
+        .build(cx, |cx| {
+            // A binding is built for each top-level block, which builds the views of the block
+            // again when it changes.
+            Binding::new(cx, Self::root.map(|markdown| markdown.blocks.len()), |cx, num_blocks| {
+                for index in 0..num_blocks.get(cx) {
+                    let block = Self::root.map(move |markdown| markdown.blocks.get(index).cloned());
+                    Binding::new(cx, block, move |cx, block| {
+                        if let Some(block) = block.get(cx) {
+                            parse_node(cx, &block, index, 0);
+                        }
+                    });
+                }
+            });
+        })
+        .height(Auto)
+    }
+
+    fn set_document(&mut self, document: String) {
+        if document != self.document {
+            (self.blocks, self.block_lines) = parse_blocks(&document);
+            self.document = document;
+            self.tasks.clear();
+        }
+    }
+
+    // Scrolls the part of the document from an anchor to its end into view, which brings the
+    // anchor to the top of the scroll view unless the document ends first.
+    fn go_to_anchor(&self, cx: &mut EventContext, name: &str) {
+        let anchor = cx.current.branch_iter(cx.tree).find(|entity| {
+            cx.views
+                .get(entity)
+                .and_then(|view| view.downcast_ref::<Anchor>())
+                .is_some_and(|anchor| anchor.name == name)
+        });
+
+        if let Some(anchor) = anchor {
+            let bounds = cx.cache.get_bounds(anchor);
+            let rect = BoundingBox::from_min_max(
+                bounds.left(),
+                bounds.top(),
+                bounds.right(),
+                cx.bounds().bottom(),
+            );
+            cx.emit(ScrollEvent::ScrollIntoView(rect));
+        }
+    }
+
+    fn update_matches(&mut self, cx: &mut EventContext) {
+        self.matches.clear();
 
-                let mut options = Options::default();
-                options.extension.strikethrough = true;
+        let Some(search) = &self.search else {
+            return;
+        };
 
-                // Parse the document into a root `AstNode`
-                let root = parse_document(&arena, document, &options);
+        for entity in cx.current.branch_iter(cx.tree) {
+            // The text of text spans is searched as part of the paragraph which contains them.
+            if entity == cx.current
+                || cx.style.text_span.get(entity).copied().unwrap_or_default()
+                || cx.text_context.text_paragraphs.get(entity).is_none()
+            {
+                continue;
+            }
+
+            let text = paragraph_text(cx.style, cx.tree, entity);
+            for range in search.find_all(&text) {
+                let start = text.current_grapheme_offset(range.start);
+                let end = text.current_grapheme_offset(range.end);
+                self.matches.push((entity, start..end));
+            }
+        }
+    }
 
-                for node in root.children() {
-                    parse_node(cx, node, 0);
+    fn select_match(&mut self, cx: &mut EventContext, index: Option<usize>) {
+        self.current_match = index.filter(|index| *index < self.matches.len());
+        self.scroll_to_match(cx);
+        cx.emit(FindBarEvent::SetMatches(self.current_match, self.matches.len()));
+        cx.needs_redraw();
+    }
+
+    // Scrolls the selected match into view, within its code block and within the nearest scroll
+    // view containing the document.
+    fn scroll_to_match(&self, cx: &mut EventContext) {
+        let Some((entity, graphemes)) = self.current_match.map(|index| self.matches[index].clone())
+        else {
+            return;
+        };
+
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(entity) else {
+            return;
+        };
+
+        let rects =
+            paragraph.get_rects_for_range(graphemes, RectHeightStyle::Tight, RectWidthStyle::Tight);
+
+        let bounds = cx.cache.get_bounds(entity);
+        let (padding_left, padding_top) =
+            text_offset(bounds, paragraph, child_space(cx.style, entity), cx.style.is_rtl(entity));
+
+        let Some(rect) = text_bounds(&rects) else {
+            return;
+        };
+
+        let rect = rect.offset(bounds.x + padding_left, bounds.y + padding_top);
+
+        let code_block = entity.parent_iter(cx.tree).take_while(|e| *e != cx.current).find(|e| {
+            cx.views.get(e).is_some_and(|view| view.downcast_ref::<ScrollView>().is_some())
+        });
+
+        if let Some(code_block) = code_block {
+            cx.emit_to(code_block, ScrollEvent::ScrollIntoView(rect));
+        }
+
+        cx.emit(ScrollEvent::ScrollIntoView(rect));
+    }
+
+    fn draw_matches(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+
+        for (index, (entity, graphemes)) in self.matches.iter().enumerate() {
+            let Some(paragraph) = cx.text_context.text_paragraphs.get(*entity) else {
+                continue;
+            };
+
+            let bounds = cx.cache.get_bounds(*entity);
+            let (padding_left, padding_top) = text_offset(
+                bounds,
+                paragraph,
+                child_space(cx.style, *entity),
+                cx.style.is_rtl(*entity),
+            );
+
+            // A match is clipped by the views around it which hide their overflow, such as the
+            // scroll view of a code block.
+            let clip = entity
+                .parent_iter(cx.tree)
+                .skip(1)
+                .take_while(|e| *e != cx.current)
+                .filter(|e| cx.style.overflowx.get(*e) == Some(&Overflow::Hidden))
+                .fold(cx.bounds(), |clip, e| clip.intersection(&cx.cache.get_bounds(e)));
+
+            paint.set_color(match_color(cx, self.current_match == Some(index)));
+
+            let rects = paragraph.get_rects_for_range(
+                graphemes.clone(),
+                RectHeightStyle::Tight,
+                RectWidthStyle::Tight,
+            );
+
+            for text_box in rects {
+                let rect = text_box.rect;
+                let rect = BoundingBox::from_min_max(rect.left, rect.top, rect.right, rect.bottom)
+                    .offset(bounds.x + padding_left, bounds.y + padding_top)
+                    .intersection(&clip);
+
+                if rect.w > 0.0 && rect.h > 0.0 {
+                    canvas.draw_rect(Rect::from(rect), &paint);
                 }
-            })
-            .height(Auto)
+            }
+        }
+    }
+}
+
+impl Handle<'_, Markdown> {
+    /// Sets the callback triggered when the checkbox of a task list item is toggled.
+    ///
+    /// Callback provides the line of the item in the document, counting from zero, and whether
+    /// the item is now checked.
+    pub fn on_toggle_task<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, usize, bool) + Send + Sync,
+    {
+        self.modify(|markdown| markdown.on_toggle_task = Some(Box::new(callback)))
     }
 }
 
@@ -32,37 +284,303 @@ impl View for Markdown {
     fn element(&self) -> Option<&'static str> {
         Some("markdown")
     }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, _| match window_event {
+            // The document takes the focus when pressed, so that it can be searched with Ctrl+F.
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                cx.focus_with_visibility(false);
+            }
+
+            WindowEvent::KeyDown(Code::KeyF, _) => {
+                if cx.modifiers == &Modifiers::COMMAND {
+                    cx.emit(FindBarEvent::Open { replace: false });
+                }
+            }
+
+            _ => {}
+        });
+
+        event.map(|markdown_event, meta| {
+            match markdown_event {
+                MarkdownEvent::GoToAnchor(name) => self.go_to_anchor(cx, name),
+
+                MarkdownEvent::ToggleTask { line, checked } => {
+                    let checked = !self.tasks.get(line).copied().unwrap_or(*checked);
+                    self.tasks.insert(*line, checked);
+                    if let Some(callback) = &self.on_toggle_task {
+                        (callback)(cx, *line, checked);
+                    }
+                }
+            }
+
+            meta.consume();
+        });
+
+        // Search Events
+        event.map(|search_event, _| match search_event {
+            SearchEvent::Search(query) => {
+                self.search = TextSearch::new(query);
+                self.update_matches(cx);
+                self.select_match(cx, Some(0));
+            }
+
+            SearchEvent::Next => {
+                let next = self.current_match.map_or(0, |index| index + 1);
+                self.select_match(cx, Some(if next < self.matches.len() { next } else { 0 }));
+            }
+
+            SearchEvent::Previous => {
+                let previous = self
+                    .current_match
+                    .and_then(|index| index.checked_sub(1))
+                    .or(self.matches.len().checked_sub(1));
+                self.select_match(cx, previous);
+            }
+
+            // The document is read only.
+            SearchEvent::Replace(_) | SearchEvent::ReplaceAll(_) => {}
+
+            SearchEvent::Clear => {
+                self.search = None;
+                self.matches.clear();
+                self.current_match = None;
+                cx.needs_redraw();
+            }
+        });
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        cx.draw_background(canvas);
+        cx.draw_shadows(canvas);
+        cx.draw_border(canvas);
+        cx.draw_outline(canvas);
+        // The matches are drawn beneath the text of the paragraphs, which are drawn after.
+        self.draw_matches(cx, canvas);
+    }
 }
 
-fn parse_node<'a>(
-    cx: &mut Context,
-    node: &'a comrak::arena_tree::Node<'a, RefCell<Ast>>,
-    list_level: usize,
-) {
-    match &node.data.borrow().value {
-        NodeValue::Paragraph => {
-            Label::rich(cx, "", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
+// The parts of the views of a document which depend on the whole document rather than on the
+// block they are in: the anchor of each heading, by the line it starts on, and the number of each
+// footnote, by its name.
+#[derive(Default)]
+struct DocumentLinks {
+    anchors: HashMap<usize, String>,
+    footnotes: HashMap<String, usize>,
+}
+
+impl DocumentLinks {
+    fn new<'a>(root: &'a AstNode<'a>) -> Self {
+        let mut links = Self::default();
+        let mut slugs = HashMap::new();
+
+        for node in root.descendants() {
+            let ast = node.data.borrow();
+            match &ast.value {
+                NodeValue::Heading(_) => {
+                    let anchor = unique_anchor(&mut slugs, anchor_slug(&ast_text(node)));
+                    links.anchors.insert(ast.sourcepos.start.line, anchor);
                 }
-            })
-            .class("p");
+
+                // Footnotes are numbered in the order they are first referenced.
+                NodeValue::FootnoteReference(reference) => {
+                    let number = links.footnotes.len() + 1;
+                    links.footnotes.entry(reference.name.clone()).or_insert(number);
+                }
+
+                _ => {}
+            }
+        }
+
+        links
+    }
+}
+
+// A node of a parsed document, which owns its children so that the blocks of the document can be
+// kept and built without parsing the document again.
+#[derive(Clone, PartialEq, Data)]
+struct MarkdownNode {
+    #[data(eq)]
+    value: NodeValue,
+    // The line the node starts on, counting from the first line of its top-level block, so that a
+    // block which only moves within the document isn't built again.
+    line: usize,
+    // The anchor of a heading, or the number of a footnote, which depend on the rest of the
+    // document.
+    link: Option<String>,
+    children: Vec<MarkdownNode>,
+}
+
+impl MarkdownNode {
+    fn new<'a>(node: &'a AstNode<'a>, first_line: usize, links: &DocumentLinks) -> Self {
+        let ast = node.data.borrow();
+        let link = match &ast.value {
+            NodeValue::Heading(_) => links.anchors.get(&ast.sourcepos.start.line).cloned(),
+            NodeValue::FootnoteReference(NodeFootnoteReference { name, .. })
+            | NodeValue::FootnoteDefinition(NodeFootnoteDefinition { name, .. }) => {
+                links.footnotes.get(name).map(usize::to_string)
+            }
+            _ => None,
+        };
+
+        Self {
+            value: ast.value.clone(),
+            line: ast.sourcepos.start.line.saturating_sub(first_line),
+            link,
+            children: node.children().map(|child| Self::new(child, first_line, links)).collect(),
+        }
+    }
+
+    // Returns the plain text of the node and its descendants.
+    fn text(&self) -> String {
+        let mut text = match &self.value {
+            NodeValue::Text(text) => text.clone(),
+            NodeValue::Code(code) => code.literal.clone(),
+            _ => String::new(),
+        };
+
+        for child in &self.children {
+            text.push_str(&child.text());
+        }
+
+        text
+    }
+
+    // Returns true for an image, or a link around only images, which are shown as blocks of their
+    // own.
+    fn is_image(&self) -> bool {
+        match &self.value {
+            NodeValue::Image(_) => true,
+            NodeValue::Link(_) => {
+                !self.children.is_empty() && self.children.iter().all(MarkdownNode::is_image)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn parse_document_with_extensions<'a>(
+    arena: &'a Arena<AstNode<'a>>,
+    document: &str,
+) -> &'a AstNode<'a> {
+    let mut options = Options::default();
+    options.extension.strikethrough = true;
+    options.extension.table = true;
+    options.extension.tasklist = true;
+    options.extension.footnotes = true;
+
+    parse_document(arena, document, &options)
+}
+
+// Parses a document into its top-level blocks, and returns the blocks along with the line each
+// block starts on, counting from zero.
+fn parse_blocks(document: &str) -> (Vec<MarkdownNode>, Vec<usize>) {
+    // The returned nodes are created in the supplied Arena, and are bound by its lifetime.
+    let arena = Arena::new();
+    let root = parse_document_with_extensions(&arena, document);
+    let links = DocumentLinks::new(root);
+
+    root.children()
+        .map(|block| {
+            let first_line = block.data.borrow().sourcepos.start.line;
+            (MarkdownNode::new(block, first_line, &links), first_line.saturating_sub(1))
+        })
+        .unzip()
+}
+
+// Returns the anchor of a heading with the given text, like GitHub: in lowercase, with spaces
+// replaced by hyphens and other punctuation removed.
+fn anchor_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            c if c.is_whitespace() => Some('-'),
+            _ => None,
+        })
+        .collect()
+}
+
+// Returns the anchor for a slug which is unique within a document, by adding a number to the
+// repeats of a slug.
+fn unique_anchor(slugs: &mut HashMap<String, usize>, slug: String) -> String {
+    let count = slugs.entry(slug.clone()).or_insert(0);
+    let anchor = if *count == 0 { slug } else { format!("{}-{}", slug, count) };
+    *count += 1;
+    anchor
+}
+
+// Returns the plain text of a parsed node and its descendants.
+fn ast_text<'a>(node: &'a AstNode<'a>) -> String {
+    node.descendants()
+        .filter_map(|node| match &node.data.borrow().value {
+            NodeValue::Text(text) => Some(text.clone()),
+            NodeValue::Code(code) => Some(code.literal.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Opens a link, either within the document if it links to an anchor, or with the default
+// application of the system.
+fn open_link(cx: &mut EventContext, url: &str) {
+    if let Some(anchor) = url.strip_prefix('#') {
+        cx.emit(MarkdownEvent::GoToAnchor(anchor.to_owned()));
+    } else if let Err(err) = open::that(url) {
+        log::warn!("Failed to open link {}: {}", url, err);
+    }
+}
+
+// Builds the views of a node of the top-level block of the document with the given index.
+fn parse_node(cx: &mut Context, node: &MarkdownNode, block: usize, list_level: usize) {
+    match &node.value {
+        NodeValue::Paragraph => {
+            if node.children.iter().any(MarkdownNode::is_image) {
+                // The text around the images of a paragraph is shown in a label before and after
+                // each image.
+                VStack::new(cx, |cx| {
+                    let mut text = Vec::new();
+                    for child in &node.children {
+                        if child.is_image() {
+                            text_run(cx, &text, block, list_level);
+                            text.clear();
+                            parse_node(cx, child, block, list_level);
+                        } else {
+                            text.push(child);
+                        }
+                    }
+
+                    text_run(cx, &text, block, list_level);
+                })
+                .class("p");
+            } else {
+                Label::rich(cx, "", |cx| {
+                    for child in &node.children {
+                        parse_node(cx, child, block, list_level);
+                    }
+                })
+                .class("p");
+            }
         }
 
         NodeValue::Heading(heading) => {
-            Label::rich(cx, "", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
-                }
-            })
-            .class(match heading.level {
-                1 => "h1",
-                2 => "h2",
-                3 => "h3",
-                4 => "h4",
-                5 => "h5",
-                6 => "h6",
-                _ => "h6",
+            let anchor = node.link.clone().unwrap_or_default();
+            Anchor::new(cx, anchor, |cx| {
+                Label::rich(cx, "", |cx| {
+                    for child in &node.children {
+                        parse_node(cx, child, block, list_level);
+                    }
+                })
+                .class(match heading.level {
+                    1 => "h1",
+                    2 => "h2",
+                    3 => "h3",
+                    4 => "h4",
+                    5 => "h5",
+                    6 => "h6",
+                    _ => "h6",
+                });
             });
         }
 
@@ -72,8 +590,8 @@ fn parse_node<'a>(
 
         NodeValue::Emph => {
             TextSpan::new(cx, "", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
+                for child in &node.children {
+                    parse_node(cx, child, block, list_level);
                 }
             })
             .class("emph");
@@ -81,8 +599,8 @@ fn parse_node<'a>(
 
         NodeValue::Strong => {
             TextSpan::new(cx, "", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
+                for child in &node.children {
+                    parse_node(cx, child, block, list_level);
                 }
             })
             .class("strong");
@@ -90,8 +608,8 @@ fn parse_node<'a>(
 
         NodeValue::Strikethrough => {
             TextSpan::new(cx, "", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
+                for child in &node.children {
+                    parse_node(cx, child, block, list_level);
                 }
             })
             .class("strikethrough");
@@ -99,8 +617,8 @@ fn parse_node<'a>(
 
         NodeValue::List(_list) => {
             VStack::new(cx, |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
+                for child in &node.children {
+                    parse_node(cx, child, block, list_level);
                 }
             })
             .height(Auto)
@@ -111,8 +629,8 @@ fn parse_node<'a>(
             HStack::new(cx, |cx| {
                 Label::new(cx, "\u{2022} ").width(Auto);
                 VStack::new(cx, |cx| {
-                    for child in node.children() {
-                        parse_node(cx, child, list_level + 1);
+                    for child in &node.children {
+                        parse_node(cx, child, block, list_level + 1);
                     }
                 })
                 .height(Auto);
@@ -121,16 +639,106 @@ fn parse_node<'a>(
             .height(Auto);
         }
 
+        NodeValue::TaskItem(symbol) => {
+            // The line of the item in the document, counting from zero, which moves with its block.
+            let offset = node.line;
+            let line = move |markdown: &Markdown| {
+                markdown.block_lines.get(block).map_or(offset, |first_line| first_line + offset)
+            };
+            let checked = symbol.is_some();
+            HStack::new(cx, |cx| {
+                Checkbox::new(
+                    cx,
+                    Markdown::root.map(move |markdown| {
+                        markdown.tasks.get(&line(markdown)).copied().unwrap_or(checked)
+                    }),
+                )
+                .on_toggle(move |cx| {
+                    let line = Markdown::root.map(line).get(cx);
+                    cx.emit(MarkdownEvent::ToggleTask { line, checked });
+                });
+
+                VStack::new(cx, |cx| {
+                    for child in &node.children {
+                        parse_node(cx, child, block, list_level + 1);
+                    }
+                })
+                .height(Auto);
+            })
+            .class("li")
+            .class("task")
+            .height(Auto);
+        }
+
+        NodeValue::BlockQuote => {
+            VStack::new(cx, |cx| {
+                for child in &node.children {
+                    parse_node(cx, child, block, list_level);
+                }
+            })
+            .class("blockquote");
+        }
+
+        NodeValue::ThematicBreak => {
+            Divider::horizontal(cx).class("hr");
+        }
+
+        // The cells of a table are laid out in a grid, in which each column is as wide as its
+        // widest cell. A table which is wider than the document scrolls horizontally.
+        NodeValue::Table(_) => {
+            let rows = node.children.clone();
+            let columns = rows.iter().map(|row| row.children.len()).max().unwrap_or_default();
+            ScrollView::new(cx, 0.0, 0.0, true, false, move |cx| {
+                VStack::new(cx, |cx| {
+                    for (row, table_row) in rows.iter().enumerate() {
+                        let header = matches!(table_row.value, NodeValue::TableRow(true));
+                        for (column, cell) in table_row.children.iter().enumerate() {
+                            Label::rich(cx, "", |cx| {
+                                for child in &cell.children {
+                                    parse_node(cx, child, block, list_level);
+                                }
+                            })
+                            .row_start(row)
+                            .column_start(column)
+                            .class("table-cell")
+                            .toggle_class("table-header", header);
+                        }
+                    }
+                })
+                .layout_type(LayoutType::Grid)
+                .grid_columns(vec![Auto; columns])
+                .grid_rows(vec![Auto; rows.len()])
+                .class("table");
+            })
+            .height(Auto)
+            .width(Stretch(1.0));
+        }
+
+        NodeValue::Image(link) => {
+            Image::new(cx, link.url.clone()).name(node.text()).class("image");
+        }
+
         NodeValue::Code(code) => {
             TextSpan::new(cx, &code.literal.to_owned(), |_| {}).class("code");
         }
 
         NodeValue::CodeBlock(code_block) => {
-            println!("{:?}", code_block);
+            // The code ends with a line break, unless the block is empty.
             let mut code = code_block.literal.to_owned();
-            code.pop().unwrap();
-            ScrollView::new(cx, 0.0, 0.0, true, false, |cx| {
-                Label::new(cx, code).class("code");
+            code.pop();
+            // The language is the first word of the info string, e.g. `toml` in
+            // "toml title=Cargo.toml".
+            let language = code_block.info.split_whitespace().next().unwrap_or_default();
+            let highlighter = ConfigHighlighter::for_language(language);
+            ScrollView::new(cx, 0.0, 0.0, true, false, move |cx| {
+                if let Some(highlighter) = highlighter {
+                    let spans = highlighter.highlight(&code);
+                    Label::rich(cx, "", |cx| highlighted_spans(cx, &code, &spans))
+                        .name(code)
+                        .class("code");
+                } else {
+                    Label::new(cx, code).class("code");
+                }
             })
             .height(Auto)
             .width(Stretch(1.0));
@@ -138,23 +746,59 @@ fn parse_node<'a>(
 
         NodeValue::Link(link) => {
             let url = link.url.clone();
-            TextSpan::new(cx, "", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
-                }
-            })
-            .cursor(CursorIcon::Hand)
-            .pointer_events(PointerEvents::Auto)
-            .on_press(move |_| {
-                open::that(url.as_str()).unwrap();
-            })
-            .class("link");
+            if node.is_image() {
+                VStack::new(cx, |cx| {
+                    for child in &node.children {
+                        parse_node(cx, child, block, list_level);
+                    }
+                })
+                .size(Auto)
+                .cursor(CursorIcon::Hand)
+                .on_press(move |cx| open_link(cx, &url))
+                .class("link");
+            } else {
+                TextSpan::new(cx, "", |cx| {
+                    for child in &node.children {
+                        parse_node(cx, child, block, list_level);
+                    }
+                })
+                .cursor(CursorIcon::Hand)
+                .pointer_events(PointerEvents::Auto)
+                .on_press(move |cx| open_link(cx, &url))
+                .class("link");
+            }
+        }
+
+        NodeValue::FootnoteReference(reference) => {
+            let number = node.link.clone().unwrap_or_default();
+            let anchor = format!("fn-{}", reference.name);
+            TextSpan::new(cx, &format!("[{}]", number), |_| {})
+                .cursor(CursorIcon::Hand)
+                .pointer_events(PointerEvents::Auto)
+                .on_press(move |cx| cx.emit(MarkdownEvent::GoToAnchor(anchor.clone())))
+                .class("footnote-ref");
+        }
+
+        NodeValue::FootnoteDefinition(definition) => {
+            let number = node.link.clone().unwrap_or_default();
+            Anchor::new(cx, format!("fn-{}", definition.name), |cx| {
+                HStack::new(cx, |cx| {
+                    Label::new(cx, format!("{}.", number)).class("footnote-number");
+                    VStack::new(cx, |cx| {
+                        for child in &node.children {
+                            parse_node(cx, child, block, list_level);
+                        }
+                    })
+                    .height(Auto);
+                })
+                .class("footnote");
+            });
         }
 
-        NodeValue::SoftBreak => {
+        NodeValue::SoftBreak | NodeValue::LineBreak => {
             TextSpan::new(cx, "\n", |cx| {
-                for child in node.children() {
-                    parse_node(cx, child, list_level);
+                for child in &node.children {
+                    parse_node(cx, child, block, list_level);
                 }
             });
         }
@@ -163,6 +807,19 @@ fn parse_node<'a>(
     }
 }
 
+// Builds a label for a run of the inline nodes of a paragraph, unless the run is only whitespace.
+fn text_run(cx: &mut Context, nodes: &[&MarkdownNode], block: usize, list_level: usize) {
+    if nodes.iter().all(|node| node.text().trim().is_empty()) {
+        return;
+    }
+
+    Label::rich(cx, "", |cx| {
+        for node in nodes {
+            parse_node(cx, node, block, list_level);
+        }
+    });
+}
+
 pub struct TextSpan {}
 
 impl TextSpan {
@@ -187,3 +844,54 @@ impl View for TextSpan {
         Some("text-span")
     }
 }
+
+// A container for a heading or footnote of a document, which links can navigate to by its name.
+struct Anchor {
+    name: String,
+}
+
+impl Anchor {
+    fn new(cx: &mut Context, name: String, content: impl FnOnce(&mut Context)) -> Handle<Self> {
+        Self { name }.build(cx, content)
+    }
+}
+
+impl View for Anchor {
+    fn element(&self) -> Option<&'static str> {
+        Some("markdown-anchor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heading_anchors() {
+        let mut slugs = HashMap::new();
+        assert_eq!(unique_anchor(&mut slugs, anchor_slug("Getting Started!")), "getting-started");
+        assert_eq!(unique_anchor(&mut slugs, anchor_slug("Getting started")), "getting-started-1");
+        assert_eq!(anchor_slug(" The `Lens` trait "), "the-lens-trait");
+    }
+
+    #[test]
+    fn blocks_change_with_their_source() {
+        let (before, lines) = parse_blocks("# Title\n\nFirst paragraph.\n\n- [ ] Task\n");
+        let (after, _) = parse_blocks("# Title\n\nFirst paragraph, edited.\n\n- [ ] Task\n");
+        assert_eq!(before.len(), 3);
+        assert_eq!(lines, [0, 2, 4]);
+        assert!(before[0] == after[0]);
+        assert!(before[1] != after[1]);
+        assert!(before[2] == after[2]);
+
+        // A block which only moves is unchanged, but starts on another line.
+        let (moved, lines) = parse_blocks("# Title\n\nFirst\nparagraph.\n\n- [ ] Task\n");
+        assert!(before[2] == moved[2]);
+        assert_eq!(lines[2], 5);
+
+        // Referencing a footnote earlier renumbers the footnotes referenced after it.
+        let (before, _) = parse_blocks("Intro.\n\nSee[^b].\n\n[^a]: A\n[^b]: B\n");
+        let (after, _) = parse_blocks("Intro[^a].\n\nSee[^b].\n\n[^a]: A\n[^b]: B\n");
+        assert!(before[1] != after[1]);
+    }
+}
@@ -11,6 +11,7 @@ mod dialog;
 mod divider;
 mod dropdown;
 mod element;
+mod find_bar;
 mod image;
 mod knob;
 mod label;
@@ -23,16 +24,20 @@ mod popup;
 mod progressbar;
 mod radio;
 mod rating;
+mod rich_text_editor;
 mod scrollbar;
 mod scrollview;
 mod slider;
 mod spinbox;
 mod stack;
 mod switch;
+mod table;
 mod tabview;
+mod text_editor;
 mod textbox;
 mod toggle_button;
 mod tooltip;
+mod tree_view;
 mod virtual_list;
 mod xypad;
 
@@ -48,6 +53,7 @@ pub use dialog::*;
 pub use divider::*;
 pub use dropdown::Dropdown;
 pub use element::Element;
+pub use find_bar::{FindBar, FindBarEvent, SearchEvent};
 pub use image::*;
 pub use knob::{ArcTrack, Knob, KnobMode, TickKnob, Ticks};
 pub use label::Label;
@@ -59,16 +65,20 @@ pub use popup::*;
 pub use progressbar::ProgressBar;
 pub use radio::RadioButton;
 pub use rating::Rating;
+pub use rich_text_editor::{RichTextEditor, RichTextEvent};
 pub use scrollbar::Scrollbar;
 pub use scrollview::{ScrollEvent, ScrollView};
 pub use slider::{NamedSlider, Slider};
 pub use spinbox::{Spinbox, SpinboxEvent, SpinboxIcons};
 pub use stack::{HStack, VStack, ZStack};
 pub use switch::Switch;
+pub use table::{SortDirection, Table, TableColumn, TableEvent};
 pub use tabview::{TabEvent, TabPair, TabView};
+pub use text_editor::{TextEditor, TextEditorEvent};
 pub use textbox::{TextEvent, Textbox};
 pub use toggle_button::{ToggleButton, ToggleButtonModifiers};
 pub use tooltip::Tooltip;
+pub use tree_view::{TreeView, TreeViewEvent};
 pub use virtual_list::*;
 pub use xypad::XYPad;
 
@@ -0,0 +1,896 @@
+use std::ops::Range;
+
+use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle, TextBox};
+use skia_safe::{Paint, PaintStyle, Rect};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::textbox::{child_space, text_offset, writing_direction};
+use crate::prelude::*;
+use crate::text::{
+    apply_movement, caret_position, offset_for_delete_backwards, Direction, EditHistory, EditKind,
+    EditableText, Movement, Selection, VerticalMovement,
+};
+
+/// Events for formatting the text of a [`RichTextEditor`].
+///
+/// Formatting applies to the selected text, or to the text typed next if nothing is selected.
+/// Editing the text uses the same [`TextEvent`]s as the [`Textbox`].
+pub enum RichTextEvent {
+    /// Toggle bold text.
+    ToggleBold,
+    /// Toggle italic text.
+    ToggleItalic,
+    /// Toggle underlined text.
+    ToggleUnderline,
+    /// Set the color of the text, or `None` to use the color of the editor.
+    SetColor(Option<Color>),
+    /// Set the font size of the text, or `None` to use the font size of the editor.
+    SetFontSize(Option<f32>),
+    /// Set the URL the text links to, or `None` to remove the link.
+    SetLink(Option<String>),
+}
+
+/// A view for editing a [`RichText`] document made up of runs of styled text.
+///
+/// The editor takes a lens to the document, which is edited in place and reported through the
+/// `on_change` callback. Selected text can be made bold, italic or underlined with the Ctrl+B,
+/// Ctrl+I and Ctrl+U shortcuts, or formatted by sending a [`RichTextEvent`] to the editor.
+/// Holding Ctrl while clicking a link opens it.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     document: RichText,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { document: RichText::from_markdown("Some **bold** text") }.build(cx);
+/// #
+/// RichTextEditor::new(cx, AppData::document)
+///     .on_change(|_, document| println!("{}", document.to_markdown()));
+/// ```
+#[derive(Lens)]
+pub struct RichTextEditor {
+    document: RichText,
+    edit: bool,
+    show_caret: bool,
+    caret_timer: Timer,
+    selection: Selection,
+    // The style of the text typed at the caret after it is formatted, until the caret moves.
+    #[lens(ignore)]
+    typing_style: Option<RunStyle>,
+    #[lens(ignore)]
+    history: EditHistory<RichText>,
+    // The text last put on the clipboard by the editor along with the document it was copied from,
+    // which is pasted in place of the text so that its styles are kept.
+    #[cfg(feature = "clipboard")]
+    #[lens(ignore)]
+    copied: Option<(String, RichText)>,
+    on_change: Option<Box<dyn Fn(&mut EventContext, RichText) + Send + Sync>>,
+}
+
+impl RichTextEditor {
+    /// Creates a new rich text editor for the document of the given lens.
+    pub fn new<L>(cx: &mut Context, lens: L) -> Handle<Self>
+    where
+        L: Lens<Target = RichText>,
+    {
+        let caret_timer = cx.environment().caret_timer;
+
+        Self {
+            document: RichText::new(),
+            edit: false,
+            show_caret: true,
+            caret_timer,
+            selection: Selection::caret(0),
+            typing_style: None,
+            history: EditHistory::default(),
+            #[cfg(feature = "clipboard")]
+            copied: None,
+            on_change: None,
+        }
+        .build(cx, |cx| {
+            cx.add_listener(move |editor: &mut Self, cx, event| {
+                let flag = editor.edit;
+                event.map(|window_event, meta| match window_event {
+                    WindowEvent::MouseDown(_) => {
+                        if flag
+                            && meta.origin != cx.current()
+                            && !cx.hovered().is_descendant_of(cx.tree, cx.current())
+                        {
+                            cx.emit(TextEvent::EndEdit);
+                        }
+                    }
+
+                    _ => {}
+                });
+            });
+
+            // Each run of the document is displayed by a text span within the paragraph of the editor.
+            let editor = cx.current();
+            Binding::new(cx, Self::document, move |cx, document| {
+                for run in document.get(cx).runs() {
+                    let style = &run.style;
+                    let mut span = TextSpan::new(cx, &run.text, |_| {});
+
+                    if style.bold {
+                        span = span.font_weight(FontWeightKeyword::Bold);
+                    }
+
+                    if style.italic {
+                        span = span.font_slant(FontSlant::Italic);
+                    }
+
+                    if style.underline {
+                        span = span.text_decoration_line(TextDecorationLine::Underline);
+                    }
+
+                    if let Some(color) = style.color {
+                        span = span.color(color);
+                    }
+
+                    if let Some(font_size) = style.font_size {
+                        span = span.font_size(font_size);
+                    }
+
+                    span.toggle_class("link", style.link.is_some());
+                }
+
+                cx.style.needs_text_update(editor);
+            });
+        })
+        .bind(lens, |handle, lens| {
+            let document = lens.get(&handle);
+            handle.modify(|editor| editor.set_document(document));
+        })
+        .text("")
+        .navigable(true)
+        .role(Role::TextInput)
+        .default_action_verb(DefaultActionVerb::Focus)
+        .toggle_class("caret", Self::show_caret)
+    }
+
+    // Replaces the document with the bound document if it was changed by something other than the editor.
+    fn set_document(&mut self, document: RichText) {
+        if document != self.document {
+            self.selection = Selection::caret(document.len());
+            self.document = document;
+            self.typing_style = None;
+            self.history.clear();
+        }
+    }
+
+    // Replaces a range of the document, recording the edit so it can be undone.
+    fn edit(
+        &mut self,
+        cx: &mut EventContext,
+        range: Range<usize>,
+        inserted: RichText,
+        kind: EditKind,
+        selection: Selection,
+    ) {
+        if range.is_empty() && inserted.is_empty() {
+            return;
+        }
+
+        self.history.edit(&mut self.document, range, inserted, kind, self.selection, selection);
+        self.selection = selection;
+        self.typing_style = None;
+
+        self.changed(cx);
+    }
+
+    fn changed(&mut self, cx: &mut EventContext) {
+        if let Some(callback) = &self.on_change {
+            (callback)(cx, self.document.clone());
+        }
+
+        cx.needs_redraw();
+    }
+
+    // Returns the style of text inserted at the selection.
+    fn insert_style(&self) -> RunStyle {
+        if let Some(style) = &self.typing_style {
+            return style.clone();
+        }
+
+        let offset = self.selection.min();
+        let mut style = self.document.style_at(offset);
+
+        // Text typed at the end of a link does not extend it.
+        if style.link.is_some()
+            && (offset >= self.document.len()
+                || self.document.style_at(offset + 1).link != style.link)
+        {
+            style.link = None;
+        }
+
+        style
+    }
+
+    fn insert_text(&mut self, cx: &mut EventContext, text: &str) {
+        let mut inserted = RichText::new();
+        inserted.push(text, self.insert_style());
+
+        // Only typing into the text is merged into a single undo step.
+        let kind = if self.selection.is_caret() && text.graphemes(true).count() == 1 {
+            EditKind::Insert
+        } else {
+            EditKind::Other
+        };
+
+        self.insert(cx, inserted, kind);
+    }
+
+    fn insert(&mut self, cx: &mut EventContext, inserted: RichText, kind: EditKind) {
+        let selection = Selection::caret(self.selection.min() + inserted.len());
+        self.edit(cx, self.selection.range(), inserted, kind, selection);
+    }
+
+    fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        let text = self.document.text();
+        let range = if !self.selection.is_caret() {
+            self.selection.range()
+        } else if movement == Movement::Grapheme(Direction::Upstream) {
+            offset_for_delete_backwards(&self.selection, &text)..self.selection.active
+        } else if let Some((paragraph, caret_stops)) =
+            cx.text_context.paragraph_with_caret_stops(cx.current)
+        {
+            apply_movement(
+                movement,
+                self.selection,
+                &text,
+                paragraph,
+                caret_stops,
+                writing_direction(cx.style, cx.current),
+                true,
+            )
+            .range()
+        } else {
+            return;
+        };
+
+        let kind = if self.selection.is_caret() { EditKind::Delete } else { EditKind::Other };
+        let selection = Selection::caret(range.start);
+        self.edit(cx, range, RichText::new(), kind, selection);
+    }
+
+    fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        if let Some((paragraph, caret_stops)) =
+            cx.text_context.paragraph_with_caret_stops(cx.current)
+        {
+            self.selection = apply_movement(
+                movement,
+                self.selection,
+                &self.document.text(),
+                paragraph,
+                caret_stops,
+                writing_direction(cx.style, cx.current),
+                selection,
+            );
+            self.typing_style = None;
+            self.history.break_merge();
+            cx.needs_redraw();
+        }
+    }
+
+    fn select_all(&mut self, cx: &mut EventContext) {
+        self.selection = Selection::new(0, self.document.len());
+        self.typing_style = None;
+        cx.needs_redraw();
+    }
+
+    fn select_word(&mut self, cx: &mut EventContext) {
+        self.move_cursor(cx, Movement::Word(Direction::Upstream), false);
+        self.move_cursor(cx, Movement::Word(Direction::Downstream), true);
+    }
+
+    fn select_paragraph(&mut self, cx: &mut EventContext) {
+        self.move_cursor(cx, Movement::ParagraphStart, false);
+        self.move_cursor(cx, Movement::ParagraphEnd, true);
+    }
+
+    /// Returns the byte offset of the caret position closest to the given window-global physical coordinates.
+    fn offset_at(&self, cx: &EventContext, x: f32, y: f32) -> Option<usize> {
+        let paragraph = cx.text_context.text_paragraphs.get(cx.current)?;
+
+        let bounds = cx.bounds();
+        let (padding_left, padding_top) = text_offset(
+            bounds,
+            paragraph,
+            child_space(cx.style, cx.current),
+            cx.style.is_rtl(cx.current),
+        );
+
+        let gp = paragraph.get_glyph_position_at_coordinate((
+            x - bounds.x - padding_left,
+            y - bounds.y - padding_top,
+        ));
+        let pos = gp.position as usize;
+
+        let text = self.document.text();
+        Some(text.grapheme_indices(true).nth(pos).map(|(offset, _)| offset).unwrap_or(text.len()))
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
+        if let Some(offset) = self.offset_at(cx, x, y) {
+            if selection {
+                self.selection.active = offset;
+            } else {
+                self.selection = Selection::caret(offset);
+            }
+
+            self.typing_style = None;
+            self.history.break_merge();
+            cx.needs_redraw();
+        }
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn drag(&mut self, cx: &mut EventContext, x: f32, y: f32) {
+        if let Some(offset) = self.offset_at(cx, x, y) {
+            self.selection.active = offset;
+            cx.needs_redraw();
+        }
+    }
+
+    // Returns the URL of the link under the given window-global physical coordinates.
+    fn link_at(&self, cx: &EventContext, x: f32, y: f32) -> Option<String> {
+        let offset = self.offset_at(cx, x, y)?;
+        let text = self.document.text();
+        let next = text.next_grapheme_offset(offset)?;
+
+        self.document.style_at(next).link
+    }
+
+    // Applies a change of style to the selected text, or to the text typed next at the caret.
+    fn format(&mut self, cx: &mut EventContext, update: impl Fn(&mut RunStyle)) {
+        if self.selection.is_caret() {
+            let mut style = self.insert_style();
+            (update)(&mut style);
+            self.typing_style = Some(style);
+            return;
+        }
+
+        // The formatted text replaces the selected text, so that formatting is undone like an edit.
+        let range = self.selection.range();
+        let mut formatted = self.document.slice(range.clone());
+        formatted.update_style(0..formatted.len(), update);
+        if formatted == self.document.slice(range.clone()) {
+            return;
+        }
+
+        self.history.edit(
+            &mut self.document,
+            range,
+            formatted,
+            EditKind::Other,
+            self.selection,
+            self.selection,
+        );
+
+        self.changed(cx);
+    }
+
+    // Returns true if the selected text, or the text typed next at the caret, matches the predicate.
+    fn is_styled(&self, predicate: impl Fn(&RunStyle) -> bool) -> bool {
+        if self.selection.is_caret() {
+            predicate(&self.insert_style())
+        } else {
+            self.document.is_styled(self.selection.range(), predicate)
+        }
+    }
+
+    fn reset_caret_timer(&mut self, cx: &mut EventContext) {
+        cx.stop_timer(self.caret_timer);
+        if !cx.is_read_only() {
+            self.show_caret = true;
+            cx.start_timer(self.caret_timer);
+        }
+    }
+
+    fn undo(&mut self, cx: &mut EventContext) {
+        if let Some(selection) = self.history.undo(&mut self.document) {
+            self.selection = selection;
+            self.typing_style = None;
+            self.changed(cx);
+        }
+    }
+
+    fn redo(&mut self, cx: &mut EventContext) {
+        if let Some(selection) = self.history.redo(&mut self.document) {
+            self.selection = selection;
+            self.typing_style = None;
+            self.changed(cx);
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn copy(&mut self, cx: &mut EventContext) {
+        let copied = self.document.slice(self.selection.range());
+        let text = clipboard_text(&copied);
+        cx.set_clipboard(text.clone()).expect("Failed to add text to clipboard");
+        self.copied = Some((text, copied));
+    }
+
+    // Returns the rectangles covering a range of graphemes of the text, relative to the bounds of the editor.
+    fn grapheme_rects(&self, cx: &DrawContext, graphemes: Range<usize>) -> Vec<TextBox> {
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return Vec::new();
+        };
+
+        let (padding_left, padding_top) = text_offset(
+            cx.bounds(),
+            paragraph,
+            [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+            cx.style.is_rtl(cx.current),
+        );
+
+        paragraph
+            .get_rects_for_range(graphemes, RectHeightStyle::Tight, RectWidthStyle::Tight)
+            .into_iter()
+            .map(|mut text_box| {
+                text_box.rect.offset((padding_left, padding_top));
+                text_box
+            })
+            .collect()
+    }
+
+    /// Draw the selection of the current view.
+    pub fn draw_selection(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if self.selection.is_caret() {
+            return;
+        }
+
+        let text = self.document.text();
+        let min = text.current_grapheme_offset(self.selection.min());
+        let max = text.current_grapheme_offset(self.selection.max());
+
+        let bounds = cx.bounds();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(cx.selection_color());
+
+        for text_box in self.grapheme_rects(cx, min..max) {
+            canvas.draw_rect(text_box.rect.with_offset((bounds.x, bounds.y)), &paint);
+        }
+    }
+
+    /// Draw text caret for the current view.
+    pub fn draw_text_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        // The zero-width space at the end of the paragraph gives a caret position after the last line.
+        let current = self.document.text().current_grapheme_offset(self.selection.active);
+        let rects = self.grapheme_rects(cx, current..current + 1);
+        let Some(cursor_rect) = rects.first() else {
+            return;
+        };
+
+        let bounds = cx.bounds();
+        let x = (bounds.x + caret_position(cursor_rect)).round();
+        let y = (bounds.y + cursor_rect.rect.top).round();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(cx.caret_color());
+
+        canvas.draw_rect(Rect::new(x, y, x + 1.0, y + cursor_rect.rect.height()), &paint);
+    }
+}
+
+impl Handle<'_, RichTextEditor> {
+    /// Sets the callback triggered when the document is edited or formatted.
+    ///
+    /// Callback provides the new document.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, RichText) + Send + Sync,
+    {
+        self.modify(|editor| editor.on_change = Some(Box::new(callback)))
+    }
+}
+
+impl View for RichTextEditor {
+    fn element(&self) -> Option<&'static str> {
+        Some("rich-text-editor")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        // Window Events
+        event.map(|window_event, _| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                if cx.is_over() && !cx.is_disabled() {
+                    let (x, y) = (cx.mouse.cursor_x, cx.mouse.cursor_y);
+
+                    if cx.modifiers.contains(Modifiers::COMMAND) {
+                        if let Some(link) = self.link_at(cx, x, y) {
+                            if let Err(err) = open::that(link.as_str()) {
+                                log::warn!("Failed to open link {}: {}", link, err);
+                            }
+                            return;
+                        }
+                    }
+
+                    cx.focus_with_visibility(false);
+                    cx.capture();
+                    cx.lock_cursor_icon();
+
+                    if !self.edit {
+                        cx.emit(TextEvent::StartEdit);
+                    }
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::Hit(x, y, cx.modifiers.shift()));
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                self.reset_caret_timer(cx);
+                cx.unlock_cursor_icon();
+                cx.release();
+            }
+
+            WindowEvent::MouseMove(x, y) => {
+                if cx.mouse.left.state == MouseButtonState::Pressed
+                    && cx.mouse.left.pressed == cx.current
+                {
+                    if self.edit {
+                        self.reset_caret_timer(cx);
+                    }
+                    if cx.mouse.left.pos_down.0 != *x || cx.mouse.left.pos_down.1 != *y {
+                        cx.emit(TextEvent::Drag(cx.mouse.cursor_x, cx.mouse.cursor_y));
+                    }
+                }
+            }
+
+            WindowEvent::MouseDoubleClick(MouseButton::Left) => {
+                cx.emit(TextEvent::SelectWord);
+            }
+
+            WindowEvent::MouseTripleClick(MouseButton::Left) => {
+                cx.emit(TextEvent::SelectParagraph);
+            }
+
+            WindowEvent::FocusIn => {
+                cx.emit(TextEvent::StartEdit);
+            }
+
+            WindowEvent::FocusOut => {
+                cx.emit(TextEvent::EndEdit);
+            }
+
+            WindowEvent::ImeCommit(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::InsertText(text.clone()));
+                }
+            }
+
+            WindowEvent::CharInput(c) => {
+                if *c != '\u{1b}' && // Escape
+                    *c != '\u{8}' && // Backspace
+                    *c != '\u{9}' && // Tab
+                    *c != '\u{7f}' && // Delete
+                    *c != '\u{0d}' && // Carriage return
+                    !cx.modifiers.ctrl() &&
+                    !cx.modifiers.logo() &&
+                    self.edit &&
+                    !cx.is_read_only()
+                {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::InsertText(String::from(*c)));
+                }
+            }
+
+            WindowEvent::KeyDown(code, _) => {
+                let shortcut = cx.modifiers == &Modifiers::COMMAND;
+
+                match code {
+                    Code::Enter => {
+                        if !cx.is_read_only() {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::InsertText("\n".to_owned()));
+                        }
+                    }
+
+                    Code::Space => {
+                        cx.emit(TextEvent::InsertText(String::from(" ")));
+                    }
+
+                    Code::ArrowLeft | Code::ArrowRight => {
+                        self.reset_caret_timer(cx);
+                        let direction = if *code == Code::ArrowLeft {
+                            Direction::Left
+                        } else {
+                            Direction::Right
+                        };
+                        let movement = if cx.modifiers.ctrl() {
+                            Movement::Word(direction)
+                        } else {
+                            Movement::Grapheme(direction)
+                        };
+
+                        cx.emit(TextEvent::MoveCursor(movement, cx.modifiers.shift()));
+                    }
+
+                    Code::ArrowUp | Code::ArrowDown => {
+                        self.reset_caret_timer(cx);
+                        let movement = if *code == Code::ArrowUp {
+                            VerticalMovement::LineUp
+                        } else {
+                            VerticalMovement::LineDown
+                        };
+
+                        cx.emit(TextEvent::MoveCursor(
+                            Movement::Vertical(movement),
+                            cx.modifiers.shift(),
+                        ));
+                    }
+
+                    Code::Backspace | Code::Delete => {
+                        self.reset_caret_timer(cx);
+                        if !cx.is_read_only() {
+                            let direction = if *code == Code::Backspace {
+                                Direction::Upstream
+                            } else {
+                                Direction::Downstream
+                            };
+
+                            cx.emit(TextEvent::DeleteText(if cx.modifiers.ctrl() {
+                                Movement::Word(direction)
+                            } else {
+                                Movement::Grapheme(direction)
+                            }));
+                        }
+                    }
+
+                    Code::Escape => {
+                        cx.emit(TextEvent::EndEdit);
+                    }
+
+                    Code::Home => {
+                        self.reset_caret_timer(cx);
+                        cx.emit(TextEvent::MoveCursor(Movement::LineStart, cx.modifiers.shift()));
+                    }
+
+                    Code::End => {
+                        self.reset_caret_timer(cx);
+                        cx.emit(TextEvent::MoveCursor(Movement::LineEnd, cx.modifiers.shift()));
+                    }
+
+                    Code::PageUp | Code::PageDown => {
+                        self.reset_caret_timer(cx);
+                        let direction = if *code == Code::PageUp {
+                            Direction::Upstream
+                        } else {
+                            Direction::Downstream
+                        };
+                        cx.emit(TextEvent::MoveCursor(
+                            if cx.modifiers.ctrl() {
+                                Movement::Body(direction)
+                            } else {
+                                Movement::Page(direction)
+                            },
+                            cx.modifiers.shift(),
+                        ));
+                    }
+
+                    Code::KeyA if shortcut => cx.emit(TextEvent::SelectAll),
+
+                    Code::KeyC if shortcut => cx.emit(TextEvent::Copy),
+
+                    Code::KeyV if shortcut => cx.emit(TextEvent::Paste),
+
+                    Code::KeyX if shortcut => cx.emit(TextEvent::Cut),
+
+                    Code::KeyB if shortcut => cx.emit(RichTextEvent::ToggleBold),
+
+                    Code::KeyI if shortcut => cx.emit(RichTextEvent::ToggleItalic),
+
+                    Code::KeyU if shortcut => cx.emit(RichTextEvent::ToggleUnderline),
+
+                    Code::KeyZ => {
+                        if shortcut {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::Undo);
+                        } else if cx.modifiers == &(Modifiers::COMMAND | Modifiers::SHIFT) {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::Redo);
+                        }
+                    }
+
+                    #[cfg(not(target_os = "macos"))]
+                    Code::KeyY if shortcut => {
+                        self.reset_caret_timer(cx);
+                        cx.emit(TextEvent::Redo);
+                    }
+
+                    _ => {}
+                }
+            }
+
+            _ => {}
+        });
+
+        // Text Events
+        event.map(|text_event, _| match text_event {
+            TextEvent::InsertText(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.insert_text(cx, text);
+                }
+            }
+
+            TextEvent::DeleteText(movement) => {
+                if self.edit && !cx.is_read_only() {
+                    self.delete_text(cx, *movement);
+                }
+            }
+
+            TextEvent::MoveCursor(movement, selection) => {
+                if self.edit {
+                    self.move_cursor(cx, *movement, *selection);
+                }
+            }
+
+            TextEvent::SelectAll => self.select_all(cx),
+
+            TextEvent::SelectWord => self.select_word(cx),
+
+            TextEvent::SelectParagraph => self.select_paragraph(cx),
+
+            TextEvent::StartEdit => {
+                if !cx.is_disabled() && !self.edit {
+                    self.edit = true;
+                    cx.focus_with_visibility(false);
+                    cx.set_checked(true);
+                    self.reset_caret_timer(cx);
+                    cx.needs_redraw();
+                }
+            }
+
+            // The selection is kept so that it can still be formatted, e.g. by the buttons of a toolbar.
+            TextEvent::EndEdit => {
+                if self.edit {
+                    self.edit = false;
+                    cx.set_checked(false);
+                    cx.stop_timer(self.caret_timer);
+                    cx.needs_redraw();
+                }
+            }
+
+            TextEvent::Hit(x, y, selection) => self.hit(cx, *x, *y, *selection),
+
+            TextEvent::Drag(x, y) => self.drag(cx, *x, *y),
+
+            TextEvent::Copy =>
+            {
+                #[cfg(feature = "clipboard")]
+                if !self.selection.is_caret() {
+                    self.copy(cx);
+                }
+            }
+
+            TextEvent::Paste =>
+            {
+                #[cfg(feature = "clipboard")]
+                if self.edit && !cx.is_read_only() {
+                    if let Ok(text) = cx.get_clipboard() {
+                        let pasted =
+                            from_clipboard(&text, self.insert_style(), self.copied.as_ref());
+                        self.insert(cx, pasted, EditKind::Other);
+                    }
+                }
+            }
+
+            TextEvent::Cut =>
+            {
+                #[cfg(feature = "clipboard")]
+                if self.edit && !cx.is_read_only() && !self.selection.is_caret() {
+                    self.copy(cx);
+                    self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
+                }
+            }
+
+            TextEvent::Undo => {
+                if self.edit && !cx.is_read_only() {
+                    self.undo(cx);
+                }
+            }
+
+            TextEvent::Redo => {
+                if self.edit && !cx.is_read_only() {
+                    self.redo(cx);
+                }
+            }
+
+            TextEvent::ToggleCaret => {
+                self.show_caret ^= true;
+            }
+
+            _ => {}
+        });
+
+        // Formatting Events
+        event.map(|rich_text_event, _| {
+            if cx.is_read_only() {
+                return;
+            }
+
+            match rich_text_event {
+                RichTextEvent::ToggleBold => {
+                    let bold = !self.is_styled(|style| style.bold);
+                    self.format(cx, |style| style.bold = bold);
+                }
+
+                RichTextEvent::ToggleItalic => {
+                    let italic = !self.is_styled(|style| style.italic);
+                    self.format(cx, |style| style.italic = italic);
+                }
+
+                RichTextEvent::ToggleUnderline => {
+                    let underline = !self.is_styled(|style| style.underline);
+                    self.format(cx, |style| style.underline = underline);
+                }
+
+                RichTextEvent::SetColor(color) => {
+                    self.format(cx, |style| style.color = *color);
+                }
+
+                RichTextEvent::SetFontSize(font_size) => {
+                    self.format(cx, |style| style.font_size = *font_size);
+                }
+
+                RichTextEvent::SetLink(link) => {
+                    self.format(cx, |style| style.link.clone_from(link));
+                }
+            }
+
+            // Formatting from outside of the editor returns focus to it so that typing can continue.
+            cx.focus_with_visibility(false);
+        });
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        cx.draw_shadows(canvas);
+        cx.draw_background(canvas);
+        cx.draw_border(canvas);
+        cx.draw_outline(canvas);
+        cx.draw_text(canvas);
+        self.draw_selection(cx, canvas);
+        if self.edit {
+            self.draw_text_caret(cx, canvas);
+        }
+    }
+}
+
+// Returns the text put on the clipboard for some copied text. Styled text is copied as markdown so
+// that its styles are kept when it is pasted, while text without styles is copied as it is.
+#[cfg(feature = "clipboard")]
+fn clipboard_text(copied: &RichText) -> String {
+    if copied.runs().iter().all(|run| run.style == RunStyle::default()) {
+        copied.text()
+    } else {
+        copied.to_markdown()
+    }
+}
+
+// Returns the text pasted from the clipboard. Text copied from the editor is pasted with its styles,
+// and any other text is pasted as it is with the given style, even if it looks like markdown.
+#[cfg(feature = "clipboard")]
+fn from_clipboard(text: &str, style: RunStyle, copied: Option<&(String, RichText)>) -> RichText {
+    if let Some((_, document)) = copied.filter(|(copied, _)| copied == text) {
+        return document.clone();
+    }
+
+    let mut pasted = RichText::new();
+    pasted.push(text, style);
+    pasted
+}
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::binding::RatioLens;
@@ -16,6 +17,9 @@ pub enum ScrollEvent {
     ScrollY(f32),
     /// Sets the Size for the inner VStack which holds the content
     ChildGeo(f32, f32),
+    /// Scrolls the least distance which brings the given bounds, in physical pixels, into view.
+    /// Sent by a descendant view, this scrolls the nearest scrollview which contains it.
+    ScrollIntoView(BoundingBox),
 }
 
 #[derive(Lens, Data, Clone)]
@@ -160,6 +164,25 @@ impl View for ScrollView {
                     }
                 }
 
+                ScrollEvent::ScrollIntoView(target) => {
+                    let bounds = cx.bounds();
+                    self.scroll_x = scroll_into_view(
+                        self.scroll_x,
+                        self.inner_width - self.container_width,
+                        bounds.left()..bounds.right(),
+                        target.left()..target.right(),
+                    );
+                    self.scroll_y = scroll_into_view(
+                        self.scroll_y,
+                        self.inner_height - self.container_height,
+                        bounds.top()..bounds.bottom(),
+                        target.top()..target.bottom(),
+                    );
+                    if let Some(callback) = &self.on_scroll {
+                        (callback)(cx, self.scroll_x, self.scroll_y);
+                    }
+                }
+
                 ScrollEvent::ChildGeo(w, h) => {
                     self.inner_width = *w;
                     self.inner_height = *h;
@@ -290,3 +313,46 @@ impl View for ScrollContent {
         });
     }
 }
+
+// Returns the scroll progress which brings the target range into the visible range along one axis,
+// scrolling the least distance. A target larger than the visible range is aligned to its start.
+fn scroll_into_view(
+    progress: f32,
+    negative_space: f32,
+    visible: Range<f32>,
+    target: Range<f32>,
+) -> f32 {
+    if negative_space <= 0.0 {
+        return progress;
+    }
+
+    let distance = if target.start < visible.start {
+        target.start - visible.start
+    } else if target.end > visible.end {
+        (target.end - visible.end).min(target.start - visible.start)
+    } else {
+        0.0
+    };
+
+    (progress + distance / negative_space).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolls_least_distance_into_view() {
+        // The target is already visible.
+        assert_eq!(scroll_into_view(0.5, 100.0, 0.0..50.0, 10.0..20.0), 0.5);
+        // The target is below the visible range, so it is scrolled to the bottom edge.
+        assert_eq!(scroll_into_view(0.0, 100.0, 0.0..50.0, 60.0..70.0), 0.2);
+        // The target is above the visible range, so it is scrolled to the top edge.
+        assert_eq!(scroll_into_view(0.5, 100.0, 0.0..50.0, -25.0..-15.0), 0.25);
+        // A target taller than the visible range is aligned to its start.
+        assert_eq!(scroll_into_view(0.0, 100.0, 0.0..50.0, 40.0..120.0), 0.4);
+        // The progress is clamped, and there is nothing to scroll without negative space.
+        assert_eq!(scroll_into_view(0.9, 100.0, 0.0..50.0, 80.0..90.0), 1.0);
+        assert_eq!(scroll_into_view(0.0, 0.0, 0.0..50.0, 80.0..90.0), 0.0);
+    }
+}
@@ -0,0 +1,910 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Range;
+use std::rc::Rc;
+
+use super::list::ItemSelection;
+use super::virtual_list::VirtualListData;
+use crate::context::TreeProps;
+use crate::icons::{ICON_ARROW_DOWN, ICON_ARROW_UP};
+use crate::prelude::*;
+
+// How far, in logical pixels, a column header is dragged before the column starts to move.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// The direction in which the rows of a [`Table`] are sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// A column of a [`Table`], which builds the content of its header and of its cell in each row.
+pub struct TableColumn<T> {
+    header: Box<dyn Fn(&mut Context)>,
+    cell: Box<dyn Fn(&mut Context, &T)>,
+    compare: Option<Rc<dyn Fn(&T, &T) -> Ordering>>,
+    width: f32,
+    min_width: f32,
+}
+
+impl<T: 'static> TableColumn<T> {
+    /// Creates a new column from the content of its header, and the content of its cell for a row
+    /// of data. The column is 100 pixels wide and can't be sorted by default.
+    pub fn new(
+        header: impl 'static + Fn(&mut Context),
+        cell: impl 'static + Fn(&mut Context, &T),
+    ) -> Self {
+        Self {
+            header: Box::new(header),
+            cell: Box::new(cell),
+            compare: None,
+            width: 100.0,
+            min_width: 24.0,
+        }
+    }
+
+    /// Sets the width of the column in logical pixels.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the width in logical pixels which the column can't be resized below.
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Allows the rows to be sorted by the column, by clicking its header, using the given function
+    /// to compare rows in ascending order.
+    pub fn sort_by(mut self, compare: impl 'static + Fn(&T, &T) -> Ordering) -> Self {
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+
+    /// Allows the rows to be sorted by the column, by clicking its header, in the order of the key
+    /// returned by the given function.
+    pub fn sort_by_key<K: Ord>(self, key: impl 'static + Fn(&T) -> K) -> Self {
+        self.sort_by(move |a, b| key(a).cmp(&key(b)))
+    }
+}
+
+/// Events for controlling a [`Table`].
+pub enum TableEvent {
+    /// Sorts the rows by the given column, cycling between ascending, descending and the order of
+    /// the data. Sent when the header of a column is clicked.
+    ToggleSort(usize),
+    /// Sorts the rows by a column in the given direction, or shows them in the order of the data.
+    SetSort(Option<(usize, SortDirection)>),
+    /// Sets the width of a column in logical pixels, limited to its minimum width.
+    ResizeColumn(usize, f32),
+    /// Moves a column to the given position in the order in which the columns are shown.
+    MoveColumn(usize, usize),
+    /// Selects every row, if more than one row can be selected.
+    SelectAll,
+    /// Deselects every row.
+    ClearSelection,
+}
+
+// Returns the index into the data and the id of each row, in the order given by a sort.
+type SortRows<K> = Box<dyn Fn(&EventContext, Option<(usize, SortDirection)>) -> Vec<(usize, K)>>;
+
+// Events sent to a table by its own views.
+enum TableUpdate {
+    // The cell at a row, in the order the rows are shown, and a column was pressed.
+    Press(usize, usize),
+    // The rows were scrolled to the given progress along each axis.
+    Scroll(f32, f32),
+    // The header of a column was dragged to the given offset, in logical pixels from the left edge
+    // of the first column.
+    DragColumn(usize, f32),
+    // The data changed, so the rows are sorted again.
+    Refresh,
+}
+
+/// A view which shows a list of data as rows of cells, with a column for each [`TableColumn`].
+///
+/// The table uses a function which returns an id for each row, which is unique within the list, so
+/// that the selected and focused rows stay the same when the rows are sorted or the data changes.
+/// Only the rows which are visible are built, so a table can show a very large number of rows.
+/// Each row has the same height.
+///
+/// Clicking the header of a column which can be sorted sorts the rows by that column, and clicking
+/// it again reverses the order. Columns can be resized by dragging the right edge of their header,
+/// and moved by dragging their header. The cells can be navigated with the arrow keys, Home, End,
+/// Page Up and Page Down, and with [`selectable`](Handle::selectable) the rows can be selected by
+/// clicking them or with the keyboard.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     people: Vec<(u32, String, u32)>,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { people: vec![(0, String::from("Ada"), 36)] }.build(cx);
+/// #
+/// Table::new(
+///     cx,
+///     AppData::people,
+///     |person| person.0,
+///     32.0,
+///     vec![
+///         TableColumn::new(|cx| { Label::new(cx, "Name"); }, |cx, person: &(u32, String, u32)| {
+///             Label::new(cx, person.1.as_str());
+///         })
+///         .sort_by_key(|person| person.1.clone()),
+///         TableColumn::new(|cx| { Label::new(cx, "Age"); }, |cx, person: &(u32, String, u32)| {
+///             Label::new(cx, person.2);
+///         })
+///         .sort_by_key(|person| person.2),
+///     ],
+/// )
+/// .selectable(Selectable::Multi);
+/// ```
+#[derive(Lens)]
+pub struct Table<K: 'static> {
+    // The width of each column in logical pixels.
+    widths: Vec<f32>,
+    // The columns in the order they are shown.
+    order: Vec<usize>,
+    sort: Option<(usize, SortDirection)>,
+    // The index into the data of each row, in the order the rows are shown.
+    rows: Vec<usize>,
+    // The id of each row, in the order the rows are shown.
+    #[lens(ignore)]
+    keys: Vec<K>,
+    #[lens(ignore)]
+    selection: ItemSelection<K>,
+    // The focused cell, as the id of its row and its column.
+    focused: Option<(K, usize)>,
+    // The positions of the rows which are built.
+    visible_range: Range<usize>,
+    scroll_x: f32,
+    // The width of the rows which is visible, in logical pixels.
+    viewport_width: f32,
+    #[lens(ignore)]
+    scroll_y: f32,
+    #[lens(ignore)]
+    row_height: f32,
+    #[lens(ignore)]
+    min_widths: Vec<f32>,
+    // Whether the rows can be sorted by each column.
+    #[lens(ignore)]
+    sortable: Vec<bool>,
+    #[lens(ignore)]
+    selectable: Selectable,
+    // The scrollview which contains the rows.
+    #[lens(ignore)]
+    body: Entity,
+    #[lens(ignore)]
+    sort_rows: SortRows<K>,
+    #[lens(ignore)]
+    on_select: Option<Box<dyn Fn(&mut EventContext, &[K])>>,
+}
+
+impl<K: Data + Eq + Hash> Table<K> {
+    /// Creates a new table which shows the items of a list as rows with the given height, in
+    /// logical pixels, and the given columns, given a function which returns the id of an item.
+    pub fn new<L, T, I>(
+        cx: &mut Context,
+        list: L,
+        id: I,
+        row_height: f32,
+        columns: Vec<TableColumn<T>>,
+    ) -> Handle<Self>
+    where
+        L: Lens<Target = Vec<T>>,
+        T: Data,
+        I: 'static + Fn(&T) -> K,
+    {
+        let keys = list.get_ref(cx).map(|list| list.iter().map(&id).collect::<Vec<_>>());
+        let keys = keys.unwrap_or_default();
+        let columns: Rc<[TableColumn<T>]> = columns.into();
+        let compare = columns.iter().map(|column| column.compare.clone()).collect::<Vec<_>>();
+        let sortable = compare.iter().map(Option::is_some).collect();
+        let mut body = Entity::null();
+
+        Self {
+            widths: columns.iter().map(|column| column.width.max(column.min_width)).collect(),
+            order: (0..columns.len()).collect(),
+            sort: None,
+            rows: (0..keys.len()).collect(),
+            keys,
+            selection: ItemSelection::default(),
+            focused: None,
+            visible_range: 0..0,
+            scroll_x: 0.0,
+            viewport_width: 0.0,
+            scroll_y: 0.0,
+            row_height,
+            min_widths: columns.iter().map(|column| column.min_width).collect(),
+            sortable,
+            selectable: Selectable::None,
+            body: Entity::null(),
+            sort_rows: Box::new(move |cx: &EventContext, sort: Option<(usize, SortDirection)>| {
+                let Some(list) = list.get_ref(cx) else {
+                    return Vec::new();
+                };
+
+                let compare = sort.and_then(|(column, direction)| {
+                    Some((compare.get(column)?.as_deref()?, direction))
+                });
+
+                sorted_rows(&list, compare).into_iter().map(|row| (row, id(&list[row]))).collect()
+            }),
+            on_select: None,
+        }
+        .build(cx, |cx| {
+            HStack::new(cx, |cx| {
+                HStack::new(cx, |cx| {
+                    for (index, column) in columns.iter().enumerate() {
+                        TableHeader::new::<K, T>(cx, index, column);
+                    }
+                })
+                .class("header-row")
+                .width(Self::root.map(|table| Pixels(table.total_width())))
+                .left(Self::root.map(|table| Pixels(-table.header_offset())));
+            })
+            .class("table-head");
+
+            body = ScrollView::new(cx, 0.0, 0.0, true, true, move |cx| {
+                // The rows are placed within a stack which is sized to fit every row, so that the
+                // scrollbars are correct.
+                VStack::new(cx, move |cx| {
+                    let num_visible_rows = Self::visible_range.map(Range::len);
+                    Binding::new(cx, num_visible_rows, move |cx, num_visible_rows| {
+                        for index in 0..num_visible_rows.get(cx) {
+                            let columns = columns.clone();
+                            let slot = Self::root.map(move |table| table.slot(index));
+                            Binding::new(cx, slot, move |cx, slot| {
+                                if let Some((row, data, id)) = slot.get(cx) {
+                                    let columns = columns.clone();
+                                    TableRow::new(cx, list, columns, row, data, id, row_height);
+                                }
+                            });
+                        }
+                    });
+                })
+                .width(Self::root.map(|table| Pixels(table.total_width())))
+                .height(Self::rows.map(move |rows| Pixels(rows.len() as f32 * row_height)));
+            })
+            .on_scroll(|cx, x, y| {
+                if x.is_finite() && y.is_finite() {
+                    cx.emit(TableUpdate::Scroll(x, y));
+                }
+            })
+            .entity();
+        })
+        .modify(|table| table.body = body)
+        .bind(list, |mut handle, _| {
+            let entity = handle.entity();
+            handle.context().emit_to(entity, TableUpdate::Refresh);
+        })
+        .navigable(true)
+        .role(Role::Table)
+    }
+
+    fn total_width(&self) -> f32 {
+        self.widths.iter().sum()
+    }
+
+    // Returns how far the columns are scrolled to the left, in logical pixels.
+    fn header_offset(&self) -> f32 {
+        (self.total_width() - self.viewport_width).max(0.0) * self.scroll_x
+    }
+
+    // Returns the offset of a column from the left edge of the first column, in logical pixels.
+    fn column_left(&self, column: usize) -> f32 {
+        self.order
+            .iter()
+            .take_while(|other| **other != column)
+            .map(|other| self.widths[*other])
+            .sum()
+    }
+
+    // Returns the position in the order of the columns which a column dragged to the given offset,
+    // in logical pixels from the left edge of the first column, is moved to.
+    fn drop_position(&self, column: usize, x: f32) -> usize {
+        let mut left = 0.0;
+        let mut position = 0;
+        for other in self.order.iter().copied() {
+            let width = self.widths[other];
+            if other != column && left + width / 2.0 < x {
+                position += 1;
+            }
+
+            left += width;
+        }
+
+        position
+    }
+
+    // Returns the position of the row, its index into the data and its id, which is shown by the
+    // row view at the given index within the visible range.
+    fn slot(&self, index: usize) -> Option<(usize, usize, K)> {
+        let range = &self.visible_range;
+        if index >= range.len() {
+            return None;
+        }
+
+        let row = VirtualListData::evaluate_index(index, range.start, range.end);
+        Some((row, *self.rows.get(row)?, self.keys.get(row)?.clone()))
+    }
+
+    // Returns the position of the focused row in the order the rows are shown, and its column.
+    fn focused_cell(&self) -> Option<(usize, usize)> {
+        let (id, column) = self.focused.as_ref()?;
+        Some((self.keys.iter().position(|other| other == id)?, *column))
+    }
+
+    fn recalc(&mut self, cx: &mut EventContext) {
+        let bounds = cx.cache.get_bounds(self.body);
+        if bounds.h == f32::MAX {
+            return;
+        }
+
+        let scale_factor = cx.scale_factor();
+        self.viewport_width = bounds.w / scale_factor;
+        self.visible_range =
+            visible_rows(self.rows.len(), self.row_height, bounds.h / scale_factor, self.scroll_y);
+    }
+
+    // Sorts the rows again and deselects the rows which were removed. The focus stays on the same
+    // row, or moves to the row shown in its place if it was removed.
+    fn update_rows(&mut self, cx: &mut EventContext) {
+        let focused = self.focused_cell();
+
+        (self.rows, self.keys) = (self.sort_rows)(cx, self.sort).into_iter().unzip();
+
+        let keys = self.keys.iter().collect::<HashSet<_>>();
+        let num_selected = self.selection.selected.len();
+        self.selection.retain(|id| keys.contains(id));
+        let deselected = self.selection.selected.len() != num_selected;
+
+        self.focused = self.focused.take().and_then(|(id, column)| {
+            if keys.contains(&id) {
+                return Some((id, column));
+            }
+
+            let row = focused?.0.min(self.keys.len().checked_sub(1)?);
+            Some((self.keys[row].clone(), column))
+        });
+
+        if deselected {
+            if let Some(callback) = &self.on_select {
+                (callback)(cx, &self.selection.selected);
+            }
+        }
+
+        self.recalc(cx);
+    }
+
+    fn set_sort(&mut self, cx: &mut EventContext, sort: Option<(usize, SortDirection)>) {
+        self.sort = sort.filter(|(column, _)| self.sortable.get(*column) == Some(&true));
+        self.update_rows(cx);
+    }
+
+    // Selects the row at the given position, either toggling whether it is selected or extending
+    // the selection to it from the anchor if more than one row can be selected.
+    fn select(&mut self, cx: &mut EventContext, row: usize, toggle: bool, extend: bool) {
+        let Some(id) = self.keys.get(row) else {
+            return;
+        };
+
+        if self.selection.select(self.selectable, &self.keys, id, toggle, extend) {
+            if let Some(callback) = &self.on_select {
+                (callback)(cx, &self.selection.selected);
+            }
+        }
+    }
+
+    fn set_selection(&mut self, cx: &mut EventContext, selected: Vec<K>) {
+        self.selection.set(selected);
+        if let Some(callback) = &self.on_select {
+            (callback)(cx, &self.selection.selected);
+        }
+    }
+
+    // Moves the focused cell by a number of rows and columns, stopping at the edges of the table.
+    // Moving to another row selects it, extends the selection with Shift, or only moves the focus
+    // with the command modifier held.
+    fn move_focus(&mut self, cx: &mut EventContext, rows: isize, columns: isize) {
+        let (num_rows, num_columns) = (self.rows.len(), self.order.len());
+        if num_rows == 0 || num_columns == 0 {
+            return;
+        }
+
+        let focused = self.focused_cell();
+        let (row, position) = match focused {
+            Some((row, column)) => {
+                let position = self.order.iter().position(|other| *other == column).unwrap_or(0);
+                (move_index(row, rows, num_rows), move_index(position, columns, num_columns))
+            }
+
+            // Nothing is focused yet, so the first cell is focused.
+            None => (0, 0),
+        };
+
+        self.focused = Some((self.keys[row].clone(), self.order[position]));
+
+        if focused.map(|(row, _)| row) != Some(row) {
+            if cx.modifiers.shift() {
+                self.select(cx, row, false, true);
+            } else if !cx.modifiers.contains(Modifiers::COMMAND) {
+                self.select(cx, row, false, false);
+            }
+        }
+
+        self.scroll_to_focused(cx);
+    }
+
+    fn scroll_to_focused(&mut self, cx: &mut EventContext) {
+        let Some((row, column)) = self.focused_cell() else {
+            return;
+        };
+
+        let Some(content) = cx.tree.get_layout_first_child(self.body) else {
+            return;
+        };
+
+        let scale_factor = cx.scale_factor();
+        let bounds = cx.cache.get_bounds(content);
+        let rect = BoundingBox {
+            x: bounds.x + self.column_left(column) * scale_factor,
+            y: bounds.y + row as f32 * self.row_height * scale_factor,
+            w: self.widths[column] * scale_factor,
+            h: self.row_height * scale_factor,
+        };
+
+        cx.emit_to(self.body, ScrollEvent::ScrollIntoView(rect));
+    }
+
+    // Returns the number of rows which Page Up and Page Down move the focus by.
+    fn page_size(&self) -> isize {
+        self.visible_range.len().saturating_sub(2).max(1) as isize
+    }
+}
+
+impl<K: Data + Eq + Hash> View for Table<K> {
+    fn element(&self) -> Option<&'static str> {
+        Some("table")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|table_event, _| match table_event {
+            TableEvent::ToggleSort(column) => {
+                if self.sortable.get(*column) == Some(&true) {
+                    self.set_sort(cx, next_sort(self.sort, *column));
+                }
+            }
+
+            TableEvent::SetSort(sort) => {
+                self.set_sort(cx, *sort);
+            }
+
+            TableEvent::ResizeColumn(column, width) => {
+                if let Some(current) = self.widths.get_mut(*column) {
+                    *current = width.max(self.min_widths[*column]);
+                }
+            }
+
+            TableEvent::MoveColumn(column, position) => {
+                move_column(&mut self.order, *column, *position);
+            }
+
+            TableEvent::SelectAll => {
+                if self.selectable == Selectable::Multi {
+                    self.set_selection(cx, self.keys.clone());
+                }
+            }
+
+            TableEvent::ClearSelection => {
+                self.set_selection(cx, Vec::new());
+            }
+        });
+
+        event.map(|table_update, _| match table_update {
+            TableUpdate::Press(row, column) => {
+                let Some(id) = self.keys.get(*row) else {
+                    return;
+                };
+
+                cx.focus_with_visibility(false);
+                self.focused = Some((id.clone(), *column));
+                let toggle = cx.modifiers.contains(Modifiers::COMMAND);
+                self.select(cx, *row, toggle, cx.modifiers.shift());
+            }
+
+            TableUpdate::Scroll(x, y) => {
+                self.scroll_x = *x;
+                self.scroll_y = *y;
+                self.recalc(cx);
+            }
+
+            TableUpdate::DragColumn(column, offset) => {
+                let position = self.drop_position(*column, *offset);
+                if self.order.get(position) != Some(column) {
+                    move_column(&mut self.order, *column, position);
+                }
+            }
+
+            TableUpdate::Refresh => {
+                self.update_rows(cx);
+            }
+        });
+
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::GeometryChanged(geo) => {
+                if geo.intersects(GeoChanged::WIDTH_CHANGED | GeoChanged::HEIGHT_CHANGED) {
+                    self.recalc(cx);
+                }
+            }
+
+            WindowEvent::KeyDown(code, _) => {
+                let (rows, columns) = match code {
+                    Code::ArrowUp => (-1, 0),
+                    Code::ArrowDown => (1, 0),
+                    Code::ArrowLeft => (0, -1),
+                    Code::ArrowRight => (0, 1),
+                    Code::PageUp => (-self.page_size(), 0),
+                    Code::PageDown => (self.page_size(), 0),
+                    Code::Home if cx.modifiers.ctrl() => (isize::MIN, 0),
+                    Code::End if cx.modifiers.ctrl() => (isize::MAX, 0),
+                    Code::Home => (0, isize::MIN),
+                    Code::End => (0, isize::MAX),
+
+                    Code::Space => {
+                        if let Some((row, _)) = self.focused_cell() {
+                            self.select(cx, row, true, false);
+                        }
+                        meta.consume();
+                        return;
+                    }
+
+                    Code::KeyA if *cx.modifiers == Modifiers::COMMAND => {
+                        cx.emit(TableEvent::SelectAll);
+                        meta.consume();
+                        return;
+                    }
+
+                    Code::Escape => {
+                        cx.emit(TableEvent::ClearSelection);
+                        return;
+                    }
+
+                    _ => return,
+                };
+
+                self.move_focus(cx, rows, columns);
+                meta.consume();
+            }
+
+            _ => {}
+        });
+    }
+}
+
+impl<K: Data + Eq + Hash> Handle<'_, Table<K>> {
+    /// Sets whether the rows of the table can be selected, and whether more than one row can be
+    /// selected. Rows can't be selected by default.
+    pub fn selectable(self, selectable: impl Res<Selectable>) -> Self {
+        self.bind(selectable, |handle, selectable| {
+            let selectable = selectable.get(&handle);
+            handle.modify(|table| table.selectable = selectable);
+        })
+    }
+
+    /// Sets a callback which is called with the ids of the selected rows when the selection
+    /// changes.
+    pub fn on_select(self, callback: impl 'static + Fn(&mut EventContext, &[K])) -> Self {
+        self.modify(|table| table.on_select = Some(Box::new(callback)))
+    }
+}
+
+// The header of a column, which sorts the rows when clicked and moves the column when dragged.
+struct TableHeader {
+    column: usize,
+    // The horizontal position of the cursor, in physical pixels, when the header was pressed.
+    press: Option<f32>,
+    dragging: bool,
+}
+
+impl TableHeader {
+    fn new<'a, K: Data, T>(
+        cx: &'a mut Context,
+        column: usize,
+        definition: &TableColumn<T>,
+    ) -> Handle<'a, Self> {
+        Self { column, press: None, dragging: false }
+            .build(cx, |cx| {
+                (definition.header)(cx);
+
+                let direction = Table::<K>::sort
+                    .map(move |sort| sort.filter(|(sorted, _)| *sorted == column).map(|s| s.1));
+                Binding::new(cx, direction, |cx, direction| {
+                    if let Some(direction) = direction.get(cx) {
+                        let icon = match direction {
+                            SortDirection::Ascending => ICON_ARROW_UP,
+                            SortDirection::Descending => ICON_ARROW_DOWN,
+                        };
+                        Svg::new(cx, icon).class("sort-indicator");
+                    }
+                });
+
+                ResizeHandle { column, drag: None }.build(cx, |_| {});
+            })
+            .toggle_class("sortable", definition.compare.is_some())
+            .width(Table::<K>::widths.map(move |widths| Pixels(widths[column])))
+            .left(Table::<K>::root.map(move |table| Pixels(table.column_left(column))))
+            .position_type(PositionType::SelfDirected)
+            .role(Role::ColumnHeader)
+    }
+}
+
+impl View for TableHeader {
+    fn element(&self) -> Option<&'static str> {
+        Some("table-header")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, _| match window_event {
+            WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
+                self.press = Some(cx.mouse().cursor_x);
+                cx.capture();
+            }
+
+            WindowEvent::MouseMove(x, _) => {
+                let Some(press) = self.press else {
+                    return;
+                };
+
+                let scale_factor = cx.scale_factor();
+                if !self.dragging && (x - press).abs() > DRAG_THRESHOLD * scale_factor {
+                    self.dragging = true;
+                    cx.toggle_class("dragging", true);
+                }
+
+                if self.dragging {
+                    let offset = (x - cx.cache.get_posx(cx.parent())) / scale_factor;
+                    cx.emit(TableUpdate::DragColumn(self.column, offset));
+                }
+            }
+
+            WindowEvent::MouseUp(button) if *button == MouseButton::Left => {
+                if self.press.take().is_some() {
+                    cx.release();
+                    if self.dragging {
+                        self.dragging = false;
+                        cx.toggle_class("dragging", false);
+                    } else if cx.is_over() {
+                        cx.emit(TableEvent::ToggleSort(self.column));
+                    }
+                }
+            }
+
+            _ => {}
+        });
+    }
+}
+
+// The right edge of a column header, which resizes the column when dragged.
+struct ResizeHandle {
+    column: usize,
+    // The horizontal position of the cursor, in physical pixels, and the width of the column, in
+    // logical pixels, when the handle was pressed.
+    drag: Option<(f32, f32)>,
+}
+
+impl View for ResizeHandle {
+    fn element(&self) -> Option<&'static str> {
+        Some("resize-handle")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
+                let width = cx.cache.get_width(cx.parent()) / cx.scale_factor();
+                self.drag = Some((cx.mouse().cursor_x, width));
+                cx.capture();
+                // The header behind the handle is not pressed.
+                meta.consume();
+            }
+
+            WindowEvent::MouseMove(x, _) => {
+                if let Some((press, width)) = self.drag {
+                    let width = width + (x - press) / cx.scale_factor();
+                    cx.emit(TableEvent::ResizeColumn(self.column, width));
+                    meta.consume();
+                }
+            }
+
+            WindowEvent::MouseUp(button) if *button == MouseButton::Left => {
+                if self.drag.take().is_some() {
+                    cx.release();
+                    meta.consume();
+                }
+            }
+
+            _ => {}
+        });
+    }
+}
+
+// A row of cells showing an item of the data.
+struct TableRow {}
+
+impl TableRow {
+    fn new<L, T, K>(
+        cx: &mut Context,
+        list: L,
+        columns: Rc<[TableColumn<T>]>,
+        row: usize,
+        data: usize,
+        id: K,
+        row_height: f32,
+    ) -> Handle<Self>
+    where
+        L: Lens<Target = Vec<T>>,
+        T: Data,
+        K: Data + Eq + Hash,
+    {
+        let item = list.map(move |list| list.get(data).cloned());
+        let selected = {
+            let id = id.clone();
+            Table::<K>::root.map(move |table| table.selection.contains(&id))
+        };
+
+        Self {}
+            .build(cx, move |cx| {
+                Binding::new(cx, item, move |cx, item| {
+                    let Some(item) = item.get(cx) else {
+                        return;
+                    };
+
+                    let columns = columns.clone();
+                    let id = id.clone();
+                    Binding::new(cx, Table::<K>::order, move |cx, order| {
+                        for column in order.get(cx) {
+                            let id = id.clone();
+                            let focused = Table::<K>::focused.map(move |focused| {
+                                matches!(focused, Some((other, c)) if *other == id && *c == column)
+                            });
+
+                            HStack::new(cx, |cx| (columns[column].cell)(cx, &item))
+                                .class("cell")
+                                .width(Table::<K>::widths.map(move |widths| Pixels(widths[column])))
+                                .toggle_class("focused", focused)
+                                .on_mouse_down(move |cx, button| {
+                                    if button == MouseButton::Left {
+                                        cx.emit(TableUpdate::Press(row, column));
+                                    }
+                                })
+                                .role(Role::Cell);
+                        }
+                    });
+                });
+            })
+            .height(Pixels(row_height))
+            .top(Pixels(row as f32 * row_height))
+            .position_type(PositionType::SelfDirected)
+            .checked(selected)
+            .role(Role::Row)
+    }
+}
+
+impl View for TableRow {
+    fn element(&self) -> Option<&'static str> {
+        Some("table-row")
+    }
+}
+
+// Returns the sort after clicking the header of a column, which cycles from ascending to
+// descending order and then to the order of the data.
+fn next_sort(
+    sort: Option<(usize, SortDirection)>,
+    column: usize,
+) -> Option<(usize, SortDirection)> {
+    match sort {
+        Some((sorted, SortDirection::Ascending)) if sorted == column => {
+            Some((column, SortDirection::Descending))
+        }
+        Some((sorted, SortDirection::Descending)) if sorted == column => None,
+        _ => Some((column, SortDirection::Ascending)),
+    }
+}
+
+// Returns the indices of the items of a list in the order given by a comparison. Items which are
+// equal stay in the order of the list.
+fn sorted_rows<T>(
+    list: &[T],
+    compare: Option<(&dyn Fn(&T, &T) -> Ordering, SortDirection)>,
+) -> Vec<usize> {
+    let mut rows = (0..list.len()).collect::<Vec<_>>();
+    if let Some((compare, direction)) = compare {
+        rows.sort_by(|a, b| match direction {
+            SortDirection::Ascending => compare(&list[*a], &list[*b]),
+            SortDirection::Descending => compare(&list[*b], &list[*a]),
+        });
+    }
+
+    rows
+}
+
+fn move_column(order: &mut Vec<usize>, column: usize, position: usize) {
+    if let Some(index) = order.iter().position(|other| *other == column) {
+        order.remove(index);
+        order.insert(position.min(order.len()), column);
+    }
+}
+
+fn move_index(index: usize, delta: isize, len: usize) -> usize {
+    index.saturating_add_signed(delta).min(len - 1)
+}
+
+// Returns the positions of the rows which are at least partly visible, given the height of the
+// rows and of the visible area in logical pixels, and the progress of the vertical scroll.
+fn visible_rows(num_rows: usize, row_height: f32, height: f32, scroll_y: f32) -> Range<usize> {
+    if num_rows == 0 || row_height <= 0.0 {
+        return 0..0;
+    }
+
+    let offset = (row_height * num_rows as f32 - height).max(0.0) * scroll_y;
+    let start = ((offset / row_height).floor() as usize).min(num_rows);
+    let end = (((offset + height) / row_height).ceil() as usize).clamp(start, num_rows);
+
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_cycle() {
+        let ascending = Some((1, SortDirection::Ascending));
+        let descending = Some((1, SortDirection::Descending));
+        assert_eq!(next_sort(None, 1), ascending);
+        assert_eq!(next_sort(ascending, 1), descending);
+        assert_eq!(next_sort(descending, 1), None);
+        assert_eq!(next_sort(descending, 0), Some((0, SortDirection::Ascending)));
+    }
+
+    #[test]
+    fn sorting_rows() {
+        let list = [(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd')];
+        let compare = |a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0);
+        assert_eq!(sorted_rows(&list, None), [0, 1, 2, 3]);
+        assert_eq!(sorted_rows(&list, Some((&compare, SortDirection::Ascending))), [1, 3, 0, 2]);
+        assert_eq!(sorted_rows(&list, Some((&compare, SortDirection::Descending))), [0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn moving_columns() {
+        let mut order = vec![0, 1, 2, 3];
+        move_column(&mut order, 0, 2);
+        assert_eq!(order, [1, 2, 0, 3]);
+        move_column(&mut order, 3, 0);
+        assert_eq!(order, [3, 1, 2, 0]);
+        move_column(&mut order, 1, 10);
+        assert_eq!(order, [3, 2, 0, 1]);
+    }
+
+    #[test]
+    fn visible_range() {
+        assert_eq!(visible_rows(0, 20.0, 100.0, 0.0), 0..0);
+        assert_eq!(visible_rows(3, 20.0, 100.0, 0.5), 0..3);
+        assert_eq!(visible_rows(100, 20.0, 100.0, 0.0), 0..5);
+        assert_eq!(visible_rows(100, 20.0, 110.0, 0.0), 0..6);
+        assert_eq!(visible_rows(100, 20.0, 100.0, 1.0), 95..100);
+        assert_eq!(visible_rows(100, 20.0, 100.0, 0.5), 47..53);
+    }
+}
@@ -0,0 +1,1733 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle, TextBox};
+use skia_safe::{Paint, PaintStyle, Rect};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::textbox::{child_space, match_color, text_offset};
+use crate::prelude::*;
+use crate::text::{
+    caret_position, find_match, highlighted_spans, Direction, EditHistory, EditKind, Movement,
+    Selection, VerticalMovement,
+};
+
+/// Events for controlling a [`TextEditor`].
+///
+/// Editing the text uses the same [`TextEvent`]s as the [`Textbox`].
+pub enum TextEditorEvent {
+    /// Moves the caret to the start of the line with the given index, counting from zero, and
+    /// scrolls the line into view.
+    GoToLine(usize),
+}
+
+// Sent by the scroll view of a text editor when it is scrolled or resized.
+enum ViewportEvent {
+    SetScrollY(f32),
+}
+
+/// A view for editing large amounts of plain text, such as logs or configuration files.
+///
+/// The text is stored in a [`Rope`], so that edits stay fast however long the text gets. Lines
+/// are laid out separately at a fixed height, and only the lines which are scrolled into view are
+/// built, so an edit only lays out again the lines it changed. Lines are not wrapped, and the
+/// text is laid out left-to-right.
+///
+/// The Tab key indents the text with spaces up to the next tab stop, or indents the selected
+/// lines, and Shift+Tab removes a level of indentation from them. A new line keeps the
+/// indentation of the line before it. Sending a [`TextEditorEvent::GoToLine`] to the editor moves
+/// the caret to a line.
+///
+/// The syntax of the text can be highlighted by a [`Highlighter`], which only highlights again
+/// the lines which an edit changes.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     text: Rope,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { text: Rope::from_str("[package]\nname = \"vizia\"") }.build(cx);
+/// #
+/// TextEditor::new(cx, AppData::text)
+///     .line_numbers(true)
+///     .tab_width(2)
+///     .highlighter(ConfigHighlighter::Toml)
+///     .on_change(|_, text| println!("{} lines", text.len_lines()));
+/// ```
+#[derive(Lens)]
+pub struct TextEditor {
+    rope: Rope,
+    edit: bool,
+    show_caret: bool,
+    // The caret and selection are given as char indices into the rope.
+    selection: Selection,
+    visible_lines: Range<usize>,
+    line_height: f32,
+    show_line_numbers: bool,
+    #[lens(ignore)]
+    caret_timer: Timer,
+    #[lens(ignore)]
+    tab_width: usize,
+    // The column of the caret to return to when moving up or down through shorter lines.
+    #[lens(ignore)]
+    column: Option<usize>,
+    // The vertical scroll position, and the height of the visible area in logical pixels.
+    #[lens(ignore)]
+    scroll_y: f32,
+    #[lens(ignore)]
+    viewport_height: f32,
+    #[lens(ignore)]
+    scroll_view: Entity,
+    #[lens(ignore)]
+    history: EditHistory<Rope>,
+    #[lens(ignore)]
+    on_change: Option<Box<dyn Fn(&mut EventContext, Rope) + Send + Sync>>,
+    #[lens(ignore)]
+    highlighter: Option<Box<dyn Highlighter>>,
+    // The highlighting of each line, which is updated for the lines which change after each edit.
+    #[lens(ignore)]
+    highlights: Highlights,
+    #[lens(ignore)]
+    search: Option<TextSearch>,
+    // The char ranges of the matches of the search, which are updated after each edit.
+    #[lens(ignore)]
+    matches: Vec<Range<usize>>,
+    #[lens(ignore)]
+    current_match: Option<usize>,
+}
+
+impl TextEditor {
+    /// Creates a new text editor for the text of the given lens.
+    pub fn new<L>(cx: &mut Context, lens: L) -> Handle<Self>
+    where
+        L: Lens<Target = Rope>,
+    {
+        let caret_timer = cx.environment().caret_timer;
+
+        let mut scroll_view = Entity::null();
+
+        Self {
+            rope: Rope::new(),
+            edit: false,
+            show_caret: true,
+            selection: Selection::caret(0),
+            visible_lines: 0..0,
+            line_height: 20.0,
+            show_line_numbers: false,
+            caret_timer,
+            tab_width: 4,
+            column: None,
+            scroll_y: 0.0,
+            viewport_height: 0.0,
+            scroll_view: Entity::null(),
+            history: EditHistory::default(),
+            on_change: None,
+            highlighter: None,
+            highlights: Highlights::new(),
+            search: None,
+            matches: Vec::new(),
+            current_match: None,
+        }
+        .build(cx, |cx| {
+            cx.add_listener(move |editor: &mut Self, cx, event| {
+                event.map(|window_event, meta| match window_event {
+                    WindowEvent::MouseDown(_) => {
+                        if editor.edit
+                            && meta.origin != cx.current()
+                            && !cx.hovered().is_descendant_of(cx.tree, cx.current())
+                        {
+                            cx.emit(TextEvent::EndEdit);
+                        }
+                    }
+
+                    // Tab indents the text instead of moving the focus to the next view.
+                    WindowEvent::KeyDown(Code::Tab, _) => {
+                        if editor.edit && !cx.is_read_only() {
+                            let dedent = cx.modifiers.shift();
+                            editor.reset_caret_timer(cx);
+                            editor.indent(cx, dedent);
+                            meta.consume();
+                        }
+                    }
+
+                    _ => {}
+                });
+            });
+
+            scroll_view = ScrollView::new(cx, 0.0, 0.0, false, true, |cx| {
+                VStack::new(cx, |cx| {
+                    // A view is built for each visible line. As the editor scrolls, the view of a
+                    // line which goes out of view is rebuilt for a line which comes into view.
+                    let num_visible_lines = Self::visible_lines.map(Range::len);
+                    Binding::new(cx, num_visible_lines, |cx, num_visible_lines| {
+                        for slot in 0..num_visible_lines.get(cx) {
+                            let line = Self::visible_lines
+                                .map(move |lines| line_for_slot(slot, lines.start, lines.end));
+                            Binding::new(cx, line, |cx, line| {
+                                let line = line.get(cx);
+                                HStack::new(cx, |cx| {
+                                    Label::new(cx, (line + 1).to_string())
+                                        .class("line-number")
+                                        .display(Self::show_line_numbers);
+                                    TextEditorLine::new(cx, line);
+                                })
+                                .class("row")
+                                .height(Self::line_height.map(|height| Pixels(*height)))
+                                .position_type(PositionType::SelfDirected)
+                                .top(
+                                    Self::line_height
+                                        .map(move |height| Pixels(line as f32 * height)),
+                                );
+                            });
+                        }
+                    });
+                })
+                .class("text-editor-content")
+                .height(
+                    Self::root
+                        .map(|editor| Pixels(editor.rope.len_lines() as f32 * editor.line_height)),
+                );
+            })
+            .on_scroll(|cx, _, y| {
+                if y.is_finite() {
+                    cx.emit(ViewportEvent::SetScrollY(y));
+                }
+            })
+            .entity();
+        })
+        .modify(|editor| editor.scroll_view = scroll_view)
+        .bind(lens, |handle, lens| {
+            let rope = lens.get(&handle);
+            handle.modify(|editor| editor.set_rope(rope));
+        })
+        .navigable(true)
+        .role(Role::MultilineTextInput)
+        .default_action_verb(DefaultActionVerb::Focus)
+    }
+
+    // Replaces the text with the bound text if it was changed by something other than the editor.
+    fn set_rope(&mut self, rope: Rope) {
+        if rope != self.rope {
+            let caret = self.selection.active.min(rope.len_chars());
+            self.selection = Selection::caret(caret);
+            self.rope = rope;
+            self.column = None;
+            self.history.clear();
+            self.update_visible_lines();
+            self.update_highlights();
+        }
+    }
+
+    fn update_visible_lines(&mut self) {
+        self.visible_lines = visible_lines(
+            self.rope.len_lines(),
+            self.line_height,
+            self.viewport_height,
+            self.scroll_y,
+        );
+    }
+
+    // Highlights every line of the text, keeping the highlighting of the lines which did not change.
+    fn update_highlights(&mut self) {
+        if let Some(highlighter) = &self.highlighter {
+            let lines = (0..self.rope.len_lines()).map(|line| line_text(&self.rope, line));
+            self.highlights.update(highlighter.as_ref(), lines);
+        }
+    }
+
+    fn update_matches(&mut self, cx: &mut EventContext) {
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        let matches = find_in_lines(&self.rope, search, 0..self.rope.len_lines());
+        self.set_matches(cx, matches);
+    }
+
+    // Searches the given lines again after an edit which changed the length of the text from
+    // `old_len` chars, keeping the matches before them and moving the matches after them.
+    fn update_line_matches(&mut self, cx: &mut EventContext, lines: Range<usize>, old_len: usize) {
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        let start = self.rope.line_to_char(lines.start);
+        let end = self.rope.line_to_char(lines.end);
+        let old_end = end + old_len - self.rope.len_chars();
+
+        let found = find_in_lines(&self.rope, search, lines);
+        let matches = splice_matches(&self.matches, start..old_end, end, found);
+        self.set_matches(cx, matches);
+    }
+
+    fn set_matches(&mut self, cx: &mut EventContext, matches: Vec<Range<usize>>) {
+        self.matches = matches;
+        self.current_match = self.matches.iter().position(|range| *range == self.selection.range());
+        cx.emit(FindBarEvent::SetMatches(self.current_match, self.matches.len()));
+        cx.needs_redraw();
+    }
+
+    // Selects the first match at or after the given char index, or with `backwards` the last match
+    // before it, and scrolls it into view.
+    fn select_match(&mut self, cx: &mut EventContext, offset: usize, backwards: bool) {
+        self.current_match = find_match(&self.matches, offset, backwards);
+        if let Some(range) = self.current_match.map(|index| self.matches[index].clone()) {
+            self.selection = Selection::new(range.start, range.end);
+            self.column = None;
+            self.history.break_merge();
+            self.scroll_to_caret(cx);
+        }
+
+        cx.emit(FindBarEvent::SetMatches(self.current_match, self.matches.len()));
+        cx.needs_redraw();
+    }
+
+    // Replaces the selected match as a single undo step, and selects the next match.
+    fn replace_match(&mut self, cx: &mut EventContext, replacement: &str) {
+        let Some(range) = self.current_match.map(|index| self.matches[index].clone()) else {
+            self.select_match(cx, self.selection.min(), false);
+            return;
+        };
+
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        // A match lies within a single line, which is searched again to expand the replacement.
+        let line = self.rope.char_to_line(range.start);
+        let start = self.rope.line_to_char(line);
+        let slice = self.rope.line(line);
+        let bytes = slice.char_to_byte(range.start - start)..slice.char_to_byte(range.end - start);
+        let replacement = search.replacement(&Cow::from(slice), bytes, replacement);
+        self.replace(cx, range, &replacement, EditKind::Other);
+        self.select_match(cx, self.selection.active, false);
+    }
+
+    // Replaces every match as a single undo step.
+    fn replace_all(&mut self, cx: &mut EventContext, replacement: &str) {
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        let text = self.rope.to_string();
+        if let Some((range, replaced)) = search.replace_all(&text, replacement) {
+            let range = self.rope.byte_to_char(range.start)..self.rope.byte_to_char(range.end);
+            self.replace(cx, range, &replaced, EditKind::Other);
+        }
+    }
+
+    fn update_viewport(&mut self, cx: &mut EventContext) {
+        let height = cx.cache.get_height(self.scroll_view);
+        if height != f32::MAX {
+            self.viewport_height = height / cx.scale_factor();
+            self.update_visible_lines();
+        }
+    }
+
+    // Scrolls the line of the caret into view.
+    fn scroll_to_caret(&mut self, cx: &mut EventContext) {
+        let line = self.rope.char_to_line(self.selection.active);
+        if let Some(scroll_y) = scroll_to_line(
+            line,
+            self.rope.len_lines(),
+            self.line_height,
+            self.viewport_height,
+            self.scroll_y,
+        ) {
+            cx.emit_to(self.scroll_view, ScrollEvent::SetY(scroll_y));
+        }
+    }
+
+    // Returns the part of the selection and the caret which are drawn on a line.
+    fn line_cursor(&self, line: usize) -> LineCursor {
+        if line >= self.rope.len_lines() {
+            return LineCursor::default();
+        }
+
+        let start = self.rope.line_to_char(line);
+        let text = line_text(&self.rope, line);
+        let end = start + text.chars().count();
+        let (min, max) = (self.selection.min(), self.selection.max());
+
+        let mut cursor = LineCursor::default();
+
+        if !self.selection.is_caret() && min <= end && max > start {
+            let range = min.max(start) - start..max.min(end) - start;
+            cursor.selection =
+                Some(column_to_grapheme(&text, range.start)..column_to_grapheme(&text, range.end));
+            cursor.selects_line_break = max > end && line + 1 < self.rope.len_lines();
+        }
+
+        if self.edit && self.show_caret && self.rope.char_to_line(self.selection.active) == line {
+            cursor.caret = Some(column_to_grapheme(&text, self.selection.active - start));
+        }
+
+        // The matches are sorted, so only those from the first which ends after the start of the
+        // line are checked.
+        let first = self.matches.partition_point(|range| range.end <= start);
+        for (index, range) in self.matches.iter().enumerate().skip(first) {
+            if range.start > end {
+                break;
+            }
+
+            let range = range.start.max(start) - start..range.end.min(end) - start;
+            if !range.is_empty() {
+                cursor.matches.push((
+                    column_to_grapheme(&text, range.start)..column_to_grapheme(&text, range.end),
+                    self.current_match == Some(index),
+                ));
+            }
+        }
+
+        cursor
+    }
+
+    // Returns the char index of the end of a line, before its line break.
+    fn line_end(&self, line: usize) -> usize {
+        self.rope.line_to_char(line) + line_text(&self.rope, line).chars().count()
+    }
+
+    // Returns the char index which the caret is moved to by a movement.
+    fn movement_offset(&mut self, movement: Movement) -> usize {
+        let active = self.selection.active;
+        let line = self.rope.char_to_line(active);
+        let start = self.rope.line_to_char(line);
+        let text = line_text(&self.rope, line);
+        let column = active - start;
+        let last_line = self.rope.len_lines() - 1;
+
+        let lines_per_page = self.visible_lines.len().saturating_sub(1).max(1);
+
+        let target_line = match movement {
+            Movement::Line(Direction::Upstream | Direction::Left)
+            | Movement::Vertical(VerticalMovement::LineUp) => line.saturating_sub(1),
+            Movement::Line(Direction::Downstream | Direction::Right)
+            | Movement::Vertical(VerticalMovement::LineDown) => (line + 1).min(last_line),
+            Movement::Page(Direction::Upstream | Direction::Left)
+            | Movement::Vertical(VerticalMovement::PageUp) => line.saturating_sub(lines_per_page),
+            Movement::Page(Direction::Downstream | Direction::Right)
+            | Movement::Vertical(VerticalMovement::PageDown) => {
+                (line + lines_per_page).min(last_line)
+            }
+
+            Movement::Body(direction) => {
+                return if is_backwards(direction) { 0 } else { self.rope.len_chars() };
+            }
+
+            Movement::Vertical(VerticalMovement::DocumentStart) => return 0,
+
+            Movement::Vertical(VerticalMovement::DocumentEnd) => return self.rope.len_chars(),
+
+            Movement::Grapheme(direction) | Movement::Word(direction) => {
+                let word = matches!(movement, Movement::Word(_));
+                return if is_backwards(direction) {
+                    if column == 0 {
+                        if line == 0 {
+                            0
+                        } else {
+                            self.line_end(line - 1)
+                        }
+                    } else if word {
+                        start + prev_word(&text, column)
+                    } else {
+                        start + prev_grapheme(&text, column)
+                    }
+                } else if column >= text.chars().count() {
+                    if line < last_line {
+                        self.rope.line_to_char(line + 1)
+                    } else {
+                        active
+                    }
+                } else if word {
+                    start + next_word(&text, column)
+                } else {
+                    start + next_grapheme(&text, column)
+                };
+            }
+
+            // The caret moves to the start of the indentation first, and then to the start of the line.
+            Movement::LineStart => {
+                let indent = indent_width(&text);
+                return start + if column == indent { 0 } else { indent };
+            }
+
+            Movement::ParagraphStart => return start,
+
+            Movement::LineEnd | Movement::ParagraphEnd => return start + text.chars().count(),
+        };
+
+        // Moving up or down keeps the column the caret was in before it moved through shorter lines.
+        let column = *self.column.get_or_insert(column);
+        let target_start = self.rope.line_to_char(target_line);
+        target_start + column.min(line_text(&self.rope, target_line).chars().count())
+    }
+
+    fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        let vertical = matches!(
+            movement,
+            Movement::Line(_)
+                | Movement::Page(_)
+                | Movement::Vertical(
+                    VerticalMovement::LineUp
+                        | VerticalMovement::LineDown
+                        | VerticalMovement::PageUp
+                        | VerticalMovement::PageDown
+                )
+        );
+
+        if !vertical {
+            self.column = None;
+        }
+
+        let offset = self.movement_offset(movement);
+        if selection {
+            self.selection.active = offset;
+        } else {
+            self.selection = Selection::caret(offset);
+        }
+
+        self.history.break_merge();
+        self.scroll_to_caret(cx);
+        cx.needs_redraw();
+    }
+
+    fn select_all(&mut self, cx: &mut EventContext) {
+        self.selection = Selection::new(0, self.rope.len_chars());
+        cx.needs_redraw();
+    }
+
+    fn select_word(&mut self, cx: &mut EventContext) {
+        self.move_cursor(cx, Movement::Word(Direction::Upstream), false);
+        self.move_cursor(cx, Movement::Word(Direction::Downstream), true);
+    }
+
+    // Selects the line of the caret along with its line break.
+    fn select_line(&mut self, cx: &mut EventContext) {
+        let line = self.rope.char_to_line(self.selection.active);
+        let end = if line + 1 < self.rope.len_lines() {
+            self.rope.line_to_char(line + 1)
+        } else {
+            self.line_end(line)
+        };
+
+        self.selection = Selection::new(self.rope.line_to_char(line), end);
+        cx.needs_redraw();
+    }
+
+    fn go_to_line(&mut self, cx: &mut EventContext, line: usize) {
+        let line = line.min(self.rope.len_lines() - 1);
+        self.selection = Selection::caret(self.rope.line_to_char(line));
+        self.column = None;
+        self.history.break_merge();
+        self.scroll_to_caret(cx);
+        cx.needs_redraw();
+    }
+
+    // Applies a change which replaces the given byte range of the text with `len` bytes and returns
+    // the new selection, then highlights and searches again only the lines which it changed.
+    fn apply(
+        &mut self,
+        cx: &mut EventContext,
+        range: Range<usize>,
+        len: usize,
+        change: impl FnOnce(&mut Self) -> Option<Selection>,
+    ) {
+        let old_lines = self.rope.byte_to_line(range.start)..self.rope.byte_to_line(range.end) + 1;
+        let old_len = self.rope.len_chars();
+
+        let Some(selection) = change(self) else {
+            return;
+        };
+
+        let lines =
+            self.rope.byte_to_line(range.start)..self.rope.byte_to_line(range.start + len) + 1;
+        self.selection = selection;
+        self.column = None;
+        self.update_visible_lines();
+
+        if let Some(highlighter) = &self.highlighter {
+            let rope = &self.rope;
+            self.highlights
+                .splice(highlighter.as_ref(), old_lines, lines.len(), |line| line_text(rope, line));
+        }
+
+        self.update_line_matches(cx, lines, old_len);
+        self.scroll_to_caret(cx);
+
+        if let Some(callback) = &self.on_change {
+            (callback)(cx, self.rope.clone());
+        }
+
+        cx.needs_redraw();
+    }
+
+    // Replaces a range of chars with some text and the selection, recording the edit so it can be undone.
+    fn edit(
+        &mut self,
+        cx: &mut EventContext,
+        range: Range<usize>,
+        text: &str,
+        kind: EditKind,
+        selection: Selection,
+    ) {
+        if range.is_empty() && text.is_empty() {
+            return;
+        }
+
+        let bytes = self.rope.char_to_byte(range.start)..self.rope.char_to_byte(range.end);
+        self.apply(cx, bytes.clone(), text.len(), |editor| {
+            let before = editor.selection;
+            editor.history.edit(&mut editor.rope, bytes, text, kind, before, selection);
+            Some(selection)
+        });
+    }
+
+    // Replaces a range of chars with some text, placing the caret after the inserted text.
+    fn replace(&mut self, cx: &mut EventContext, range: Range<usize>, text: &str, kind: EditKind) {
+        let selection = Selection::caret(range.start + text.chars().count());
+        self.edit(cx, range, text, kind, selection);
+    }
+
+    fn insert_text(&mut self, cx: &mut EventContext, text: &str) {
+        // Only typing into the text is merged into a single undo step.
+        let kind = if self.selection.is_caret() && text.graphemes(true).count() == 1 {
+            EditKind::Insert
+        } else {
+            EditKind::Other
+        };
+
+        self.replace(cx, self.selection.range(), text, kind);
+    }
+
+    // Starts a new line with the indentation of the line of the caret.
+    fn insert_line_break(&mut self, cx: &mut EventContext) {
+        let min = self.selection.min();
+        let line = self.rope.char_to_line(min);
+        let column = min - self.rope.line_to_char(line);
+        let text = line_text(&self.rope, line);
+        let indent = text.chars().take(indent_width(&text).min(column)).collect::<String>();
+
+        self.replace(cx, self.selection.range(), &format!("\n{}", indent), EditKind::Other);
+    }
+
+    fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        if !self.selection.is_caret() {
+            self.replace(cx, self.selection.range(), "", EditKind::Other);
+            return;
+        }
+
+        let active = self.selection.active;
+        let line = self.rope.char_to_line(active);
+        let column = active - self.rope.line_to_char(line);
+        let text = line_text(&self.rope, line);
+
+        let offset = match movement {
+            // Deleting backwards through the indentation removes a level of indentation at a time.
+            Movement::Grapheme(Direction::Upstream)
+                if column > 0 && text.chars().take(column).all(|c| c == ' ') =>
+            {
+                active - ((column - 1) % self.tab_width + 1)
+            }
+
+            _ => self.movement_offset(movement),
+        };
+
+        self.replace(cx, offset.min(active)..offset.max(active), "", EditKind::Delete);
+    }
+
+    // Indents the selected lines, or removes a level of indentation from them. Without a selection
+    // across lines, indenting inserts spaces up to the next tab stop instead.
+    fn indent(&mut self, cx: &mut EventContext, dedent: bool) {
+        let (min, max) = (self.selection.min(), self.selection.max());
+        let first = self.rope.char_to_line(min);
+        let mut last = self.rope.char_to_line(max);
+
+        // A line is not indented if the selection ends at its start.
+        if last > first && max == self.rope.line_to_char(last) {
+            last -= 1;
+        }
+
+        if !dedent && first == last {
+            let column = min - self.rope.line_to_char(first);
+            let spaces = " ".repeat(self.tab_width - column % self.tab_width);
+            self.replace(cx, self.selection.range(), &spaces, EditKind::Other);
+            return;
+        }
+
+        // The lines are replaced by a single edit, so that indenting them is a single undo step.
+        let mut lines = Vec::new();
+        let mut selection = self.selection;
+        let mut changed = false;
+
+        // Lines are visited from the last to the first, so that the selection is moved by the
+        // lines before it.
+        for line in (first..=last).rev() {
+            let start = self.rope.line_to_char(line);
+            let mut text = self.rope.line(line).to_string();
+            let content = line_text(&self.rope, line);
+
+            if dedent {
+                let width = dedent_width(&content, self.tab_width);
+                if width > 0 {
+                    text.drain(..width);
+                    for offset in [&mut selection.anchor, &mut selection.active] {
+                        if *offset > start {
+                            *offset -= (*offset - start).min(width);
+                        }
+                    }
+
+                    changed = true;
+                }
+            } else if !content.trim().is_empty() {
+                text.insert_str(0, &" ".repeat(self.tab_width));
+                for offset in [&mut selection.anchor, &mut selection.active] {
+                    if *offset >= start {
+                        *offset += self.tab_width;
+                    }
+                }
+
+                changed = true;
+            }
+
+            lines.push(text);
+        }
+
+        if changed {
+            let range = self.rope.line_to_char(first)..self.rope.line_to_char(last + 1);
+            let text = lines.into_iter().rev().collect::<String>();
+            self.edit(cx, range, &text, EditKind::Other, selection);
+        }
+    }
+
+    fn reset_caret_timer(&mut self, cx: &mut EventContext) {
+        cx.stop_timer(self.caret_timer);
+        self.show_caret = !cx.is_read_only();
+        if self.show_caret {
+            cx.start_timer(self.caret_timer);
+        }
+    }
+
+    fn undo(&mut self, cx: &mut EventContext) {
+        if let Some((range, len)) = self.history.next_undo() {
+            self.apply(cx, range, len, |editor| editor.history.undo(&mut editor.rope));
+        }
+    }
+
+    fn redo(&mut self, cx: &mut EventContext) {
+        if let Some((range, len)) = self.history.next_redo() {
+            self.apply(cx, range, len, |editor| editor.history.redo(&mut editor.rope));
+        }
+    }
+
+    /// Returns the char index of the caret position closest to the given window-global physical coordinates.
+    fn offset_at(&self, cx: &EventContext, x: f32, y: f32) -> usize {
+        let scale_factor = cx.scale_factor();
+        let scroll_height =
+            (self.rope.len_lines() as f32 * self.line_height - self.viewport_height).max(0.0);
+        let top =
+            cx.cache.get_bounds(self.scroll_view).y - scroll_height * self.scroll_y * scale_factor;
+        let line = (((y - top) / (self.line_height * scale_factor)).max(0.0) as usize)
+            .min(self.rope.len_lines() - 1);
+
+        let text = line_text(&self.rope, line);
+
+        // The column is found from the layout of the view of the line, if the line is in view.
+        let column = self
+            .scroll_view
+            .branch_iter(cx.tree)
+            .find(|entity| {
+                cx.views
+                    .get(entity)
+                    .and_then(|view| view.downcast_ref::<TextEditorLine>())
+                    .is_some_and(|view| view.line == line)
+            })
+            .and_then(|entity| {
+                let paragraph = cx.text_context.text_paragraphs.get(entity)?;
+                let bounds = cx.cache.get_bounds(entity);
+                let (padding_left, _) =
+                    text_offset(bounds, paragraph, child_space(cx.style, entity), false);
+
+                let gp = paragraph.get_glyph_position_at_coordinate((
+                    x - bounds.x - padding_left,
+                    paragraph.height() / 2.0,
+                ));
+
+                Some(grapheme_to_column(&text, gp.position as usize))
+            })
+            .unwrap_or_default();
+
+        self.rope.line_to_char(line) + column
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
+        let offset = self.offset_at(cx, x, y);
+        if selection {
+            self.selection.active = offset;
+        } else {
+            self.selection = Selection::caret(offset);
+        }
+
+        self.column = None;
+        self.history.break_merge();
+        cx.needs_redraw();
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn drag(&mut self, cx: &mut EventContext, x: f32, y: f32) {
+        self.selection.active = self.offset_at(cx, x, y);
+        self.scroll_to_caret(cx);
+        cx.needs_redraw();
+    }
+}
+
+impl Handle<'_, TextEditor> {
+    /// Sets the callback triggered when the text is edited.
+    ///
+    /// Callback provides the new text.
+    pub fn on_change<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut EventContext, Rope) + Send + Sync,
+    {
+        self.modify(|editor| editor.on_change = Some(Box::new(callback)))
+    }
+
+    /// Sets whether the number of each line is shown in a gutter beside it.
+    pub fn line_numbers(self, flag: impl Res<bool>) -> Self {
+        self.bind(flag, |handle, flag| {
+            let flag = flag.get(&handle);
+            handle.modify(|editor| editor.show_line_numbers = flag);
+        })
+    }
+
+    /// Sets the number of spaces between tab stops, which the Tab key indents the text to.
+    /// Defaults to 4.
+    pub fn tab_width(self, width: usize) -> Self {
+        self.modify(|editor| editor.tab_width = width.max(1))
+    }
+
+    /// Sets the [`Highlighter`] used to highlight the syntax of the text. Each highlighted part of
+    /// a line is displayed with the `token` class and the class given by the highlighter.
+    pub fn highlighter(self, highlighter: impl Highlighter + 'static) -> Self {
+        self.modify(|editor| {
+            editor.highlighter = Some(Box::new(highlighter));
+            editor.highlights = Highlights::new();
+            editor.update_highlights();
+        })
+    }
+
+    /// Sets the height of each line of the editor in logical pixels. Defaults to 20.
+    pub fn line_height(self, height: f32) -> Self {
+        self.modify(|editor| {
+            editor.line_height = height;
+            editor.update_visible_lines();
+        })
+    }
+}
+
+impl View for TextEditor {
+    fn element(&self) -> Option<&'static str> {
+        Some("text-editor")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        // Window Events
+        event.map(|window_event, _| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left) => {
+                // The scrollbar is dragged without moving the caret.
+                let on_scrollbar = cx.hovered().parent_iter(cx.tree).any(|entity| {
+                    cx.views
+                        .get(&entity)
+                        .is_some_and(|view| view.downcast_ref::<Scrollbar>().is_some())
+                });
+
+                if cx.is_over() && !cx.is_disabled() && !on_scrollbar {
+                    cx.focus_with_visibility(false);
+                    cx.capture();
+                    cx.lock_cursor_icon();
+
+                    if !self.edit {
+                        cx.emit(TextEvent::StartEdit);
+                    }
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::Hit(
+                        cx.mouse.cursor_x,
+                        cx.mouse.cursor_y,
+                        cx.modifiers.shift(),
+                    ));
+                }
+            }
+
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                if cx.mouse.left.pressed == cx.current {
+                    self.reset_caret_timer(cx);
+                    cx.unlock_cursor_icon();
+                    cx.release();
+                }
+            }
+
+            WindowEvent::MouseMove(x, y) => {
+                if cx.mouse.left.state == MouseButtonState::Pressed
+                    && cx.mouse.left.pressed == cx.current
+                {
+                    if self.edit {
+                        self.reset_caret_timer(cx);
+                    }
+                    if cx.mouse.left.pos_down.0 != *x || cx.mouse.left.pos_down.1 != *y {
+                        cx.emit(TextEvent::Drag(cx.mouse.cursor_x, cx.mouse.cursor_y));
+                    }
+                }
+            }
+
+            WindowEvent::MouseDoubleClick(MouseButton::Left) => {
+                cx.emit(TextEvent::SelectWord);
+            }
+
+            WindowEvent::MouseTripleClick(MouseButton::Left) => {
+                cx.emit(TextEvent::SelectParagraph);
+            }
+
+            WindowEvent::FocusIn => {
+                cx.emit(TextEvent::StartEdit);
+            }
+
+            WindowEvent::FocusOut => {
+                cx.emit(TextEvent::EndEdit);
+            }
+
+            WindowEvent::GeometryChanged(_) => {
+                self.update_viewport(cx);
+            }
+
+            WindowEvent::ImeCommit(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::InsertText(text.clone()));
+                }
+            }
+
+            WindowEvent::CharInput(c) => {
+                if *c != '\u{1b}' && // Escape
+                    *c != '\u{8}' && // Backspace
+                    *c != '\u{9}' && // Tab
+                    *c != '\u{7f}' && // Delete
+                    *c != '\u{0d}' && // Carriage return
+                    !cx.modifiers.ctrl() &&
+                    !cx.modifiers.logo() &&
+                    self.edit &&
+                    !cx.is_read_only()
+                {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::InsertText(String::from(*c)));
+                }
+            }
+
+            WindowEvent::KeyDown(code, _) => {
+                let shortcut = cx.modifiers == &Modifiers::COMMAND;
+
+                match code {
+                    Code::Enter => {
+                        if self.edit && !cx.is_read_only() {
+                            self.reset_caret_timer(cx);
+                            self.insert_line_break(cx);
+                        }
+                    }
+
+                    Code::Space => {
+                        cx.emit(TextEvent::InsertText(String::from(" ")));
+                    }
+
+                    Code::ArrowLeft | Code::ArrowRight => {
+                        self.reset_caret_timer(cx);
+                        let direction = if *code == Code::ArrowLeft {
+                            Direction::Left
+                        } else {
+                            Direction::Right
+                        };
+                        let movement = if cx.modifiers.ctrl() {
+                            Movement::Word(direction)
+                        } else {
+                            Movement::Grapheme(direction)
+                        };
+
+                        cx.emit(TextEvent::MoveCursor(movement, cx.modifiers.shift()));
+                    }
+
+                    Code::ArrowUp | Code::ArrowDown => {
+                        self.reset_caret_timer(cx);
+                        let movement = if *code == Code::ArrowUp {
+                            VerticalMovement::LineUp
+                        } else {
+                            VerticalMovement::LineDown
+                        };
+
+                        cx.emit(TextEvent::MoveCursor(
+                            Movement::Vertical(movement),
+                            cx.modifiers.shift(),
+                        ));
+                    }
+
+                    Code::Backspace | Code::Delete => {
+                        self.reset_caret_timer(cx);
+                        if !cx.is_read_only() {
+                            let direction = if *code == Code::Backspace {
+                                Direction::Upstream
+                            } else {
+                                Direction::Downstream
+                            };
+
+                            cx.emit(TextEvent::DeleteText(if cx.modifiers.ctrl() {
+                                Movement::Word(direction)
+                            } else {
+                                Movement::Grapheme(direction)
+                            }));
+                        }
+                    }
+
+                    Code::Escape => {
+                        cx.emit(TextEvent::EndEdit);
+                    }
+
+                    Code::Home | Code::End => {
+                        self.reset_caret_timer(cx);
+                        let movement = match (*code == Code::Home, cx.modifiers.ctrl()) {
+                            (true, true) => Movement::Body(Direction::Upstream),
+                            (true, false) => Movement::LineStart,
+                            (false, true) => Movement::Body(Direction::Downstream),
+                            (false, false) => Movement::LineEnd,
+                        };
+
+                        cx.emit(TextEvent::MoveCursor(movement, cx.modifiers.shift()));
+                    }
+
+                    Code::PageUp | Code::PageDown => {
+                        self.reset_caret_timer(cx);
+                        let direction = if *code == Code::PageUp {
+                            Direction::Upstream
+                        } else {
+                            Direction::Downstream
+                        };
+
+                        cx.emit(TextEvent::MoveCursor(
+                            Movement::Page(direction),
+                            cx.modifiers.shift(),
+                        ));
+                    }
+
+                    Code::KeyA if shortcut => cx.emit(TextEvent::SelectAll),
+
+                    Code::KeyF if shortcut => {
+                        cx.emit(FindBarEvent::Open { replace: !cx.is_read_only() })
+                    }
+
+                    Code::KeyC if shortcut => cx.emit(TextEvent::Copy),
+
+                    Code::KeyV if shortcut => cx.emit(TextEvent::Paste),
+
+                    Code::KeyX if shortcut => cx.emit(TextEvent::Cut),
+
+                    Code::KeyZ => {
+                        if shortcut {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::Undo);
+                        } else if cx.modifiers == &(Modifiers::COMMAND | Modifiers::SHIFT) {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::Redo);
+                        }
+                    }
+
+                    #[cfg(not(target_os = "macos"))]
+                    Code::KeyY if shortcut => {
+                        self.reset_caret_timer(cx);
+                        cx.emit(TextEvent::Redo);
+                    }
+
+                    _ => {}
+                }
+            }
+
+            _ => {}
+        });
+
+        // Text Events
+        event.map(|text_event, _| match text_event {
+            TextEvent::InsertText(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.insert_text(cx, text);
+                }
+            }
+
+            TextEvent::DeleteText(movement) => {
+                if self.edit && !cx.is_read_only() {
+                    self.delete_text(cx, *movement);
+                }
+            }
+
+            TextEvent::MoveCursor(movement, selection) => {
+                if self.edit {
+                    self.move_cursor(cx, *movement, *selection);
+                }
+            }
+
+            TextEvent::SelectAll => self.select_all(cx),
+
+            TextEvent::SelectWord => self.select_word(cx),
+
+            TextEvent::SelectParagraph => self.select_line(cx),
+
+            TextEvent::StartEdit => {
+                if !cx.is_disabled() && !self.edit {
+                    self.edit = true;
+                    cx.focus_with_visibility(false);
+                    cx.set_checked(true);
+                    self.reset_caret_timer(cx);
+                    cx.needs_redraw();
+                }
+            }
+
+            TextEvent::EndEdit => {
+                if self.edit {
+                    self.edit = false;
+                    self.selection = Selection::caret(self.selection.active);
+                    cx.set_checked(false);
+                    cx.stop_timer(self.caret_timer);
+                    cx.needs_redraw();
+                }
+            }
+
+            TextEvent::Hit(x, y, selection) => self.hit(cx, *x, *y, *selection),
+
+            TextEvent::Drag(x, y) => self.drag(cx, *x, *y),
+
+            TextEvent::Copy =>
+            {
+                #[cfg(feature = "clipboard")]
+                if !self.selection.is_caret() {
+                    let copied = self.rope.slice(self.selection.range()).to_string();
+                    cx.set_clipboard(copied).expect("Failed to add text to clipboard");
+                }
+            }
+
+            TextEvent::Paste =>
+            {
+                #[cfg(feature = "clipboard")]
+                if self.edit && !cx.is_read_only() {
+                    if let Ok(text) = cx.get_clipboard() {
+                        self.replace(cx, self.selection.range(), &text, EditKind::Other);
+                    }
+                }
+            }
+
+            TextEvent::Cut =>
+            {
+                #[cfg(feature = "clipboard")]
+                if self.edit && !cx.is_read_only() && !self.selection.is_caret() {
+                    let copied = self.rope.slice(self.selection.range()).to_string();
+                    cx.set_clipboard(copied).expect("Failed to add text to clipboard");
+                    self.replace(cx, self.selection.range(), "", EditKind::Other);
+                }
+            }
+
+            TextEvent::Undo => {
+                if self.edit && !cx.is_read_only() {
+                    self.undo(cx);
+                }
+            }
+
+            TextEvent::Redo => {
+                if self.edit && !cx.is_read_only() {
+                    self.redo(cx);
+                }
+            }
+
+            TextEvent::ToggleCaret => {
+                self.show_caret ^= true;
+            }
+
+            _ => {}
+        });
+
+        event.map(|text_editor_event, _| match text_editor_event {
+            TextEditorEvent::GoToLine(line) => self.go_to_line(cx, *line),
+        });
+
+        // Search Events
+        event.map(|search_event, _| match search_event {
+            SearchEvent::Search(query) => {
+                self.search = TextSearch::new(query);
+                self.matches.clear();
+                self.current_match = None;
+                self.update_matches(cx);
+                self.select_match(cx, self.selection.min(), false);
+            }
+
+            SearchEvent::Next => {
+                self.select_match(cx, self.selection.max(), false);
+            }
+
+            SearchEvent::Previous => {
+                self.select_match(cx, self.selection.min(), true);
+            }
+
+            SearchEvent::Replace(replacement) => {
+                if !cx.is_read_only() && !cx.is_disabled() {
+                    self.replace_match(cx, replacement);
+                }
+            }
+
+            SearchEvent::ReplaceAll(replacement) => {
+                if !cx.is_read_only() && !cx.is_disabled() {
+                    self.replace_all(cx, replacement);
+                }
+            }
+
+            SearchEvent::Clear => {
+                self.search = None;
+                self.matches.clear();
+                self.current_match = None;
+                cx.needs_redraw();
+            }
+        });
+
+        event.map(|viewport_event, _| match viewport_event {
+            ViewportEvent::SetScrollY(scroll_y) => {
+                self.scroll_y = *scroll_y;
+                self.update_viewport(cx);
+            }
+        });
+    }
+}
+
+// The part of the selection and the caret of a text editor which are drawn on a line.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+struct LineCursor {
+    // The range of graphemes of the line which are selected.
+    selection: Option<Range<usize>>,
+    // Whether the selection continues onto the next line.
+    selects_line_break: bool,
+    // The grapheme offset of the caret, if the caret is shown on the line.
+    caret: Option<usize>,
+    // The ranges of graphemes of the line which match a search, and whether each is selected.
+    matches: Vec<(Range<usize>, bool)>,
+}
+
+// A line of the text of a text editor.
+struct TextEditorLine {
+    line: usize,
+    cursor: LineCursor,
+}
+
+impl TextEditorLine {
+    fn new(cx: &mut Context, line: usize) -> Handle<Self> {
+        Self { line, cursor: LineCursor::default() }
+            .build(cx, |cx| {
+                // The text of the line is displayed by a text span for each highlighted part.
+                let view = cx.current();
+                let text = TextEditor::root.map(move |editor| {
+                    (line_text(&editor.rope, line), editor.highlights.line(line).to_vec())
+                });
+                Binding::new(cx, text, move |cx, text| {
+                    let (text, spans) = text.get(cx);
+                    highlighted_spans(cx, &text, &spans);
+                    cx.style.needs_text_update(view);
+                });
+            })
+            .class("line")
+            .text("")
+            .bind(
+                TextEditor::root.map(move |editor| editor.line_cursor(line)),
+                |mut handle, cursor| {
+                    let cursor = cursor.get(&handle);
+                    handle.needs_redraw();
+                    handle.modify(|view| view.cursor = cursor);
+                },
+            )
+    }
+
+    // Returns the rectangles covering a range of graphemes of the line, relative to its bounds.
+    fn grapheme_rects(&self, cx: &DrawContext, graphemes: Range<usize>) -> Vec<TextBox> {
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return Vec::new();
+        };
+
+        let (padding_left, padding_top) = text_offset(
+            cx.bounds(),
+            paragraph,
+            [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+            false,
+        );
+
+        paragraph
+            .get_rects_for_range(graphemes, RectHeightStyle::Tight, RectWidthStyle::Tight)
+            .into_iter()
+            .map(|mut text_box| {
+                text_box.rect.offset((padding_left, padding_top));
+                text_box
+            })
+            .collect()
+    }
+
+    fn draw_selection(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some(selection) = self.cursor.selection.clone() else {
+            return;
+        };
+
+        let bounds = cx.bounds();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(cx.selection_color());
+
+        let mut rects = self.grapheme_rects(cx, selection.clone());
+
+        // The line break is shown as a selected space after the end of the line.
+        if self.cursor.selects_line_break {
+            if let Some(mut text_box) =
+                self.grapheme_rects(cx, selection.end..selection.end + 1).pop()
+            {
+                let left = caret_position(&text_box);
+                text_box.rect.left = left;
+                text_box.rect.right = left + cx.font_size() / 3.0;
+                rects.push(text_box);
+            }
+        }
+
+        for text_box in rects {
+            canvas.draw_rect(text_box.rect.with_offset((bounds.x, bounds.y)), &paint);
+        }
+    }
+
+    fn draw_matches(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let bounds = cx.bounds();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+
+        for (graphemes, selected) in &self.cursor.matches {
+            paint.set_color(match_color(cx, *selected));
+            for text_box in self.grapheme_rects(cx, graphemes.clone()) {
+                canvas.draw_rect(text_box.rect.with_offset((bounds.x, bounds.y)), &paint);
+            }
+        }
+    }
+
+    fn draw_text_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some(caret) = self.cursor.caret else {
+            return;
+        };
+
+        // The zero-width space at the end of the paragraph gives a caret position at the end of the line.
+        let rects = self.grapheme_rects(cx, caret..caret + 1);
+        let Some(cursor_rect) = rects.first() else {
+            return;
+        };
+
+        let bounds = cx.bounds();
+        let x = (bounds.x + caret_position(cursor_rect)).round();
+        let y = (bounds.y + cursor_rect.rect.top).round();
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_style(PaintStyle::Fill);
+        paint.set_color(cx.caret_color());
+
+        canvas.draw_rect(Rect::new(x, y, x + 1.0, y + cursor_rect.rect.height()), &paint);
+    }
+}
+
+impl View for TextEditorLine {
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        cx.draw_background(canvas);
+        self.draw_matches(cx, canvas);
+        cx.draw_text(canvas);
+        self.draw_selection(cx, canvas);
+        self.draw_text_caret(cx, canvas);
+    }
+}
+
+// Returns the text of a line without its line break, or an empty string if there is no such line.
+fn line_text(rope: &Rope, line: usize) -> String {
+    if line >= rope.len_lines() {
+        return String::new();
+    }
+
+    let mut text = rope.line(line).to_string();
+    let len = text.trim_end_matches(is_line_break).len();
+    text.truncate(len);
+    text
+}
+
+// Replaces the matches within the range `old` of the text before an edit with the matches found in
+// its place, which ends at `end`, moving the matches after it.
+fn splice_matches(
+    matches: &[Range<usize>],
+    old: Range<usize>,
+    end: usize,
+    found: impl IntoIterator<Item = Range<usize>>,
+) -> Vec<Range<usize>> {
+    let before = matches.iter().filter(|range| range.start < old.start && range.end <= old.start);
+    let after = matches
+        .iter()
+        .filter(|range| range.start >= old.end)
+        .map(|range| range.start + end - old.end..range.end + end - old.end);
+
+    before.cloned().chain(found).chain(after).collect()
+}
+
+// Returns the char ranges of the matches of a search within the given lines of a rope. Each line is
+// searched on its own, borrowing it from the rope unless it is split between chunks, so matches
+// never span a line break.
+fn find_in_lines(rope: &Rope, search: &TextSearch, lines: Range<usize>) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    for line in lines {
+        let start = rope.line_to_char(line);
+        let slice = rope.line(line);
+        matches.extend(search.find_all(&Cow::from(slice)).into_iter().map(|range| {
+            start + slice.byte_to_char(range.start)..start + slice.byte_to_char(range.end)
+        }));
+    }
+
+    matches
+}
+
+// Returns true for the characters which end a line of a rope.
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\u{b}' | '\u{c}' | '\r' | '\u{85}' | '\u{2028}' | '\u{2029}')
+}
+
+// Maps a slot of the visible lines to a line, so that each slot keeps its line while the editor
+// scrolls, like the items of a virtual list.
+fn line_for_slot(slot: usize, start: usize, end: usize) -> usize {
+    match end - start {
+        0 => 0,
+        len => start + (len - (start % len) + slot) % len,
+    }
+}
+
+// Returns the range of lines which are at least partly within a viewport of the given height.
+fn visible_lines(
+    num_lines: usize,
+    line_height: f32,
+    viewport_height: f32,
+    scroll_y: f32,
+) -> Range<usize> {
+    if line_height <= 0.0 || viewport_height <= 0.0 {
+        return 0..0;
+    }
+
+    let scroll_height = (num_lines as f32 * line_height - viewport_height).max(0.0);
+    let top = scroll_height * scroll_y.clamp(0.0, 1.0);
+
+    let start = (top / line_height) as usize;
+    let end = ((top + viewport_height) / line_height).ceil() as usize;
+
+    start.min(num_lines)..end.min(num_lines)
+}
+
+// Returns the scroll position which brings a line fully into view, or `None` if it is already in view.
+fn scroll_to_line(
+    line: usize,
+    num_lines: usize,
+    line_height: f32,
+    viewport_height: f32,
+    scroll_y: f32,
+) -> Option<f32> {
+    let scroll_height = num_lines as f32 * line_height - viewport_height;
+    if scroll_height <= 0.0 {
+        return None;
+    }
+
+    let top = scroll_height * scroll_y;
+    let line_top = line as f32 * line_height;
+
+    let target = if line_top < top {
+        line_top
+    } else if line_top + line_height > top + viewport_height {
+        line_top + line_height - viewport_height
+    } else {
+        return None;
+    };
+
+    Some((target / scroll_height).clamp(0.0, 1.0))
+}
+
+fn is_backwards(direction: Direction) -> bool {
+    matches!(direction, Direction::Left | Direction::Upstream)
+}
+
+// Returns the byte offset of a column, counted in chars, of some text.
+fn byte_offset(text: &str, column: usize) -> usize {
+    text.char_indices().nth(column).map_or(text.len(), |(offset, _)| offset)
+}
+
+fn column_to_grapheme(text: &str, column: usize) -> usize {
+    text[..byte_offset(text, column)].graphemes(true).count()
+}
+
+fn grapheme_to_column(text: &str, grapheme: usize) -> usize {
+    text.grapheme_indices(true)
+        .nth(grapheme)
+        .map_or(text.chars().count(), |(offset, _)| text[..offset].chars().count())
+}
+
+fn prev_grapheme(text: &str, column: usize) -> usize {
+    let offset = byte_offset(text, column);
+    text[..offset]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(offset, _)| text[..offset].chars().count())
+}
+
+fn next_grapheme(text: &str, column: usize) -> usize {
+    let offset = byte_offset(text, column);
+    column + text[offset..].graphemes(true).next().map_or(0, |grapheme| grapheme.chars().count())
+}
+
+// Returns the column of the start of the word before a column.
+fn prev_word(text: &str, column: usize) -> usize {
+    let offset = byte_offset(text, column);
+    let start = text[..offset]
+        .split_word_bound_indices()
+        .rev()
+        .find(|(_, word)| !word.trim().is_empty())
+        .map_or(0, |(start, _)| start);
+
+    text[..start].chars().count()
+}
+
+// Returns the column of the end of the word after a column.
+fn next_word(text: &str, column: usize) -> usize {
+    let offset = byte_offset(text, column);
+    let end = text[offset..]
+        .split_word_bound_indices()
+        .find(|(_, word)| !word.trim().is_empty())
+        .map_or(text.len(), |(start, word)| offset + start + word.len());
+
+    text[..end].chars().count()
+}
+
+// Returns the number of whitespace characters a line starts with.
+fn indent_width(text: &str) -> usize {
+    text.chars().take_while(|c| *c == ' ' || *c == '\t').count()
+}
+
+// Returns the number of characters to remove from the start of a line to remove a level of
+// indentation, which is either a tab or up to a tab width of spaces.
+fn dedent_width(text: &str, tab_width: usize) -> usize {
+    if text.starts_with('\t') {
+        1
+    } else {
+        text.chars().take(tab_width).take_while(|c| *c == ' ').count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::ViewHandler;
+
+    #[derive(Lens)]
+    struct AppData {
+        text: Rope,
+    }
+
+    impl Model for AppData {}
+
+    // Builds an editor for some TOML and takes its view out of the context, so that it can be
+    // edited directly.
+    fn editor(cx: &mut Context, text: &str) -> Box<dyn ViewHandler> {
+        AppData { text: Rope::from_str(text) }.build(cx);
+        let entity =
+            TextEditor::new(cx, AppData::text).highlighter(ConfigHighlighter::Toml).entity();
+        cx.views.remove(&entity).unwrap()
+    }
+
+    fn selection(editor: &TextEditor) -> (usize, usize) {
+        (editor.selection.anchor, editor.selection.active)
+    }
+
+    // Checks that the highlighting of the editor is the same as highlighting its text again.
+    fn assert_highlights(editor: &TextEditor) {
+        let mut highlights = Highlights::new();
+        let lines = (0..editor.rope.len_lines()).map(|line| line_text(&editor.rope, line));
+        highlights.update(&ConfigHighlighter::Toml, lines);
+
+        for line in 0..editor.rope.len_lines() {
+            assert_eq!(editor.highlights.line(line), highlights.line(line), "line {}", line);
+        }
+    }
+
+    #[test]
+    fn line_texts() {
+        let rope = Rope::from_str("first\r\nsecond\n\nlast");
+        assert_eq!(rope.len_lines(), 4);
+        assert_eq!(line_text(&rope, 0), "first");
+        assert_eq!(line_text(&rope, 1), "second");
+        assert_eq!(line_text(&rope, 2), "");
+        assert_eq!(line_text(&rope, 3), "last");
+        assert_eq!(line_text(&rope, 4), "");
+    }
+
+    #[test]
+    fn slots_keep_lines_while_scrolling() {
+        let lines = |range: Range<usize>| {
+            (0..range.len())
+                .map(|slot| line_for_slot(slot, range.start, range.end))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(lines(0..4), [0, 1, 2, 3]);
+        assert_eq!(lines(1..5), [4, 1, 2, 3]);
+        assert_eq!(lines(2..6), [4, 5, 2, 3]);
+    }
+
+    #[test]
+    fn visible_range() {
+        assert_eq!(visible_lines(1000, 20.0, 100.0, 0.0), 0..5);
+        assert_eq!(visible_lines(1000, 20.0, 100.0, 1.0), 995..1000);
+        // A partly visible line at the top and bottom of the viewport.
+        assert_eq!(visible_lines(1000, 20.0, 100.0, 10.0 / 19900.0), 0..6);
+        assert_eq!(visible_lines(3, 20.0, 100.0, 0.5), 0..3);
+        assert_eq!(visible_lines(1000, 20.0, 0.0, 0.0), 0..0);
+    }
+
+    #[test]
+    fn scroll_into_view() {
+        // 100 lines of 20px in a 100px viewport scroll through 1900px.
+        assert_eq!(scroll_to_line(2, 100, 20.0, 100.0, 0.0), None);
+        assert_eq!(scroll_to_line(10, 100, 20.0, 100.0, 0.0), Some(120.0 / 1900.0));
+        assert_eq!(scroll_to_line(0, 100, 20.0, 100.0, 0.5), Some(0.0));
+        assert_eq!(scroll_to_line(99, 100, 20.0, 100.0, 0.0), Some(1.0));
+        assert_eq!(scroll_to_line(1, 3, 20.0, 100.0, 0.0), None);
+    }
+
+    #[test]
+    fn columns_and_graphemes() {
+        let text = "e\u{301}x = 1";
+        assert_eq!(column_to_grapheme(text, 2), 1);
+        assert_eq!(grapheme_to_column(text, 1), 2);
+        assert_eq!(grapheme_to_column(text, 100), text.chars().count());
+        assert_eq!(prev_grapheme(text, 2), 0);
+        assert_eq!(next_grapheme(text, 0), 2);
+    }
+
+    #[test]
+    fn word_movement() {
+        let text = "let value = 42;";
+        assert_eq!(next_word(text, 0), 3);
+        assert_eq!(next_word(text, 3), 9);
+        assert_eq!(prev_word(text, 9), 4);
+        assert_eq!(prev_word(text, 4), 0);
+        assert_eq!(next_word(text, text.len()), text.len());
+    }
+
+    #[test]
+    fn spliced_matches() {
+        let matches = [0..2, 4..6, 10..12, 14..16];
+
+        // The lines from 4 to 12 become the lines from 4 to 8.
+        assert_eq!(splice_matches(&matches, 4..12, 8, [5..7]), vec![0..2, 5..7, 10..12]);
+        assert_eq!(splice_matches(&matches, 4..12, 16, []), vec![0..2, 18..20]);
+    }
+
+    #[test]
+    fn indentation() {
+        assert_eq!(indent_width("    key = 1"), 4);
+        assert_eq!(indent_width("\t  key"), 3);
+        assert_eq!(dedent_width("      key", 4), 4);
+        assert_eq!(dedent_width("  key", 4), 2);
+        assert_eq!(dedent_width("\tkey", 4), 1);
+        assert_eq!(dedent_width("key", 4), 0);
+    }
+
+    #[test]
+    fn indenting_selected_lines() {
+        let mut context = Context::default();
+        let mut view = editor(&mut context, "a\n  b\nc");
+        let editor = view.downcast_mut::<TextEditor>().unwrap();
+        let cx = &mut EventContext::new(&mut context);
+
+        editor.selection = Selection::new(0, 5);
+        editor.indent(cx, false);
+        assert_eq!(editor.rope, "    a\n      b\nc");
+        assert_eq!(selection(editor), (4, 13));
+
+        editor.indent(cx, true);
+        assert_eq!(editor.rope, "a\n  b\nc");
+        assert_eq!(selection(editor), (0, 5));
+
+        // Lines without enough indentation lose what they have.
+        editor.indent(cx, true);
+        assert_eq!(editor.rope, "a\nb\nc");
+        assert_eq!(selection(editor), (0, 3));
+
+        // Indenting the lines is a single undo step.
+        editor.undo(cx);
+        assert_eq!(editor.rope, "a\n  b\nc");
+        assert_eq!(selection(editor), (0, 5));
+    }
+
+    #[test]
+    fn undoing_and_redoing_edits() {
+        let mut context = Context::default();
+        let mut view = editor(&mut context, "a = 1\nb = 2");
+        let editor = view.downcast_mut::<TextEditor>().unwrap();
+        let cx = &mut EventContext::new(&mut context);
+
+        editor.selection = Selection::caret(5);
+        editor.insert_text(cx, "0");
+        editor.insert_text(cx, "0");
+        editor.delete_text(cx, Movement::ParagraphStart);
+        assert_eq!(editor.rope, "\nb = 2");
+
+        editor.undo(cx);
+        assert_eq!(editor.rope, "a = 100\nb = 2");
+        assert_eq!(selection(editor), (7, 7));
+
+        // The typed characters are undone together.
+        editor.undo(cx);
+        assert_eq!(editor.rope, "a = 1\nb = 2");
+        assert_eq!(selection(editor), (5, 5));
+        assert!(editor.history.next_undo().is_none());
+
+        editor.redo(cx);
+        editor.redo(cx);
+        assert_eq!(editor.rope, "\nb = 2");
+        assert_eq!(selection(editor), (0, 0));
+        assert!(editor.history.next_redo().is_none());
+    }
+
+    #[test]
+    fn highlighting_edited_lines() {
+        let mut context = Context::default();
+        let mut view = editor(&mut context, "a = \"\"\"\nb = 1\n\"\"\"\nc = 2");
+        let editor = view.downcast_mut::<TextEditor>().unwrap();
+        let cx = &mut EventContext::new(&mut context);
+        assert_eq!(editor.highlights.line(1)[0].class, "string");
+
+        // Closing the string on its first line changes the highlighting of the lines after it.
+        editor.selection = Selection::caret(7);
+        editor.insert_text(cx, "\"\"\"");
+        assert_eq!(editor.highlights.line(1)[0].class, "key");
+        assert_highlights(editor);
+
+        editor.undo(cx);
+        assert_eq!(editor.highlights.line(1)[0].class, "string");
+        assert_highlights(editor);
+
+        editor.selection = Selection::new(6, 13);
+        editor.insert_text(cx, "\"");
+        assert_eq!(editor.rope, "a = \"\"\"\n\"\"\"\nc = 2");
+        assert_highlights(editor);
+    }
+}
@@ -2,13 +2,14 @@
 use crate::prelude::*;
 
 use crate::text::{
-    apply_movement, offset_for_delete_backwards, Direction, EditableText, Movement, Selection,
-    VerticalMovement,
+    apply_movement, caret_position, find_match, offset_for_delete_backwards, Direction,
+    EditHistory, EditKind, EditableText, Movement, Selection, VerticalMovement, WritingDirection,
 };
 // use crate::views::scrollview::SCROLL_SENSITIVITY;
-use accesskit::{ActionData, ActionRequest};
-use skia_safe::textlayout::{RectHeightStyle, RectWidthStyle};
-use skia_safe::{Paint, PaintStyle, Rect};
+use accesskit::{ActionData, ActionRequest, TextDirection};
+use skia_safe::textlayout::{Paragraph, RectHeightStyle, RectWidthStyle, TextBox};
+use skia_safe::{Paint, PaintStyle, Path, Rect};
+use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Events for modifying a textbox.
@@ -45,10 +46,26 @@ pub enum TextEvent {
     Paste,
     /// Cut the textbox text and place it in the clipboard.
     Cut,
+    /// Undo the last edit, restoring the selection from before it.
+    Undo,
+    /// Redo the last undone edit.
+    Redo,
     /// Set the placeholder text of the textbox.
     SetPlaceholder(String),
     /// Trigger the `on_blur` callback.
     Blur,
+    /// Set the text being composed by an input method editor (IME) and the byte range of the cursor within it.
+    /// An empty string ends the composition and restores the text it replaced. The composed text
+    /// isn't passed to `on_edit` until it is committed.
+    SetPreedit(String, Option<(usize, usize)>),
+    /// Run the text checker on the current text.
+    CheckText,
+    /// Show the suggestions for the checked range at the given position, in physical coordinates.
+    ShowSuggestions(f32, f32),
+    /// Hide the suggestions popup.
+    HideSuggestions,
+    /// Replace the range of the open suggestions popup with the given suggestion.
+    ApplySuggestion(String),
 
     ToggleCaret,
 }
@@ -75,6 +92,30 @@ pub struct Textbox<L: Lens> {
     show_caret: bool,
     caret_timer: Timer,
     selection: Selection,
+    history: EditHistory,
+    #[lens(ignore)]
+    preedit: Option<Preedit>,
+    #[lens(ignore)]
+    checker: Option<Box<dyn TextChecker>>,
+    // The ranges of the current text flagged by the checker.
+    #[lens(ignore)]
+    checks: Vec<TextCheck>,
+    // The text the checks were found in, so that the same text is not checked again.
+    #[lens(ignore)]
+    checked_text: Option<String>,
+    // The suggestions shown in a popup for a checked range, which is replaced when one is chosen.
+    suggestions: Vec<String>,
+    #[lens(ignore)]
+    suggestion_range: Option<Range<usize>>,
+    // The position of the suggestions popup relative to the textbox, in logical pixels.
+    suggestions_position: (f32, f32),
+    // The byte ranges of the matches of the current search, and the index of the selected match.
+    #[lens(ignore)]
+    search: Option<TextSearch>,
+    #[lens(ignore)]
+    matches: Vec<Range<usize>>,
+    #[lens(ignore)]
+    current_match: Option<usize>,
 }
 
 // Determines whether the enter key submits the text or inserts a new line.
@@ -85,6 +126,15 @@ enum TextboxKind {
     MultiLineWrapped,
 }
 
+// The text being composed by an IME, which is shown in place of the selection while composing.
+struct Preedit {
+    // The byte range of the composed text.
+    range: Range<usize>,
+    // The selection replaced by the composed text, and the text it contained.
+    selection: Selection,
+    replaced: String,
+}
+
 impl<L> Textbox<L>
 where
     L: Lens<Target: Data + Clone + ToStringLocalized + std::str::FromStr>,
@@ -160,13 +210,27 @@ where
             show_caret: true,
             caret_timer,
             selection: Selection::new(0, 0),
+            history: EditHistory::default(),
+            preedit: None,
+            checker: None,
+            checks: Vec::new(),
+            checked_text: None,
+            suggestions: Vec::new(),
+            suggestion_range: None,
+            suggestions_position: (0.0, 0.0),
+            search: None,
+            matches: Vec::new(),
+            current_match: None,
         }
         .build(cx, move |cx| {
             cx.add_listener(move |textbox: &mut Self, cx, event| {
                 let flag: bool = textbox.edit;
                 event.map(|window_event, meta| match window_event {
                     WindowEvent::MouseDown(_) => {
-                        if flag && meta.origin != cx.current() && cx.hovered() != cx.current() {
+                        if flag
+                            && meta.origin != cx.current()
+                            && !cx.hovered().is_descendant_of(cx.tree, cx.current())
+                        {
                             cx.emit(TextEvent::Blur);
                         }
                     }
@@ -191,6 +255,25 @@ where
                         });
                 }
             });
+
+            Binding::new(cx, Self::suggestions, |cx, suggestions| {
+                let suggestions = suggestions.get(cx);
+                if !suggestions.is_empty() {
+                    Popup::new(cx, move |cx| {
+                        for suggestion in suggestions {
+                            Label::new(cx, &suggestion).on_press(move |cx| {
+                                cx.emit(TextEvent::ApplySuggestion(suggestion.clone()))
+                            });
+                        }
+                    })
+                    .placement(Placement::Over)
+                    .arrow(false)
+                    .left(Self::suggestions_position.map(|(x, _)| Pixels(*x)))
+                    .top(Self::suggestions_position.map(|(_, y)| Pixels(*y)))
+                    .on_blur(|cx| cx.emit(TextEvent::HideSuggestions))
+                    .class("suggestions");
+                }
+            });
         })
         .toggle_class("multiline", kind == TextboxKind::MultiLineWrapped)
         .text_wrap(kind == TextboxKind::MultiLineWrapped)
@@ -204,50 +287,96 @@ where
 
     fn insert_text(&mut self, cx: &mut EventContext, txt: &str) {
         if let Some(text) = cx.style.text.get_mut(cx.current) {
-            text.edit(self.selection.range(), txt);
-            self.selection = Selection::caret(self.selection.min() + txt.len());
+            // Only typing into the text is merged into a single undo step.
+            let kind = if self.selection.is_caret() && txt.graphemes(true).count() == 1 {
+                EditKind::Insert
+            } else {
+                EditKind::Other
+            };
+
+            let selection = Selection::caret(self.selection.min() + txt.len());
+            self.history.edit(text, self.selection.range(), txt, kind, self.selection, selection);
+            self.selection = selection;
             cx.style.needs_text_update(cx.current);
         }
     }
 
     fn delete_text(&mut self, cx: &mut EventContext, movement: Movement) {
+        let writing_direction = writing_direction(cx.style, cx.current);
         if self.selection.is_caret() {
             if movement == Movement::Grapheme(Direction::Upstream) {
                 if let Some(text) = cx.style.text.get_mut(cx.current) {
                     let del_offset = offset_for_delete_backwards(&self.selection, text);
                     let del_range = del_offset..self.selection.active;
 
-                    self.selection = Selection::caret(del_range.start);
+                    let selection = Selection::caret(del_range.start);
 
-                    text.edit(del_range, "");
+                    self.history.edit(
+                        text,
+                        del_range,
+                        "",
+                        EditKind::Delete,
+                        self.selection,
+                        selection,
+                    );
+
+                    self.selection = selection;
 
                     cx.style.needs_text_update(cx.current);
                 }
             } else if let Some(text) = cx.style.text.get_mut(cx.current) {
-                if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                    let to_delete = apply_movement(movement, self.selection, text, paragraph, true);
-                    self.selection = to_delete;
-                    let new_cursor_pos = self.selection.min();
-                    text.edit(to_delete.range(), "");
-                    self.selection = Selection::caret(new_cursor_pos);
+                if let Some((paragraph, caret_stops)) =
+                    cx.text_context.paragraph_with_caret_stops(cx.current)
+                {
+                    let to_delete = apply_movement(
+                        movement,
+                        self.selection,
+                        text,
+                        paragraph,
+                        caret_stops,
+                        writing_direction,
+                        true,
+                    );
+                    let selection = Selection::caret(to_delete.min());
+                    self.history.edit(
+                        text,
+                        to_delete.range(),
+                        "",
+                        EditKind::Delete,
+                        self.selection,
+                        selection,
+                    );
+                    self.selection = selection;
                     cx.style.needs_text_update(cx.current);
                 }
             }
         } else if let Some(text) = cx.style.text.get_mut(cx.current) {
             let del_range = self.selection.range();
-            self.selection = Selection::caret(del_range.start);
+            let selection = Selection::caret(del_range.start);
+
+            self.history.edit(text, del_range, "", EditKind::Other, self.selection, selection);
 
-            text.edit(del_range, "");
+            self.selection = selection;
 
             cx.style.needs_text_update(cx.current);
         }
     }
 
     fn move_cursor(&mut self, cx: &mut EventContext, movement: Movement, selection: bool) {
+        let writing_direction = writing_direction(cx.style, cx.current);
         if let Some(text) = cx.style.text.get_mut(cx.current) {
-            if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                let new_selection =
-                    apply_movement(movement, self.selection, text, paragraph, selection);
+            if let Some((paragraph, caret_stops)) =
+                cx.text_context.paragraph_with_caret_stops(cx.current)
+            {
+                let new_selection = apply_movement(
+                    movement,
+                    self.selection,
+                    text,
+                    paragraph,
+                    caret_stops,
+                    writing_direction,
+                    selection,
+                );
                 self.selection = new_selection;
                 cx.needs_redraw();
             }
@@ -282,7 +411,13 @@ where
     fn coordinates_global_to_text(&self, cx: &EventContext, x: f32, y: f32) -> (f32, f32) {
         let bounds = cx.bounds();
 
-        let child_left = cx.style.child_left.get(cx.current).copied().unwrap_or_default();
+        // The horizontal child space is mirrored in a right-to-left direction.
+        let child_left = if cx.style.is_rtl(cx.current) {
+            cx.style.child_right.get(cx.current)
+        } else {
+            cx.style.child_left.get(cx.current)
+        };
+        let child_left = child_left.copied().unwrap_or_default();
         let child_top = cx.style.child_top.get(cx.current).copied().unwrap_or_default();
         let _child_right = cx.style.child_right.get(cx.current).copied().unwrap_or_default();
         let _child_bottom = cx.style.child_bottom.get(cx.current).copied().unwrap_or_default();
@@ -306,54 +441,37 @@ where
         (x, y)
     }
 
-    /// This function takes window-global physical coordinates.
-    fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
-        if let Some(text) = cx.style.text.get(cx.current) {
-            if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                let gp = paragraph
-                    .get_glyph_position_at_coordinate(self.coordinates_global_to_text(cx, x, y));
-                let num_graphemes = text.graphemes(true).count();
-                let pos = (gp.position as usize).min(num_graphemes);
-                let mut cursor = text.len();
-                for (i, (j, _)) in text.grapheme_indices(true).enumerate() {
-                    if pos == i {
-                        cursor = j;
-                        break;
-                    }
-                }
+    /// Returns the byte offset of the caret position closest to the given window-global physical coordinates.
+    fn offset_at(&self, cx: &EventContext, x: f32, y: f32) -> Option<usize> {
+        let text = cx.style.text.get(cx.current)?;
+        let paragraph = cx.text_context.text_paragraphs.get(cx.current)?;
 
-                if selection {
-                    self.selection.active = cursor;
-                } else {
-                    self.selection = Selection::caret(cursor);
-                }
+        let gp =
+            paragraph.get_glyph_position_at_coordinate(self.coordinates_global_to_text(cx, x, y));
+        let pos = gp.position as usize;
 
-                cx.needs_redraw();
+        Some(text.grapheme_indices(true).nth(pos).map(|(offset, _)| offset).unwrap_or(text.len()))
+    }
+
+    /// This function takes window-global physical coordinates.
+    fn hit(&mut self, cx: &mut EventContext, x: f32, y: f32, selection: bool) {
+        if let Some(cursor) = self.offset_at(cx, x, y) {
+            if selection {
+                self.selection.active = cursor;
+            } else {
+                self.selection = Selection::caret(cursor);
             }
+
+            cx.needs_redraw();
         }
     }
 
     /// This function takes window-global physical coordinates.
     fn drag(&mut self, cx: &mut EventContext, x: f32, y: f32) {
-        if let Some(text) = cx.style.text.get(cx.current) {
-            if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
-                let gp = paragraph
-                    .get_glyph_position_at_coordinate(self.coordinates_global_to_text(cx, x, y));
-                let num_graphemes = text.graphemes(true).count();
-                let pos = (gp.position as usize).min(num_graphemes);
-
-                let mut cursor = text.len();
-                for (i, (j, _)) in text.grapheme_indices(true).enumerate() {
-                    if pos == i {
-                        cursor = j;
-                        break;
-                    }
-                }
-
-                self.selection.active = cursor;
+        if let Some(cursor) = self.offset_at(cx, x, y) {
+            self.selection.active = cursor;
 
-                cx.needs_redraw();
-            }
+            cx.needs_redraw();
         }
     }
 
@@ -385,6 +503,333 @@ where
         }
     }
 
+    // Replaces the text being composed by an IME, which is shown in place of the selection. The
+    // composed text is neither recorded in the history nor passed to `on_edit`, and the selection
+    // is restored when the composition ends, so that only committed text edits the bound data.
+    fn set_preedit(
+        &mut self,
+        cx: &mut EventContext,
+        preedit: &str,
+        cursor: Option<(usize, usize)>,
+    ) {
+        if self.preedit.is_none() && !preedit.is_empty() {
+            // The text before the caret is unchanged, so the laid out paragraph is used to place
+            // the IME candidate window at the start of the composition.
+            self.update_ime_cursor_area(cx);
+        }
+
+        let Some(text) = cx.style.text.get_mut(cx.current) else {
+            return;
+        };
+
+        // The bound data can replace the text while composing, in which case the composition
+        // starts again at the selection.
+        let composition = match self.preedit.take() {
+            Some(composition) if text.get(composition.range.clone()).is_some() => composition,
+            _ if preedit.is_empty() => return,
+            _ => Preedit {
+                range: self.selection.range(),
+                selection: self.selection,
+                replaced: text[self.selection.range()].to_owned(),
+            },
+        };
+
+        if preedit.is_empty() {
+            text.edit(composition.range, &composition.replaced);
+            self.selection = composition.selection;
+        } else {
+            text.edit(composition.range.clone(), preedit);
+
+            let start = composition.range.start;
+            let cursor = cursor.map(|(start, _)| start).unwrap_or(preedit.len());
+            self.selection = Selection::caret(start + cursor.min(preedit.len()));
+            self.preedit = Some(Preedit { range: start..start + preedit.len(), ..composition });
+        }
+
+        cx.style.needs_text_update(cx.current);
+    }
+
+    // Validates the edited text and passes it to the `on_edit` callback, which is expected to update
+    // the bound data to match.
+    fn text_edited(&self, cx: &mut EventContext) {
+        let text = self.clone_text(cx);
+
+        if let Ok(value) = &text.parse::<L::Target>() {
+            if let Some(validate) = &self.validate {
+                cx.set_valid(validate(value));
+            } else {
+                cx.set_valid(true);
+            }
+        } else {
+            cx.set_valid(false);
+        }
+
+        if let Some(callback) = &self.on_edit {
+            (callback)(cx, text);
+        }
+    }
+
+    // Runs the checker on the current text, unless it was already checked. An edit checks the text
+    // directly and again when the bound data changes. The checked ranges of the previous text no
+    // longer apply, so any open suggestions are hidden.
+    fn check_text(&mut self, cx: &mut EventContext) {
+        if let Some(checker) = &self.checker {
+            let text = cx.style.text.get(cx.current).map(String::as_str).unwrap_or_default();
+            if self.checked_text.as_deref() == Some(text) {
+                return;
+            }
+
+            self.checks = checker.check(text);
+            self.checked_text = Some(text.to_owned());
+            self.hide_suggestions();
+            cx.needs_redraw();
+        }
+    }
+
+    // Shows the suggestions for the checked range at the given window-global physical coordinates
+    // in a popup beneath it.
+    fn show_suggestions(&mut self, cx: &mut EventContext, x: f32, y: f32) {
+        let Some(offset) = self.offset_at(cx, x, y) else {
+            return;
+        };
+
+        let Some(text) = cx.style.text.get(cx.current) else {
+            return;
+        };
+
+        // The suggestions are only looked up for the check which is pressed.
+        let Some((check, suggestions)) = self
+            .checks
+            .iter()
+            .find(|check| check.range.start <= offset && offset <= check.range.end)
+            .zip(self.checker.as_ref())
+            .map(|(check, checker)| (check.clone(), checker.suggest(text, check)))
+            .filter(|(_, suggestions)| !suggestions.is_empty())
+        else {
+            self.hide_suggestions();
+            return;
+        };
+
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return;
+        };
+
+        let min = text.current_grapheme_offset(check.range.start);
+        let max = text.current_grapheme_offset(check.range.end);
+        let rects =
+            paragraph.get_rects_for_range(min..max, RectHeightStyle::Tight, RectWidthStyle::Tight);
+
+        let Some(rect) = rects.first() else {
+            return;
+        };
+
+        let scale_factor = cx.scale_factor();
+        let child_space = child_space(cx.style, cx.current);
+
+        let (padding_left, padding_top) =
+            text_offset(cx.bounds(), paragraph, child_space, cx.style.is_rtl(cx.current));
+
+        self.suggestions_position = (
+            (padding_left + rect.rect.left) / scale_factor,
+            (padding_top + rect.rect.bottom) / scale_factor,
+        );
+        self.suggestions = suggestions;
+        self.suggestion_range = Some(check.range);
+
+        // The textbox captures the mouse while editing, which would stop the popup from being pressed.
+        cx.release();
+    }
+
+    fn hide_suggestions(&mut self) {
+        self.suggestions.clear();
+        self.suggestion_range = None;
+    }
+
+    // Replaces the range of the open suggestions with the given suggestion as a single undo step.
+    fn apply_suggestion(&mut self, cx: &mut EventContext, suggestion: &str) {
+        let Some(range) = self.suggestion_range.take() else {
+            return;
+        };
+
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            let selection = Selection::caret(range.start + suggestion.len());
+            self.history.edit(text, range, suggestion, EditKind::Other, self.selection, selection);
+            self.selection = selection;
+            cx.style.needs_text_update(cx.current);
+        }
+    }
+
+    // Finds the matches of the current search in the text, which is done again after each edit.
+    fn update_matches(&mut self, cx: &mut EventContext) {
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        let text = cx.style.text.get(cx.current).map(String::as_str).unwrap_or_default();
+        self.matches = search.find_all(text);
+        self.current_match = self.matches.iter().position(|range| *range == self.selection.range());
+        cx.emit(FindBarEvent::SetMatches(self.current_match, self.matches.len()));
+        cx.needs_redraw();
+    }
+
+    // Selects the first match at or after the given byte offset, or with `backwards` the last match
+    // before it, and scrolls it into view.
+    fn select_match(&mut self, cx: &mut EventContext, offset: usize, backwards: bool) {
+        self.current_match = find_match(&self.matches, offset, backwards);
+        if let Some(range) = self.current_match.map(|index| self.matches[index].clone()) {
+            self.history.break_merge();
+            self.selection = Selection::new(range.start, range.end);
+            self.scroll_to_selection(cx);
+        }
+
+        cx.emit(FindBarEvent::SetMatches(self.current_match, self.matches.len()));
+        cx.needs_redraw();
+    }
+
+    // Replaces the selected match as a single undo step, and selects the next match.
+    fn replace_match(&mut self, cx: &mut EventContext, replacement: &str) {
+        let Some(range) = self.current_match.map(|index| self.matches[index].clone()) else {
+            self.select_match(cx, self.selection.min(), false);
+            return;
+        };
+
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            let replacement = search.replacement(text, range.clone(), replacement);
+            let selection = Selection::caret(range.start + replacement.len());
+            self.history.edit(
+                text,
+                range,
+                &replacement,
+                EditKind::Other,
+                self.selection,
+                selection,
+            );
+            self.selection = selection;
+            cx.style.needs_text_update(cx.current);
+        }
+
+        self.update_matches(cx);
+        self.select_match(cx, self.selection.active, false);
+    }
+
+    // Replaces every match as a single undo step.
+    fn replace_all(&mut self, cx: &mut EventContext, replacement: &str) {
+        let Some(search) = &self.search else {
+            return;
+        };
+
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            if let Some((range, replaced)) = search.replace_all(text, replacement) {
+                let selection = Selection::caret(range.start + replaced.len());
+                self.history.edit(
+                    text,
+                    range,
+                    &replaced,
+                    EditKind::Other,
+                    self.selection,
+                    selection,
+                );
+                self.selection = selection;
+                cx.style.needs_text_update(cx.current);
+            }
+        }
+
+        self.update_matches(cx);
+    }
+
+    // Scrolls the nearest scroll view containing the textbox so that the selection is visible.
+    fn scroll_to_selection(&self, cx: &mut EventContext) {
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return;
+        };
+
+        let Some(text) = cx.style.text.get(cx.current) else {
+            return;
+        };
+
+        let min = text.current_grapheme_offset(self.selection.min());
+        let max = text.current_grapheme_offset(self.selection.max());
+        let rects =
+            paragraph.get_rects_for_range(min..max, RectHeightStyle::Tight, RectWidthStyle::Tight);
+
+        let bounds = cx.bounds();
+        let (padding_left, padding_top) = text_offset(
+            bounds,
+            paragraph,
+            child_space(cx.style, cx.current),
+            cx.style.is_rtl(cx.current),
+        );
+
+        if let Some(rect) = text_bounds(&rects) {
+            let rect = rect.offset(bounds.x + padding_left, bounds.y + padding_top);
+            cx.emit(ScrollEvent::ScrollIntoView(rect));
+        }
+    }
+
+    fn undo(&mut self, cx: &mut EventContext) -> bool {
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            if let Some(selection) = self.history.undo(text) {
+                self.selection = selection;
+                cx.style.needs_text_update(cx.current);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn redo(&mut self, cx: &mut EventContext) -> bool {
+        if let Some(text) = cx.style.text.get_mut(cx.current) {
+            if let Some(selection) = self.history.redo(text) {
+                self.selection = selection;
+                cx.style.needs_text_update(cx.current);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Reports the area of the caret to the platform so that the IME candidate window can be placed next to it.
+    fn update_ime_cursor_area(&self, cx: &mut EventContext) {
+        let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) else {
+            return;
+        };
+
+        let Some(text) = cx.style.text.get(cx.current) else {
+            return;
+        };
+
+        let current = text.current_grapheme_offset(self.selection.active);
+        let rects = paragraph.get_rects_for_range(
+            current..current + 1,
+            RectHeightStyle::Tight,
+            RectWidthStyle::Tight,
+        );
+
+        let Some(cursor_rect) = rects.first() else {
+            return;
+        };
+
+        let child_space = child_space(cx.style, cx.current);
+        let bounds = cx.bounds();
+        let (padding_left, padding_top) =
+            text_offset(bounds, paragraph, child_space, cx.style.is_rtl(cx.current));
+
+        let x = bounds.x + padding_left + caret_position(cursor_rect);
+        let y = bounds.y + padding_top + cursor_rect.rect.top;
+        let height = cursor_rect.rect.bottom - cursor_rect.rect.top;
+
+        cx.emit(WindowEvent::SetImeCursorArea(
+            (x.max(0.0) as u32, y.max(0.0) as u32),
+            (1, height.max(0.0) as u32),
+        ));
+    }
+
     pub fn draw_selection(&self, cx: &mut DrawContext, canvas: &Canvas) {
         if !self.selection.is_caret() {
             if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
@@ -398,63 +843,15 @@ where
                         RectWidthStyle::Tight,
                     );
 
-                    for cursor_rect in cursor_rects {
-                        let bounds = cx.bounds();
-
-                        let mut vertical_flex_sum = 0.0;
-                        let mut horizontal_flex_sum = 0.0;
-
-                        let mut padding_top = match cx.child_top() {
-                            Units::Pixels(val) => val,
-                            Units::Stretch(val) => {
-                                vertical_flex_sum += val;
-                                0.0
-                            }
-                            _ => 0.0,
-                        };
-
-                        let padding_bottom = match cx.child_bottom() {
-                            Units::Pixels(val) => val,
-                            Units::Stretch(val) => {
-                                vertical_flex_sum += val;
-                                0.0
-                            }
-                            _ => 0.0,
-                        };
-
-                        let vertical_free_space =
-                            bounds.height() - paragraph.height() - padding_top - padding_bottom;
-
-                        if let Units::Stretch(val) = cx.child_top() {
-                            padding_top = (vertical_free_space * val / vertical_flex_sum).round()
-                        }
-
-                        let mut padding_left = match cx.child_left() {
-                            Units::Pixels(val) => val,
-                            Units::Stretch(val) => {
-                                horizontal_flex_sum += val;
-                                0.0
-                            }
-                            _ => 0.0,
-                        };
-
-                        let padding_right = match cx.child_right() {
-                            Units::Pixels(val) => val,
-                            Units::Stretch(val) => {
-                                horizontal_flex_sum += val;
-                                0.0
-                            }
-                            _ => 0.0,
-                        };
-
-                        let horizontal_free_space =
-                            bounds.width() - paragraph.max_width() - padding_left - padding_right;
-
-                        if let Units::Stretch(val) = cx.child_left() {
-                            padding_left =
-                                (horizontal_free_space * val / horizontal_flex_sum).round()
-                        }
+                    let bounds = cx.bounds();
+                    let (padding_left, padding_top) = text_offset(
+                        bounds,
+                        paragraph,
+                        [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+                        cx.style.is_rtl(cx.current),
+                    );
 
+                    for cursor_rect in cursor_rects {
                         let x = bounds.x + padding_left + cursor_rect.rect.left;
                         let y = bounds.y + padding_top + cursor_rect.rect.top;
 
@@ -473,76 +870,196 @@ where
         }
     }
 
-    /// Draw text caret for the current view.
-    pub fn draw_text_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+    /// Draw a highlight behind each match of the current search.
+    pub fn draw_matches(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if self.matches.is_empty() {
+            return;
+        }
+
         if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
             if let Some(text) = cx.style.text.get(cx.current) {
                 let bounds = cx.bounds();
+                let (padding_left, padding_top) = text_offset(
+                    bounds,
+                    paragraph,
+                    [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+                    cx.style.is_rtl(cx.current),
+                );
 
-                let current = text.current_grapheme_offset(self.selection.active);
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Fill);
+
+                for (index, range) in self.matches.iter().enumerate() {
+                    let min = text.current_grapheme_offset(range.start);
+                    let max = text.current_grapheme_offset(range.end);
+
+                    let rects = paragraph.get_rects_for_range(
+                        min..max,
+                        RectHeightStyle::Tight,
+                        RectWidthStyle::Tight,
+                    );
+
+                    paint.set_color(match_color(cx, self.current_match == Some(index)));
+
+                    for rect in rects {
+                        canvas.draw_rect(
+                            rect.rect
+                                .with_offset((bounds.x + padding_left, bounds.y + padding_top)),
+                            &paint,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draw an underline beneath the text being composed by an IME.
+    pub fn draw_preedit(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        let Some(preedit) = &self.preedit else {
+            return;
+        };
+
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            if let Some(text) = cx.style.text.get(cx.current) {
+                let min = text.current_grapheme_offset(preedit.range.start);
+                let max = text.current_grapheme_offset(preedit.range.end);
 
                 let rects = paragraph.get_rects_for_range(
-                    current..current + 1,
+                    min..max,
                     RectHeightStyle::Tight,
                     RectWidthStyle::Tight,
                 );
 
-                let cursor_rect = rects.first().unwrap();
+                let bounds = cx.bounds();
+                let (padding_left, padding_top) = text_offset(
+                    bounds,
+                    paragraph,
+                    [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+                    cx.style.is_rtl(cx.current),
+                );
 
-                let mut vertical_flex_sum = 0.0;
-                let mut horizontal_flex_sum = 0.0;
+                let thickness = cx.logical_to_physical(1.0).round().max(1.0);
 
-                let mut padding_top = match cx.child_top() {
-                    Units::Pixels(val) => val,
-                    Units::Stretch(val) => {
-                        vertical_flex_sum += val;
-                        0.0
-                    }
-                    _ => 0.0,
-                };
-
-                let padding_bottom = match cx.child_bottom() {
-                    Units::Pixels(val) => val,
-                    Units::Stretch(val) => {
-                        vertical_flex_sum += val;
-                        0.0
-                    }
-                    _ => 0.0,
-                };
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Fill);
+                paint.set_color(cx.font_color());
 
-                let vertical_free_space =
-                    bounds.height() - paragraph.height() - padding_top - padding_bottom;
+                for rect in rects {
+                    let x = bounds.x + padding_left + rect.rect.left;
+                    let x2 = bounds.x + padding_left + rect.rect.right;
+                    let y = (bounds.y + padding_top + rect.rect.bottom).round();
 
-                if let Units::Stretch(val) = cx.child_top() {
-                    padding_top = (vertical_free_space * val / vertical_flex_sum).round()
+                    canvas.draw_rect(Rect::new(x, y - thickness, x2, y), &paint);
                 }
+            }
+        }
+    }
 
-                let mut padding_left = match cx.child_left() {
-                    Units::Pixels(val) => val,
-                    Units::Stretch(val) => {
-                        horizontal_flex_sum += val;
-                        0.0
-                    }
-                    _ => 0.0,
-                };
-
-                let padding_right = match cx.child_right() {
-                    Units::Pixels(val) => val,
-                    Units::Stretch(val) => {
-                        horizontal_flex_sum += val;
-                        0.0
+    /// Draw a wavy underline beneath each range flagged by the text checker, in the color of the
+    /// `--spelling-color`, `--grammar-color` or `--validation-color` custom property.
+    pub fn draw_checks(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if self.checks.is_empty() {
+            return;
+        }
+
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            if let Some(text) = cx.style.text.get(cx.current) {
+                let bounds = cx.bounds();
+                let (padding_left, padding_top) = text_offset(
+                    bounds,
+                    paragraph,
+                    [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+                    cx.style.is_rtl(cx.current),
+                );
+
+                let amplitude = cx.logical_to_physical(1.0).round().max(1.0);
+
+                let mut paint = Paint::default();
+                paint.set_anti_alias(true);
+                paint.set_style(PaintStyle::Stroke);
+                paint.set_stroke_width(amplitude);
+
+                for check in self.checks.iter() {
+                    // The text being composed by an IME is not checked until it is committed.
+                    if let Some(preedit) = &self.preedit {
+                        if check.range.start < preedit.range.end
+                            && preedit.range.start < check.range.end
+                        {
+                            continue;
+                        }
                     }
-                    _ => 0.0,
-                };
 
-                let horizontal_free_space =
-                    bounds.width() - paragraph.max_width() - padding_left - padding_right;
+                    let min = text.current_grapheme_offset(check.range.start);
+                    let max = text.current_grapheme_offset(check.range.end);
+
+                    let rects = paragraph.get_rects_for_range(
+                        min..max,
+                        RectHeightStyle::Tight,
+                        RectWidthStyle::Tight,
+                    );
 
-                if let Units::Stretch(val) = cx.child_left() {
-                    padding_left = (horizontal_free_space * val / horizontal_flex_sum).round()
+                    let color = match check.kind {
+                        CheckKind::Spelling => cx.custom_color("--spelling-color"),
+                        CheckKind::Grammar => cx.custom_color("--grammar-color"),
+                        CheckKind::Validation => cx.custom_color("--validation-color"),
+                    };
+
+                    paint.set_color(color.unwrap_or_else(|| cx.font_color()));
+
+                    for rect in rects {
+                        let x = bounds.x + padding_left + rect.rect.left;
+                        let x2 = bounds.x + padding_left + rect.rect.right;
+                        let y = (bounds.y + padding_top + rect.rect.bottom).round() - amplitude;
+
+                        let mut path = Path::new();
+                        path.move_to((x, y));
+
+                        // Each half wave curves alternately above and below the line.
+                        let mut start = x;
+                        let mut direction = -1.0;
+                        while start < x2 {
+                            let end = (start + 2.0 * amplitude).min(x2);
+                            path.quad_to(
+                                ((start + end) / 2.0, y + direction * amplitude),
+                                (end, y),
+                            );
+                            start = end;
+                            direction = -direction;
+                        }
+
+                        canvas.draw_path(&path, &paint);
+                    }
                 }
+            }
+        }
+    }
+
+    /// Draw text caret for the current view.
+    pub fn draw_text_caret(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        if let Some(paragraph) = cx.text_context.text_paragraphs.get(cx.current) {
+            if let Some(text) = cx.style.text.get(cx.current) {
+                let bounds = cx.bounds();
+
+                let current = text.current_grapheme_offset(self.selection.active);
+
+                let rects = paragraph.get_rects_for_range(
+                    current..current + 1,
+                    RectHeightStyle::Tight,
+                    RectWidthStyle::Tight,
+                );
 
-                let x = (bounds.x + padding_left + cursor_rect.rect.left).round();
+                let cursor_rect = rects.first().unwrap();
+
+                let (padding_left, padding_top) = text_offset(
+                    bounds,
+                    paragraph,
+                    [cx.child_left(), cx.child_right(), cx.child_top(), cx.child_bottom()],
+                    cx.style.is_rtl(cx.current),
+                );
+
+                let x = (bounds.x + padding_left + caret_position(cursor_rect)).round();
                 let y = (bounds.y + padding_top + cursor_rect.rect.top).round();
 
                 let x2 = x + 1.0;
@@ -619,6 +1136,48 @@ impl<'a, L: Lens> Handle<'a, Textbox<L>> {
 
         self
     }
+
+    /// Sets a checker which flags ranges of the text, such as misspelled words, to be underlined.
+    ///
+    /// Right-clicking a flagged range while editing shows its suggested replacements in a popup,
+    /// and choosing one replaces the range as a single undo step.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use vizia_core::prelude::*;
+    /// #
+    /// # #[derive(Lens)]
+    /// # struct AppData {
+    /// #     text: String,
+    /// # }
+    /// #
+    /// # impl Model for AppData {}
+    /// #
+    /// # let cx = &mut Context::default();
+    /// #
+    /// # AppData { text: String::from("Hello World") }.build(cx);
+    /// #
+    /// Textbox::new_multiline(cx, AppData::text, true)
+    ///     .text_checker(DictionaryChecker::new(["hello", "world"]));
+    /// ```
+    pub fn text_checker(self, checker: impl TextChecker + 'static) -> Self
+    where
+        L::Target: Data,
+    {
+        let mut lens = None;
+        let handle = self.modify(|textbox| {
+            textbox.checker = Some(Box::new(checker));
+            textbox.checked_text = None;
+            lens = Some(textbox.lens);
+        });
+
+        // The text is checked again whenever the bound data changes.
+        if let Some(lens) = lens {
+            lens.set_or_bind(handle.cx, handle.entity, |cx, _| cx.emit(TextEvent::CheckText));
+        }
+
+        handle
+    }
 }
 
 impl<L> View for Textbox<L>
@@ -657,11 +1216,11 @@ where
                         w: line.width as f32,
                         h: line.height as f32,
                     });
-                    // line_node.set_text_direction(if line.ltr {
-                    //     TextDirection::RightToLeft
-                    // } else {
-                    //     TextDirection::LeftToRight
-                    // });
+                    line_node.set_text_direction(if cx.style.is_rtl(cx.current) {
+                        TextDirection::RightToLeft
+                    } else {
+                        TextDirection::LeftToRight
+                    });
 
                     let mut character_lengths = Vec::new();
                     let mut character_positions = Vec::new();
@@ -784,6 +1343,13 @@ where
                     return;
                 }
 
+                // Presses within the suggestions popup are handled by the popup.
+                if cx.hovered() != cx.current()
+                    && cx.hovered().is_descendant_of(cx.tree, cx.current)
+                {
+                    return;
+                }
+
                 if cx.is_over() {
                     if !cx.is_disabled() {
                         cx.focus_with_visibility(false);
@@ -816,6 +1382,12 @@ where
                 }
             }
 
+            WindowEvent::MouseDown(MouseButton::Right) => {
+                if self.edit && !self.checks.is_empty() {
+                    cx.emit(TextEvent::ShowSuggestions(cx.mouse.cursor_x, cx.mouse.cursor_y));
+                }
+            }
+
             WindowEvent::FocusIn => {
                 if cx.mouse.left.pressed != cx.current()
                     || cx.mouse.left.state == MouseButtonState::Released
@@ -859,6 +1431,24 @@ where
                 cx.emit(TextEvent::Scroll(*x, *y));
             }
 
+            WindowEvent::ImePreedit(preedit, cursor) => {
+                if self.edit && !cx.is_read_only() {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::SetPreedit(preedit.clone(), *cursor));
+                }
+            }
+
+            WindowEvent::ImeCommit(text) => {
+                if self.edit && !cx.is_read_only() {
+                    self.reset_caret_timer(cx);
+                    cx.emit(TextEvent::SetPreedit(String::new(), None));
+                    cx.emit(TextEvent::InsertText(text.clone()));
+                }
+            }
+
+            // Keys pressed while composing are handled by the IME.
+            WindowEvent::CharInput(_) | WindowEvent::KeyDown(_, _) if self.preedit.is_some() => {}
+
             WindowEvent::CharInput(c) => {
                 if *c != '\u{1b}' && // Escape
                     *c != '\u{8}' && // Backspace
@@ -992,49 +1582,55 @@ where
                 }
 
                 Code::KeyA => {
-                    #[cfg(target_os = "macos")]
-                    let modifier = Modifiers::SUPER;
-                    #[cfg(not(target_os = "macos"))]
-                    let modifier = Modifiers::CTRL;
-
-                    if cx.modifiers == &modifier {
+                    if cx.modifiers == &Modifiers::COMMAND {
                         cx.emit(TextEvent::SelectAll);
                     }
                 }
 
                 Code::KeyC => {
-                    #[cfg(target_os = "macos")]
-                    let modifier = Modifiers::SUPER;
-                    #[cfg(not(target_os = "macos"))]
-                    let modifier = Modifiers::CTRL;
-
-                    if cx.modifiers == &modifier {
+                    if cx.modifiers == &Modifiers::COMMAND {
                         cx.emit(TextEvent::Copy);
                     }
                 }
 
                 Code::KeyV => {
-                    #[cfg(target_os = "macos")]
-                    let modifier = Modifiers::SUPER;
-                    #[cfg(not(target_os = "macos"))]
-                    let modifier = Modifiers::CTRL;
-
-                    if cx.modifiers == &modifier {
+                    if cx.modifiers == &Modifiers::COMMAND {
                         cx.emit(TextEvent::Paste);
                     }
                 }
 
                 Code::KeyX => {
-                    #[cfg(target_os = "macos")]
-                    let modifier = Modifiers::SUPER;
-                    #[cfg(not(target_os = "macos"))]
-                    let modifier = Modifiers::CTRL;
-
-                    if cx.modifiers == &modifier && !cx.is_read_only() {
+                    if cx.modifiers == &Modifiers::COMMAND && !cx.is_read_only() {
                         cx.emit(TextEvent::Cut);
                     }
                 }
 
+                Code::KeyZ => {
+                    if !cx.is_read_only() {
+                        if cx.modifiers == &Modifiers::COMMAND {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::Undo);
+                        } else if cx.modifiers == &(Modifiers::COMMAND | Modifiers::SHIFT) {
+                            self.reset_caret_timer(cx);
+                            cx.emit(TextEvent::Redo);
+                        }
+                    }
+                }
+
+                Code::KeyF => {
+                    if cx.modifiers == &Modifiers::COMMAND {
+                        cx.emit(FindBarEvent::Open { replace: !cx.is_read_only() });
+                    }
+                }
+
+                #[cfg(not(target_os = "macos"))]
+                Code::KeyY => {
+                    if cx.modifiers == &Modifiers::CTRL && !cx.is_read_only() {
+                        self.reset_caret_timer(cx);
+                        cx.emit(TextEvent::Redo);
+                    }
+                }
+
                 _ => {}
             },
 
@@ -1099,6 +1695,8 @@ where
         event.map(|text_event, _| match text_event {
             TextEvent::InsertText(text) => {
                 self.insert_text(cx, text);
+                self.check_text(cx);
+                self.update_matches(cx);
 
                 let text = self.clone_text(cx);
 
@@ -1129,28 +1727,26 @@ where
             TextEvent::DeleteText(movement) => {
                 if self.edit {
                     self.delete_text(cx, *movement);
+                    self.check_text(cx);
+                    self.update_matches(cx);
 
-                    let text = self.clone_text(cx);
-
-                    if let Ok(value) = &text.parse::<L::Target>() {
-                        if let Some(validate) = &self.validate {
-                            cx.set_valid(validate(value));
-                        } else {
-                            cx.set_valid(true);
-                        }
-                    } else {
-                        cx.set_valid(false);
-                    }
-
-                    if let Some(callback) = &self.on_edit {
-                        (callback)(cx, text);
-                    }
+                    self.text_edited(cx);
                 }
             }
 
             TextEvent::MoveCursor(movement, selection) => {
                 if self.edit {
+                    self.history.break_merge();
                     self.move_cursor(cx, *movement, *selection);
+                    self.update_ime_cursor_area(cx);
+                }
+            }
+
+            TextEvent::SetPreedit(preedit, cursor) => {
+                if self.edit {
+                    self.set_preedit(cx, preedit, *cursor);
+                    self.check_text(cx);
+                    self.update_matches(cx);
                 }
             }
 
@@ -1163,11 +1759,17 @@ where
                     cx.capture();
                     cx.set_checked(true);
                     self.reset_caret_timer(cx);
-
                     let text = self.lens.get(cx);
                     let text = text.to_string_local(cx);
 
-                    self.select_all(cx);
+                    // The history is kept, so edits from before losing focus can be undone. Edits
+                    // from a new focus are never merged with them.
+                    self.history.break_merge();
+
+                    // When returning from a search the selected match is kept.
+                    if self.search.is_none() {
+                        self.select_all(cx);
+                    }
 
                     if let Ok(value) = &text.parse::<L::Target>() {
                         if let Some(validate) = &self.validate {
@@ -1182,6 +1784,10 @@ where
             }
 
             TextEvent::EndEdit => {
+                if self.preedit.is_some() {
+                    self.set_preedit(cx, "", None);
+                }
+                self.hide_suggestions();
                 self.deselect();
                 self.edit = false;
                 cx.set_checked(false);
@@ -1238,7 +1844,9 @@ where
             }
 
             TextEvent::Hit(posx, posy, selection) => {
+                self.history.break_merge();
                 self.hit(cx, *posx, *posy, *selection);
+                self.update_ime_cursor_area(cx);
             }
 
             TextEvent::Drag(posx, posy) => {
@@ -1281,31 +1889,100 @@ where
                             cx.set_clipboard(selected_text)
                                 .expect("Failed to add text to clipboard");
                             self.delete_text(cx, Movement::Grapheme(Direction::Upstream));
+                            self.check_text(cx);
+                            self.update_matches(cx);
 
-                            let text = self.clone_text(cx);
-
-                            if let Ok(value) = &text.parse::<L::Target>() {
-                                if let Some(validate) = &self.validate {
-                                    cx.set_valid(validate(value));
-                                } else {
-                                    cx.set_valid(true);
-                                }
-                            } else {
-                                cx.set_valid(false);
-                            }
-
-                            if let Some(callback) = &self.on_edit {
-                                (callback)(cx, text);
-                            }
+                            self.text_edited(cx);
                         }
                     }
                 }
             }
 
+            TextEvent::Undo | TextEvent::Redo => {
+                if self.edit && self.preedit.is_none() && !cx.is_read_only() {
+                    let changed = if matches!(text_event, TextEvent::Undo) {
+                        self.undo(cx)
+                    } else {
+                        self.redo(cx)
+                    };
+
+                    if changed {
+                        self.check_text(cx);
+                        self.update_matches(cx);
+
+                        self.text_edited(cx);
+                    }
+                }
+            }
+
+            TextEvent::CheckText => {
+                self.check_text(cx);
+            }
+
+            TextEvent::ShowSuggestions(x, y) => {
+                if self.edit && !cx.is_read_only() {
+                    self.show_suggestions(cx, *x, *y);
+                }
+            }
+
+            TextEvent::HideSuggestions => {
+                self.hide_suggestions();
+            }
+
+            TextEvent::ApplySuggestion(suggestion) => {
+                if self.edit && self.preedit.is_none() && !cx.is_read_only() {
+                    self.apply_suggestion(cx, suggestion);
+                    self.check_text(cx);
+                    self.update_matches(cx);
+
+                    self.text_edited(cx);
+                }
+            }
+
             TextEvent::ToggleCaret => {
                 self.show_caret ^= true;
             }
         });
+
+        // Search Events
+        event.map(|search_event, _| match search_event {
+            SearchEvent::Search(query) => {
+                self.search = TextSearch::new(query);
+                self.matches.clear();
+                self.current_match = None;
+                self.update_matches(cx);
+                self.select_match(cx, self.selection.min(), false);
+            }
+
+            SearchEvent::Next => {
+                self.select_match(cx, self.selection.max(), false);
+            }
+
+            SearchEvent::Previous => {
+                self.select_match(cx, self.selection.min(), true);
+            }
+
+            SearchEvent::Replace(replacement) | SearchEvent::ReplaceAll(replacement) => {
+                if self.preedit.is_none() && !cx.is_read_only() && !cx.is_disabled() {
+                    if matches!(search_event, SearchEvent::Replace(_)) {
+                        self.replace_match(cx, replacement);
+                    } else {
+                        self.replace_all(cx, replacement);
+                    }
+
+                    self.check_text(cx);
+
+                    self.text_edited(cx);
+                }
+            }
+
+            SearchEvent::Clear => {
+                self.search = None;
+                self.matches.clear();
+                self.current_match = None;
+                cx.needs_redraw();
+            }
+        });
     }
 
     // Use custom drawing for the textbox so a transform can be applied to just the text.
@@ -1317,11 +1994,124 @@ where
         // canvas.save();
         // canvas.translate(self.transform.0, self.transform.1);
         // cx.draw_text_and_selection(canvas);
+        self.draw_matches(cx, canvas);
         cx.draw_text(canvas);
+        self.draw_checks(cx, canvas);
         if self.edit {
             self.draw_selection(cx, canvas);
+            self.draw_preedit(cx, canvas);
             self.draw_text_caret(cx, canvas);
         }
         // canvas.restore();
     }
 }
+
+// Returns the color of the highlight behind a match of a search, given by the `--match-color` custom
+// property, or by `--current-match-color` for the selected match.
+pub(crate) fn match_color(cx: &DrawContext, selected: bool) -> Color {
+    let name = if selected { "--current-match-color" } else { "--match-color" };
+    cx.custom_color(name).unwrap_or_else(|| cx.selection_color())
+}
+
+// Returns the base writing direction of the text of a textbox.
+pub(crate) fn writing_direction(style: &Style, entity: Entity) -> WritingDirection {
+    if style.is_rtl(entity) {
+        WritingDirection::RightToLeft
+    } else {
+        WritingDirection::LeftToRight
+    }
+}
+
+// Returns the offset of the text within the bounds of a textbox from its child space, given in physical pixels.
+// The horizontal child space is mirrored in a right-to-left direction.
+pub(crate) fn text_offset(
+    bounds: BoundingBox,
+    paragraph: &Paragraph,
+    child_space: [Units; 4],
+    rtl: bool,
+) -> (f32, f32) {
+    let [mut child_left, mut child_right, child_top, child_bottom] = child_space;
+    if rtl {
+        std::mem::swap(&mut child_left, &mut child_right);
+    }
+
+    let mut vertical_flex_sum = 0.0;
+    let mut horizontal_flex_sum = 0.0;
+
+    let mut padding_top = match child_top {
+        Units::Pixels(val) => val,
+        Units::Stretch(val) => {
+            vertical_flex_sum += val;
+            0.0
+        }
+        _ => 0.0,
+    };
+
+    let padding_bottom = match child_bottom {
+        Units::Pixels(val) => val,
+        Units::Stretch(val) => {
+            vertical_flex_sum += val;
+            0.0
+        }
+        _ => 0.0,
+    };
+
+    let vertical_free_space = bounds.height() - paragraph.height() - padding_top - padding_bottom;
+
+    if let Units::Stretch(val) = child_top {
+        padding_top = (vertical_free_space * val / vertical_flex_sum).round()
+    }
+
+    let mut padding_left = match child_left {
+        Units::Pixels(val) => val,
+        Units::Stretch(val) => {
+            horizontal_flex_sum += val;
+            0.0
+        }
+        _ => 0.0,
+    };
+
+    let padding_right = match child_right {
+        Units::Pixels(val) => val,
+        Units::Stretch(val) => {
+            horizontal_flex_sum += val;
+            0.0
+        }
+        _ => 0.0,
+    };
+
+    let horizontal_free_space =
+        bounds.width() - paragraph.max_width() - padding_left - padding_right;
+
+    if let Units::Stretch(val) = child_left {
+        padding_left = (horizontal_free_space * val / horizontal_flex_sum).round()
+    }
+
+    (padding_left, padding_top)
+}
+
+// Returns the bounds around some rectangles of a paragraph, or `None` if there are none.
+pub(crate) fn text_bounds(rects: &[TextBox]) -> Option<BoundingBox> {
+    rects
+        .iter()
+        .map(|text_box| {
+            let rect = text_box.rect;
+            BoundingBox::from_min_max(rect.left, rect.top, rect.right, rect.bottom)
+        })
+        .reduce(|bounds, rect| bounds.union(&rect))
+}
+
+// Returns the child space of a view in physical pixels.
+pub(crate) fn child_space(style: &Style, entity: Entity) -> [Units; 4] {
+    let scale_factor = style.scale_factor();
+    [
+        style.child_left.get(entity),
+        style.child_right.get(entity),
+        style.child_top.get(entity),
+        style.child_bottom.get(entity),
+    ]
+    .map(|units| match units.copied().unwrap_or_default() {
+        Units::Pixels(val) => Units::Pixels(val * scale_factor),
+        units => units,
+    })
+}
@@ -0,0 +1,855 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use super::list::ItemSelection;
+use crate::icons::{ICON_CHEVRON_DOWN, ICON_CHEVRON_RIGHT};
+use crate::prelude::*;
+
+/// Events for controlling a [`TreeView`].
+///
+/// Items are identified by the id which the tree view returns for them, so the state of an item,
+/// such as whether it is expanded or selected, stays with it when the items of the tree change.
+pub enum TreeViewEvent<K> {
+    /// Expands an item to show its children, which are built the first time it is expanded.
+    Expand(K),
+    /// Collapses an item to hide its children.
+    Collapse(K),
+    /// Expands an item if it is collapsed, or collapses it if it is expanded.
+    Toggle(K),
+    /// Selects only the given item, if items can be selected.
+    Select(K),
+    /// Selects every item which is shown, if more than one item can be selected.
+    SelectAll,
+    /// Deselects every item.
+    ClearSelection,
+}
+
+// An item which is shown, with how deeply it is nested, counting from zero for the roots, and
+// whether it has children.
+#[derive(Debug, Clone, PartialEq)]
+struct VisibleItem<K> {
+    id: K,
+    depth: usize,
+    has_children: bool,
+}
+
+// Returns each item which is shown, in the order they are shown, given the ids of the expanded
+// items.
+type VisibleItems<K> = Box<dyn Fn(&EventContext, &HashSet<K>) -> Vec<VisibleItem<K>>>;
+
+// Returns the id of every item of the tree.
+type ItemIds<K> = Box<dyn Fn(&EventContext) -> HashSet<K>>;
+
+// Events sent to a tree view by its own views.
+enum TreeUpdate<K> {
+    // The row of the item with the given id was pressed.
+    Press(K),
+    // The data changed, so the items which are shown are found again.
+    Refresh,
+}
+
+/// A view which shows hierarchical data as a tree of items which can be expanded and collapsed.
+///
+/// The tree is bound to a list of root items, and uses a function which returns the children of
+/// an item to find the rest of the tree, and a function which returns an id for each item, which
+/// is unique within the tree. The children of an item are only built when it is first expanded.
+///
+/// The items can be navigated with the arrow keys, Home and End. Right expands the focused item or
+/// moves to its first child, and Left collapses it or moves to its parent. With
+/// [`selectable`](Handle::selectable) the items can be selected by clicking them or with the
+/// keyboard.
+///
+/// # Example
+/// ```
+/// # use vizia_core::prelude::*;
+/// #
+/// #[derive(Clone, Data)]
+/// struct Node {
+///     path: String,
+///     children: Vec<Node>,
+/// }
+/// #
+/// # #[derive(Lens)]
+/// # struct AppData {
+/// #     nodes: Vec<Node>,
+/// # }
+/// #
+/// # impl Model for AppData {}
+/// #
+/// # let cx = &mut Context::default();
+/// #
+/// # AppData { nodes: vec![Node { path: String::from("src"), children: Vec::new() }] }.build(cx);
+/// #
+/// TreeView::new(
+///     cx,
+///     AppData::nodes,
+///     |node| node.children.as_slice(),
+///     |node| node.path.clone(),
+///     |cx, node| {
+///         Label::new(cx, node.map_ref(|node| &node.path));
+///     },
+/// )
+/// .selectable(Selectable::Single);
+/// ```
+#[derive(Lens)]
+pub struct TreeView<K: 'static> {
+    // The expanded items.
+    expanded: HashSet<K>,
+    // The items whose children have been built. The children stay built when their parent is
+    // collapsed.
+    loaded: HashSet<K>,
+    #[lens(ignore)]
+    selection: ItemSelection<K>,
+    focused: Option<K>,
+    #[lens(ignore)]
+    selectable: Selectable,
+    // The scrollview which contains the items.
+    #[lens(ignore)]
+    body: Entity,
+    // The items which are shown, in the order they are shown.
+    #[lens(ignore)]
+    visible: Vec<VisibleItem<K>>,
+    #[lens(ignore)]
+    visible_items: VisibleItems<K>,
+    #[lens(ignore)]
+    item_ids: ItemIds<K>,
+    #[lens(ignore)]
+    on_select: Option<Box<dyn Fn(&mut EventContext, &[K])>>,
+}
+
+impl<K: Data + Eq + Hash + Send> TreeView<K> {
+    /// Creates a new tree view which shows the items of a list and their descendants, given a
+    /// function which returns the children of an item, a function which returns the id of an item
+    /// and a template for the content of an item.
+    pub fn new<L, T, C, I>(
+        cx: &mut Context,
+        list: L,
+        children: C,
+        id: I,
+        item_content: impl 'static + Fn(&mut Context, MapRef<L, T>),
+    ) -> Handle<Self>
+    where
+        L: Lens<Target = Vec<T>>,
+        T: 'static,
+        C: 'static + Copy + Fn(&T) -> &[T],
+        I: 'static + Copy + Fn(&T) -> K,
+    {
+        let content: Rc<dyn Fn(&mut Context, MapRef<L, T>)> = Rc::new(item_content);
+        let tree = Tree { list, children, id, content };
+        let visible = match list.get_ref(cx) {
+            Some(roots) => visible_items(&roots, &HashSet::new(), children, id),
+            None => Vec::new(),
+        };
+        let mut body = Entity::null();
+
+        Self {
+            expanded: HashSet::new(),
+            loaded: HashSet::new(),
+            selection: ItemSelection::default(),
+            focused: None,
+            selectable: Selectable::None,
+            body: Entity::null(),
+            visible,
+            visible_items: Box::new(move |cx: &EventContext, expanded: &HashSet<K>| {
+                match cx.data().and_then(|source| list.view(source)) {
+                    Some(roots) => visible_items(&roots, expanded, children, id),
+                    None => Vec::new(),
+                }
+            }),
+            item_ids: Box::new(move |cx: &EventContext| {
+                match cx.data().and_then(|source| list.view(source)) {
+                    Some(roots) => item_ids(&roots, children, id),
+                    None => HashSet::new(),
+                }
+            }),
+            on_select: None,
+        }
+        .build(cx, |cx| {
+            body = ScrollView::new(cx, 0.0, 0.0, false, true, move |cx| {
+                Binding::new(cx, list.map(Vec::len), move |cx, num_roots| {
+                    for index in 0..num_roots.get(cx) {
+                        tree.build_item(cx, vec![index]);
+                    }
+                });
+            })
+            .entity();
+        })
+        .modify(|tree_view| tree_view.body = body)
+        .bind(list, |mut handle, _| {
+            let entity = handle.entity();
+            handle.context().emit_to(entity, TreeUpdate::<K>::Refresh);
+        })
+        .navigable(true)
+        .role(Role::Tree)
+    }
+
+    fn update_visible(&mut self, cx: &EventContext) {
+        self.visible = (self.visible_items)(cx, &self.expanded);
+    }
+
+    // Finds the items which are shown again and forgets the state of the items which were removed.
+    // The focus moves to the item shown in place of the focused item if it was removed.
+    fn update_items(&mut self, cx: &mut EventContext) {
+        let position = self.focused_position();
+
+        let ids = (self.item_ids)(cx);
+        self.expanded.retain(|id| ids.contains(id));
+        self.loaded.retain(|id| ids.contains(id));
+        let num_selected = self.selection.selected.len();
+        self.selection.retain(|id| ids.contains(id));
+
+        self.update_visible(cx);
+
+        if self.focused.as_ref().is_some_and(|focused| !ids.contains(focused)) {
+            self.focused = position.and_then(|position| {
+                let last = self.visible.len().checked_sub(1)?;
+                Some(self.visible[position.min(last)].id.clone())
+            });
+        }
+
+        if self.selection.selected.len() != num_selected {
+            if let Some(callback) = &self.on_select {
+                (callback)(cx, &self.selection.selected);
+            }
+        }
+    }
+
+    // Returns the position of the focused item in the order the items are shown.
+    fn focused_position(&self) -> Option<usize> {
+        let focused = self.focused.as_ref()?;
+        self.visible.iter().position(|item| item.id == *focused)
+    }
+
+    fn expand(&mut self, cx: &EventContext, id: &K) {
+        self.loaded.insert(id.clone());
+        self.expanded.insert(id.clone());
+        self.update_visible(cx);
+    }
+
+    // Collapses an item, moving the focus to it from any of its descendants, which are hidden.
+    fn collapse(&mut self, cx: &EventContext, id: &K) {
+        let visible = &self.visible;
+        if let Some(position) = visible.iter().position(|item| item.id == *id) {
+            let depth = visible[position].depth;
+            let mut hidden = visible[position + 1..].iter().take_while(|item| item.depth > depth);
+            if hidden.any(|item| self.focused.as_ref() == Some(&item.id)) {
+                self.focused = Some(id.clone());
+            }
+        }
+
+        self.expanded.remove(id);
+        self.update_visible(cx);
+    }
+
+    fn toggle(&mut self, cx: &EventContext, id: &K) {
+        if self.expanded.contains(id) {
+            self.collapse(cx, id);
+        } else {
+            self.expand(cx, id);
+        }
+    }
+
+    // Returns the ids of the items which are shown, in the order they are shown.
+    fn visible_ids(&self) -> Vec<K> {
+        self.visible.iter().map(|item| item.id.clone()).collect()
+    }
+
+    // Selects an item, either toggling whether it is selected or extending the selection to it
+    // from the anchor, over the items which are shown, if more than one item can be selected.
+    fn select(&mut self, cx: &mut EventContext, id: &K, toggle: bool, extend: bool) {
+        let visible = self.visible_ids();
+        if self.selection.select(self.selectable, &visible, id, toggle, extend) {
+            if let Some(callback) = &self.on_select {
+                (callback)(cx, &self.selection.selected);
+            }
+        }
+    }
+
+    fn set_selection(&mut self, cx: &mut EventContext, selected: Vec<K>) {
+        self.selection.set(selected);
+        if let Some(callback) = &self.on_select {
+            (callback)(cx, &self.selection.selected);
+        }
+    }
+
+    // Moves the focus to an item, which selects it, extends the selection to it with Shift, or
+    // only moves the focus with the command modifier held.
+    fn move_focus(&mut self, cx: &mut EventContext, id: K) {
+        if self.focused.as_ref() != Some(&id) {
+            if cx.modifiers.shift() {
+                self.select(cx, &id, false, true);
+            } else if !cx.modifiers.contains(Modifiers::COMMAND) {
+                self.select(cx, &id, false, false);
+            }
+        }
+
+        self.focused = Some(id);
+        self.scroll_to_focused(cx);
+    }
+
+    fn scroll_to_focused(&self, cx: &mut EventContext) {
+        let Some(focused) = &self.focused else {
+            return;
+        };
+
+        let item = self.body.branch_iter(cx.tree).find(|entity| {
+            cx.views
+                .get(entity)
+                .and_then(|view| view.downcast_ref::<TreeItem<K>>())
+                .is_some_and(|item| item.id == *focused)
+        });
+
+        // The first child of an item is the row which shows its content.
+        if let Some(row) = item.and_then(|item| cx.tree.get_layout_first_child(item)) {
+            let bounds = cx.cache.get_bounds(row);
+            cx.emit_to(self.body, ScrollEvent::ScrollIntoView(bounds));
+        }
+    }
+}
+
+impl<K: Data + Eq + Hash + Send> View for TreeView<K> {
+    fn element(&self) -> Option<&'static str> {
+        Some("tree-view")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|tree_view_event: &TreeViewEvent<K>, _| match tree_view_event {
+            TreeViewEvent::Expand(id) => {
+                self.expand(cx, id);
+            }
+
+            TreeViewEvent::Collapse(id) => {
+                self.collapse(cx, id);
+            }
+
+            TreeViewEvent::Toggle(id) => {
+                self.toggle(cx, id);
+            }
+
+            TreeViewEvent::Select(id) => {
+                self.select(cx, id, false, false);
+            }
+
+            TreeViewEvent::SelectAll => {
+                if self.selectable == Selectable::Multi {
+                    self.set_selection(cx, self.visible_ids());
+                }
+            }
+
+            TreeViewEvent::ClearSelection => {
+                self.set_selection(cx, Vec::new());
+            }
+        });
+
+        event.map(|tree_update: &TreeUpdate<K>, _| match tree_update {
+            TreeUpdate::Press(id) => {
+                cx.focus_with_visibility(false);
+                self.focused = Some(id.clone());
+                let (toggle, extend) =
+                    (cx.modifiers.contains(Modifiers::COMMAND), cx.modifiers.shift());
+                self.select(cx, id, toggle, extend);
+            }
+
+            TreeUpdate::Refresh => {
+                self.update_items(cx);
+            }
+        });
+
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::KeyDown(code, _) => {
+                let Some(last) = self.visible.len().checked_sub(1) else {
+                    return;
+                };
+
+                let position = self.focused_position();
+                let target = match (code, position) {
+                    (Code::ArrowDown, Some(position)) => (position + 1).min(last),
+                    (Code::ArrowUp, Some(position)) => position.saturating_sub(1),
+                    (Code::Home, _) => 0,
+                    (Code::End, _) => last,
+
+                    // Nothing is focused yet, so the first item is focused.
+                    (
+                        Code::ArrowDown | Code::ArrowUp | Code::ArrowRight | Code::ArrowLeft,
+                        None,
+                    ) => 0,
+
+                    (Code::ArrowRight, Some(position)) => {
+                        let item = &self.visible[position];
+                        if !item.has_children {
+                            meta.consume();
+                            return;
+                        }
+
+                        if !self.expanded.contains(&item.id) {
+                            let id = item.id.clone();
+                            self.expand(cx, &id);
+                            meta.consume();
+                            return;
+                        }
+
+                        // The first child of an expanded item is shown after it.
+                        position + 1
+                    }
+
+                    (Code::ArrowLeft, Some(position)) => {
+                        let item = &self.visible[position];
+                        if self.expanded.contains(&item.id) {
+                            let id = item.id.clone();
+                            self.collapse(cx, &id);
+                            meta.consume();
+                            return;
+                        }
+
+                        // The parent of an item is the last item shown before it which is less
+                        // deeply nested.
+                        let parent = self.visible[..position]
+                            .iter()
+                            .rposition(|other| other.depth < item.depth);
+                        match parent {
+                            Some(parent) => parent,
+                            None => {
+                                meta.consume();
+                                return;
+                            }
+                        }
+                    }
+
+                    (Code::Enter, Some(position)) => {
+                        let item = &self.visible[position];
+                        if item.has_children {
+                            let id = item.id.clone();
+                            self.toggle(cx, &id);
+                        }
+                        meta.consume();
+                        return;
+                    }
+
+                    (Code::Space, Some(position)) => {
+                        let id = self.visible[position].id.clone();
+                        self.select(cx, &id, true, false);
+                        meta.consume();
+                        return;
+                    }
+
+                    (Code::KeyA, _) if *cx.modifiers == Modifiers::COMMAND => {
+                        cx.emit(TreeViewEvent::<K>::SelectAll);
+                        meta.consume();
+                        return;
+                    }
+
+                    (Code::Escape, _) => {
+                        cx.emit(TreeViewEvent::<K>::ClearSelection);
+                        return;
+                    }
+
+                    _ => return,
+                };
+
+                let id = self.visible[target].id.clone();
+                self.move_focus(cx, id);
+                meta.consume();
+            }
+
+            _ => {}
+        });
+    }
+}
+
+impl<K: Data + Eq + Hash + Send> Handle<'_, TreeView<K>> {
+    /// Sets whether the items of the tree view can be selected, and whether more than one item can
+    /// be selected. Items can't be selected by default.
+    pub fn selectable(self, selectable: impl Res<Selectable>) -> Self {
+        self.bind(selectable, |handle, selectable| {
+            let selectable = selectable.get(&handle);
+            handle.modify(|tree_view| tree_view.selectable = selectable);
+        })
+    }
+
+    /// Sets a callback which is called with the ids of the selected items when the selection
+    /// changes.
+    pub fn on_select(self, callback: impl 'static + Fn(&mut EventContext, &[K])) -> Self {
+        self.modify(|tree_view| tree_view.on_select = Some(Box::new(callback)))
+    }
+}
+
+// The data of a tree and the functions which describe its items, which are shared by the views of
+// its items.
+struct Tree<L: Lens, T, C, I> {
+    list: L,
+    children: C,
+    id: I,
+    content: Rc<dyn Fn(&mut Context, MapRef<L, T>)>,
+}
+
+impl<L: Lens, T, C: Copy, I: Copy> Clone for Tree<L, T, C, I> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list,
+            children: self.children,
+            id: self.id,
+            content: self.content.clone(),
+        }
+    }
+}
+
+impl<L, T, C, I> Tree<L, T, C, I>
+where
+    L: Lens<Target = Vec<T>>,
+    T: 'static,
+    C: 'static + Copy + Fn(&T) -> &[T],
+    I: 'static + Copy,
+{
+    // Builds the view of the item at the given path, which is built again when another item moves
+    // to the path, so that the view shows the state of that item.
+    fn build_item<K>(&self, cx: &mut Context, path: Vec<usize>)
+    where
+        I: Fn(&T) -> K,
+        K: Data + Eq + Hash + Send,
+    {
+        let item_id = {
+            let (path, children, id) = (path.clone(), self.children, self.id);
+            self.list.map(move |roots| item_at(roots, &path, children).map(id))
+        };
+
+        let tree = self.clone();
+        Binding::new(cx, item_id, move |cx, item_id| {
+            if let Some(item_id) = item_id.get(cx) {
+                TreeItem::new(cx, &tree, path.clone(), item_id);
+            }
+        });
+    }
+}
+
+// An item of a tree, with a row showing its content followed by the views of its children.
+struct TreeItem<K> {
+    id: K,
+    has_children: bool,
+    expanded: bool,
+    selected: bool,
+}
+
+impl<K: Data + Eq + Hash + Send> TreeItem<K> {
+    fn new<L, T, C, I>(
+        cx: &mut Context,
+        tree: &Tree<L, T, C, I>,
+        path: Vec<usize>,
+        id: K,
+    ) -> Handle<Self>
+    where
+        L: Lens<Target = Vec<T>>,
+        T: 'static,
+        C: 'static + Copy + Fn(&T) -> &[T],
+        I: 'static + Copy + Fn(&T) -> K,
+    {
+        let (list, children) = (tree.list, tree.children);
+
+        let has_children = {
+            let path = path.clone();
+            list.map(move |roots| {
+                item_at(roots, &path, children).is_some_and(|item| !children(item).is_empty())
+            })
+        };
+
+        let expanded = {
+            let id = id.clone();
+            TreeView::<K>::expanded.map(move |expanded| expanded.contains(&id))
+        };
+
+        let selected = {
+            let id = id.clone();
+            TreeView::<K>::root.map(move |tree_view| tree_view.selection.contains(&id))
+        };
+
+        let focused = {
+            let id = id.clone();
+            TreeView::<K>::focused.map(move |focused| focused.as_ref() == Some(&id))
+        };
+
+        let tree = tree.clone();
+        Self { id: id.clone(), has_children: false, expanded: false, selected: false }
+            .build(cx, move |cx| {
+                HStack::new(cx, |cx| {
+                    for _ in 1..path.len() {
+                        Element::new(cx).class("indent-guide");
+                    }
+
+                    let toggle_id = id.clone();
+                    Binding::new(cx, has_children, move |cx, has_children| {
+                        if has_children.get(cx) {
+                            TreeToggle::new(cx, toggle_id.clone(), expanded);
+                        } else {
+                            Element::new(cx).class("tree-toggle");
+                        }
+                    });
+
+                    // The item has no value once it is removed, until its view is removed.
+                    let item_path = path.clone();
+                    let item = list.try_map_ref(move |roots| item_at(roots, &item_path, children));
+                    (tree.content)(cx, item);
+                })
+                .class("tree-row")
+                .checked(selected)
+                .toggle_class("focused", focused);
+
+                VStack::new(cx, move |cx| {
+                    let loaded = TreeView::<K>::loaded.map(move |loaded| loaded.contains(&id));
+
+                    Binding::new(cx, loaded, move |cx, loaded| {
+                        if !loaded.get(cx) {
+                            return;
+                        }
+
+                        let num_children = {
+                            let path = path.clone();
+                            list.map(move |roots| {
+                                item_at(roots, &path, children)
+                                    .map_or(0, |item| children(item).len())
+                            })
+                        };
+
+                        let path = path.clone();
+                        let tree = tree.clone();
+                        Binding::new(cx, num_children, move |cx, num_children| {
+                            for index in 0..num_children.get(cx) {
+                                let mut child = path.clone();
+                                child.push(index);
+                                tree.build_item(cx, child);
+                            }
+                        });
+                    });
+                })
+                .class("tree-children")
+                .display(expanded)
+                .role(Role::Group);
+            })
+            .toggle_class("expanded", expanded)
+            .bind(has_children, |mut handle, has_children| {
+                let has_children = has_children.get(&handle);
+                let entity = handle.entity();
+                handle.context().style.needs_access_update(entity);
+                handle.modify(|item| item.has_children = has_children);
+            })
+            .bind(expanded, |mut handle, expanded| {
+                let expanded = expanded.get(&handle);
+                let entity = handle.entity();
+                handle.context().style.needs_access_update(entity);
+                handle.modify(|item| item.expanded = expanded);
+            })
+            .bind(selected, |mut handle, selected| {
+                let selected = selected.get(&handle);
+                let entity = handle.entity();
+                handle.context().style.needs_access_update(entity);
+                handle.modify(|item| item.selected = selected);
+            })
+            .role(Role::TreeItem)
+    }
+}
+
+impl<K: Data + Eq + Hash + Send> View for TreeItem<K> {
+    fn element(&self) -> Option<&'static str> {
+        Some("tree-item")
+    }
+
+    fn accessibility(&self, _cx: &mut AccessContext, node: &mut AccessNode) {
+        if self.has_children {
+            node.set_expanded(self.expanded);
+        }
+
+        node.set_selected(self.selected);
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        // Presses within the children of the item are handled by the items of the children.
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
+                cx.emit(TreeUpdate::Press(self.id.clone()));
+                meta.consume();
+            }
+
+            WindowEvent::MouseDoubleClick(button) if *button == MouseButton::Left => {
+                if self.has_children {
+                    cx.emit(TreeViewEvent::Toggle(self.id.clone()));
+                }
+                meta.consume();
+            }
+
+            _ => {}
+        });
+    }
+}
+
+// The chevron of an item with children, which expands and collapses the item when pressed.
+struct TreeToggle<K> {
+    id: K,
+}
+
+impl<K: Data + Send> TreeToggle<K> {
+    fn new(cx: &mut Context, id: K, expanded: impl Lens<Target = bool>) -> Handle<Self> {
+        Self { id }.build(cx, |cx| {
+            let icon = expanded
+                .map(|expanded| if *expanded { ICON_CHEVRON_DOWN } else { ICON_CHEVRON_RIGHT });
+            Svg::new(cx, icon);
+        })
+    }
+}
+
+impl<K: Data + Send> View for TreeToggle<K> {
+    fn element(&self) -> Option<&'static str> {
+        Some("tree-toggle")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
+                cx.emit(TreeViewEvent::Toggle(self.id.clone()));
+                meta.consume();
+            }
+
+            // Each click of a double click on the chevron toggles the item, so the row doesn't
+            // toggle it again.
+            WindowEvent::MouseDoubleClick(_) => {
+                meta.consume();
+            }
+
+            _ => {}
+        });
+    }
+}
+
+// Returns the item at the given path, given the function which returns the children of an item.
+fn item_at<'a, T>(roots: &'a [T], path: &[usize], children: impl Fn(&T) -> &[T]) -> Option<&'a T> {
+    let (first, rest) = path.split_first()?;
+    rest.iter().try_fold(roots.get(*first)?, |item, index| children(item).get(*index))
+}
+
+// Returns each item which is shown, in the order they are shown. The children of an item are shown
+// when it and each of its ancestors are expanded.
+fn visible_items<T, K: Eq + Hash>(
+    roots: &[T],
+    expanded: &HashSet<K>,
+    children: impl Fn(&T) -> &[T],
+    id: impl Fn(&T) -> K,
+) -> Vec<VisibleItem<K>> {
+    fn visit<T, K: Eq + Hash>(
+        items: &[T],
+        depth: usize,
+        expanded: &HashSet<K>,
+        children: &dyn Fn(&T) -> &[T],
+        id: &dyn Fn(&T) -> K,
+        visible: &mut Vec<VisibleItem<K>>,
+    ) {
+        for item in items {
+            let items = children(item);
+            let item_id = id(item);
+            let show_children = !items.is_empty() && expanded.contains(&item_id);
+            visible.push(VisibleItem { id: item_id, depth, has_children: !items.is_empty() });
+            if show_children {
+                visit(items, depth + 1, expanded, children, id, visible);
+            }
+        }
+    }
+
+    let mut visible = Vec::new();
+    visit(roots, 0, expanded, &children, &id, &mut visible);
+    visible
+}
+
+// Returns the id of every item, whether or not it is shown.
+fn item_ids<T, K: Eq + Hash>(
+    roots: &[T],
+    children: impl Fn(&T) -> &[T],
+    id: impl Fn(&T) -> K,
+) -> HashSet<K> {
+    let mut ids = HashSet::new();
+    let mut items = roots.iter().collect::<Vec<_>>();
+    while let Some(item) = items.pop() {
+        ids.insert(id(item));
+        items.extend(children(item));
+    }
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Node {
+        id: u32,
+        children: Vec<Node>,
+    }
+
+    fn node(id: u32, children: Vec<Node>) -> Node {
+        Node { id, children }
+    }
+
+    fn children(node: &Node) -> &[Node] {
+        &node.children
+    }
+
+    fn id(node: &Node) -> u32 {
+        node.id
+    }
+
+    // Two roots, where the second has a leaf and an item with a leaf as its children.
+    fn tree() -> Vec<Node> {
+        vec![node(0, vec![]), node(1, vec![node(2, vec![]), node(3, vec![node(4, vec![])])])]
+    }
+
+    fn item(id: u32, depth: usize, has_children: bool) -> VisibleItem<u32> {
+        VisibleItem { id, depth, has_children }
+    }
+
+    #[test]
+    fn item_paths() {
+        let roots = tree();
+
+        assert_eq!(item_at(&roots, &[1, 1], children).map(|item| item.children.len()), Some(1));
+        assert!(item_at(&roots, &[1, 1, 0], children).is_some());
+        assert!(item_at(&roots, &[0, 0], children).is_none());
+        assert!(item_at(&roots, &[2], children).is_none());
+        assert!(item_at(&roots, &[], children).is_none());
+    }
+
+    #[test]
+    fn visible_items_of_expanded_items() {
+        let mut roots = tree();
+        let mut expanded = HashSet::new();
+
+        let collapsed = vec![item(0, 0, false), item(1, 0, true)];
+        assert_eq!(visible_items(&roots, &expanded, children, id), collapsed);
+
+        // The children of an expanded item are hidden while its parent is collapsed.
+        expanded.insert(3);
+        assert_eq!(visible_items(&roots, &expanded, children, id), collapsed);
+
+        expanded.insert(1);
+        assert_eq!(
+            visible_items(&roots, &expanded, children, id),
+            vec![
+                item(0, 0, false),
+                item(1, 0, true),
+                item(2, 1, false),
+                item(3, 1, true),
+                item(4, 2, false)
+            ]
+        );
+
+        // An item stays expanded when it moves.
+        roots.reverse();
+        roots[0].children.remove(0);
+        assert_eq!(
+            visible_items(&roots, &expanded, children, id),
+            vec![item(1, 0, true), item(3, 1, true), item(4, 2, false), item(0, 0, false)]
+        );
+    }
+
+    #[test]
+    fn ids_of_every_item() {
+        let ids = item_ids(&tree(), children, id);
+        assert_eq!(ids, HashSet::from([0, 1, 2, 3, 4]));
+        assert!(item_ids(&[], children, id).is_empty());
+    }
+}
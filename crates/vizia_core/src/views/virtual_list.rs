@@ -13,7 +13,7 @@ pub enum VirtualListEvent {
 }
 
 #[derive(Lens)]
-struct VirtualListData {
+pub(crate) struct VirtualListData {
     num_items: usize,
     item_height: f32,
     visible_range: Range<usize>,
@@ -21,7 +21,10 @@ struct VirtualListData {
 }
 
 impl VirtualListData {
-    fn evaluate_index(index: usize, start: usize, end: usize) -> usize {
+    // Returns the index of the item shown by the view at the given index within the visible range.
+    // Views keep their item while the range moves, so only views of items scrolled into view are
+    // rebuilt.
+    pub(crate) fn evaluate_index(index: usize, start: usize, end: usize) -> usize {
         match end - start {
             0 => 0,
             len => start + (len - (start % len) + index) % len,
@@ -65,6 +65,11 @@ pub enum WindowEvent {
     FocusOut,
     /// Emitted when a character is typed.
     CharInput(char),
+    /// Emitted when an input method editor (IME) updates the text being composed, along with the
+    /// byte range of the cursor within it. An empty string indicates that composition has ended.
+    ImePreedit(String, Option<(usize, usize)>),
+    /// Emitted when an input method editor (IME) commits the composed text.
+    ImeCommit(String),
     /// Emitted when a keyboard key is pressed.
     KeyDown(Code, Option<Key>),
     /// Emitted when a keyboard key is released.
@@ -77,6 +82,9 @@ pub enum WindowEvent {
     GrabCursor(bool),
     /// Sets the (x,y) position of the mouse cursor in window coordinates.
     SetCursorPosition(u32, u32),
+    /// Sets the (x,y) position and (width,height) size, in physical pixels, of the area the input
+    /// method editor (IME) should avoid covering, typically the text caret.
+    SetImeCursorArea((u32, u32), (u32, u32)),
     /// Sets the title of the window.
     SetTitle(String),
     /// Sets the size of the window.
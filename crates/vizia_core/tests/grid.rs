@@ -0,0 +1,80 @@
+use vizia_core::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+#[test]
+fn grid_tracks_are_sized_in_the_first_frame() {
+    let mut app = HeadlessApplication::new(|cx| {
+        VStack::new(cx, |cx| {
+            Element::new(cx).width(Pixels(30.0)).height(Pixels(10.0)).id("fixed");
+            // A stretch item in an auto track fills the track rather than the grid.
+            Element::new(cx).row_start(1).height(Pixels(20.0)).id("stretch");
+            Element::new(cx).column_start(1).row_span(2).id("column");
+        })
+        .layout_type(LayoutType::Grid)
+        .grid_columns(vec![Auto, Stretch(1.0)])
+        .grid_rows(vec![Auto, Auto])
+        .col_between(Pixels(10.0))
+        .width(Pixels(200.0))
+        .height(Auto)
+        .id("grid");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+
+    let grid = app.bounds("grid");
+    let fixed = app.bounds("fixed");
+    let stretch = app.bounds("stretch");
+    let column = app.bounds("column");
+
+    // The auto grid is sized to fit its auto tracks.
+    assert_eq!(grid.height(), 30.0);
+    assert_eq!((fixed.x - grid.x, fixed.y - grid.y), (0.0, 0.0));
+    assert_eq!((fixed.width(), fixed.height()), (30.0, 10.0));
+    assert_eq!((stretch.x - grid.x, stretch.y - grid.y), (0.0, 10.0));
+    assert_eq!((stretch.width(), stretch.height()), (30.0, 20.0));
+    assert_eq!((column.x - grid.x, column.y - grid.y), (40.0, 0.0));
+    assert_eq!((column.width(), column.height()), (160.0, 30.0));
+
+    // Laying out again doesn't move anything.
+    app.context().needs_relayout();
+    app.run_frame();
+    assert_eq!(app.bounds("stretch"), stretch);
+    assert_eq!(app.bounds("column"), column);
+}
+
+#[test]
+fn nested_grids_are_placed_within_their_cell() {
+    let mut app = HeadlessApplication::new(|cx| {
+        VStack::new(cx, |cx| {
+            Element::new(cx).id("first");
+            VStack::new(cx, |cx| {
+                Element::new(cx).id("left");
+                Element::new(cx).column_start(1).max_width(Pixels(50.0)).id("right");
+            })
+            .layout_type(LayoutType::Grid)
+            .grid_columns(vec![Stretch(1.0), Stretch(1.0)])
+            .column_start(1)
+            .id("inner");
+        })
+        .layout_type(LayoutType::Grid)
+        .grid_columns(vec![Pixels(100.0), Stretch(1.0)])
+        .width(Pixels(300.0))
+        .height(Pixels(100.0))
+        .id("grid");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+
+    let grid = app.bounds("grid");
+    let inner = app.bounds("inner");
+    let left = app.bounds("left");
+    let right = app.bounds("right");
+
+    assert_eq!((inner.x - grid.x, inner.y - grid.y), (100.0, 0.0));
+    assert_eq!((inner.width(), inner.height()), (200.0, 100.0));
+    assert_eq!((left.x - grid.x, left.width(), left.height()), (100.0, 100.0, 100.0));
+    // The item is no wider than its max width.
+    assert_eq!((right.x - grid.x, right.width(), right.height()), (200.0, 50.0, 100.0));
+}
@@ -0,0 +1,159 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use vizia_core::prelude::*;
+use vizia_core::vg::{self, image::CachingHint, AlphaType, ColorType, ImageInfo};
+use vizia_headless::HeadlessApplication;
+
+// Returns a new directory for the files of a test, named after the test and the process so that
+// tests running at the same time don't share files.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vizia_images_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Returns a PNG file of a 16x16 image filled with the given color.
+fn png(color: vg::Color) -> Vec<u8> {
+    let mut surface = vg::surfaces::raster_n32_premul((16, 16)).unwrap();
+    surface.canvas().clear(color);
+    surface.image_snapshot().encode_to_data(vg::EncodedImageFormat::PNG).unwrap().to_vec()
+}
+
+// Builds an image view which loads the given image from the given directory.
+fn image_app(dir: &Path, name: &str) -> HeadlessApplication {
+    let (dir, name) = (dir.to_owned(), name.to_owned());
+    let mut app = HeadlessApplication::new(move |cx| {
+        cx.set_asset_root(dir);
+        Image::new(cx, name).id("image");
+    })
+    .inner_size((100, 100));
+
+    app.run_frame();
+    app
+}
+
+// Returns the pixels drawn within the bounds of the image view.
+fn image_pixels(app: &mut HeadlessApplication) -> Vec<[u8; 4]> {
+    let bounds = app.bounds("image");
+    let image = app.snapshot(Entity::root()).unwrap();
+    let width = image.width() as usize;
+
+    let info = ImageInfo::new(
+        (image.width(), image.height()),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    );
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; row_bytes * image.height() as usize];
+    assert!(image.read_pixels(&info, &mut pixels, row_bytes, (0, 0), CachingHint::Disallow));
+
+    pixels
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(index, _)| {
+            bounds.contains_point((index % width) as f32 + 0.5, (index / width) as f32 + 0.5)
+        })
+        .map(|(_, pixel)| [pixel[0], pixel[1], pixel[2], pixel[3]])
+        .collect()
+}
+
+// Returns whether the image view shows any pixels of the given color.
+fn shows(app: &mut HeadlessApplication, color: vg::Color) -> bool {
+    let color = [color.r(), color.g(), color.b()];
+    image_pixels(app).iter().any(|pixel| (0..3).all(|i| pixel[i].abs_diff(color[i]) < 50))
+}
+
+// Returns whether nothing but the white background of the window is drawn in the image view.
+fn is_blank(app: &mut HeadlessApplication) -> bool {
+    let pixels = image_pixels(app);
+    !pixels.is_empty() && pixels.iter().all(|pixel| pixel == &[255, 255, 255, 255])
+}
+
+// Runs frames until the condition holds, as images are loaded on another thread.
+fn wait_for(app: &mut HeadlessApplication, condition: impl Fn(&mut HeadlessApplication) -> bool) {
+    let start = Instant::now();
+    while !condition(app) {
+        assert!(start.elapsed() < Duration::from_secs(10), "timed out waiting for the image");
+        std::thread::sleep(Duration::from_millis(10));
+        app.run_frame();
+    }
+}
+
+#[test]
+fn image_files_show_a_placeholder_until_they_are_decoded() {
+    let dir = test_dir("decoded");
+    std::fs::write(dir.join("red.png"), png(vg::Color::RED)).unwrap();
+
+    // The decoded image arrives at the earliest in the next frame.
+    let mut app = image_app(&dir, "red.png");
+    assert!(is_blank(&mut app));
+
+    wait_for(&mut app, |app| shows(app, vg::Color::RED));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn svg_files_are_parsed_before_they_arrive() {
+    let dir = test_dir("svg");
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16">
+        <rect width="16" height="16" fill="red"/>
+    </svg>"#;
+    std::fs::write(dir.join("red.svg"), svg).unwrap();
+
+    let mut app = image_app(&dir, "red.svg");
+    assert!(is_blank(&mut app));
+
+    wait_for(&mut app, |app| shows(app, vg::Color::RED));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn files_which_fail_to_load_show_the_broken_image() {
+    let dir = test_dir("broken");
+    std::fs::write(dir.join("corrupt.png"), b"not an image").unwrap();
+
+    for name in ["missing.png", "corrupt.png"] {
+        let mut app = image_app(&dir, name);
+        assert!(is_blank(&mut app));
+
+        wait_for(&mut app, |app| !is_blank(app));
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn changed_image_files_are_reloaded_and_redrawn() {
+    let dir = test_dir("reload");
+    let path = dir.join("icon.png");
+    std::fs::write(&path, png(vg::Color::RED)).unwrap();
+
+    let root = dir.clone();
+    let mut app = HeadlessApplication::new(move |cx| {
+        cx.set_asset_root(root);
+        cx.watch_image_files(Duration::from_millis(100));
+        Image::new(cx, "icon.png").id("image");
+    })
+    .inner_size((100, 100));
+
+    app.run_frame();
+    wait_for(&mut app, |app| shows(app, vg::Color::RED));
+
+    // The file is given a later modification time, as writing it again so soon may not change it.
+    std::fs::write(&path, png(vg::Color::BLUE)).unwrap();
+    let file = std::fs::File::options().write(true).open(&path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+
+    // The watcher notices the change when it next ticks, and the view which shows the image is
+    // redrawn when the new image arrives.
+    app.advance_time(Duration::from_millis(100));
+    app.run_frame();
+    wait_for(&mut app, |app| shows(app, vg::Color::BLUE));
+    assert!(!shows(&mut app, vg::Color::RED));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
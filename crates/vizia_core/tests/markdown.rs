@@ -0,0 +1,13 @@
+use vizia_core::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+#[test]
+fn empty_code_blocks() {
+    let mut app = HeadlessApplication::new(|cx| {
+        Markdown::new(cx, "```\n```\n\n```toml\n```\n");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+    assert_eq!(app.context().query_selector_all("label.code").len(), 2);
+}
@@ -0,0 +1,23 @@
+use vizia_core::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+#[test]
+fn media_queries_match_the_scale_factor_of_the_window() {
+    let mut app = HeadlessApplication::new(|cx| {
+        cx.add_stylesheet(
+            ".element { width: 20px; } @media (resolution >= 2dppx) { .element { width: 50px; } }",
+        )
+        .unwrap();
+
+        Element::new(cx).class("element").id("element");
+    })
+    .inner_size((400, 300))
+    .scale_factor(2.0);
+
+    app.run_frame();
+    assert_eq!(app.bounds("element").width(), 100.0);
+
+    app.set_scale_factor(1.0);
+    app.run_frame();
+    assert_eq!(app.bounds("element").width(), 20.0);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "clipboard")]
+extern crate vizia_core as vizia;
+
+use vizia::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+#[derive(Lens)]
+struct AppData {
+    document: RichText,
+}
+
+enum AppEvent {
+    SetDocument(RichText),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetDocument(document) => self.document = document.clone(),
+        });
+    }
+}
+
+fn app(markdown: &str) -> HeadlessApplication {
+    let document = RichText::from_markdown(markdown);
+    let mut app = HeadlessApplication::new(move |cx| {
+        AppData { document }.build(cx);
+
+        RichTextEditor::new(cx, AppData::document)
+            .on_change(|cx, document| cx.emit(AppEvent::SetDocument(document)))
+            .width(Pixels(300.0))
+            .id("editor");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+    app
+}
+
+fn document(app: &HeadlessApplication) -> &RichText {
+    &app.data::<AppData>().unwrap().document
+}
+
+// Copies the whole document and pastes it at its end.
+fn copy_and_paste_all(app: &mut HeadlessApplication) {
+    app.click("editor");
+    app.press_chord(KeyChord::new(Modifiers::COMMAND, Code::KeyA));
+    app.press_chord(KeyChord::new(Modifiers::COMMAND, Code::KeyC));
+    app.press_key(Code::End, Some(Key::End));
+    app.press_chord(KeyChord::new(Modifiers::COMMAND, Code::KeyV));
+}
+
+#[test]
+fn copying_and_pasting_styled_text() {
+    let mut app = app("**bold** and a\\*b\\*c");
+    let original = document(&app).clone();
+    assert_eq!(original.text(), "bold and a*b*c");
+
+    copy_and_paste_all(&mut app);
+
+    let mut expected = original.clone();
+    expected.append(&original);
+    assert_eq!(document(&app), &expected);
+}
+
+#[test]
+fn copying_and_pasting_text_which_looks_like_markdown() {
+    let mut app = app("a\\*b\\*c \\_x\\_");
+    copy_and_paste_all(&mut app);
+
+    assert_eq!(document(&app).text(), "a*b*c _x_a*b*c _x_");
+    assert!(document(&app).runs().iter().all(|run| run.style == RunStyle::default()));
+}
+
+#[test]
+fn pasting_markdown_from_elsewhere_as_it_is() {
+    let mut app = app("");
+    EventContext::new(app.context()).set_clipboard(String::from("_x_ and **y**")).unwrap();
+
+    app.click("editor");
+    app.press_chord(KeyChord::new(Modifiers::COMMAND, Code::KeyV));
+
+    assert_eq!(document(&app).text(), "_x_ and **y**");
+    assert!(document(&app).runs().iter().all(|run| run.style == RunStyle::default()));
+}
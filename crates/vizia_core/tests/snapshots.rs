@@ -0,0 +1,62 @@
+//! Golden-image snapshots of the built-in views with the default theme.
+//!
+//! The stored images are in `tests/snapshots`. After an intended change to the appearance of a
+//! view, update them with `VIZIA_BLESS_SNAPSHOTS=1 cargo test -p vizia_core --test snapshots`.
+extern crate vizia_core as vizia;
+
+use vizia::prelude::*;
+use vizia_headless::Snapshot;
+
+#[derive(Lens)]
+struct AppData {
+    value: f32,
+    checked: bool,
+    text: String,
+}
+
+impl Model for AppData {}
+
+// Text is rendered with the fonts installed on the system, so the snapshots only contain views
+// which draw no text.
+fn snapshot(name: &str, content: impl 'static + FnOnce(&mut Context)) {
+    Snapshot::new(name).size((160, 60)).tolerance(2).assert(|cx| {
+        AppData { value: 0.3, checked: true, text: String::new() }.build(cx);
+
+        VStack::new(cx, content).child_space(Stretch(1.0));
+    });
+}
+
+#[test]
+fn button() {
+    snapshot("button", |cx| {
+        Button::new(cx, |cx| Element::new(cx).size(Pixels(16.0)));
+    });
+}
+
+#[test]
+fn slider() {
+    snapshot("slider", |cx| {
+        Slider::new(cx, AppData::value).width(Pixels(120.0));
+    });
+}
+
+#[test]
+fn knob() {
+    snapshot("knob", |cx| {
+        Knob::new(cx, 0.5, AppData::value, false);
+    });
+}
+
+#[test]
+fn switch() {
+    snapshot("switch", |cx| {
+        Switch::new(cx, AppData::checked);
+    });
+}
+
+#[test]
+fn textbox() {
+    snapshot("textbox", |cx| {
+        Textbox::new(cx, AppData::text).width(Pixels(120.0));
+    });
+}
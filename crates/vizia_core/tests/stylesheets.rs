@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use vizia_core::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+// Creates a directory for the stylesheets of a test which isn't shared with other test runs.
+fn stylesheet_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vizia_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn imported_stylesheets_are_reloaded() {
+    let dir = stylesheet_dir("imported_stylesheets_are_reloaded");
+    let main = dir.join("main.css");
+    let sizes = dir.join("sizes.css");
+    std::fs::write(&main, "@import \"sizes.css\";\n.element { height: 10px; }").unwrap();
+    std::fs::write(&sizes, ".element { width: 30px; }").unwrap();
+
+    let mut app = HeadlessApplication::new(move |cx| {
+        cx.add_stylesheet(main).unwrap();
+
+        Element::new(cx).class("element").id("element");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+    assert_eq!(app.bounds("element").width(), 30.0);
+    assert_eq!(app.bounds("element").height(), 10.0);
+
+    std::fs::write(&sizes, ".element { width: 50px; }").unwrap();
+    EventContext::new(app.context()).reload_styles().unwrap();
+    app.run_frame();
+    assert_eq!(app.bounds("element").width(), 50.0);
+    assert_eq!(app.bounds("element").height(), 10.0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,134 @@
+extern crate vizia_core as vizia;
+
+use vizia::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+#[derive(Lens)]
+struct AppData {
+    // The id of each row.
+    rows: Vec<u32>,
+    selected: Vec<u32>,
+}
+
+enum AppEvent {
+    Insert(usize, u32),
+    Remove(u32),
+    SetSelected(Vec<u32>),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::Insert(index, id) => self.rows.insert(*index, *id),
+            AppEvent::Remove(id) => self.rows.retain(|row| row != id),
+            AppEvent::SetSelected(selected) => self.selected = selected.clone(),
+        });
+    }
+}
+
+fn app() -> HeadlessApplication {
+    let mut app = HeadlessApplication::new(|cx| {
+        AppData { rows: vec![1, 2, 3, 4, 5], selected: Vec::new() }.build(cx);
+
+        Table::new(
+            cx,
+            AppData::rows,
+            |row| *row,
+            20.0,
+            vec![TableColumn::new(
+                |cx| {
+                    Label::new(cx, "Id");
+                },
+                |cx, row: &u32| {
+                    Label::new(cx, *row);
+                },
+            )
+            .min_width(40.0)],
+        )
+        .selectable(Selectable::Multi)
+        .on_select(|cx, selected| cx.emit(AppEvent::SetSelected(selected.to_vec())));
+    })
+    .inner_size((300, 300));
+
+    // The rows are built once the table has been laid out.
+    app.run_frame();
+    app.run_frame();
+    app
+}
+
+fn selected(app: &HeadlessApplication) -> &[u32] {
+    &app.data::<AppData>().unwrap().selected
+}
+
+fn press(app: &mut HeadlessApplication, modifiers: Modifiers, code: Code) {
+    app.press_chord(KeyChord::new(modifiers, code));
+}
+
+#[test]
+fn keyboard_navigation_moves_the_focus_and_selection() {
+    let mut app = app();
+
+    // Clicking the first row focuses the table.
+    app.click("table-row");
+    assert_eq!(selected(&app), [1]);
+
+    press(&mut app, Modifiers::empty(), Code::ArrowDown);
+    assert_eq!(selected(&app), [2]);
+
+    press(&mut app, Modifiers::SHIFT, Code::ArrowDown);
+    assert_eq!(selected(&app), [2, 3]);
+
+    // With the command modifier held only the focus moves, and Space toggles the focused row.
+    press(&mut app, Modifiers::COMMAND, Code::ArrowDown);
+    assert_eq!(selected(&app), [2, 3]);
+    press(&mut app, Modifiers::empty(), Code::Space);
+    assert_eq!(selected(&app), [2, 3, 4]);
+
+    press(&mut app, Modifiers::CTRL, Code::End);
+    assert_eq!(selected(&app), [5]);
+    press(&mut app, Modifiers::CTRL, Code::Home);
+    assert_eq!(selected(&app), [1]);
+
+    press(&mut app, Modifiers::COMMAND, Code::KeyA);
+    assert_eq!(selected(&app), [1, 2, 3, 4, 5]);
+
+    press(&mut app, Modifiers::empty(), Code::Escape);
+    assert!(selected(&app).is_empty());
+}
+
+#[test]
+fn the_selection_and_focus_stay_with_their_rows_when_the_data_changes() {
+    let mut app = app();
+
+    app.click("table-row");
+    press(&mut app, Modifiers::empty(), Code::ArrowDown);
+    press(&mut app, Modifiers::empty(), Code::ArrowDown);
+    assert_eq!(selected(&app), [3]);
+
+    // Inserting a row before the selected row doesn't change which row is selected or focused.
+    app.context().emit_to(Entity::root(), AppEvent::Insert(0, 10));
+    app.run_frame();
+    assert_eq!(selected(&app), [3]);
+    press(&mut app, Modifiers::empty(), Code::ArrowDown);
+    assert_eq!(selected(&app), [4]);
+
+    // Removing the focused row deselects it and focuses the row shown in its place.
+    app.context().emit_to(Entity::root(), AppEvent::Remove(4));
+    app.run_frame();
+    assert!(selected(&app).is_empty());
+    press(&mut app, Modifiers::empty(), Code::Space);
+    assert_eq!(selected(&app), [5]);
+}
+
+#[test]
+fn columns_are_not_resized_below_their_minimum_width() {
+    let mut app = app();
+    let scale_factor = app.context().scale_factor();
+    assert_eq!(app.bounds("table-header").w, 100.0 * scale_factor);
+
+    app.drag("resize-handle", 30.0, 0.0);
+    assert_eq!(app.bounds("table-header").w, 130.0 * scale_factor);
+
+    app.drag("resize-handle", -200.0, 0.0);
+    assert_eq!(app.bounds("table-header").w, 40.0 * scale_factor);
+}
@@ -0,0 +1,259 @@
+extern crate vizia_core as vizia;
+
+use vizia::prelude::*;
+use vizia::vg::{image::CachingHint, AlphaType, ColorType, ImageInfo};
+use vizia_headless::HeadlessApplication;
+
+#[derive(Lens)]
+struct AppData {
+    text: String,
+    edits: usize,
+}
+
+enum AppEvent {
+    SetText(String),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetText(text) => {
+                self.text = text.clone();
+                self.edits += 1;
+            }
+        });
+    }
+}
+
+fn app() -> HeadlessApplication {
+    let mut app = HeadlessApplication::new(|cx| {
+        AppData { text: String::new(), edits: 0 }.build(cx);
+
+        Button::new(cx, |cx| Label::new(cx, "Press")).id("button");
+
+        Textbox::new(cx, AppData::text)
+            .on_edit(|cx, text| cx.emit(AppEvent::SetText(text)))
+            .width(Pixels(200.0))
+            .id("textbox");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+    app
+}
+
+// Builds a textbox with the given text which flags the words other than "the" and "cat" as
+// misspelled.
+fn checked_app(text: &str) -> HeadlessApplication {
+    let text = text.to_owned();
+    let mut app = HeadlessApplication::new(move |cx| {
+        AppData { text, edits: 0 }.build(cx);
+
+        Textbox::new(cx, AppData::text)
+            .text_checker(DictionaryChecker::new(["the", "cat"]))
+            .on_edit(|cx, text| cx.emit(AppEvent::SetText(text)))
+            .width(Pixels(200.0))
+            .id("textbox");
+    })
+    .inner_size((400, 300));
+
+    app.run_frame();
+    app
+}
+
+fn text(app: &HeadlessApplication) -> &str {
+    &app.data::<AppData>().unwrap().text
+}
+
+fn edits(app: &HeadlessApplication) -> usize {
+    app.data::<AppData>().unwrap().edits
+}
+
+#[test]
+fn composing_text_with_an_ime() {
+    let mut app = app();
+
+    app.click("textbox");
+    app.type_text("ab");
+    let typed = edits(&app);
+
+    // The text being composed isn't passed to the bound data until it is committed.
+    app.ime_preedit("k", Some((1, 1)));
+    app.ime_preedit("か", Some((3, 3)));
+    assert_eq!(text(&app), "ab");
+    assert_eq!(edits(&app), typed);
+
+    // Keys pressed while composing are handled by the IME.
+    app.press_key(Code::Backspace, Some(Key::Backspace));
+    assert_eq!(text(&app), "ab");
+
+    app.ime_preedit("", None);
+    app.ime_commit("か");
+    assert_eq!(text(&app), "abか");
+    assert_eq!(edits(&app), typed + 1);
+
+    // The committed text is edited like typed text.
+    app.press_key(Code::Backspace, Some(Key::Backspace));
+    assert_eq!(text(&app), "ab");
+}
+
+#[test]
+fn cancelling_an_ime_composition() {
+    let mut app = app();
+
+    app.click("textbox");
+    app.type_text("ab");
+    let typed = edits(&app);
+
+    app.ime_preedit("k", Some((1, 1)));
+    app.ime_preedit("", None);
+    assert_eq!(text(&app), "ab");
+    assert_eq!(edits(&app), typed);
+
+    app.type_text("c");
+    assert_eq!(text(&app), "abc");
+}
+
+#[test]
+fn cancelling_an_ime_composition_restores_the_selection() {
+    let mut app = app();
+
+    app.click("textbox");
+    app.type_text("abc");
+    app.press_key(Code::ArrowLeft, Some(Key::ArrowLeft));
+    app.set_modifiers(Modifiers::SHIFT);
+    app.press_key(Code::ArrowLeft, Some(Key::ArrowLeft));
+    app.set_modifiers(Modifiers::empty());
+
+    app.ime_preedit("k", Some((1, 1)));
+    app.ime_preedit("", None);
+    assert_eq!(text(&app), "abc");
+
+    // The composition replaces the selection when it is committed.
+    app.ime_preedit("か", Some((3, 3)));
+    app.ime_preedit("", None);
+    app.ime_commit("か");
+    assert_eq!(text(&app), "aかc");
+}
+
+#[test]
+fn undoing_edits_after_refocusing_a_textbox() {
+    let mut app = app();
+
+    app.click("textbox");
+    app.type_text("hello");
+
+    app.click("button");
+    app.click("textbox");
+
+    app.press_chord(KeyChord::new(Modifiers::COMMAND, Code::KeyZ));
+    assert_eq!(text(&app), "");
+}
+
+// Returns the offset of the caret, found by typing a marker at the caret and deleting it again.
+fn caret_offset(app: &mut HeadlessApplication) -> usize {
+    app.type_text("|");
+    let offset = text(app).find('|').unwrap();
+    app.press_key(Code::Backspace, Some(Key::Backspace));
+    offset
+}
+
+#[test]
+fn moving_the_caret_through_mixed_direction_text() {
+    let mut app = app();
+
+    app.click("textbox");
+    app.type_text("ab אב cd");
+    app.press_key(Code::Home, Some(Key::Home));
+
+    let mut offsets = vec![caret_offset(&mut app)];
+    for _ in 0..8 {
+        app.press_key(Code::ArrowRight, Some(Key::ArrowRight));
+        offsets.push(caret_offset(&mut app));
+    }
+
+    // The caret moves in display order, so it visits every grapheme boundary once, passing
+    // through the right-to-left run backwards.
+    let mut visited = offsets.clone();
+    visited.sort();
+    assert_eq!(visited, [0, 1, 2, 3, 5, 7, 8, 9, 10]);
+    assert_ne!(offsets, visited);
+    assert_eq!(offsets.last(), Some(&10));
+
+    for expected in offsets.iter().rev().skip(1) {
+        app.press_key(Code::ArrowLeft, Some(Key::ArrowLeft));
+        assert_eq!(caret_offset(&mut app), *expected);
+    }
+}
+
+// Returns the number of red pixels within the textbox, which are only drawn by the underline of
+// misspelled words.
+fn underline_pixels(app: &mut HeadlessApplication) -> usize {
+    let bounds = app.bounds("textbox");
+    let image = app.snapshot(Entity::root()).unwrap();
+    let width = image.width() as usize;
+
+    let info = ImageInfo::new(
+        (image.width(), image.height()),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    );
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; row_bytes * image.height() as usize];
+    assert!(image.read_pixels(&info, &mut pixels, row_bytes, (0, 0), CachingHint::Disallow));
+
+    pixels
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(index, _)| {
+            bounds.contains_point((index % width) as f32 + 0.5, (index / width) as f32 + 0.5)
+        })
+        .filter(|(_, pixel)| pixel[0] > 180 && pixel[1] < 120 && pixel[2] < 120)
+        .count()
+}
+
+// Right-clicks the start of the text of the textbox.
+fn right_click_text(app: &mut HeadlessApplication) {
+    let bounds = app.bounds("textbox");
+    let scale_factor = app.context().scale_factor();
+    app.move_mouse((bounds.left() + 12.0) / scale_factor, bounds.center().1 / scale_factor);
+    app.mouse_down(MouseButton::Right);
+    app.mouse_up(MouseButton::Right);
+}
+
+#[test]
+fn misspelled_words_are_underlined() {
+    assert!(underline_pixels(&mut checked_app("teh cat")) > 0);
+    assert_eq!(underline_pixels(&mut checked_app("the cat")), 0);
+}
+
+#[test]
+fn right_clicking_a_misspelled_word_shows_its_suggestions() {
+    let mut app = checked_app("teh cat");
+
+    app.click("textbox");
+    right_click_text(&mut app);
+    assert!(app.context().query_selector(".suggestions label").is_some());
+
+    // The popup is closed when editing ends.
+    app.press_key(Code::Escape, Some(Key::Escape));
+    assert!(app.context().query_selector(".suggestions label").is_none());
+}
+
+#[test]
+fn applying_a_suggestion_is_undone_in_one_step() {
+    let mut app = checked_app("teh cat");
+
+    app.click("textbox");
+    right_click_text(&mut app);
+    app.click(".suggestions label");
+    assert_eq!(text(&app), "the cat");
+    assert_eq!(underline_pixels(&mut app), 0);
+
+    app.press_chord(KeyChord::new(Modifiers::COMMAND, Code::KeyZ));
+    assert_eq!(text(&app), "teh cat");
+
+    app.press_chord(KeyChord::new(Modifiers::COMMAND | Modifiers::SHIFT, Code::KeyZ));
+    assert_eq!(text(&app), "the cat");
+}
@@ -0,0 +1,160 @@
+extern crate vizia_core as vizia;
+
+use vizia::prelude::*;
+use vizia_headless::HeadlessApplication;
+
+#[derive(Clone, Data)]
+struct Node {
+    id: u32,
+    children: Vec<Node>,
+}
+
+fn node(id: u32, children: Vec<Node>) -> Node {
+    Node { id, children }
+}
+
+// Two roots, where the first has an item with a child and a leaf as its children.
+fn nodes() -> Vec<Node> {
+    vec![node(1, vec![node(2, vec![node(3, vec![])]), node(4, vec![])]), node(5, vec![])]
+}
+
+#[derive(Lens)]
+struct AppData {
+    nodes: Vec<Node>,
+    selected: Vec<u32>,
+}
+
+enum AppEvent {
+    SetNodes(Vec<Node>),
+    SetSelected(Vec<u32>),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetNodes(nodes) => self.nodes = nodes.clone(),
+            AppEvent::SetSelected(selected) => self.selected = selected.clone(),
+        });
+    }
+}
+
+fn app() -> HeadlessApplication {
+    let mut app = HeadlessApplication::new(|cx| {
+        AppData { nodes: nodes(), selected: Vec::new() }.build(cx);
+
+        TreeView::new(
+            cx,
+            AppData::nodes,
+            |node| node.children.as_slice(),
+            |node| node.id,
+            |cx, node| {
+                Label::new(cx, node.map_ref(|node| &node.id));
+            },
+        )
+        .selectable(Selectable::Multi)
+        .on_select(|cx, selected| cx.emit(AppEvent::SetSelected(selected.to_vec())));
+    })
+    .inner_size((300, 300));
+
+    app.run_frame();
+    app
+}
+
+fn selected(app: &HeadlessApplication) -> &[u32] {
+    &app.data::<AppData>().unwrap().selected
+}
+
+fn num_items(app: &mut HeadlessApplication) -> usize {
+    app.context().query_selector_all("tree-item").len()
+}
+
+fn press(app: &mut HeadlessApplication, modifiers: Modifiers, code: Code) {
+    app.press_chord(KeyChord::new(modifiers, code));
+}
+
+#[test]
+fn children_are_built_when_their_parent_is_first_expanded() {
+    let mut app = app();
+    assert_eq!(num_items(&mut app), 2);
+
+    // Clicking the first root focuses the tree view.
+    app.click("tree-item");
+    assert_eq!(selected(&app), [1]);
+
+    // Right expands the item, which builds its children but not theirs.
+    press(&mut app, Modifiers::empty(), Code::ArrowRight);
+    assert!(app.matches("tree-item", ".expanded"));
+    assert_eq!(num_items(&mut app), 4);
+
+    // Left collapses the item, and its children stay built.
+    press(&mut app, Modifiers::empty(), Code::ArrowLeft);
+    assert!(!app.matches("tree-item", ".expanded"));
+    assert_eq!(num_items(&mut app), 4);
+    assert_eq!(selected(&app), [1]);
+
+    // Right moves to the first child of an expanded item, and Left moves from a collapsed item to
+    // its parent.
+    press(&mut app, Modifiers::empty(), Code::ArrowRight);
+    press(&mut app, Modifiers::empty(), Code::ArrowRight);
+    assert_eq!(selected(&app), [2]);
+    press(&mut app, Modifiers::empty(), Code::ArrowLeft);
+    assert_eq!(selected(&app), [1]);
+}
+
+#[test]
+fn keyboard_navigation_moves_over_the_items_which_are_shown() {
+    let mut app = app();
+    app.click("tree-item");
+    press(&mut app, Modifiers::empty(), Code::ArrowRight);
+
+    // The child of the collapsed item 2 is skipped.
+    for id in [2, 4, 5] {
+        press(&mut app, Modifiers::empty(), Code::ArrowDown);
+        assert_eq!(selected(&app), [id]);
+    }
+
+    press(&mut app, Modifiers::empty(), Code::ArrowDown);
+    assert_eq!(selected(&app), [5]);
+
+    press(&mut app, Modifiers::empty(), Code::Home);
+    assert_eq!(selected(&app), [1]);
+    press(&mut app, Modifiers::empty(), Code::End);
+    assert_eq!(selected(&app), [5]);
+
+    press(&mut app, Modifiers::SHIFT, Code::ArrowUp);
+    assert_eq!(selected(&app), [4, 5]);
+
+    // With the command modifier held only the focus moves, and Space toggles the focused item.
+    press(&mut app, Modifiers::COMMAND, Code::ArrowUp);
+    press(&mut app, Modifiers::empty(), Code::Space);
+    assert_eq!(selected(&app), [4, 5, 2]);
+
+    press(&mut app, Modifiers::COMMAND, Code::KeyA);
+    assert_eq!(selected(&app), [1, 2, 4, 5]);
+
+    press(&mut app, Modifiers::empty(), Code::Escape);
+    assert!(selected(&app).is_empty());
+}
+
+#[test]
+fn the_state_of_removed_items_is_forgotten() {
+    let mut app = app();
+    app.click("tree-item");
+    press(&mut app, Modifiers::empty(), Code::ArrowRight);
+    press(&mut app, Modifiers::empty(), Code::ArrowDown);
+    assert_eq!(selected(&app), [2]);
+
+    // Removing the first root deselects its child, and focuses the item shown in its place.
+    app.context().emit_to(Entity::root(), AppEvent::SetNodes(vec![node(5, vec![])]));
+    app.run_frame();
+    assert!(selected(&app).is_empty());
+    press(&mut app, Modifiers::empty(), Code::Space);
+    assert_eq!(selected(&app), [5]);
+
+    // An item which is added again is collapsed, and its children are built again once it is
+    // expanded.
+    app.context().emit_to(Entity::root(), AppEvent::SetNodes(nodes()));
+    app.run_frame();
+    assert!(!app.matches("tree-item", ".expanded"));
+    assert_eq!(num_items(&mut app), 2);
+}
@@ -0,0 +1,355 @@
+use crate::proxy::{HeadlessProxy, ProxyQueue};
+use crate::window::{HeadlessWindow, WindowSurface};
+use hashbrown::HashMap;
+use skia_safe::Image;
+use std::cell::Cell;
+use vizia_core::backend::*;
+use vizia_core::events::EventManager;
+use vizia_core::prelude::*;
+
+/// The interval by which the virtual clock is advanced between frames in [`HeadlessApplication::run_for`].
+pub const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+type IdleCallback = Option<Box<dyn Fn(&mut Context)>>;
+
+/// An application which renders into CPU raster surfaces without opening a platform window.
+///
+/// Unlike the windowed backends, a headless application has no event loop. Instead the caller
+/// drives it by sending [`WindowEvent`]s, advancing the virtual clock and calling [`run_frame`](Self::run_frame).
+/// Timers, scheduled events and animations all use the virtual clock, so tests are deterministic
+/// regardless of how long each frame takes to process.
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::prelude::*;
+/// # use vizia_headless::HeadlessApplication;
+/// let mut app = HeadlessApplication::new(|cx| {
+///     Button::new(cx, |cx| Label::new(cx, "Press Me")).id("button");
+/// });
+///
+/// app.run_frame();
+/// app.send_window_event(WindowEvent::MouseMove(20.0, 10.0));
+/// app.send_window_event(WindowEvent::MouseDown(MouseButton::Left));
+/// app.send_window_event(WindowEvent::MouseUp(MouseButton::Left));
+/// app.run_frame();
+/// ```
+pub struct HeadlessApplication {
+    pub(crate) cx: BackendContext,
+    event_manager: EventManager,
+    on_idle: IdleCallback,
+    window_description: WindowDescription,
+    scale_factor: f64,
+    surfaces: HashMap<Entity, WindowSurface>,
+    proxy_queue: ProxyQueue,
+    time: Instant,
+    animating: bool,
+}
+
+impl HeadlessApplication {
+    /// Creates a new headless application and builds its content.
+    ///
+    /// The root window is created with a default size of 800x600 logical pixels and a scale factor
+    /// of 1.0 when the first frame is run.
+    pub fn new<F>(content: F) -> Self
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        let mut cx = BackendContext::new(Context::new());
+
+        let time = Instant::now();
+        cx.set_virtual_time(Some(time));
+
+        let proxy_queue = ProxyQueue::default();
+        cx.set_event_proxy(Box::new(HeadlessProxy(proxy_queue.clone())));
+
+        #[cfg(feature = "clipboard")]
+        cx.set_clipboard_provider(Box::new(crate::MemoryClipboard::default()));
+
+        cx.0.remove_user_themes();
+        (content)(cx.context());
+
+        Self {
+            cx,
+            event_manager: EventManager::new(),
+            on_idle: None,
+            window_description: WindowDescription::new(),
+            scale_factor: 1.0,
+            surfaces: HashMap::new(),
+            proxy_queue,
+            time,
+            animating: false,
+        }
+    }
+
+    /// Sets the default built-in theming to be ignored.
+    pub fn ignore_default_theme(mut self) -> Self {
+        self.cx.context().ignore_default_theme = true;
+        self
+    }
+
+    /// Sets the logical size of the root window.
+    pub fn inner_size(mut self, size: impl Into<WindowSize>) -> Self {
+        self.window_description.inner_size = size.into();
+        self
+    }
+
+    /// Sets the scale factor of the simulated display, i.e. the number of physical pixels per logical pixel.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Sets the title of the root window.
+    pub fn title(mut self, title: &str) -> Self {
+        self.window_description.title = title.to_owned();
+        self
+    }
+
+    /// Takes a closure which will be called at the end of every frame, after events have been handled
+    /// but before drawing.
+    pub fn on_idle<F: 'static + Fn(&mut Context)>(mut self, callback: F) -> Self {
+        self.on_idle = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns a mutable reference to the context of the application.
+    pub fn context(&mut self) -> &mut Context {
+        self.cx.context()
+    }
+
+    /// Returns a mutable reference to the backend context of the application.
+    pub fn backend_context(&mut self) -> &mut BackendContext {
+        &mut self.cx
+    }
+
+    /// Returns a `ContextProxy` which can be used to send events from another thread.
+    ///
+    /// Events sent through the proxy are handled on the next call to [`run_frame`](Self::run_frame).
+    pub fn get_proxy(&self) -> ContextProxy {
+        self.cx.0.get_proxy()
+    }
+
+    /// Sends a window event to the root window, as a platform window would in response to OS input.
+    pub fn send_window_event(&mut self, event: WindowEvent) {
+        self.send_window_event_to(Entity::root(), event);
+    }
+
+    /// Sends a window event to the window with the given entity.
+    pub fn send_window_event_to(&mut self, window_entity: Entity, event: WindowEvent) {
+        self.cx.emit_window_event(window_entity, event);
+    }
+
+    /// Sends an event with custom origin and propagation information.
+    pub fn send_event(&mut self, event: Event) {
+        self.cx.send_event(event);
+    }
+
+    /// Returns the current time of the virtual clock.
+    pub fn now(&self) -> Instant {
+        self.time
+    }
+
+    /// Advances the virtual clock by the given duration without running a frame.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.time += duration;
+        self.cx.set_virtual_time(Some(self.time));
+    }
+
+    /// Runs frames at a fixed [`FRAME_INTERVAL`], advancing the virtual clock between each one,
+    /// until the given duration has elapsed.
+    pub fn run_for(&mut self, duration: Duration) {
+        let end = self.time + duration;
+        self.run_frame();
+        while self.time < end {
+            self.advance_time(FRAME_INTERVAL.min(end - self.time));
+            self.run_frame();
+        }
+    }
+
+    /// Returns true if any animations or transitions were playing during the last frame.
+    pub fn is_animating(&self) -> bool {
+        self.animating
+    }
+
+    /// Runs a single frame of the application.
+    ///
+    /// This creates any pending windows, fires timers and scheduled events which are due according
+    /// to the virtual clock, handles all queued events, updates styles, animations and layout,
+    /// and draws any windows which need redrawing. Returns true if any window was redrawn.
+    pub fn run_frame(&mut self) -> bool {
+        self.sync_windows();
+
+        let proxied_events = self.proxy_queue.lock().unwrap().drain(..).collect::<Vec<_>>();
+        for event in proxied_events {
+            self.cx.send_event(event);
+        }
+
+        self.cx.process_timers();
+        self.cx.emit_scheduled_events();
+
+        while self.event_manager.flush_events(self.cx.context()) {}
+
+        self.cx.process_style_updates();
+
+        self.animating = self.cx.process_animations();
+
+        self.cx.process_visual_updates();
+
+        self.cx.process_tree_updates(|_| {});
+
+        if let Some(idle_callback) = &self.on_idle {
+            self.cx.set_current(Entity::root());
+            (idle_callback)(self.cx.context());
+        }
+
+        self.apply_requested_sizes();
+
+        let closed_windows = self
+            .cx
+            .0
+            .windows
+            .iter()
+            .filter_map(|(entity, state)| state.should_close.then_some(*entity))
+            .collect::<Vec<_>>();
+
+        for window_entity in closed_windows {
+            self.cx.0.remove(window_entity);
+        }
+
+        self.surfaces.retain(|entity, _| self.cx.0.windows.contains_key(entity));
+
+        let mut redrawn = false;
+        for (window_entity, window_surface) in self.surfaces.iter_mut() {
+            redrawn |= self.cx.draw(
+                *window_entity,
+                &mut window_surface.surface,
+                &mut window_surface.dirty_surface,
+            );
+        }
+
+        redrawn
+    }
+
+    /// Returns true if all windows have been closed.
+    pub fn is_closed(&self) -> bool {
+        !self.surfaces.is_empty() && self.cx.0.windows.is_empty()
+    }
+
+    /// Resizes the window with the given entity to the given logical size, as if the user had resized it.
+    pub fn set_window_size(&mut self, window_entity: Entity, size: impl Into<WindowSize>) {
+        let size = size.into();
+
+        let Some(window_state) = self.cx.0.windows.get_mut(&window_entity) else {
+            return;
+        };
+
+        window_state.window_description.inner_size = size;
+        let dpi_factor = self.scale_factor * window_state.window_description.user_scale_factor;
+
+        let physical_width = (size.width as f64 * dpi_factor).round() as i32;
+        let physical_height = (size.height as f64 * dpi_factor).round() as i32;
+
+        self.surfaces.insert(window_entity, WindowSurface::new(physical_width, physical_height));
+        self.cx.set_window_scale_factor(window_entity, dpi_factor);
+        self.cx.set_window_size(window_entity, physical_width as f32, physical_height as f32);
+        self.cx.needs_refresh(window_entity);
+    }
+
+    /// Changes the scale factor of the simulated display, as if the window had been moved to
+    /// another monitor.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.cx.set_scale_factor(scale_factor);
+
+        let windows = self
+            .cx
+            .0
+            .windows
+            .iter()
+            .map(|(entity, state)| (*entity, state.window_description.inner_size))
+            .collect::<Vec<_>>();
+
+        for (window_entity, size) in windows {
+            if self.surfaces.contains_key(&window_entity) {
+                self.set_window_size(window_entity, size);
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the raster surface of the window with the given entity.
+    pub fn surface(&mut self, window_entity: Entity) -> Option<&mut skia_safe::Surface> {
+        self.surfaces.get_mut(&window_entity).map(|window_surface| &mut window_surface.surface)
+    }
+
+    /// Returns an image of the current contents of the window with the given entity.
+    pub fn snapshot(&mut self, window_entity: Entity) -> Option<Image> {
+        self.surface(window_entity).map(|surface| surface.image_snapshot())
+    }
+
+    // Creates surfaces for the root window and any windows which were added to the context since the last frame.
+    fn sync_windows(&mut self) {
+        if !self.cx.0.windows.contains_key(&Entity::root()) && self.surfaces.is_empty() {
+            self.cx.add_main_window(
+                Entity::root(),
+                &self.window_description,
+                (self.scale_factor * self.window_description.user_scale_factor) as f32,
+            );
+            self.cx.add_window(HeadlessWindow::new(&self.window_description.title));
+            self.cx.0.windows.insert(
+                Entity::root(),
+                WindowState {
+                    window_description: self.window_description.clone(),
+                    ..Default::default()
+                },
+            );
+
+            self.cx.0.remove_user_themes();
+        }
+
+        for (window_entity, window_state) in self.cx.0.windows.clone().into_iter() {
+            if self.surfaces.contains_key(&window_entity) {
+                continue;
+            }
+
+            let window_description = &window_state.window_description;
+            let dpi_factor = self.scale_factor * window_description.user_scale_factor;
+
+            if window_entity != Entity::root() {
+                self.cx.add_main_window(window_entity, window_description, dpi_factor as f32);
+            }
+            self.cx.set_window_scale_factor(window_entity, dpi_factor);
+
+            let physical_width = (window_description.inner_size.width as f64 * dpi_factor).round();
+            let physical_height =
+                (window_description.inner_size.height as f64 * dpi_factor).round();
+
+            self.surfaces.insert(
+                window_entity,
+                WindowSurface::new(physical_width as i32, physical_height as i32),
+            );
+
+            self.cx.needs_refresh(window_entity);
+        }
+    }
+
+    // Applies any sizes requested by views with `WindowEvent::SetSize`.
+    fn apply_requested_sizes(&mut self) {
+        let window_entities = self.surfaces.keys().copied().collect::<Vec<_>>();
+        for window_entity in window_entities {
+            let requested_size = Cell::new(None);
+            self.cx.mutate_window(window_entity, |_, window: &mut HeadlessWindow| {
+                requested_size.set(window.requested_size.take());
+            });
+
+            if let Some(size) = requested_size.take() {
+                self.set_window_size(window_entity, size);
+            }
+        }
+    }
+}
+
+impl Drop for HeadlessApplication {
+    fn drop(&mut self) {
+        self.cx.set_virtual_time(None);
+    }
+}
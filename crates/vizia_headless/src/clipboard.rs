@@ -0,0 +1,23 @@
+use copypasta::ClipboardProvider;
+use std::error::Error;
+
+/// An in-memory clipboard used by the headless backend so that copy and paste can be tested
+/// without access to a system clipboard.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    contents: String,
+}
+
+impl ClipboardProvider for MemoryClipboard {
+    fn get_contents(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
+        Ok(self.contents.clone())
+    }
+
+    fn set_contents(
+        &mut self,
+        contents: String,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        self.contents = contents;
+        Ok(())
+    }
+}
@@ -0,0 +1,361 @@
+use crate::HeadlessApplication;
+use vizia_core::prelude::*;
+
+/// The number of intermediate mouse moves used by [`HeadlessApplication::drag`].
+const DRAG_STEPS: usize = 8;
+
+/// Identifies a view targeted by the simulated input methods of a [`HeadlessApplication`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// A view with the given entity.
+    Entity(Entity),
+    /// A view with the given identifier, as set with the `id` modifier.
+    Id(String),
+    /// The view with the given identifier or, if no view has that identifier, the first view in
+    /// tree order which matches the given CSS selector.
+    Selector(String),
+}
+
+impl Target {
+    /// Creates a target for the view with the given identifier.
+    pub fn id(id: impl Into<String>) -> Self {
+        Target::Id(id.into())
+    }
+
+    /// Creates a target for the view with the given identifier or, if there is none, the first
+    /// view which matches the given CSS selector.
+    pub fn selector(selector: impl Into<String>) -> Self {
+        Target::Selector(selector.into())
+    }
+
+    /// Returns the entity of the targeted view, if it exists.
+    pub fn resolve(&self, cx: &Context) -> Option<Entity> {
+        match self {
+            Target::Entity(entity) => Some(*entity),
+            Target::Id(id) => cx.resolve_entity_identifier(id),
+            Target::Selector(selector) => {
+                cx.resolve_entity_identifier(selector).or_else(|| cx.query_selector(selector))
+            }
+        }
+    }
+}
+
+impl From<Entity> for Target {
+    fn from(entity: Entity) -> Self {
+        Target::Entity(entity)
+    }
+}
+
+/// A string is resolved as a [`Target::Selector`], so it is first resolved as a view identifier
+/// and, if no view has that identifier, as a CSS selector.
+impl From<&str> for Target {
+    fn from(target: &str) -> Self {
+        Target::Selector(target.to_owned())
+    }
+}
+
+/// Methods for simulating user input and inspecting the resulting state.
+///
+/// Positions are given in logical pixels relative to the top-left of the root window. Each method
+/// sends the same sequence of [`WindowEvent`]s a windowing backend would and then runs a frame, so
+/// that any changes to models, styles and layout are visible to the next call.
+impl HeadlessApplication {
+    /// Returns the entity of the targeted view.
+    ///
+    /// # Panics
+    /// Panics if the target does not resolve to a view.
+    #[track_caller]
+    pub fn resolve(&mut self, target: impl Into<Target>) -> Entity {
+        let target = target.into();
+
+        match target.resolve(self.context()) {
+            Some(entity) => entity,
+            None => panic!("no view matches the target {:?}", target),
+        }
+    }
+
+    /// Moves the mouse cursor to the given position.
+    pub fn move_mouse(&mut self, x: f32, y: f32) {
+        let scale_factor = self.context().scale_factor();
+        self.send_window_event(WindowEvent::MouseMove(x * scale_factor, y * scale_factor));
+        self.run_frame();
+    }
+
+    /// Moves the mouse cursor to the center of the targeted view.
+    #[track_caller]
+    pub fn hover(&mut self, target: impl Into<Target>) {
+        let (x, y) = self.center(target);
+        self.move_mouse(x, y);
+    }
+
+    /// Presses the given mouse button at the current cursor position.
+    pub fn mouse_down(&mut self, button: MouseButton) {
+        self.send_window_event(WindowEvent::MouseDown(button));
+        self.run_frame();
+    }
+
+    /// Releases the given mouse button at the current cursor position.
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.send_window_event(WindowEvent::MouseUp(button));
+        self.run_frame();
+    }
+
+    /// Moves the mouse cursor to the center of the targeted view and clicks the left mouse button.
+    #[track_caller]
+    pub fn click(&mut self, target: impl Into<Target>) {
+        self.click_with(target, MouseButton::Left);
+    }
+
+    /// Moves the mouse cursor to the center of the targeted view and clicks the given mouse button.
+    #[track_caller]
+    pub fn click_with(&mut self, target: impl Into<Target>, button: MouseButton) {
+        self.hover(target);
+        self.send_window_event(WindowEvent::MouseDown(button));
+        self.send_window_event(WindowEvent::MouseUp(button));
+        self.run_frame();
+    }
+
+    /// Moves the mouse cursor to the center of the targeted view and double clicks the left mouse button.
+    #[track_caller]
+    pub fn double_click(&mut self, target: impl Into<Target>) {
+        self.hover(target);
+        for _ in 0..2 {
+            self.send_window_event(WindowEvent::MouseDown(MouseButton::Left));
+            self.send_window_event(WindowEvent::MouseUp(MouseButton::Left));
+        }
+        self.run_frame();
+    }
+
+    /// Presses the left mouse button over the center of the targeted view, moves the cursor by the
+    /// given offset, and releases the button.
+    #[track_caller]
+    pub fn drag(&mut self, target: impl Into<Target>, dx: f32, dy: f32) {
+        let (x, y) = self.center(target);
+        self.move_mouse(x, y);
+        self.mouse_down(MouseButton::Left);
+
+        for step in 1..=DRAG_STEPS {
+            let t = step as f32 / DRAG_STEPS as f32;
+            self.move_mouse(x + dx * t, y + dy * t);
+        }
+
+        self.mouse_up(MouseButton::Left);
+    }
+
+    /// Scrolls the mouse wheel over the targeted view by the given number of lines.
+    #[track_caller]
+    pub fn scroll(&mut self, target: impl Into<Target>, x: f32, y: f32) {
+        self.hover(target);
+        self.send_window_event(WindowEvent::MouseScroll(x, y));
+        self.run_frame();
+    }
+
+    /// Types the given text into the focused view as a sequence of character inputs.
+    pub fn type_text(&mut self, text: &str) {
+        for character in text.chars() {
+            self.send_window_event(WindowEvent::CharInput(character));
+        }
+        self.run_frame();
+    }
+
+    /// Sets the text being composed by an input method editor (IME) in the focused view, with the
+    /// cursor at the given byte range. An empty string ends the composition.
+    pub fn ime_preedit(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+        self.send_window_event(WindowEvent::ImePreedit(text.to_owned(), cursor));
+        self.run_frame();
+    }
+
+    /// Commits the given text from an input method editor (IME) to the focused view.
+    pub fn ime_commit(&mut self, text: &str) {
+        self.send_window_event(WindowEvent::ImeCommit(text.to_owned()));
+        self.run_frame();
+    }
+
+    /// Presses and releases the given key.
+    pub fn press_key(&mut self, code: Code, key: Option<Key>) {
+        self.send_window_event(WindowEvent::KeyDown(code, key.clone()));
+        self.send_window_event(WindowEvent::KeyUp(code, key));
+        self.run_frame();
+    }
+
+    /// Presses and releases the given key chord, holding its modifiers for the duration of the key press.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vizia_core::prelude::*;
+    /// # use vizia_headless::HeadlessApplication;
+    /// # let mut app = HeadlessApplication::new(|cx| {});
+    /// app.press_chord(KeyChord::new(Modifiers::CTRL, Code::KeyZ));
+    /// ```
+    pub fn press_chord(&mut self, chord: KeyChord) {
+        let previous = *self.backend_context().modifiers();
+        *self.backend_context().modifiers() = chord.modifiers;
+        self.send_window_event(WindowEvent::KeyDown(chord.code, None));
+        self.send_window_event(WindowEvent::KeyUp(chord.code, None));
+        self.run_frame();
+        *self.backend_context().modifiers() = previous;
+    }
+
+    /// Sets the keyboard modifiers which are held down for subsequent input.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        *self.backend_context().modifiers() = modifiers;
+    }
+
+    /// Returns the entity of the view with keyboard focus.
+    pub fn focused(&self) -> Entity {
+        self.cx.focused()
+    }
+
+    /// Returns true if the targeted view has keyboard focus.
+    #[track_caller]
+    pub fn is_focused(&mut self, target: impl Into<Target>) -> bool {
+        self.resolve(target) == self.focused()
+    }
+
+    /// Returns true if the targeted view matches the given CSS selector, e.g. `:hover` or `.selected`.
+    #[track_caller]
+    pub fn matches(&mut self, target: impl Into<Target>, selector: &str) -> bool {
+        let entity = self.resolve(target);
+        self.context().matches_selector(entity, selector)
+    }
+
+    /// Returns the bounds of the targeted view in physical pixels, as computed by the last layout.
+    #[track_caller]
+    pub fn bounds(&mut self, target: impl Into<Target>) -> BoundingBox {
+        let entity = self.resolve(target);
+        self.backend_context().cache().get_bounds(entity)
+    }
+
+    /// Returns model or view data of the given type, searching up the tree from the root window.
+    pub fn data<T: 'static>(&self) -> Option<&T> {
+        self.cx.0.data::<T>()
+    }
+
+    /// Asserts that the targeted view has keyboard focus.
+    #[track_caller]
+    pub fn assert_focused(&mut self, target: impl Into<Target>) {
+        let target = target.into();
+        let entity = self.resolve(target.clone());
+        let focused = self.focused();
+        assert_eq!(
+            focused, entity,
+            "expected {:?} to be focused but {:?} is focused",
+            target, focused
+        );
+    }
+
+    /// Asserts that the targeted view matches the given CSS selector.
+    #[track_caller]
+    pub fn assert_matches(&mut self, target: impl Into<Target>, selector: &str) {
+        let target = target.into();
+        assert!(
+            self.matches(target.clone(), selector),
+            "expected {:?} to match `{}`",
+            target,
+            selector
+        );
+    }
+
+    // Returns the center of the targeted view in logical pixels.
+    #[track_caller]
+    fn center(&mut self, target: impl Into<Target>) -> (f32, f32) {
+        let (x, y) = self.bounds(target).center();
+        let scale_factor = self.context().scale_factor();
+        (x / scale_factor, y / scale_factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AppData {
+        presses: usize,
+        text: String,
+    }
+
+    enum AppEvent {
+        Press,
+        SetText(String),
+    }
+
+    impl Model for AppData {
+        fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+            event.map(|app_event, _| match app_event {
+                AppEvent::Press => self.presses += 1,
+                AppEvent::SetText(text) => self.text = text.clone(),
+            });
+        }
+    }
+
+    // The lens derive macro refers to the `vizia` crate, so the lens is implemented by hand.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Text;
+
+    impl Lens for Text {
+        type Source = AppData;
+        type Target = String;
+
+        fn view<'a>(&self, source: &'a AppData) -> Option<LensValue<'a, String>> {
+            Some(LensValue::Borrowed(&source.text))
+        }
+    }
+
+    fn app() -> HeadlessApplication {
+        let mut app = HeadlessApplication::new(|cx| {
+            AppData { presses: 0, text: String::new() }.build(cx);
+
+            Button::new(cx, |cx| Label::new(cx, "Press"))
+                .on_press(|cx| cx.emit(AppEvent::Press))
+                .id("button");
+
+            Textbox::new(cx, Text)
+                .on_edit(|cx, text| cx.emit(AppEvent::SetText(text)))
+                .width(Pixels(200.0))
+                .id("textbox");
+        })
+        .inner_size((400, 300));
+
+        app.run_frame();
+        app
+    }
+
+    #[test]
+    fn clicking_a_button() {
+        let mut app = app();
+
+        app.click("button");
+        app.click("button");
+
+        assert_eq!(app.data::<AppData>().map(|data| data.presses), Some(2));
+        app.assert_matches("button", ":hover");
+    }
+
+    #[test]
+    fn typing_into_a_textbox() {
+        let mut app = app();
+
+        app.click("textbox");
+        app.assert_focused("textbox");
+
+        app.type_text("hello");
+        app.press_key(Code::Backspace, Some(Key::Backspace));
+
+        assert_eq!(app.data::<AppData>().map(|data| data.text.as_str()), Some("hell"));
+    }
+
+    #[test]
+    fn resolving_targets() {
+        let mut app = app();
+
+        let button = app.resolve(Target::id("button"));
+        assert_eq!(Target::from("button").resolve(app.context()), Some(button));
+        assert_eq!(app.resolve("button"), button);
+
+        let label = app.resolve("button > label");
+        assert_ne!(label, button);
+        assert_eq!(Target::selector("button > label").resolve(app.context()), Some(label));
+
+        assert_eq!(Target::from("#missing").resolve(app.context()), None);
+    }
+}
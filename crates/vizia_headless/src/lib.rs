@@ -0,0 +1,35 @@
+//! A headless backend for vizia which renders into CPU raster surfaces.
+//!
+//! The headless backend does not open any platform windows and does not require a display or GPU.
+//! Instead of running an event loop, the application is driven manually by injecting window events,
+//! advancing a virtual clock, and running frames. This makes it suitable for testing views in
+//! continuous integration environments.
+//!
+//! # Example
+//! ```no_run
+//! # use vizia_core::prelude::*;
+//! # use vizia_headless::HeadlessApplication;
+//! let mut app = HeadlessApplication::new(|cx| {
+//!     Label::new(cx, "Hello Vizia");
+//! })
+//! .inner_size((400, 300));
+//!
+//! app.run_frame();
+//!
+//! let image = app.snapshot(Entity::root()).unwrap();
+//! assert_eq!(image.width(), 400);
+//! ```
+mod application;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod input;
+pub(crate) mod proxy;
+mod snapshot;
+mod window;
+
+pub use application::{HeadlessApplication, FRAME_INTERVAL};
+#[cfg(feature = "clipboard")]
+pub use clipboard::MemoryClipboard;
+pub use input::Target;
+pub use snapshot::{compare_pixels, PixelComparison, Snapshot, SnapshotError, BLESS_ENV_VAR};
+pub use window::HeadlessWindow;
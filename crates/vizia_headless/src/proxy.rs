@@ -0,0 +1,21 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use vizia_core::context::EventProxy;
+use vizia_core::events::Event;
+
+/// Queue of events sent from other threads, drained at the start of every frame.
+pub(crate) type ProxyQueue = Arc<Mutex<VecDeque<Event>>>;
+
+#[derive(Clone)]
+pub(crate) struct HeadlessProxy(pub ProxyQueue);
+
+impl EventProxy for HeadlessProxy {
+    fn send(&self, event: Event) -> Result<(), ()> {
+        self.0.lock().map_err(|_| ())?.push_back(event);
+        Ok(())
+    }
+
+    fn make_clone(&self) -> Box<dyn EventProxy> {
+        Box::new(self.clone())
+    }
+}
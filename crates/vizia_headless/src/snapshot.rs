@@ -0,0 +1,347 @@
+use crate::HeadlessApplication;
+use skia_safe::{
+    image::CachingHint, images, AlphaType, ColorType, Data, EncodedImageFormat, Image, ImageInfo,
+};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use vizia_core::prelude::*;
+
+/// The environment variable which, when set to any value other than `0`, causes snapshot tests to
+/// overwrite the stored images with the newly rendered ones instead of comparing against them.
+pub const BLESS_ENV_VAR: &str = "VIZIA_BLESS_SNAPSHOTS";
+
+/// A golden-image snapshot test.
+///
+/// A snapshot renders a view tree into a raster image using the headless backend and compares it,
+/// pixel by pixel, against a PNG stored on disk. When the images differ by more than the configured
+/// tolerance, the rendered image and a diff image are written next to the stored one.
+///
+/// Snapshots are stored in `tests/snapshots` within the directory of the crate being tested, unless
+/// a different directory is provided with [`directory`](Self::directory). To create or update the
+/// stored images, run the tests with the [`BLESS_ENV_VAR`] environment variable set.
+///
+/// Note that text is rendered with the fonts available on the system, so snapshots which contain
+/// text should either register their fonts with `Context::add_font_mem` or be generated on the same
+/// platform they are checked on.
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::prelude::*;
+/// # use vizia_headless::Snapshot;
+/// Snapshot::new("button/default").size((120, 40)).scale_factor(2.0).assert(|cx| {
+///     Button::new(cx, |cx| Label::new(cx, "Press Me"));
+/// });
+/// ```
+pub struct Snapshot {
+    name: String,
+    directory: PathBuf,
+    size: WindowSize,
+    scale_factor: f64,
+    tolerance: u8,
+    max_differing_pixels: usize,
+    ignore_default_theme: bool,
+}
+
+impl Snapshot {
+    /// Creates a new snapshot test with the given name.
+    ///
+    /// The name is used as the path of the stored image relative to the snapshot directory,
+    /// without the `.png` extension, and may contain `/` to group snapshots into folders.
+    pub fn new(name: impl Into<String>) -> Self {
+        let directory = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("tests")
+            .join("snapshots");
+
+        Self {
+            name: name.into(),
+            directory,
+            size: WindowSize::new(400, 300),
+            scale_factor: 1.0,
+            tolerance: 0,
+            max_differing_pixels: 0,
+            ignore_default_theme: false,
+        }
+    }
+
+    /// Sets the directory the snapshot images are stored in.
+    pub fn directory(mut self, directory: impl AsRef<Path>) -> Self {
+        self.directory = directory.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the logical size of the window the view tree is rendered into. Defaults to 400x300.
+    pub fn size(mut self, size: impl Into<WindowSize>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// Sets the scale factor the view tree is rendered with. Defaults to 1.0.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Sets the maximum difference allowed in any color channel of a pixel before the pixel is
+    /// considered to differ. Defaults to 0.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the number of pixels which may differ before the snapshot is considered to have failed.
+    /// Defaults to 0.
+    pub fn max_differing_pixels(mut self, count: usize) -> Self {
+        self.max_differing_pixels = count;
+        self
+    }
+
+    /// Sets the default built-in theming to be ignored when rendering the snapshot.
+    pub fn ignore_default_theme(mut self) -> Self {
+        self.ignore_default_theme = true;
+        self
+    }
+
+    /// Returns the path of the stored image for this snapshot.
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(format!("{}.png", self.name))
+    }
+
+    /// Renders the view tree built by `content` into an image.
+    pub fn render(&self, content: impl 'static + FnOnce(&mut Context)) -> Image {
+        let mut app =
+            HeadlessApplication::new(content).inner_size(self.size).scale_factor(self.scale_factor);
+
+        if self.ignore_default_theme {
+            app = app.ignore_default_theme();
+        }
+
+        app.run_frame();
+
+        app.snapshot(Entity::root()).expect("Failed to snapshot the root window")
+    }
+
+    /// Renders the view tree built by `content` and compares it against the stored image.
+    ///
+    /// If the [`BLESS_ENV_VAR`] environment variable is set, the stored image is replaced by the
+    /// rendered one instead.
+    pub fn check(&self, content: impl 'static + FnOnce(&mut Context)) -> Result<(), SnapshotError> {
+        let actual = self.render(content);
+        let path = self.path();
+
+        if should_bless() {
+            return write_png(&path, &actual);
+        }
+
+        let Ok(bytes) = std::fs::read(&path) else {
+            write_png(&self.sibling_path("actual"), &actual)?;
+            return Err(SnapshotError::Missing(path));
+        };
+
+        let expected = Image::from_encoded(Data::new_copy(&bytes))
+            .ok_or_else(|| SnapshotError::Decode(path.clone()))?;
+
+        if expected.dimensions() != actual.dimensions() {
+            write_png(&self.sibling_path("actual"), &actual)?;
+            return Err(SnapshotError::SizeMismatch {
+                expected: (expected.width() as u32, expected.height() as u32),
+                actual: (actual.width() as u32, actual.height() as u32),
+            });
+        }
+
+        let expected_pixels = read_pixels(&expected).ok_or(SnapshotError::ReadPixels)?;
+        let actual_pixels = read_pixels(&actual).ok_or(SnapshotError::ReadPixels)?;
+
+        let comparison = compare_pixels(&expected_pixels, &actual_pixels, self.tolerance);
+
+        if comparison.differing_pixels > self.max_differing_pixels {
+            let diff_path = self.sibling_path("diff");
+            write_png(&self.sibling_path("actual"), &actual)?;
+            write_png(
+                &diff_path,
+                &rgba_image(&comparison.diff, expected.width(), expected.height()),
+            )?;
+
+            return Err(SnapshotError::Mismatch {
+                differing_pixels: comparison.differing_pixels,
+                max_difference: comparison.max_difference,
+                diff_path,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Renders the view tree built by `content` and compares it against the stored image,
+    /// panicking if they differ.
+    #[track_caller]
+    pub fn assert(&self, content: impl 'static + FnOnce(&mut Context)) {
+        if let Err(error) = self.check(content) {
+            panic!("snapshot `{}` failed: {}", self.name, error);
+        }
+    }
+
+    fn sibling_path(&self, suffix: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}.png", self.name, suffix))
+    }
+}
+
+/// Errors which can occur when checking a [`Snapshot`].
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// There is no stored image for the snapshot.
+    Missing(PathBuf),
+    /// The stored image could not be decoded.
+    Decode(PathBuf),
+    /// The pixels of an image could not be read.
+    ReadPixels,
+    /// The rendered image has a different size to the stored image.
+    SizeMismatch { expected: (u32, u32), actual: (u32, u32) },
+    /// The rendered image differs from the stored image by more than the allowed tolerance.
+    Mismatch { differing_pixels: usize, max_difference: u8, diff_path: PathBuf },
+    /// An image could not be encoded as PNG.
+    Encode,
+    /// An image could not be written to disk.
+    Io(std::io::Error),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Missing(path) => write!(
+                f,
+                "no stored image at {}, run with {}=1 to create it",
+                path.display(),
+                BLESS_ENV_VAR
+            ),
+            SnapshotError::Decode(path) => write!(f, "failed to decode {}", path.display()),
+            SnapshotError::ReadPixels => write!(f, "failed to read image pixels"),
+            SnapshotError::SizeMismatch { expected, actual } => write!(
+                f,
+                "expected an image of {}x{} but rendered {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            SnapshotError::Mismatch { differing_pixels, max_difference, diff_path } => write!(
+                f,
+                "{} pixels differ by up to {}, see {}",
+                differing_pixels,
+                max_difference,
+                diff_path.display()
+            ),
+            SnapshotError::Encode => write!(f, "failed to encode image as PNG"),
+            SnapshotError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(error: std::io::Error) -> Self {
+        SnapshotError::Io(error)
+    }
+}
+
+/// The result of comparing two RGBA images pixel by pixel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelComparison {
+    /// The number of pixels with a channel which differs by more than the tolerance.
+    pub differing_pixels: usize,
+    /// The largest difference found in any channel of any pixel.
+    pub max_difference: u8,
+    /// An RGBA image highlighting differing pixels in red over a faded copy of the expected image.
+    pub diff: Vec<u8>,
+}
+
+/// Compares two buffers of unpremultiplied RGBA8888 pixels of the same size.
+pub fn compare_pixels(expected: &[u8], actual: &[u8], tolerance: u8) -> PixelComparison {
+    let mut differing_pixels = 0;
+    let mut max_difference = 0;
+    let mut diff = Vec::with_capacity(expected.len());
+
+    for (expected, actual) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        let difference =
+            expected.iter().zip(actual).map(|(e, a)| e.abs_diff(*a)).max().unwrap_or_default();
+
+        max_difference = max_difference.max(difference);
+
+        if difference > tolerance {
+            differing_pixels += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            diff.extend_from_slice(&[expected[0], expected[1], expected[2], expected[3] / 4]);
+        }
+    }
+
+    PixelComparison { differing_pixels, max_difference, diff }
+}
+
+fn should_bless() -> bool {
+    std::env::var(BLESS_ENV_VAR).is_ok_and(|value| value != "0")
+}
+
+fn rgba_info(width: i32, height: i32) -> ImageInfo {
+    ImageInfo::new((width, height), ColorType::RGBA8888, AlphaType::Unpremul, None)
+}
+
+fn read_pixels(image: &Image) -> Option<Vec<u8>> {
+    let info = rgba_info(image.width(), image.height());
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; row_bytes * image.height() as usize];
+
+    image
+        .read_pixels(&info, &mut pixels, row_bytes, (0, 0), CachingHint::Disallow)
+        .then_some(pixels)
+}
+
+fn rgba_image(pixels: &[u8], width: i32, height: i32) -> Image {
+    let info = rgba_info(width, height);
+    let row_bytes = info.min_row_bytes();
+    images::raster_from_data(&info, Data::new_copy(pixels), row_bytes)
+        .expect("Failed to create diff image")
+}
+
+fn write_png(path: &Path, image: &Image) -> Result<(), SnapshotError> {
+    let data = image.encode(None, EncodedImageFormat::PNG, None).ok_or(SnapshotError::Encode)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, data.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_pixels() {
+        let pixels = [10, 20, 30, 255, 40, 50, 60, 255];
+        let comparison = compare_pixels(&pixels, &pixels, 0);
+        assert_eq!(comparison.differing_pixels, 0);
+        assert_eq!(comparison.max_difference, 0);
+    }
+
+    #[test]
+    fn pixels_within_tolerance() {
+        let expected = [10, 20, 30, 255, 40, 50, 60, 255];
+        let actual = [12, 20, 30, 255, 40, 47, 60, 255];
+        let comparison = compare_pixels(&expected, &actual, 3);
+        assert_eq!(comparison.differing_pixels, 0);
+        assert_eq!(comparison.max_difference, 3);
+    }
+
+    #[test]
+    fn pixels_outside_tolerance() {
+        let expected = [10, 20, 30, 255, 40, 50, 60, 255];
+        let actual = [10, 20, 30, 255, 40, 50, 60, 0];
+        let comparison = compare_pixels(&expected, &actual, 3);
+        assert_eq!(comparison.differing_pixels, 1);
+        assert_eq!(comparison.max_difference, 255);
+        assert_eq!(&comparison.diff[4..], &[255, 0, 0, 255]);
+    }
+}
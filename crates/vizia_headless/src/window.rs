@@ -0,0 +1,89 @@
+use skia_safe::{surfaces, Surface};
+use vizia_core::prelude::*;
+
+/// The view used for windows created by the headless backend.
+///
+/// Requests which would normally be forwarded to the platform window, such as changing the title
+/// or size, are recorded so that they can be inspected and applied by the [`HeadlessApplication`](crate::HeadlessApplication).
+#[derive(Default)]
+pub struct HeadlessWindow {
+    /// The most recent title set with [`WindowEvent::SetTitle`].
+    pub title: String,
+    /// The most recent cursor icon set with [`WindowEvent::SetCursor`].
+    pub cursor: CursorIcon,
+    /// Whether the window is visible.
+    pub visible: bool,
+    pub(crate) requested_size: Option<WindowSize>,
+}
+
+impl HeadlessWindow {
+    pub(crate) fn new(title: &str) -> Self {
+        Self { title: title.to_owned(), visible: true, ..Default::default() }
+    }
+}
+
+impl View for HeadlessWindow {
+    fn element(&self) -> Option<&'static str> {
+        Some("window")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event, meta| match window_event {
+            WindowEvent::SetTitle(title) => {
+                self.title = title.clone();
+            }
+
+            WindowEvent::SetCursor(cursor) => {
+                self.cursor = *cursor;
+            }
+
+            WindowEvent::SetSize(size) => {
+                self.requested_size = Some(*size);
+            }
+
+            WindowEvent::SetVisible(flag) => {
+                self.visible = *flag;
+                meta.consume();
+            }
+
+            WindowEvent::ReloadStyles => {
+                cx.reload_styles().unwrap();
+            }
+
+            WindowEvent::WindowClose => {
+                cx.close_window();
+                meta.consume();
+            }
+
+            WindowEvent::FocusNext => {
+                cx.focus_next();
+            }
+
+            WindowEvent::FocusPrev => {
+                cx.focus_prev();
+            }
+
+            _ => {}
+        })
+    }
+}
+
+/// The raster surfaces a window is drawn into.
+pub(crate) struct WindowSurface {
+    pub surface: Surface,
+    pub dirty_surface: Surface,
+}
+
+impl WindowSurface {
+    pub fn new(physical_width: i32, physical_height: i32) -> Self {
+        let mut surface =
+            surfaces::raster_n32_premul((physical_width.max(1), physical_height.max(1)))
+                .expect("Could not create raster surface");
+
+        let dirty_surface = surface
+            .new_surface_with_dimensions((physical_width.max(1), physical_height.max(1)))
+            .expect("Could not create raster surface");
+
+        Self { surface, dirty_surface }
+    }
+}
@@ -12,6 +12,15 @@ bitflags! {
 }
 
 impl Modifiers {
+    /// The modifier used for keyboard shortcuts, which is `SUPER` (Cmd) on macOS and `CTRL` on
+    /// other platforms.
+    #[cfg(target_os = "macos")]
+    pub const COMMAND: Self = Self::SUPER;
+    /// The modifier used for keyboard shortcuts, which is `SUPER` (Cmd) on macOS and `CTRL` on
+    /// other platforms.
+    #[cfg(not(target_os = "macos"))]
+    pub const COMMAND: Self = Self::CTRL;
+
     pub fn shift(&self) -> bool {
         self.contains(Modifiers::SHIFT)
     }
@@ -64,6 +64,7 @@ pub enum CustomParseError<'i> {
     AtRuleInvalid(CowRcStr<'i>),
     AtRuleBodyInvalid,
     QualifiedRuleInvalid,
+    UnexpectedImportRule,
 }
 
 impl<'i> From<SelectorParseErrorKind<'i>> for CustomParseError<'i> {
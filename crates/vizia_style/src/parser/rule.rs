@@ -1,7 +1,7 @@
 use crate::{
     parse_declaration, CssRule, CssRuleList, CustomParseError, DeclarationBlock, DeclarationList,
-    KeyframeListParser, KeyframesName, KeyframesRule, Location, Parse, ParserOptions,
-    SelectorParser, Selectors, StyleRule,
+    FontFaceRule, ImportRule, KeyframeListParser, KeyframesName, KeyframesRule, Location,
+    MediaList, MediaRule, Parse, ParserOptions, SelectorParser, Selectors, StyleRule,
 };
 use cssparser::*;
 use selectors::SelectorList;
@@ -10,7 +10,7 @@ use selectors::SelectorList;
 enum State {
     Start = 1,
     // Layers = 2,
-    // Imports = 3,
+    Imports = 3,
     // Namespaces = 4,
     Body = 5,
 }
@@ -35,6 +35,9 @@ impl<'a, 'o, 'b, 'i> TopLevelRuleParser<'a, 'o, 'i> {
 pub enum AtRulePrelude<'i> {
     // Property(DashedIdent<'i>),
     Keyframes(KeyframesName<'i>),
+    Media(MediaList),
+    Import(CowRcStr<'i>),
+    FontFace,
 }
 
 impl<'a, 'o, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'o, 'i> {
@@ -53,6 +56,15 @@ impl<'a, 'o, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'o, 'i> {
             //     let name = DashedIdent::parse(input)?;
             //     return Ok(AtRulePrelude::Property(name));
             // },
+            "import" => {
+                // Imports must precede all other rules.
+                if self.state > State::Imports {
+                    return Err(input.new_custom_error(CustomParseError::UnexpectedImportRule));
+                }
+
+                let url = input.expect_url_or_string()?.clone();
+                return Ok(AtRulePrelude::Import(url));
+            },
             _=> {}
         }
 
@@ -69,6 +81,23 @@ impl<'a, 'o, 'i> AtRuleParser<'i> for TopLevelRuleParser<'a, 'o, 'i> {
         let rule = AtRuleParser::parse_block(&mut self.nested(), prelude, start, input)?;
         Ok((start.position(), rule))
     }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        start: &ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude {
+            AtRulePrelude::Import(url) => {
+                self.state = State::Imports;
+                let loc = start.source_location();
+                let loc = Location { line: loc.line, column: loc.column };
+                Ok((start.position(), CssRule::Import(ImportRule { url, loc })))
+            }
+
+            _ => Err(()),
+        }
+    }
 }
 
 impl<'a, 'o, 'i> QualifiedRuleParser<'i> for TopLevelRuleParser<'a, 'o, 'i> {
@@ -101,7 +130,7 @@ struct NestedRuleParser<'a, 'o, 'i> {
 }
 
 impl<'a, 'o, 'i> NestedRuleParser<'a, 'o, 'i> {
-    fn _parse_nested_rules<'t>(&mut self, input: &mut Parser<'i, 't>) -> CssRuleList<'i> {
+    fn parse_nested_rules<'t>(&mut self, input: &mut Parser<'i, 't>) -> CssRuleList<'i> {
         let nested_parser =
             NestedRuleParser { default_namespace: self.default_namespace, options: self.options };
 
@@ -141,6 +170,13 @@ impl<'a, 'o, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'o, 'i> {
                 let name = input.try_parse(KeyframesName::parse)?;
                 Ok(AtRulePrelude::Keyframes(name))
             },
+            "media" => {
+                let query = MediaList::parse(input)?;
+                Ok(AtRulePrelude::Media(query))
+            },
+            "font-face" => {
+                Ok(AtRulePrelude::FontFace)
+            },
             _=> Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)))
         }
     }
@@ -151,7 +187,7 @@ impl<'a, 'o, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'o, 'i> {
         start: &ParserState,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
-        let _loc = self.loc(start);
+        let loc = self.loc(start);
         match prelude {
             AtRulePrelude::Keyframes(name) => {
                 let iter = RuleListParser::new_for_nested_rule(input, KeyframeListParser);
@@ -160,6 +196,16 @@ impl<'a, 'o, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'o, 'i> {
                     keyframes: iter.filter_map(Result::ok).collect(),
                 }))
             }
+
+            AtRulePrelude::Media(query) => {
+                Ok(CssRule::Media(MediaRule { query, rules: self.parse_nested_rules(input), loc }))
+            }
+
+            AtRulePrelude::FontFace => Ok(CssRule::FontFace(FontFaceRule::parse(input, loc))),
+
+            AtRulePrelude::Import(_) => {
+                Err(input.new_error(BasicParseErrorKind::AtRuleBodyInvalid))
+            }
         }
     }
 }
@@ -1,13 +1,15 @@
 use crate::{
-    define_property, Angle, BackgroundImage, BackgroundSize, BlendMode, Border, BorderStyle,
-    BorderWidth, ClipPath, Color, CornerRadius, CornerShape, CursorIcon, CustomParseError,
-    CustomProperty, Display, Filter, FontFamily, FontSize, FontSlant, FontVariation, FontWeight,
-    FontWidth, LayoutType, LengthOrPercentage, LineClamp, Opacity, Outline, Overflow, Parse,
-    PointerEvents, Position, PositionType, Rect, Scale, Shadow, TextAlign, TextDecoration,
-    TextDecorationLine, TextDecorationStyle, TextOverflow, Transform, Transition, Translate, Units,
-    UnparsedProperty, Visibility,
+    define_property, Angle, Animation, AnimationDirection, AnimationFillMode,
+    AnimationIterationCount, AnimationName, BackgroundImage, BackgroundSize, BlendMode, Border,
+    BorderStyle, BorderWidth, ClipPath, Color, CornerRadius, CornerShape, CursorIcon,
+    CustomParseError, CustomProperty, Direction, Display, Duration, EasingFunction, Filter,
+    FontFamily, FontSize, FontSlant, FontVariation, FontWeight, FontWidth, LayoutType,
+    LengthOrPercentage, LineClamp, Opacity, Outline, Overflow, Parse, PointerEvents, Position,
+    PositionType, Rect, Scale, Shadow, TextAlign, TextDecoration, TextDecorationLine,
+    TextDecorationStyle, TextOverflow, Transform, Transition, Translate, Units, UnparsedProperty,
+    Visibility,
 };
-use cssparser::Parser;
+use cssparser::{CowRcStr, ParseError, Parser, ParserInput};
 
 define_property! {
     pub enum Property<'i> {
@@ -21,6 +23,7 @@ define_property! {
         "opacity": Opacity(Opacity),
         "z-index": ZIndex(i32),
         "blend-mode": BlendMode(BlendMode),
+        "direction": Direction(Direction),
 
         // Positioning
         "layout-type": LayoutType(LayoutType),
@@ -65,6 +68,15 @@ define_property! {
         "child-bottom": ChildBottom(Units),
         "row-between": RowBetween(Units),
         "col-between": ColBetween(Units),
+
+        // Grid
+        "grid-columns": GridColumns(Vec<Units>),
+        "grid-rows": GridRows(Vec<Units>),
+        "column-start": ColumnStart(usize),
+        "column-span": ColumnSpan(usize),
+        "row-start": RowStart(usize),
+        "row-span": RowSpan(usize),
+
         // ----- Border -----
 
         // Border Shorthand
@@ -178,6 +190,14 @@ define_property! {
 
         // Animations
         "transition": Transition(Vec<Transition>),
+        "animation": Animation(Vec<Animation>),
+        "animation-name": AnimationName(Vec<AnimationName>),
+        "animation-duration": AnimationDuration(Vec<Duration>),
+        "animation-timing-function": AnimationTimingFunction(EasingFunction),
+        "animation-delay": AnimationDelay(Vec<Duration>),
+        "animation-iteration-count": AnimationIterationCount(Vec<AnimationIterationCount>),
+        "animation-direction": AnimationDirection(Vec<AnimationDirection>),
+        "animation-fill-mode": AnimationFillMode(Vec<AnimationFillMode>),
 
         // Transform
         "transform": Transform(Vec<Transform>),
@@ -192,10 +212,21 @@ define_property! {
     }
 }
 
+impl<'i> Property<'i> {
+    /// Parses the value of the property with the given name from a string, e.g. the result of
+    /// substituting the `var()` references of a declaration.
+    pub fn parse_declaration(
+        name: &'i str,
+        value: &'i str,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut parser_input = ParserInput::new(value);
+        let mut parser = Parser::new(&mut parser_input);
+        Property::parse_value(CowRcStr::from(name), &mut parser)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use cssparser::{CowRcStr, ParserInput};
-
     use super::*;
 
     #[test]
@@ -205,4 +236,21 @@ mod tests {
         let _parsed_property =
             Property::parse_value(CowRcStr::from("background-color"), &mut parser);
     }
+
+    #[test]
+    fn parse_declaration() {
+        let parsed_property = Property::parse_declaration("z-index", "5");
+        assert_eq!(parsed_property, Ok(Property::ZIndex(5)));
+
+        let parsed_property = Property::parse_declaration("direction", "rtl");
+        assert_eq!(parsed_property, Ok(Property::Direction(Direction::Rtl)));
+    }
+
+    #[test]
+    fn parse_variable_declaration() {
+        let parsed_property = Property::parse_declaration("width", "var(--width, 10px)").unwrap();
+        assert!(
+            matches!(parsed_property, Property::Unparsed(unparsed) if unparsed.value.has_variables())
+        );
+    }
 }
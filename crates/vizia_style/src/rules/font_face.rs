@@ -0,0 +1,121 @@
+use cssparser::*;
+
+use crate::{CustomParseError, Location, Parse, Url};
+
+/// A `@font-face` rule, which declares a font family from a font file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FontFaceRule<'i> {
+    /// The name used to refer to the font in `font-family` properties.
+    pub family: Option<CowRcStr<'i>>,
+    /// The sources of the font in order of preference.
+    pub sources: Vec<FontFaceSource<'i>>,
+    pub loc: Location,
+}
+
+/// A source of a font declared with the `src` descriptor of a `@font-face` rule.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FontFaceSource<'i> {
+    /// A font file, e.g. `url("fonts/Inter.ttf") format("truetype")`.
+    Url(Url<'i>),
+    /// A font installed on the system, e.g. `local("Inter")`.
+    Local(CowRcStr<'i>),
+}
+
+impl<'i> Parse<'i> for FontFaceSource<'i> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if let Ok(url) = input.try_parse(|input| input.expect_url().cloned()) {
+            // The format hint is ignored as the font type is detected from the file.
+            let _ = input.try_parse(|input| {
+                input.expect_function_matching("format")?;
+                input.parse_nested_block(|input| {
+                    while input.next().is_ok() {}
+                    Ok::<_, ParseError<'i, CustomParseError<'i>>>(())
+                })
+            });
+
+            return Ok(FontFaceSource::Url(Url { url }));
+        }
+
+        input.expect_function_matching("local")?;
+        input.parse_nested_block(|input| {
+            Ok(FontFaceSource::Local(input.expect_ident_or_string()?.clone()))
+        })
+    }
+}
+
+enum FontFaceDescriptor<'i> {
+    Family(CowRcStr<'i>),
+    Sources(Vec<FontFaceSource<'i>>),
+    Other,
+}
+
+struct FontFaceDeclarationParser;
+
+impl<'i> DeclarationParser<'i> for FontFaceDeclarationParser {
+    type Declaration = FontFaceDescriptor<'i>;
+    type Error = CustomParseError<'i>;
+
+    fn parse_value<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Declaration, ParseError<'i, Self::Error>> {
+        match_ignore_ascii_case! { &name,
+            "font-family" => {
+                Ok(FontFaceDescriptor::Family(input.expect_ident_or_string()?.clone()))
+            },
+            "src" => {
+                Ok(FontFaceDescriptor::Sources(input.parse_comma_separated(FontFaceSource::parse)?))
+            },
+            _ => {
+                // Descriptors such as `font-weight` are not used to select fonts yet.
+                while input.next().is_ok() {}
+                Ok(FontFaceDescriptor::Other)
+            }
+        }
+    }
+}
+
+impl<'i> AtRuleParser<'i> for FontFaceDeclarationParser {
+    type Prelude = ();
+    type AtRule = FontFaceDescriptor<'i>;
+    type Error = CustomParseError<'i>;
+}
+
+impl<'i> FontFaceRule<'i> {
+    pub(crate) fn parse<'t>(input: &mut Parser<'i, 't>, loc: Location) -> Self {
+        let mut rule = FontFaceRule { family: None, sources: Vec::new(), loc };
+
+        for descriptor in DeclarationListParser::new(input, FontFaceDeclarationParser).flatten() {
+            match descriptor {
+                FontFaceDescriptor::Family(family) => rule.family = Some(family),
+                FontFaceDescriptor::Sources(sources) => rule.sources = sources,
+                FontFaceDescriptor::Other => {}
+            }
+        }
+
+        rule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_font_face() {
+        let css = r#"font-family: "Inter"; src: local(Inter), url("fonts/Inter.ttf") format("truetype"); font-weight: 400;"#;
+        let mut parser_input = ParserInput::new(css);
+        let mut parser = Parser::new(&mut parser_input);
+        let rule = FontFaceRule::parse(&mut parser, Location { line: 0, column: 1 });
+
+        assert_eq!(rule.family.as_deref(), Some("Inter"));
+        assert_eq!(
+            rule.sources,
+            vec![
+                FontFaceSource::Local("Inter".into()),
+                FontFaceSource::Url(Url { url: "fonts/Inter.ttf".into() })
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use cssparser::*;
+
+use crate::Location;
+
+/// An `@import` rule, which includes the rules of another stylesheet.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImportRule<'i> {
+    /// The url of the imported stylesheet, relative to the importing stylesheet.
+    pub url: CowRcStr<'i>,
+    pub loc: Location,
+}
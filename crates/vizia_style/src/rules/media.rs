@@ -0,0 +1,386 @@
+use cssparser::*;
+
+use crate::{CssRuleList, CustomParseError, Location, Parse};
+
+/// A `@media` rule, containing rules which only apply when its media query list matches.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MediaRule<'i> {
+    pub query: MediaList,
+    pub rules: CssRuleList<'i>,
+    pub loc: Location,
+}
+
+/// The color scheme preferred by the user, as queried by the `prefers-color-scheme` media feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// The values of the media features which media queries are evaluated against.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MediaEnvironment {
+    /// The preferred color scheme.
+    pub color_scheme: ColorScheme,
+    /// The width of the window in logical pixels.
+    pub width: f32,
+    /// The height of the window in logical pixels.
+    pub height: f32,
+    /// The number of physical pixels per logical pixel.
+    pub resolution: f32,
+}
+
+/// A comma-separated list of media queries, which matches if any of its queries match.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MediaList(pub Vec<MediaQuery>);
+
+impl MediaList {
+    /// Returns true if any of the media queries in the list match the given environment.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        self.0.iter().any(|query| query.matches(environment))
+    }
+}
+
+impl<'i> Parse<'i> for MediaList {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        Ok(MediaList(input.parse_comma_separated(MediaQuery::parse)?))
+    }
+}
+
+/// The type of device a media query applies to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    #[default]
+    All,
+    Screen,
+    Print,
+}
+
+/// A single media query, e.g. `screen and (min-width: 600px)`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MediaQuery {
+    /// Whether the query is negated with `not`.
+    pub negated: bool,
+    pub media_type: MediaType,
+    /// Media features which must all match, joined with `and`.
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery {
+    /// Returns true if the media query matches the given environment.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        let matches = self.media_type != MediaType::Print
+            && self.features.iter().all(|feature| feature.matches(environment));
+
+        matches != self.negated
+    }
+}
+
+impl<'i> Parse<'i> for MediaQuery {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut query = MediaQuery::default();
+        let mut expect_and = false;
+
+        let location = input.current_source_location();
+        if let Ok(ident) = input.try_parse(|input| input.expect_ident_cloned()) {
+            let media_type = match_ignore_ascii_case! { &ident,
+                "not" => {
+                    query.negated = true;
+                    input.try_parse(parse_media_type).ok()
+                },
+                "only" => Some(parse_media_type(input)?),
+                _ => Some(media_type_from_ident(&ident).ok_or_else(|| {
+                    location.new_unexpected_token_error(Token::Ident(ident.clone()))
+                })?),
+            };
+
+            if let Some(media_type) = media_type {
+                query.media_type = media_type;
+                expect_and = true;
+            }
+        }
+
+        loop {
+            if expect_and && input.try_parse(|input| input.expect_ident_matching("and")).is_err() {
+                break;
+            }
+
+            query.features.push(MediaFeature::parse(input)?);
+            expect_and = true;
+        }
+
+        Ok(query)
+    }
+}
+
+fn media_type_from_ident(ident: &str) -> Option<MediaType> {
+    match_ignore_ascii_case! { ident,
+        "all" => Some(MediaType::All),
+        "screen" => Some(MediaType::Screen),
+        "print" => Some(MediaType::Print),
+        _ => None,
+    }
+}
+
+fn parse_media_type<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaType, ParseError<'i, CustomParseError<'i>>> {
+    let location = input.current_source_location();
+    let ident = input.expect_ident_cloned()?;
+    media_type_from_ident(&ident)
+        .ok_or_else(|| location.new_unexpected_token_error(Token::Ident(ident.clone())))
+}
+
+/// The comparison used by a range media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaComparison {
+    Equal,
+    LessThan,
+    LessThanEqual,
+    GreaterThan,
+    GreaterThanEqual,
+}
+
+impl MediaComparison {
+    fn compare(&self, value: f32, reference: f32) -> bool {
+        match self {
+            MediaComparison::Equal => value == reference,
+            MediaComparison::LessThan => value < reference,
+            MediaComparison::LessThanEqual => value <= reference,
+            MediaComparison::GreaterThan => value > reference,
+            MediaComparison::GreaterThanEqual => value >= reference,
+        }
+    }
+
+    // Returns the comparison with its operands swapped, e.g. `600px <= width` becomes `width >= 600px`.
+    fn flip(self) -> Self {
+        match self {
+            MediaComparison::Equal => MediaComparison::Equal,
+            MediaComparison::LessThan => MediaComparison::GreaterThan,
+            MediaComparison::LessThanEqual => MediaComparison::GreaterThanEqual,
+            MediaComparison::GreaterThan => MediaComparison::LessThan,
+            MediaComparison::GreaterThanEqual => MediaComparison::LessThanEqual,
+        }
+    }
+}
+
+impl<'i> Parse<'i> for MediaComparison {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let location = input.current_source_location();
+        let comparison = match input.next()?.clone() {
+            Token::Delim('=') => return Ok(MediaComparison::Equal),
+            Token::Delim('<') => MediaComparison::LessThan,
+            Token::Delim('>') => MediaComparison::GreaterThan,
+            token => return Err(location.new_unexpected_token_error(token)),
+        };
+
+        let or_equal = input.try_parse(|input| input.expect_delim('=')).is_ok();
+
+        Ok(match (comparison, or_equal) {
+            (MediaComparison::LessThan, true) => MediaComparison::LessThanEqual,
+            (MediaComparison::GreaterThan, true) => MediaComparison::GreaterThanEqual,
+            (comparison, _) => comparison,
+        })
+    }
+}
+
+/// A media feature within parentheses, e.g. `(prefers-color-scheme: dark)` or `(width >= 600px)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaFeature {
+    PrefersColorScheme(ColorScheme),
+    /// The width of the window in logical pixels.
+    Width(MediaComparison, f32),
+    /// The height of the window in logical pixels.
+    Height(MediaComparison, f32),
+    /// The scale factor of the window in dots per pixel.
+    Resolution(MediaComparison, f32),
+}
+
+impl MediaFeature {
+    /// Returns true if the media feature matches the given environment.
+    pub fn matches(&self, environment: &MediaEnvironment) -> bool {
+        match self {
+            MediaFeature::PrefersColorScheme(color_scheme) => {
+                environment.color_scheme == *color_scheme
+            }
+            MediaFeature::Width(comparison, width) => comparison.compare(environment.width, *width),
+            MediaFeature::Height(comparison, height) => {
+                comparison.compare(environment.height, *height)
+            }
+            MediaFeature::Resolution(comparison, resolution) => {
+                comparison.compare(environment.resolution, *resolution)
+            }
+        }
+    }
+
+    // Creates a range media feature from a feature name, comparison and value.
+    fn range<'i>(
+        name: &str,
+        comparison: MediaComparison,
+        value: f32,
+        unit: &str,
+        location: SourceLocation,
+    ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let invalid = || location.new_custom_error(CustomParseError::InvalidValue);
+        match_ignore_ascii_case! { name,
+            "width" => Ok(MediaFeature::Width(comparison, length_to_px(value, unit).ok_or_else(invalid)?)),
+            "height" => Ok(MediaFeature::Height(comparison, length_to_px(value, unit).ok_or_else(invalid)?)),
+            "resolution" => Ok(MediaFeature::Resolution(comparison, resolution_to_dppx(value, unit).ok_or_else(invalid)?)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl<'i> Parse<'i> for MediaFeature {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.expect_parenthesis_block()?;
+        input.parse_nested_block(|input| {
+            let location = input.current_source_location();
+
+            // Range syntax with the value first, e.g. `(600px <= width)`.
+            if let Ok((value, unit)) = input.try_parse(parse_dimension) {
+                let comparison = MediaComparison::parse(input)?.flip();
+                let name = input.expect_ident_cloned()?;
+                return MediaFeature::range(&name, comparison, value, &unit, location);
+            }
+
+            let name = input.expect_ident_cloned()?;
+
+            // Range syntax with the name first, e.g. `(width >= 600px)`.
+            if input.try_parse(|input| input.expect_colon()).is_err() {
+                let comparison = MediaComparison::parse(input)?;
+                let (value, unit) = parse_dimension(input)?;
+                return MediaFeature::range(&name, comparison, value, &unit, location);
+            }
+
+            if name.eq_ignore_ascii_case("prefers-color-scheme") {
+                let value_location = input.current_source_location();
+                let ident = input.expect_ident_cloned()?;
+                return match_ignore_ascii_case! { &ident,
+                    "light" => Ok(MediaFeature::PrefersColorScheme(ColorScheme::Light)),
+                    "dark" => Ok(MediaFeature::PrefersColorScheme(ColorScheme::Dark)),
+                    _ => Err(value_location.new_unexpected_token_error(Token::Ident(ident.clone()))),
+                };
+            }
+
+            let (comparison, feature_name) = if let Some(feature_name) = name.strip_prefix("min-")
+            {
+                (MediaComparison::GreaterThanEqual, feature_name)
+            } else if let Some(feature_name) = name.strip_prefix("max-") {
+                (MediaComparison::LessThanEqual, feature_name)
+            } else {
+                (MediaComparison::Equal, name.as_ref())
+            };
+
+            let (value, unit) = parse_dimension(input)?;
+            MediaFeature::range(feature_name, comparison, value, &unit, location)
+        })
+    }
+}
+
+// Parses a dimension, or a unitless zero, into its value and unit.
+fn parse_dimension<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<(f32, String), ParseError<'i, CustomParseError<'i>>> {
+    let location = input.current_source_location();
+    match input.next()?.clone() {
+        Token::Dimension { value, unit, .. } => Ok((value, unit.to_ascii_lowercase())),
+        Token::Number { value, .. } if value == 0.0 => Ok((0.0, String::new())),
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
+
+fn length_to_px(value: f32, unit: &str) -> Option<f32> {
+    match unit {
+        "px" | "" => Some(value),
+        "in" => Some(value * 96.0),
+        "cm" => Some(value * 96.0 / 2.54),
+        "mm" => Some(value * 96.0 / 25.4),
+        "pt" => Some(value * 96.0 / 72.0),
+        _ => None,
+    }
+}
+
+fn resolution_to_dppx(value: f32, unit: &str) -> Option<f32> {
+    match unit {
+        "dppx" | "x" | "" => Some(value),
+        "dpi" => Some(value / 96.0),
+        "dpcm" => Some(value * 2.54 / 96.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(css: &str) -> MediaList {
+        let mut parser_input = ParserInput::new(css);
+        let mut parser = Parser::new(&mut parser_input);
+        MediaList::parse(&mut parser).unwrap()
+    }
+
+    const ENVIRONMENT: MediaEnvironment = MediaEnvironment {
+        color_scheme: ColorScheme::Dark,
+        width: 800.0,
+        height: 600.0,
+        resolution: 2.0,
+    };
+
+    #[test]
+    fn parse_color_scheme() {
+        let list = parse("(prefers-color-scheme: dark)");
+        assert_eq!(list.0[0].features, vec![MediaFeature::PrefersColorScheme(ColorScheme::Dark)]);
+        assert!(list.matches(&ENVIRONMENT));
+        assert!(!parse("(prefers-color-scheme: light)").matches(&ENVIRONMENT));
+    }
+
+    #[test]
+    fn parse_min_max() {
+        let list = parse("(min-width: 600px)");
+        assert_eq!(
+            list.0[0].features,
+            vec![MediaFeature::Width(MediaComparison::GreaterThanEqual, 600.0)]
+        );
+        assert!(list.matches(&ENVIRONMENT));
+        assert!(!parse("(max-height: 500px)").matches(&ENVIRONMENT));
+    }
+
+    #[test]
+    fn parse_range() {
+        let list = parse("(resolution >= 2dppx)");
+        assert_eq!(
+            list.0[0].features,
+            vec![MediaFeature::Resolution(MediaComparison::GreaterThanEqual, 2.0)]
+        );
+        assert!(list.matches(&ENVIRONMENT));
+
+        let list = parse("(900px < width)");
+        assert_eq!(
+            list.0[0].features,
+            vec![MediaFeature::Width(MediaComparison::GreaterThan, 900.0)]
+        );
+        assert!(!list.matches(&ENVIRONMENT));
+    }
+
+    #[test]
+    fn parse_combined() {
+        let list = parse("screen and (min-width: 600px) and (max-width: 1000px), print");
+        assert_eq!(list.0.len(), 2);
+        assert_eq!(list.0[0].media_type, MediaType::Screen);
+        assert_eq!(list.0[0].features.len(), 2);
+        assert!(list.matches(&ENVIRONMENT));
+
+        assert!(!parse("print").matches(&ENVIRONMENT));
+        assert!(parse("not print").matches(&ENVIRONMENT));
+        assert!(!parse("not (min-width: 600px)").matches(&ENVIRONMENT));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let mut parser_input = ParserInput::new("(min-width: 600deg)");
+        let mut parser = Parser::new(&mut parser_input);
+        assert!(MediaList::parse(&mut parser).is_err());
+    }
+}
@@ -4,6 +4,15 @@ pub use style::*;
 pub mod keyframes;
 pub use keyframes::*;
 
+pub mod media;
+pub use media::*;
+
+pub mod import;
+pub use import::*;
+
+pub mod font_face;
+pub use font_face::*;
+
 pub mod property;
 pub use property::*;
 
@@ -16,4 +25,7 @@ pub enum CssRule<'i> {
     Property(PropertyRule<'i>),
     Ignored,
     Keyframes(KeyframesRule<'i>),
+    Media(MediaRule<'i>),
+    Import(ImportRule<'i>),
+    FontFace(FontFaceRule<'i>),
 }
@@ -1,5 +1,5 @@
 use cssparser::*;
-use selectors::SelectorImpl;
+use selectors::{SelectorImpl, SelectorList};
 
 use crate::{CustomParseError, Direction, Parse, PseudoClass, PseudoElement};
 
@@ -157,12 +157,21 @@ impl<'a, 'i> selectors::parser::Parser<'i> for SelectorParser<'a, 'i> {
     }
 }
 
+/// Parses a comma separated list of selectors, such as `button.primary, #ok:hover`.
+pub fn parse_selector_list(
+    input: &str,
+) -> Result<SelectorList<Selectors>, ParseError<'_, CustomParseError<'_>>> {
+    let mut parser_input = ParserInput::new(input);
+    let mut parser = Parser::new(&mut parser_input);
+    SelectorList::parse(
+        &SelectorParser { default_namespace: &None, is_nesting_allowed: false },
+        &mut parser,
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use selectors::{
-        parser::{Component, LocalName, Selector},
-        SelectorList,
-    };
+    use selectors::parser::{Component, LocalName, Selector};
 
     use super::*;
 
@@ -190,6 +199,12 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn parse_selector_list_from_str() {
+        assert!(parse_selector_list("button.primary, #ok:hover").is_ok());
+        assert!(parse_selector_list("button >").is_err());
+    }
+
     // TODO - Some fancy macros for making this easier
     #[test]
     fn parse_universal() {
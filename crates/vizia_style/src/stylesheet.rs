@@ -134,6 +134,64 @@ test {
         let style_sheet = StyleSheet::parse("test.css", CSS_EXAMPLE, ParserOptions::default());
         println!("{:#?}", style_sheet);
     }
+
+    #[test]
+    fn parse_media_rule() {
+        let style_sheet = StyleSheet::parse(
+            "test.css",
+            "@media (prefers-color-scheme: dark) and (min-width: 600px) { button { width: 10px; } }",
+            ParserOptions::default(),
+        )
+        .unwrap();
+
+        let CssRule::Media(media_rule) = &style_sheet.rules.0[0] else {
+            panic!("Expected a media rule");
+        };
+
+        assert_eq!(media_rule.query.0[0].features.len(), 2);
+        assert!(matches!(media_rule.rules.0[0], CssRule::Style(_)));
+    }
+
+    #[test]
+    fn parse_import_and_font_face_rules() {
+        let style_sheet = StyleSheet::parse(
+            "test.css",
+            r#"@import "theme.css";
+            @import url(buttons.css);
+            @font-face { font-family: Inter; src: url("Inter.ttf"); }
+            button { font-family: Inter; }"#,
+            ParserOptions::default(),
+        )
+        .unwrap();
+
+        let urls: Vec<_> = style_sheet
+            .rules
+            .0
+            .iter()
+            .filter_map(|rule| match rule {
+                CssRule::Import(import_rule) => Some(import_rule.url.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(urls, vec!["theme.css", "buttons.css"]);
+
+        let CssRule::FontFace(font_face_rule) = &style_sheet.rules.0[2] else {
+            panic!("Expected a font-face rule");
+        };
+        assert_eq!(font_face_rule.family.as_deref(), Some("Inter"));
+        assert_eq!(font_face_rule.sources.len(), 1);
+    }
+
+    #[test]
+    fn import_after_style_rule() {
+        let style_sheet = StyleSheet::parse(
+            "test.css",
+            r#"button { width: 10px; } @import "theme.css";"#,
+            ParserOptions::default(),
+        );
+
+        assert!(style_sheet.is_err());
+    }
 }
 
 // use cssparser::*;
@@ -0,0 +1,296 @@
+use crate::{
+    duration::Duration, AnimationDirection, AnimationFillMode, CustomParseError, EasingFunction,
+    Parse,
+};
+use cssparser::{match_ignore_ascii_case, ParseError, ParseErrorKind, Parser, Token};
+
+/// The name of the `@keyframes` rule played by an animation.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum AnimationName {
+    /// No animation is played.
+    #[default]
+    None,
+    /// The name of a `@keyframes` rule.
+    Name(String),
+}
+
+impl<'i> Parse<'i> for AnimationName {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let location = input.current_source_location();
+        match input.next()?.clone() {
+            Token::Ident(ident) => Ok(match_ignore_ascii_case! { &ident,
+                "none" => AnimationName::None,
+                "initial" | "inherit" | "unset" | "default" => {
+                    return Err(location.new_unexpected_token_error(Token::Ident(ident.clone())))
+                },
+                _ => AnimationName::Name(ident.to_string()),
+            }),
+            Token::QuotedString(name) => Ok(AnimationName::Name(name.to_string())),
+            token => Err(location.new_unexpected_token_error(token)),
+        }
+    }
+}
+
+/// The number of times an animation is played.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimationIterationCount {
+    /// The animation is played the given number of times, which may be fractional.
+    Count(f32),
+    /// The animation repeats forever.
+    Infinite,
+}
+
+impl Default for AnimationIterationCount {
+    fn default() -> Self {
+        AnimationIterationCount::Count(1.0)
+    }
+}
+
+impl<'i> Parse<'i> for AnimationIterationCount {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        if input.try_parse(|input| input.expect_ident_matching("infinite")).is_ok() {
+            return Ok(AnimationIterationCount::Infinite);
+        }
+
+        let location = input.current_source_location();
+        let count = input.expect_number()?;
+        if count < 0.0 {
+            return Err(location.new_custom_error(CustomParseError::InvalidValue));
+        }
+
+        Ok(AnimationIterationCount::Count(count))
+    }
+}
+
+/// A keyframe animation, as defined by the `animation` shorthand property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// The name of the `@keyframes` rule to play.
+    pub name: AnimationName,
+    /// The duration of one iteration of the animation.
+    pub duration: Duration,
+    /// The easing function used between keyframes which don't specify their own.
+    pub timing_function: EasingFunction,
+    /// The delay before the animation starts.
+    pub delay: Duration,
+    /// The number of times the animation is played.
+    pub iteration_count: AnimationIterationCount,
+    /// The direction in which the animation is played.
+    pub direction: AnimationDirection,
+    /// Whether the values of the animation apply before it starts and after it ends.
+    pub fill_mode: AnimationFillMode,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            name: AnimationName::None,
+            duration: Duration::default(),
+            timing_function: EasingFunction::Ease,
+            delay: Duration::default(),
+            iteration_count: AnimationIterationCount::default(),
+            direction: AnimationDirection::default(),
+            fill_mode: AnimationFillMode::default(),
+        }
+    }
+}
+
+impl<'i> Parse<'i> for Animation {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let location = input.current_source_location();
+
+        let mut name = None;
+        let mut duration = None;
+        let mut timing_function = None;
+        let mut delay = None;
+        let mut iteration_count = None;
+        let mut direction = None;
+        let mut fill_mode = None;
+        let mut parsed_any = false;
+
+        // The components can be given in any order, except that the first time is the duration
+        // and the second is the delay. Keywords take precedence over animation names.
+        loop {
+            if duration.is_none() || delay.is_none() {
+                if let Ok(time) = input.try_parse(Duration::parse) {
+                    if duration.is_none() {
+                        duration = Some(time);
+                    } else {
+                        delay = Some(time);
+                    }
+                    parsed_any = true;
+                    continue;
+                }
+            }
+
+            if timing_function.is_none() {
+                if let Ok(value) = input.try_parse(EasingFunction::parse) {
+                    timing_function = Some(value);
+                    parsed_any = true;
+                    continue;
+                }
+            }
+
+            if iteration_count.is_none() {
+                if let Ok(value) = input.try_parse(AnimationIterationCount::parse) {
+                    iteration_count = Some(value);
+                    parsed_any = true;
+                    continue;
+                }
+            }
+
+            if direction.is_none() {
+                if let Ok(value) = input.try_parse(AnimationDirection::parse) {
+                    direction = Some(value);
+                    parsed_any = true;
+                    continue;
+                }
+            }
+
+            if fill_mode.is_none() {
+                if let Ok(value) = input.try_parse(AnimationFillMode::parse) {
+                    fill_mode = Some(value);
+                    parsed_any = true;
+                    continue;
+                }
+            }
+
+            if name.is_none() {
+                if let Ok(value) = input.try_parse(AnimationName::parse) {
+                    name = Some(value);
+                    parsed_any = true;
+                    continue;
+                }
+            }
+
+            break;
+        }
+
+        if !input.is_exhausted() || !parsed_any {
+            return Err(ParseError {
+                kind: ParseErrorKind::Custom(CustomParseError::InvalidDeclaration),
+                location,
+            });
+        }
+
+        Ok(Animation {
+            name: name.unwrap_or_default(),
+            duration: duration.unwrap_or_default(),
+            timing_function: timing_function.unwrap_or(EasingFunction::Ease),
+            delay: delay.unwrap_or_default(),
+            iteration_count: iteration_count.unwrap_or_default(),
+            direction: direction.unwrap_or_default(),
+            fill_mode: fill_mode.unwrap_or_default(),
+        })
+    }
+}
+
+impl<'i> Parse<'i> for Vec<Animation> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(Animation::parse)
+    }
+}
+
+impl<'i> Parse<'i> for Vec<AnimationName> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(AnimationName::parse)
+    }
+}
+
+impl<'i> Parse<'i> for Vec<AnimationIterationCount> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(AnimationIterationCount::parse)
+    }
+}
+
+impl<'i> Parse<'i> for Vec<AnimationDirection> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(AnimationDirection::parse)
+    }
+}
+
+impl<'i> Parse<'i> for Vec<AnimationFillMode> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        input.parse_comma_separated(AnimationFillMode::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        Animation, assert_animation,
+
+        custom {
+            success {
+                "spin 1s infinite" => Animation {
+                    name: AnimationName::Name(String::from("spin")),
+                    duration: Duration::from_secs(1),
+                    timing_function: EasingFunction::Ease,
+                    iteration_count: AnimationIterationCount::Infinite,
+                    ..Default::default()
+                },
+                "200ms 1s linear 2.5 alternate-reverse both fade" => Animation {
+                    name: AnimationName::Name(String::from("fade")),
+                    duration: Duration::from_millis(200),
+                    timing_function: EasingFunction::Linear,
+                    delay: Duration::from_secs(1),
+                    iteration_count: AnimationIterationCount::Count(2.5),
+                    direction: AnimationDirection::AlternateReverse,
+                    fill_mode: AnimationFillMode::Both,
+                },
+                "none" => Animation::default(),
+                "\"slide in\" 2s steps(4)" => Animation {
+                    name: AnimationName::Name(String::from("slide in")),
+                    duration: Duration::from_secs(2),
+                    timing_function: EasingFunction::Steps(4, crate::StepPosition::JumpEnd),
+                    ..Default::default()
+                },
+            }
+
+            failure {
+                "spin 1s 2s 3s",
+                "spin fade",
+                "",
+            }
+        }
+    }
+
+    assert_parse! {
+        AnimationIterationCount, assert_animation_iteration_count,
+
+        custom {
+            success {
+                "infinite" => AnimationIterationCount::Infinite,
+                "3" => AnimationIterationCount::Count(3.0),
+                "0.5" => AnimationIterationCount::Count(0.5),
+            }
+
+            failure {
+                "-1",
+                "forever",
+            }
+        }
+    }
+
+    assert_parse! {
+        Vec<AnimationName>, assert_animation_names,
+
+        custom {
+            success {
+                "spin, none, \"fade\"" => vec![
+                    AnimationName::Name(String::from("spin")),
+                    AnimationName::None,
+                    AnimationName::Name(String::from("fade")),
+                ],
+            }
+
+            failure {
+                "inherit",
+                "1s",
+            }
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{define_enum, Parse};
+
+define_enum! {
+    /// Determines whether an animation plays forwards, backwards, or alternates between the two.
+    #[derive(Default)]
+    pub enum AnimationDirection {
+        /// Every iteration plays forwards.
+        #[default]
+        "normal": Normal,
+        /// Every iteration plays backwards.
+        "reverse": Reverse,
+        /// Iterations alternate between playing forwards and backwards, starting forwards.
+        "alternate": Alternate,
+        /// Iterations alternate between playing backwards and forwards, starting backwards.
+        "alternate-reverse": AlternateReverse,
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{define_enum, Parse};
+
+define_enum! {
+    /// Determines whether the values of an animation apply before it starts and after it ends.
+    #[derive(Default)]
+    pub enum AnimationFillMode {
+        /// The animation only applies while it is playing.
+        #[default]
+        "none": None,
+        /// The final value of the animation is kept after it ends.
+        "forwards": Forwards,
+        /// The first value of the animation applies during its delay.
+        "backwards": Backwards,
+        /// Both `forwards` and `backwards`.
+        "both": Both,
+    }
+}
+
+impl AnimationFillMode {
+    /// Returns true if the final value of the animation is kept after it ends.
+    pub fn fills_forwards(&self) -> bool {
+        matches!(self, AnimationFillMode::Forwards | AnimationFillMode::Both)
+    }
+
+    /// Returns true if the first value of the animation applies during its delay.
+    pub fn fills_backwards(&self) -> bool {
+        matches!(self, AnimationFillMode::Backwards | AnimationFillMode::Both)
+    }
+}
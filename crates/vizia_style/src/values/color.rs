@@ -827,6 +827,16 @@ impl From<&str> for Color {
     }
 }
 
+impl std::str::FromStr for Color {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        parser.parse_entirely(Color::parse).map_err(|_| ())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -849,4 +859,10 @@ mod tests {
             "#FFFFFFFFF",
         }
     }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(" #123456 ".parse(), Ok(Color::rgb(18, 52, 86)));
+        assert_eq!("red blue".parse::<Color>(), Err(()));
+    }
 }
@@ -6,6 +6,8 @@ use crate::{CustomParseError, DashedIdent, Parse};
 pub struct CustomProperty<'i> {
     pub name: CowRcStr<'i>,
     pub value: TokenList<'i>,
+    /// The source text of the value.
+    pub css: CowRcStr<'i>,
 }
 
 impl<'i> CustomProperty<'i> {
@@ -19,8 +21,10 @@ impl<'i> CustomProperty<'i> {
         //     value: TokenList(vec![TokenOrValue::Color(Color::CurrentColor)]),
         // })
 
+        let start = input.position();
         let value = TokenList::parse(input)?;
-        Ok(CustomProperty { name, value })
+        let css = input.slice_from(start).trim().into();
+        Ok(CustomProperty { name, value, css })
     }
 }
 
@@ -28,6 +32,8 @@ impl<'i> CustomProperty<'i> {
 pub struct UnparsedProperty<'i> {
     pub name: CowRcStr<'i>,
     pub value: TokenList<'i>,
+    /// The source text of the value.
+    pub css: CowRcStr<'i>,
 }
 
 impl<'i> UnparsedProperty<'i> {
@@ -35,8 +41,10 @@ impl<'i> UnparsedProperty<'i> {
         name: CowRcStr<'i>,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let start = input.position();
         let value = TokenList::parse(input)?;
-        Ok(UnparsedProperty { name, value })
+        let css = input.slice_from(start).trim().into();
+        Ok(UnparsedProperty { name, value, css })
     }
 }
 
@@ -69,6 +77,11 @@ impl<'i> TokenOrValue<'i> {
 }
 
 impl<'i> TokenList<'i> {
+    /// Returns true if the token list contains a `var()` reference.
+    pub fn has_variables(&self) -> bool {
+        self.0.iter().any(|token| matches!(token, TokenOrValue::Var(_)))
+    }
+
     fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
         input.parse_until_before(Delimiter::Bang | Delimiter::Semicolon, |input| {
             let mut tokens = vec![];
@@ -235,11 +248,94 @@ impl<'i> Variable<'i> {
     }
 }
 
+/// Replaces every `var()` reference in a CSS value with the value returned by `lookup` for the
+/// referenced custom property, or with the fallback if `lookup` returns `None`.
+///
+/// Returns `None` if a referenced custom property is undefined and has no fallback, in which case
+/// the declaration containing the value is invalid.
+///
+/// # Example
+/// ```
+/// # use vizia_style::substitute_variables;
+/// let value = substitute_variables("var(--gap) var(--missing, 4px)", &mut |name| {
+///     (name == "--gap").then(|| String::from("10px"))
+/// });
+///
+/// assert_eq!(value, Some(String::from("10px 4px")));
+/// ```
+pub fn substitute_variables(
+    value: &str,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Option<String> {
+    let mut parser_input = ParserInput::new(value);
+    let mut parser = Parser::new(&mut parser_input);
+    let mut css = String::new();
+    substitute_variables_into(&mut parser, lookup, &mut css).ok()?;
+    Some(css.trim().to_owned())
+}
+
+fn substitute_variables_into<'i>(
+    input: &mut Parser<'i, '_>,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+    css: &mut String,
+) -> Result<(), ParseError<'i, ()>> {
+    loop {
+        let start = input.position();
+        let token = match input.next_including_whitespace_and_comments() {
+            Ok(token) => token.clone(),
+            Err(_) => return Ok(()),
+        };
+
+        match token {
+            Token::Function(name) if name.eq_ignore_ascii_case("var") => {
+                let value = input.parse_nested_block(|input| {
+                    input.skip_whitespace();
+                    let name = input.expect_ident_cloned()?;
+                    if !name.starts_with("--") {
+                        return Err(input.new_custom_error(()));
+                    }
+
+                    input.skip_whitespace();
+                    if let Some(value) = lookup(&name) {
+                        // The fallback is unused but must still be consumed.
+                        while input.next_including_whitespace_and_comments().is_ok() {}
+                        return Ok(value);
+                    }
+
+                    input.expect_comma()?;
+                    let mut fallback = String::new();
+                    substitute_variables_into(input, lookup, &mut fallback)?;
+                    Ok(fallback.trim().to_owned())
+                })?;
+
+                css.push_str(&value);
+            }
+
+            Token::Function(_)
+            | Token::ParenthesisBlock
+            | Token::SquareBracketBlock
+            | Token::CurlyBracketBlock => {
+                let closing = match token {
+                    Token::SquareBracketBlock => ']',
+                    Token::CurlyBracketBlock => '}',
+                    _ => ')',
+                };
+
+                css.push_str(input.slice_from(start));
+                input.parse_nested_block(|input| substitute_variables_into(input, lookup, css))?;
+                css.push(closing);
+            }
+
+            _ => css.push_str(input.slice_from(start)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cssparser::CowRcStr;
 
-    use crate::CustomProperty;
+    use crate::{substitute_variables, CustomProperty};
 
     #[test]
     fn parse_custom_ident() {
@@ -276,4 +372,43 @@ mod tests {
         let _result = CustomProperty::parse(CowRcStr::from("custom"), &mut parser);
         // assert_eq!(result, Ok($value));
     }
+
+    #[test]
+    fn parse_custom_variable() {
+        let success_string = " 3px solid var(--foo, calc(2px + 1px)) ";
+        let mut parser_input = cssparser::ParserInput::new(success_string);
+        let mut parser = cssparser::Parser::new(&mut parser_input);
+        let result = CustomProperty::parse(CowRcStr::from("custom"), &mut parser).unwrap();
+        assert!(result.value.has_variables());
+        assert_eq!(result.css.as_ref(), "3px solid var(--foo, calc(2px + 1px))");
+    }
+
+    #[test]
+    fn substitute_defined_variable() {
+        let result = substitute_variables("1px solid var(--color)", &mut |name| {
+            (name == "--color").then(|| String::from("red"))
+        });
+        assert_eq!(result, Some(String::from("1px solid red")));
+    }
+
+    #[test]
+    fn substitute_fallback() {
+        let result =
+            substitute_variables("var(--missing, var(--also-missing, 5px))", &mut |_| None);
+        assert_eq!(result, Some(String::from("5px")));
+    }
+
+    #[test]
+    fn substitute_nested_function() {
+        let result = substitute_variables("rgb(var(--r), 0, 0)", &mut |name| {
+            (name == "--r").then(|| String::from("255"))
+        });
+        assert_eq!(result, Some(String::from("rgb(255, 0, 0)")));
+    }
+
+    #[test]
+    fn substitute_undefined_variable() {
+        let result = substitute_variables("var(--missing)", &mut |_| None);
+        assert_eq!(result, None);
+    }
 }
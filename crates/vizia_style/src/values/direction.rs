@@ -1,12 +1,13 @@
 use crate::{macros::define_enum, Parse};
 
 define_enum! {
+    /// The direction of text and of the horizontal layout of children.
     #[derive(Default)]
     pub enum Direction {
-        /// The entity will be rendered and acted on by the layout system.
+        /// Text and children are laid out from left to right.
         #[default]
         "ltr": Ltr,
-        /// The entity will not be rendered and acted on by the layout system.
+        /// Text and children are laid out from right to left, mirroring the left and right space.
         "rtl": Rtl,
     }
 }
@@ -20,3 +21,12 @@ impl From<bool> for Direction {
         }
     }
 }
+
+impl From<Direction> for skia_safe::textlayout::TextDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Ltr => skia_safe::textlayout::TextDirection::LTR,
+            Direction::Rtl => skia_safe::textlayout::TextDirection::RTL,
+        }
+    }
+}
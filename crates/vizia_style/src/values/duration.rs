@@ -16,6 +16,14 @@ impl_parse! {
     }
 }
 
+impl<'i> Parse<'i> for Vec<Duration> {
+    fn parse<'t>(
+        input: &mut cssparser::Parser<'i, 't>,
+    ) -> Result<Self, cssparser::ParseError<'i, crate::CustomParseError<'i>>> {
+        input.parse_comma_separated(Duration::parse)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,8 @@
 use crate::{CustomParseError, Parse};
 use cssparser::*;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+/// A function which maps the progress of an animation or transition to the progress of the animated value.
+#[derive(Debug, Default, Clone, PartialEq)]
 pub enum EasingFunction {
     #[default]
     Linear,
@@ -10,7 +11,66 @@ pub enum EasingFunction {
     EaseOut,
     EaseInOut,
     CubicBezier(f32, f32, f32, f32),
-    // TODO: Steps
+    /// Divides the animation into a number of equal steps, jumping between them at the given position.
+    Steps(u32, StepPosition),
+    /// The `linear()` function, which interpolates linearly between a list of points given as
+    /// (input progress, output progress).
+    ///
+    /// The points are stored resolved, so that every point has an input and the inputs are in
+    /// increasing order.
+    LinearPoints(Vec<(f32, f32)>),
+    /// The `spring()` function, which follows the motion of a damped spring.
+    ///
+    /// Transitions with a spring easing move with the physics of the spring and come to rest in
+    /// their own time, ignoring the transition duration, so that they can be interrupted without
+    /// losing velocity. Elsewhere the motion of the spring is scaled to the duration.
+    Spring {
+        /// The mass of the object attached to the spring.
+        mass: f32,
+        /// The stiffness of the spring.
+        stiffness: f32,
+        /// The damping which slows the spring down.
+        damping: f32,
+        /// The initial velocity, in units of the animated distance per second.
+        velocity: f32,
+    },
+}
+
+impl EasingFunction {
+    /// Creates a spring easing function with the given mass, stiffness and damping and no
+    /// initial velocity.
+    pub fn spring(mass: f32, stiffness: f32, damping: f32) -> Self {
+        EasingFunction::Spring { mass, stiffness, damping, velocity: 0.0 }
+    }
+}
+
+/// The position of the jumps of a [`EasingFunction::Steps`] easing function.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StepPosition {
+    /// The first jump happens at the start of the animation.
+    JumpStart,
+    /// The last jump happens at the end of the animation.
+    #[default]
+    JumpEnd,
+    /// There is no jump at the start or end of the animation, which holds the first and last values
+    /// for an equal step.
+    JumpNone,
+    /// There are jumps at both the start and end of the animation.
+    JumpBoth,
+}
+
+impl<'i> Parse<'i> for StepPosition {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let location = input.current_source_location();
+        let ident = input.expect_ident()?;
+        Ok(match_ignore_ascii_case! { ident,
+            "jump-start" | "start" => StepPosition::JumpStart,
+            "jump-end" | "end" => StepPosition::JumpEnd,
+            "jump-none" => StepPosition::JumpNone,
+            "jump-both" => StepPosition::JumpBoth,
+            _ => return Err(location.new_unexpected_token_error(Token::Ident(ident.clone())))
+        })
+    }
 }
 
 impl<'i> Parse<'i> for EasingFunction {
@@ -23,8 +83,8 @@ impl<'i> Parse<'i> for EasingFunction {
               "ease-in" => EasingFunction::EaseIn,
               "ease-out" => EasingFunction::EaseOut,
               "ease-in-out" => EasingFunction::EaseInOut,
-            //   "step-start" => EasingFunction::Steps { count: 1, position: StepPosition::Start },
-            //   "step-end" => EasingFunction::Steps { count: 1, position: StepPosition::End },
+              "step-start" => EasingFunction::Steps(1, StepPosition::JumpStart),
+              "step-end" => EasingFunction::Steps(1, StepPosition::JumpEnd),
               _ => return Err(location.new_unexpected_token_error(Token::Ident(ident.clone())))
             };
             return Ok(keyword);
@@ -44,16 +104,152 @@ impl<'i> Parse<'i> for EasingFunction {
                 let y2 = input.try_parse(|input| input.expect_number())?;
                 Ok(EasingFunction::CubicBezier(x1, y1, x2, y2))
               },
-            //   "steps" => {
-            //     let count = CSSInteger::parse(input)?;
-            //     let position = input.try_parse(|input| {
-            //       input.expect_comma()?;
-            //       StepPosition::parse(input)
-            //     }).unwrap_or_default();
-            //     Ok(EasingFunction::Steps { count, position })
-            //   },
+              "steps" => {
+                let count = input.expect_integer()?;
+                let position = input.try_parse(|input| {
+                  input.expect_comma()?;
+                  StepPosition::parse(input)
+                }).unwrap_or_default();
+
+                // A step function without jumps at either end needs at least two steps.
+                let min_count = if position == StepPosition::JumpNone { 2 } else { 1 };
+                if count < min_count {
+                  return Err(input.new_custom_error(CustomParseError::InvalidValue));
+                }
+
+                Ok(EasingFunction::Steps(count as u32, position))
+              },
+              "spring" => {
+                if input.is_exhausted() {
+                  return Ok(EasingFunction::spring(1.0, 100.0, 10.0));
+                }
+
+                let mass = input.expect_number()?;
+                let stiffness = input.expect_number()?;
+                let damping = input.expect_number()?;
+                let velocity = input.try_parse(|input| input.expect_number()).unwrap_or_default();
+                if mass <= 0.0 || stiffness <= 0.0 || damping < 0.0 {
+                  return Err(input.new_custom_error(CustomParseError::InvalidValue));
+                }
+
+                Ok(EasingFunction::Spring { mass, stiffness, damping, velocity })
+              },
+              "linear" => {
+                let stops = input.parse_comma_separated(parse_linear_stop)?;
+                if stops.len() < 2 {
+                  return Err(input.new_custom_error(CustomParseError::InvalidValue));
+                }
+
+                Ok(EasingFunction::LinearPoints(resolve_linear_stops(stops)))
+              },
               _ => return Err(location.new_unexpected_token_error(Token::Ident(function.clone())))
             }
         })
     }
 }
+
+// Parses a stop of the `linear()` function, which is an output value followed by up to two input percentages.
+fn parse_linear_stop<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<(f32, Vec<f32>), ParseError<'i, CustomParseError<'i>>> {
+    let output = input.expect_number()?;
+    let mut inputs = Vec::new();
+    while inputs.len() < 2 {
+        match input.try_parse(|input| input.expect_percentage()) {
+            Ok(percentage) => inputs.push(percentage),
+            Err(_) => break,
+        }
+    }
+
+    Ok((output, inputs))
+}
+
+// Resolves the stops of a `linear()` function into points with increasing inputs.
+fn resolve_linear_stops(stops: Vec<(f32, Vec<f32>)>) -> Vec<(f32, f32)> {
+    let mut points = stops
+        .into_iter()
+        .flat_map(|(output, inputs)| match inputs.as_slice() {
+            [] => vec![(None, output)],
+            [input] => vec![(Some(*input), output)],
+            [start, end, ..] => vec![(Some(*start), output), (Some(*end), output)],
+        })
+        .collect::<Vec<_>>();
+
+    // The first and last points default to the start and end of the animation.
+    let last = points.len() - 1;
+    points[0].0.get_or_insert(0.0);
+    points[last].0.get_or_insert(1.0);
+
+    // An input smaller than a previous input is raised to match it.
+    let mut max_input = 0.0f32;
+    for (input, _) in points.iter_mut() {
+        if let Some(input) = input {
+            max_input = max_input.max(*input);
+            *input = max_input;
+        }
+    }
+
+    // Points without an input are spaced evenly between the surrounding points.
+    let mut index = 1;
+    while index < last {
+        if points[index].0.is_some() {
+            index += 1;
+            continue;
+        }
+
+        let start = index - 1;
+        let end = (index..=last).find(|i| points[*i].0.is_some()).unwrap_or(last);
+        let start_input = points[start].0.unwrap_or_default();
+        let end_input = points[end].0.unwrap_or(1.0);
+        for (i, point) in points.iter_mut().enumerate().take(end).skip(index) {
+            let fraction = (i - start) as f32 / (end - start) as f32;
+            point.0 = Some(start_input + (end_input - start_input) * fraction);
+        }
+
+        index = end;
+    }
+
+    points.into_iter().map(|(input, output)| (input.unwrap_or_default(), output)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_parse;
+
+    assert_parse! {
+        EasingFunction, assert_easing_function,
+
+        custom {
+            success {
+                "ease-in" => EasingFunction::EaseIn,
+                "cubic-bezier(0.1, 0.2, 0.3, 0.4)" => EasingFunction::CubicBezier(0.1, 0.2, 0.3, 0.4),
+                "step-start" => EasingFunction::Steps(1, StepPosition::JumpStart),
+                "step-end" => EasingFunction::Steps(1, StepPosition::JumpEnd),
+                "steps(4)" => EasingFunction::Steps(4, StepPosition::JumpEnd),
+                "steps(4, start)" => EasingFunction::Steps(4, StepPosition::JumpStart),
+                "steps(3, jump-both)" => EasingFunction::Steps(3, StepPosition::JumpBoth),
+                "steps(2, jump-none)" => EasingFunction::Steps(2, StepPosition::JumpNone),
+                "linear(0, 1)" => EasingFunction::LinearPoints(vec![(0.0, 0.0), (1.0, 1.0)]),
+                "linear(0, 0.25, 1)" => EasingFunction::LinearPoints(vec![(0.0, 0.0), (0.5, 0.25), (1.0, 1.0)]),
+                "linear(0, 0.5 25% 75%, 1)" => EasingFunction::LinearPoints(vec![(0.0, 0.0), (0.25, 0.5), (0.75, 0.5), (1.0, 1.0)]),
+                "linear(0, 1 50%, 0.5 25%, 1)" => EasingFunction::LinearPoints(vec![(0.0, 0.0), (0.5, 1.0), (0.5, 0.5), (1.0, 1.0)]),
+                "spring()" => EasingFunction::spring(1.0, 100.0, 10.0),
+                "spring(1 170 26)" => EasingFunction::spring(1.0, 170.0, 26.0),
+                "spring(2 100 5 -1.5)" => EasingFunction::Spring { mass: 2.0, stiffness: 100.0, damping: 5.0, velocity: -1.5 },
+            }
+
+            failure {
+                "steps(0)",
+                "steps(1, jump-none)",
+                "steps(2, middle)",
+                "linear(0)",
+                "linear()",
+                "spring(1 100)",
+                "spring(0 100 10)",
+                "spring(1 100 -1)",
+                "bounce",
+            }
+        }
+    }
+}
@@ -1,14 +1,25 @@
-use crate::{impl_parse, Parse};
-pub use morphorm::LayoutType;
+use crate::{define_enum, Parse};
 
-impl_parse! {
-    LayoutType,
+define_enum! {
+    /// Determines how a parent positions its parent-directed children.
+    #[derive(Default)]
+    pub enum LayoutType {
+        /// Children are stacked horizontally.
+        "row": Row,
+        /// Children are stacked vertically.
+        #[default]
+        "column": Column,
+        /// Children are placed into the cells of a grid, see `grid-columns` and `grid-rows`.
+        "grid": Grid,
+    }
+}
 
-    tokens {
-        ident {
-            "row" => LayoutType::Row,
-            "column" => LayoutType::Column,
-            // "grid" => LayoutType::Grid,
+impl From<LayoutType> for morphorm::LayoutType {
+    fn from(layout_type: LayoutType) -> Self {
+        match layout_type {
+            LayoutType::Row => morphorm::LayoutType::Row,
+            // Grid items are placed by vizia after morphorm has sized the grid as a column.
+            LayoutType::Column | LayoutType::Grid => morphorm::LayoutType::Column,
         }
     }
 }
@@ -24,7 +35,7 @@ mod tests {
         ident {
             "row" => LayoutType::Row,
             "column" => LayoutType::Column,
-            // "grid" => LayoutType::Grid,
+            "grid" => LayoutType::Grid,
         }
     }
 }
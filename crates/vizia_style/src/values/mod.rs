@@ -1,5 +1,8 @@
 pub mod alpha;
 pub mod angle;
+pub mod animation;
+pub mod animation_direction;
+pub mod animation_fill_mode;
 pub mod backdrop_filter;
 pub mod background_size;
 pub mod basic;
@@ -57,6 +60,9 @@ pub mod visibility;
 
 pub use alpha::*;
 pub use angle::*;
+pub use animation::*;
+pub use animation_direction::*;
+pub use animation_fill_mode::*;
 pub use backdrop_filter::*;
 pub use background_size::*;
 pub use basic::*;
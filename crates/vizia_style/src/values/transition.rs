@@ -55,7 +55,7 @@ impl<'i> Parse<'i> for Vec<Transition> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::assert_parse;
+    use crate::{tests::assert_parse, StepPosition};
 
     assert_parse! {
         Transition, assert_transition,
@@ -65,6 +65,8 @@ mod tests {
                 "width 2s" => Transition::new(String::from("width"), Duration::from_secs(2), None, None),
                 "height 2s 1s" => Transition::new(String::from("height"), Duration::from_secs(2), Some(Duration::from_secs(1)), None),
                 "color 200ms linear" => Transition::new(String::from("color"), Duration::from_millis(200), None, Some(EasingFunction::Linear)),
+                "opacity 1s steps(4)" => Transition::new(String::from("opacity"), Duration::from_secs(1), None, Some(EasingFunction::Steps(4, StepPosition::JumpEnd))),
+                "left 300ms spring(1 170 26)" => Transition::new(String::from("left"), Duration::from_millis(300), None, Some(EasingFunction::spring(1.0, 170.0, 26.0))),
             }
 
             failure {
@@ -1,4 +1,7 @@
-use crate::{macros::impl_parse, AutoKeyword, LengthPixels, Parse, Percentage, Stretch};
+use crate::{
+    macros::impl_parse, AutoKeyword, CustomParseError, LengthPixels, Parse, Percentage, Stretch,
+};
+use cssparser::{ParseError, Parser};
 pub use morphorm::Units;
 
 impl_parse! {
@@ -36,6 +39,18 @@ impl From<LengthPixels> for Units {
     }
 }
 
+/// A whitespace separated list of units, used for the tracks of a grid.
+impl<'i> Parse<'i> for Vec<Units> {
+    fn parse<'t>(input: &mut Parser<'i, 't>) -> Result<Self, ParseError<'i, CustomParseError<'i>>> {
+        let mut units = vec![Units::parse(input)?];
+        while let Ok(value) = input.try_parse(Units::parse) {
+            units.push(value);
+        }
+
+        Ok(units)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,4 +78,15 @@ mod tests {
             "s" => Units::Stretch,
         }
     }
+
+    #[test]
+    fn parse_units_list() {
+        let mut parser_input = cssparser::ParserInput::new("1s 100px auto 25%");
+        let mut parser = Parser::new(&mut parser_input);
+        let result = Vec::<Units>::parse(&mut parser).unwrap();
+        assert_eq!(
+            result,
+            vec![Units::Stretch(1.0), Units::Pixels(100.0), Units::Auto, Units::Percentage(25.0)]
+        );
+    }
 }
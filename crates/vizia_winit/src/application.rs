@@ -302,6 +302,7 @@ impl ApplicationHandler<UserEvent> for Application {
                 ..Default::default()
             },
         );
+        self.cx.set_window_scale_factor(Entity::root(), main_window.scale_factor());
 
         self.cx.0.remove_user_themes();
 
@@ -319,6 +320,7 @@ impl ApplicationHandler<UserEvent> for Application {
                 .create_window(event_loop, window_entity, &window_state.window_description, owner)
                 .expect("Failed to create window");
             self.cx.add_main_window(window_entity, &window_state.window_description, 1.0);
+            self.cx.set_window_scale_factor(window_entity, window.scale_factor());
             self.cx.mutate_window(window_entity, |cx, win: &mut Window| {
                 win.window = Some(window.clone());
                 win.custom_cursors = custom_cursors.clone();
@@ -436,7 +438,20 @@ impl ApplicationHandler<UserEvent> for Application {
 
                 window.window().request_redraw();
             }
-            winit::event::WindowEvent::Ime(_) => {}
+            winit::event::WindowEvent::Ime(ime) => {
+                let event = match ime {
+                    winit::event::Ime::Preedit(text, cursor) => {
+                        WindowEvent::ImePreedit(text, cursor)
+                    }
+                    winit::event::Ime::Commit(text) => WindowEvent::ImeCommit(text),
+                    // Disabling the IME discards any text being composed.
+                    winit::event::Ime::Disabled => WindowEvent::ImePreedit(String::new(), None),
+                    winit::event::Ime::Enabled => return,
+                };
+
+                self.cx.emit_window_event(window.entity, event);
+                window.window().request_redraw();
+            }
             winit::event::WindowEvent::CursorMoved { device_id: _, position } => {
                 self.cx.emit_window_event(
                     window.entity,
@@ -492,6 +507,7 @@ impl ApplicationHandler<UserEvent> for Application {
                 inner_size_writer: _,
             } => {
                 self.cx.set_scale_factor(scale_factor);
+                self.cx.set_window_scale_factor(window.entity, scale_factor);
                 self.cx.needs_refresh(window.entity);
             }
             winit::event::WindowEvent::ThemeChanged(theme) => {
@@ -501,7 +517,9 @@ impl ApplicationHandler<UserEvent> for Application {
                 };
                 self.cx.emit_window_event(window.entity, WindowEvent::ThemeChanged(theme));
             }
-            winit::event::WindowEvent::Occluded(_) => {}
+            winit::event::WindowEvent::Occluded(occluded) => {
+                self.cx.set_window_occluded(window.entity, occluded);
+            }
             winit::event::WindowEvent::RedrawRequested => {
                 for window in self.windows.values_mut() {
                     window.make_current();
@@ -611,6 +629,7 @@ impl ApplicationHandler<UserEvent> for Application {
                         )
                         .expect("Failed to create window");
 
+                    self.cx.set_window_scale_factor(*window_entity, window.scale_factor());
                     self.cx.mutate_window(*window_entity, |cx, win: &mut Window| {
                         win.window = Some(window.clone());
                         if let Some(callback) = &win.on_create {
@@ -361,6 +361,13 @@ impl View for Window {
                     .expect("Failed to set cursor position");
             }
 
+            WindowEvent::SetImeCursorArea((x, y), (width, height)) => {
+                self.window().set_ime_cursor_area(
+                    PhysicalPosition::new(*x, *y),
+                    PhysicalSize::new(*width, *height),
+                );
+            }
+
             WindowEvent::SetCursor(cursor) => {
                 let Some(icon) = cursor_icon_to_cursor_icon(*cursor) else {
                     self.window().set_cursor_visible(false);
@@ -12,6 +12,19 @@ const STYLE: &str = r#"
             left: 200px;
         }
     }
+
+    @keyframes pulse {
+        from {
+            opacity: 1;
+        }
+        to {
+            opacity: 0.3;
+        }
+    }
+
+    .pulse {
+        animation: pulse 1s ease-in-out infinite alternate;
+    }
 "#;
 
 fn main() -> Result<(), ApplicationError> {
@@ -24,7 +37,13 @@ fn main() -> Result<(), ApplicationError> {
 
         let anim_id = cx.add_animation(animation);
 
-        Element::new(cx).background_color(Color::red()).size(Pixels(100.0)).id("elem");
+        Element::new(cx)
+            .background_color(Color::red())
+            .size(Pixels(100.0))
+            .id("elem")
+            .on_animation_end(|_, event| println!("{:?}", event));
+
+        Element::new(cx).background_color(Color::blue()).size(Pixels(100.0)).class("pulse");
 
         Button::new(cx, |cx| Label::new(cx, "Play 1")).on_press(|cx| {
             cx.play_animation_for("slidein", "elem", Duration::from_secs(2), Duration::default())
@@ -32,6 +51,21 @@ fn main() -> Result<(), ApplicationError> {
         Button::new(cx, |cx| Label::new(cx, "Play 2")).on_press(move |cx| {
             cx.play_animation_for(anim_id, "elem", Duration::from_secs(2), Duration::default())
         });
+        Button::new(cx, |cx| Label::new(cx, "Pause")).on_press(|cx| {
+            if let Some(elem) = cx.resolve_entity_identifier("elem") {
+                cx.with_current(elem, |cx| cx.pause_animation("slidein"));
+            }
+        });
+        Button::new(cx, |cx| Label::new(cx, "Resume")).on_press(|cx| {
+            if let Some(elem) = cx.resolve_entity_identifier("elem") {
+                cx.with_current(elem, |cx| cx.resume_animation("slidein"));
+            }
+        });
+        Button::new(cx, |cx| Label::new(cx, "Reverse")).on_press(|cx| {
+            if let Some(elem) = cx.resolve_entity_identifier("elem") {
+                cx.with_current(elem, |cx| cx.reverse_animation("slidein"));
+            }
+        });
     })
     .run()
 }
@@ -14,6 +14,12 @@ const STYLE: &str = r#"
     height: auto;
 }
 
+.file-image {
+    background-image: url("Ghostscript_Tiger.svg");
+    width: 300px;
+    height: 300px;
+}
+
 .web-image {
     background-image: url("https://download.samplelib.com/png/sample-bumblebee-400x300.png");
     width: auto;
@@ -51,6 +57,9 @@ fn main() -> Result<(), ApplicationError> {
         //     }
         // });
 
+        // Images which haven't been loaded are read from files in this directory.
+        cx.set_asset_root(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/resources/images"));
+
         // Load an image into the binary
         cx.load_image(
             "sample.png",
@@ -58,6 +67,10 @@ fn main() -> Result<(), ApplicationError> {
             ImageRetentionPolicy::Forever
         );
 
+        // Editing the image files while the example is running shows the changes.
+        cx.set_image_file("sample.png", "sample-hut-400x300.png");
+        cx.watch_image_files(Duration::from_secs(1));
+
         Label::new(cx, "Any view can be styled with a background image. An Image view can be used to present a non-tiling background image.")
             .width(Stretch(1.0))
             .position_type(PositionType::SelfDirected)
@@ -65,6 +78,7 @@ fn main() -> Result<(), ApplicationError> {
 
         Element::new(cx).class("auto-size").background_color(Color::red());
         Element::new(cx).class("fixed-size");
+        Element::new(cx).class("file-image");
         // Element::new(cx).class("web-image");
         // Image::new(cx, "https://download.samplelib.com/png/sample-bumblebee-400x300.png");
         // Label::new(cx, "Wait for the image to load :)");
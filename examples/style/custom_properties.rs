@@ -0,0 +1,64 @@
+use vizia::prelude::*;
+
+const STYLE: &str = r#"
+    .theme {
+        --surface: rgb(240, 240, 240);
+        --accent: rgb(30, 120, 220);
+        --gap: 8px;
+
+        background-color: var(--surface);
+        child-space: 1s;
+        row-between: var(--gap);
+    }
+
+    .theme.dark {
+        --surface: rgb(30, 30, 30);
+        --accent: rgb(240, 160, 40);
+    }
+
+    .swatch {
+        size: 100px;
+        corner-radius: var(--radius, 8px);
+        background-color: var(--accent);
+    }
+
+    .swatch.square {
+        --radius: 0px;
+    }
+"#;
+
+#[derive(Lens)]
+pub struct AppData {
+    dark: bool,
+}
+
+pub enum AppEvent {
+    ToggleTheme,
+}
+
+impl Model for AppData {
+    fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::ToggleTheme => self.dark ^= true,
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        cx.add_stylesheet(STYLE).expect("Failed to add stylesheet");
+
+        AppData { dark: false }.build(cx);
+
+        VStack::new(cx, |cx| {
+            Button::new(cx, |cx| Label::new(cx, "Toggle Theme"))
+                .on_press(|cx| cx.emit(AppEvent::ToggleTheme));
+            Element::new(cx).class("swatch");
+            Element::new(cx).class("swatch").class("square");
+        })
+        .class("theme")
+        .toggle_class("dark", AppData::dark);
+    })
+    .title("Custom Properties")
+    .run()
+}
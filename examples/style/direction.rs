@@ -0,0 +1,70 @@
+use vizia::prelude::*;
+
+const STYLE: &str = r#"
+    .row {
+        height: auto;
+        col-between: 8px;
+        child-space: 8px;
+    }
+
+    element {
+        size: 50px;
+        background-color: #606060;
+    }
+
+    .first {
+        left: 20px;
+        background-color: #c04040;
+    }
+
+    .row:dir(rtl) {
+        background-color: #303050;
+    }
+"#;
+
+#[derive(Lens)]
+pub struct AppData {
+    rtl: bool,
+    text: String,
+}
+
+pub enum AppEvent {
+    ToggleDirection,
+}
+
+impl Model for AppData {
+    fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::ToggleDirection => self.rtl ^= true,
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        cx.add_stylesheet(STYLE).expect("Failed to add stylesheet");
+
+        AppData { rtl: true, text: String::from("Hello שלום עולם and مرحبا 123") }.build(cx);
+
+        VStack::new(cx, |cx| {
+            Button::new(cx, |cx| Label::new(cx, "Toggle direction"))
+                .on_press(|cx| cx.emit(AppEvent::ToggleDirection));
+
+            HStack::new(cx, |cx| {
+                Element::new(cx).class("first");
+                Element::new(cx);
+                Element::new(cx);
+            })
+            .class("row");
+
+            Label::new(cx, AppData::text).width(Stretch(1.0));
+
+            Textbox::new(cx, AppData::text).width(Stretch(1.0));
+        })
+        .direction(AppData::rtl.map(|rtl| if *rtl { Direction::Rtl } else { Direction::Ltr }))
+        .child_space(Pixels(20.0))
+        .row_between(Pixels(20.0));
+    })
+    .title("Direction")
+    .run()
+}
@@ -0,0 +1,46 @@
+use vizia::prelude::*;
+
+const STYLE: &str = r#"
+    .dashboard {
+        layout-type: grid;
+        grid-columns: 200px 1s 1s;
+        grid-rows: auto 1s 1s;
+        child-space: 8px;
+        col-between: 8px;
+        row-between: 8px;
+    }
+
+    .dashboard > element {
+        background-color: rgb(200, 200, 200);
+        corner-radius: 4px;
+    }
+
+    .header {
+        column-span: 3;
+        height: 48px;
+    }
+
+    .sidebar {
+        row-start: 1;
+        row-span: 2;
+    }
+"#;
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        cx.add_stylesheet(STYLE).expect("Failed to add stylesheet");
+
+        VStack::new(cx, |cx| {
+            Element::new(cx).class("header");
+            Element::new(cx).class("sidebar");
+
+            // Grid properties can also be set with modifiers.
+            Element::new(cx).column_start(1).row_start(1);
+            Element::new(cx).column_start(2).row_start(1);
+            Element::new(cx).column_start(1).row_start(2).column_span(2);
+        })
+        .class("dashboard");
+    })
+    .title("Grid")
+    .run()
+}
@@ -0,0 +1,67 @@
+use vizia::prelude::*;
+
+const STYLE: &str = r#"
+    .container {
+        layout-type: row;
+        child-space: 20px;
+        col-between: 20px;
+        row-between: 20px;
+        background-color: rgb(240, 240, 240);
+    }
+
+    .card {
+        size: 150px;
+        corner-radius: 8px;
+        background-color: rgb(30, 120, 220);
+    }
+
+    @media (max-width: 600px) {
+        .container {
+            layout-type: column;
+        }
+
+        .card {
+            width: 1s;
+            height: 80px;
+        }
+    }
+
+    @media (prefers-color-scheme: dark) {
+        .container {
+            background-color: rgb(30, 30, 30);
+        }
+
+        .card {
+            background-color: rgb(240, 160, 40);
+        }
+    }
+
+    @media (resolution >= 2dppx) {
+        .card {
+            border: 1px white;
+        }
+    }
+"#;
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        cx.add_stylesheet(STYLE).expect("Failed to add stylesheet");
+
+        VStack::new(cx, |cx| {
+            Button::new(cx, |cx| Label::new(cx, "Toggle Theme"))
+                .on_press(|cx| cx.emit(EnvironmentEvent::ToggleThemeMode));
+
+            HStack::new(cx, |cx| {
+                Element::new(cx).class("card");
+                Element::new(cx).class("card");
+                Element::new(cx).class("card");
+            })
+            .class("container");
+        })
+        .child_space(Pixels(20.0))
+        .row_between(Pixels(20.0));
+    })
+    .title("Media Queries")
+    .inner_size((800, 400))
+    .run()
+}
@@ -27,6 +27,11 @@ const STYLE: &str = r#"
         transition: left 1s ease-in-out;
     }
 
+    .container:over .spring {
+        left: 300px;
+        transition: left 1s spring(1 170 12);
+    }
+
     element {
         size: 100px;
         left: 0px;
@@ -92,6 +97,11 @@ fn main() -> Result<(), ApplicationError> {
                 Element::new(cx).class("easeinout").text("ease-in-out");
             })
             .class("row");
+
+            HStack::new(cx, |cx| {
+                Element::new(cx).class("spring").text("spring");
+            })
+            .class("row");
         })
         .class("container");
     })
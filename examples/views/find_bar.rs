@@ -0,0 +1,78 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+#[derive(Lens)]
+pub struct AppData {
+    notes: String,
+    log: Rope,
+}
+
+pub enum AppEvent {
+    SetNotes(String),
+    SetLog(Rope),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetNotes(notes) => self.notes = notes.clone(),
+            AppEvent::SetLog(log) => self.log = log.clone(),
+        });
+    }
+}
+
+const DOCUMENT: &str = r#"
+# Finding text
+
+Press **Ctrl+F** in any of the views on this page to search its text. Enter and Shift+Enter
+step through the matches, and Escape closes the find bar.
+
+Turn on `.*` to search with a regular expression, such as `request \d+`, and refer to its
+capture groups in the replacement with `$1`.
+
+```json
+{ "request": 42, "status": "handled" }
+```
+"#;
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        let log = (1..=1000)
+            .map(|index| format!("request {} handled in {}ms\n", index, index % 97))
+            .collect::<String>();
+
+        AppData {
+            notes: String::from("Find the text in this textbox, and replace it with other text."),
+            log: Rope::from_str(&log),
+        }
+        .build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            // Ctrl+F opens the find bar above the view which has the focus.
+            FindBar::new(cx, |cx| {
+                HStack::new(cx, |cx| {
+                    VStack::new(cx, |cx| {
+                        Textbox::new_multiline(cx, AppData::notes, true)
+                            .on_edit(|cx, text| cx.emit(AppEvent::SetNotes(text)))
+                            .width(Stretch(1.0));
+
+                        TextEditor::new(cx, AppData::log)
+                            .line_numbers(true)
+                            .on_change(|cx, log| cx.emit(AppEvent::SetLog(log)))
+                            .size(Stretch(1.0));
+                    })
+                    .row_between(Pixels(8.0));
+
+                    ScrollView::new(cx, 0.0, 0.0, false, true, |cx| {
+                        Markdown::new(cx, DOCUMENT);
+                    });
+                })
+                .col_between(Pixels(8.0));
+            })
+            .size(Pixels(800.0));
+        });
+    })
+    .title("Find Bar")
+    .run()
+}
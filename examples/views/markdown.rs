@@ -1,13 +1,80 @@
 use vizia::prelude::*;
 
 #[derive(Lens)]
-pub struct AppData {}
+pub struct AppData {
+    document: String,
+}
+
+pub enum AppEvent {
+    SetDocument(String),
+    ToggleTask(usize, bool),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetDocument(document) => self.document = document.clone(),
+
+            // Writes the state of a task back into the source of the document.
+            AppEvent::ToggleTask(line, checked) => {
+                let (from, to) = if *checked { ("[ ]", "[x]") } else { ("[x]", "[ ]") };
+                self.document =
+                    self.document
+                        .lines()
+                        .enumerate()
+                        .map(|(index, text)| {
+                            if index == *line {
+                                text.replacen(from, to, 1)
+                            } else {
+                                text.to_owned()
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+            }
+        });
+    }
+}
+
+const DOCUMENT: &str = r#"# Markdown
+
+Edit the source on the left, and only the blocks which change are built again.
+Jump to the [table](#a-table) or the [footnotes](#fn-note).
+
+some `code` inline, and a footnote[^note].
 
-impl Model for AppData {}
+> A block quote
+> spanning two lines.
+
+---
+
+## Tasks
+
+- [x] Parse the document
+- [ ] Check off this task
+
+## A table
+
+| View | Element | Editable |
+| ---- | ------- | -------- |
+| Label | `label` | No |
+| Textbox | `textbox` | Yes |
+
+```json
+{
+  "name": "vizia",
+  "version": 0.1,
+  "features": ["markdown", "highlighting"],
+  "stable": false
+}
+```
+
+[^note]: Footnotes are listed at the end of the document.
+"#;
 
 fn main() -> Result<(), ApplicationError> {
     Application::new(|cx| {
-        AppData {}.build(cx);
+        AppData { document: String::from(DOCUMENT) }.build(cx);
 
         //         Markdown::new(
         //             cx,
@@ -33,14 +100,18 @@ fn main() -> Result<(), ApplicationError> {
         // A [link](https://github.com/vizia/vizia)
         // "#,
         //         );
-        ScrollView::new(cx, 0.0, 0.0, false, true, |cx| {
-            Markdown::new(
-                cx,
-                r#"
-some `code` inline
-"#,
-            );
-        });
+        HStack::new(cx, |cx| {
+            Textbox::new_multiline(cx, AppData::document, false)
+                .on_edit(|cx, document| cx.emit(AppEvent::SetDocument(document)))
+                .size(Stretch(1.0));
+
+            ScrollView::new(cx, 0.0, 0.0, false, true, |cx| {
+                Markdown::with_lens(cx, AppData::document).on_toggle_task(|cx, line, checked| {
+                    cx.emit(AppEvent::ToggleTask(line, checked))
+                });
+            });
+        })
+        .col_between(Pixels(8.0));
     })
     .title("Markdown")
     .run()
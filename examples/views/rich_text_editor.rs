@@ -0,0 +1,73 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+const DOCUMENT: &str = "# Rich text
+
+Text can be **bold**, *italic*, <u>underlined</u> or [linked](https://github.com/vizia/vizia).
+
+- Select some text and press Ctrl+B, Ctrl+I or Ctrl+U
+- Hold Ctrl and click a link to open it";
+
+#[derive(Lens)]
+pub struct AppData {
+    document: RichText,
+    markdown: String,
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetDocument(document) => {
+                self.markdown = document.to_markdown();
+                self.document = document.clone();
+            }
+        });
+    }
+}
+
+pub enum AppEvent {
+    SetDocument(RichText),
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        let document = RichText::from_markdown(DOCUMENT);
+        AppData { markdown: document.to_markdown(), document }.build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            let editor = RichTextEditor::new(cx, AppData::document)
+                .width(Pixels(400.0))
+                .on_change(|cx, document| cx.emit(AppEvent::SetDocument(document)))
+                .entity();
+
+            // Formatting is sent to the editor, which applies it to its selection.
+            HStack::new(cx, |cx| {
+                Button::new(cx, |cx| Label::new(cx, "Bold"))
+                    .on_press(move |cx| cx.emit_to(editor, RichTextEvent::ToggleBold));
+                Button::new(cx, |cx| Label::new(cx, "Italic"))
+                    .on_press(move |cx| cx.emit_to(editor, RichTextEvent::ToggleItalic));
+                Button::new(cx, |cx| Label::new(cx, "Underline"))
+                    .on_press(move |cx| cx.emit_to(editor, RichTextEvent::ToggleUnderline));
+                Button::new(cx, |cx| Label::new(cx, "Red")).on_press(move |cx| {
+                    cx.emit_to(editor, RichTextEvent::SetColor(Some(Color::rgb(229, 57, 53))))
+                });
+                Button::new(cx, |cx| Label::new(cx, "Large"))
+                    .on_press(move |cx| cx.emit_to(editor, RichTextEvent::SetFontSize(Some(24.0))));
+                Button::new(cx, |cx| Label::new(cx, "Link")).on_press(move |cx| {
+                    cx.emit_to(
+                        editor,
+                        RichTextEvent::SetLink(Some(String::from("https://vizia.dev"))),
+                    )
+                });
+            })
+            .height(Auto)
+            .width(Auto)
+            .col_between(Pixels(4.0));
+
+            Label::new(cx, AppData::markdown).width(Pixels(400.0)).text_wrap(true);
+        });
+    })
+    .title("Rich Text Editor")
+    .run()
+}
@@ -0,0 +1,106 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+const LEVELS: [&str; 4] = ["DEBUG", "INFO", "WARN", "ERROR"];
+const SOURCES: [&str; 5] = ["server", "database", "scheduler", "auth", "cache"];
+
+#[derive(Debug, Clone, Data)]
+pub struct LogEntry {
+    id: u32,
+    level: &'static str,
+    source: &'static str,
+    message: String,
+}
+
+#[derive(Lens)]
+pub struct AppData {
+    logs: Vec<LogEntry>,
+    num_selected: usize,
+}
+
+pub enum AppEvent {
+    SetSelected(usize),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetSelected(num_selected) => self.num_selected = *num_selected,
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        let logs = (0..50_000u32)
+            .map(|id| LogEntry {
+                id,
+                level: LEVELS[(id * 7 % 13 % 4) as usize],
+                source: SOURCES[(id % 5) as usize],
+                message: format!("request {} handled in {}ms", id, id * 31 % 997),
+            })
+            .collect();
+
+        AppData { logs, num_selected: 0 }.build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            Table::new(
+                cx,
+                AppData::logs,
+                |entry| entry.id,
+                28.0,
+                vec![
+                    TableColumn::new(
+                        |cx| {
+                            Label::new(cx, "Id");
+                        },
+                        |cx, entry: &LogEntry| {
+                            Label::new(cx, entry.id);
+                        },
+                    )
+                    .width(80.0)
+                    .sort_by_key(|entry| entry.id),
+                    TableColumn::new(
+                        |cx| {
+                            Label::new(cx, "Level");
+                        },
+                        |cx, entry: &LogEntry| {
+                            Label::new(cx, entry.level);
+                        },
+                    )
+                    .width(90.0)
+                    .min_width(60.0)
+                    .sort_by_key(|entry| LEVELS.iter().position(|level| *level == entry.level)),
+                    TableColumn::new(
+                        |cx| {
+                            Label::new(cx, "Source");
+                        },
+                        |cx, entry: &LogEntry| {
+                            Label::new(cx, entry.source);
+                        },
+                    )
+                    .width(120.0)
+                    .sort_by_key(|entry| entry.source),
+                    TableColumn::new(
+                        |cx| {
+                            Label::new(cx, "Message");
+                        },
+                        |cx, entry: &LogEntry| {
+                            Label::new(cx, entry.message.as_str());
+                        },
+                    )
+                    .width(320.0)
+                    .min_width(120.0),
+                ],
+            )
+            .selectable(Selectable::Multi)
+            .on_select(|cx, selected| cx.emit(AppEvent::SetSelected(selected.len())))
+            .size(Pixels(600.0));
+
+            Label::new(cx, AppData::num_selected.map(|num| format!("{} rows selected", num)));
+        });
+    })
+    .title("Table")
+    .run()
+}
@@ -0,0 +1,109 @@
+mod helpers;
+use helpers::*;
+use vizia::prelude::*;
+
+#[derive(Lens)]
+pub struct AppData {
+    text: Rope,
+    config: Rope,
+    line_numbers: bool,
+    line: usize,
+}
+
+pub enum AppEvent {
+    SetText(Rope),
+    SetConfig(Rope),
+    ToggleLineNumbers,
+    SetLine(usize),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetText(text) => self.text = text.clone(),
+            AppEvent::SetConfig(config) => self.config = config.clone(),
+            AppEvent::ToggleLineNumbers => self.line_numbers ^= true,
+            AppEvent::SetLine(line) => self.line = *line,
+        });
+    }
+}
+
+const CONFIG: &str = r#"# Server configuration
+[server]
+host = "127.0.0.1"
+port = 8080
+log-level = 'info'
+started = 1979-05-27T07:32:00Z
+
+[server.tls]
+enabled = false
+ciphers = ["TLS_AES_128_GCM_SHA256", "TLS_AES_256_GCM_SHA384"]
+
+[[routes]]
+path = "/"
+description = """
+Serves the index page,
+and the static files."""
+timeout = 2.5
+"#;
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        // A large log, of which only the lines in view are laid out.
+        let log = (1..=10000)
+            .map(|index| format!("[{:05}] INFO  request handled in {}ms\n", index, index % 97))
+            .collect::<String>();
+
+        AppData {
+            text: Rope::from_str(&log),
+            config: Rope::from_str(CONFIG),
+            line_numbers: true,
+            line: 1,
+        }
+        .build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            let mut editor = Entity::null();
+
+            HStack::new(cx, |cx| {
+                editor = TextEditor::new(cx, AppData::text)
+                    .line_numbers(AppData::line_numbers)
+                    .on_change(|cx, text| cx.emit(AppEvent::SetText(text)))
+                    .size(Pixels(500.0))
+                    .entity();
+
+                // A configuration file with its syntax highlighted.
+                TextEditor::new(cx, AppData::config)
+                    .line_numbers(AppData::line_numbers)
+                    .highlighter(ConfigHighlighter::Toml)
+                    .tab_width(2)
+                    .on_change(|cx, config| cx.emit(AppEvent::SetConfig(config)))
+                    .size(Pixels(500.0));
+            })
+            .size(Auto)
+            .col_between(Pixels(8.0));
+
+            HStack::new(cx, |cx| {
+                Checkbox::new(cx, AppData::line_numbers)
+                    .on_toggle(|cx| cx.emit(AppEvent::ToggleLineNumbers))
+                    .id("line-numbers");
+                Label::new(cx, "Line numbers").describing("line-numbers");
+
+                Label::new(cx, "Go to line");
+                Textbox::new(cx, AppData::line).width(Pixels(80.0)).on_submit(
+                    move |cx, line, _| {
+                        cx.emit(AppEvent::SetLine(line));
+                        cx.emit_to(editor, TextEditorEvent::GoToLine(line.saturating_sub(1)));
+                    },
+                );
+            })
+            .height(Auto)
+            .width(Auto)
+            .child_top(Stretch(1.0))
+            .child_bottom(Stretch(1.0))
+            .col_between(Pixels(8.0));
+        });
+    })
+    .title("Text Editor")
+    .run()
+}
@@ -25,6 +25,10 @@ pub enum AppEvent {
     SetMultilineText(String),
 }
 
+// The words known to the spell checker of the multiline textbox.
+const WORDS: &str = "a an and are be but can edited is it line lines multiple not of selected \
+    some spans text the this type which with editable spelling checked";
+
 fn main() -> Result<(), ApplicationError> {
     Application::new(|cx| {
         AppData {
@@ -58,6 +62,7 @@ fn main() -> Result<(), ApplicationError> {
 
             Textbox::new_multiline(cx, AppData::multiline_text, true)
                 .width(Pixels(300.0))
+                .text_checker(DictionaryChecker::new(WORDS.split_whitespace()))
                 .on_edit(|cx, text| cx.emit(AppEvent::SetMultilineText(text)));
 
             Textbox::new(cx, AppData::non_editable_text).width(Auto).read_only(true);
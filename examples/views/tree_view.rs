@@ -0,0 +1,84 @@
+mod helpers;
+use helpers::*;
+use vizia::icons::{ICON_FILE, ICON_FOLDER};
+use vizia::prelude::*;
+
+#[derive(Debug, Clone, Data)]
+pub struct Node {
+    name: String,
+    children: Vec<Node>,
+}
+
+fn folder(name: &str, children: Vec<Node>) -> Node {
+    Node { name: name.to_string(), children }
+}
+
+fn file(name: &str) -> Node {
+    Node { name: name.to_string(), children: Vec::new() }
+}
+
+fn icon(node: &Node) -> &'static str {
+    if node.children.is_empty() {
+        ICON_FILE
+    } else {
+        ICON_FOLDER
+    }
+}
+
+#[derive(Lens)]
+pub struct AppData {
+    nodes: Vec<Node>,
+    num_selected: usize,
+}
+
+pub enum AppEvent {
+    SetSelected(usize),
+}
+
+impl Model for AppData {
+    fn event(&mut self, _: &mut EventContext, event: &mut Event) {
+        event.map(|app_event, _| match app_event {
+            AppEvent::SetSelected(num_selected) => self.num_selected = *num_selected,
+        });
+    }
+}
+
+fn main() -> Result<(), ApplicationError> {
+    Application::new(|cx| {
+        let nodes = vec![
+            folder(
+                "src",
+                vec![
+                    folder("views", vec![file("button.rs"), file("label.rs"), file("mod.rs")]),
+                    file("lib.rs"),
+                    file("main.rs"),
+                ],
+            ),
+            folder("examples", vec![file("counter.rs"), file("tree_view.rs")]),
+            file("Cargo.toml"),
+            file("README.md"),
+        ];
+
+        AppData { nodes, num_selected: 0 }.build(cx);
+
+        ExamplePage::vertical(cx, |cx| {
+            TreeView::new(
+                cx,
+                AppData::nodes,
+                |node| node.children.as_slice(),
+                |node| node.name.clone(),
+                |cx, node| {
+                    Svg::new(cx, node.map(icon)).size(Pixels(16.0)).right(Pixels(6.0));
+                    Label::new(cx, node.map_ref(|node| &node.name));
+                },
+            )
+            .selectable(Selectable::Multi)
+            .on_select(|cx, selected| cx.emit(AppEvent::SetSelected(selected.len())))
+            .size(Pixels(300.0));
+
+            Label::new(cx, AppData::num_selected.map(|num| format!("{} items selected", num)));
+        });
+    })
+    .title("Tree View")
+    .run()
+}
@@ -12,6 +12,13 @@ pub use vizia_baseview::{
 
 pub use vizia_core::*;
 
+/// Utilities for testing views without a window, including a headless application which can be
+/// driven manually and golden-image snapshot tests.
+#[cfg(feature = "testing")]
+pub mod testing {
+    pub use vizia_headless::*;
+}
+
 #[doc(hidden)]
 pub mod prelude {
     pub use vizia_core::prelude::*;